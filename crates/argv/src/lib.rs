@@ -8,7 +8,7 @@
 //! # Example
 //!
 //! ```
-//! extern "C" fn extern_main(argc: i32, argv: *mut *mut i8) {}
+//! extern "C" fn extern_main(argc: i32, argv: *mut *mut std::os::raw::c_char) {}
 //!
 //! use argv::Args;
 //!
@@ -24,6 +24,9 @@
 //!
 //! # Notes
 //!
+//! On Windows, `WideArgs` similarly provides UTF-16 arguments for
+//! `wmain`-style C entry points.
+//!
 //! This crate was built to facilitate calling DPDK's [`rte_eal_init`](http://doc.dpdk.org/api/rte__eal_8h.html#a5c3f4dddc25e38c5a186ecd8a69260e3).
 //!
 //! The implementation aims to be as safe as possible, while not necessarily as
@@ -60,19 +63,297 @@
 //! ptrs.to_argv(); // Can't use ptrs because it is tied to args' lifetime
 //! ```
 
-use std::{ffi::CString, os::raw::c_char, ptr};
+#[cfg(unix)]
+use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+use std::{
+    ffi::{CStr, CString},
+    mem,
+    os::raw::c_char,
+    ptr,
+};
+
+#[cfg(any(test, feature = "test_support"))]
+pub mod test_support;
+#[cfg(windows)]
+mod wide;
+#[cfg(windows)]
+pub use wide::{WideArgPtrs, WideArgs};
+
+/// Error returned when an argument contains an interior NUL byte, which can't
+/// be passed to C.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("argument {index} contains a NUL byte at position {nul_position}")]
+pub struct NulError {
+    index: usize,
+    nul_position: usize,
+}
+
+impl NulError {
+    /// Returns the index of the offending argument.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the position of the (first) NUL byte in the offending
+    /// argument.
+    pub fn nul_position(&self) -> usize {
+        self.nul_position
+    }
+}
+
+/// Error returned when arguments exceed what can be passed to C, see
+/// [`Args::check_limits`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum LimitError {
+    #[error("too many arguments: {0} (argc is an i32)")]
+    TooManyArgs(usize),
+    #[error("arguments take {size} bytes, over the limit of {max_size}")]
+    TooLarge { size: usize, max_size: usize },
+}
+
+/// Converts a number of arguments into `argc`, failing if it doesn't fit.
+pub(crate) fn try_argc(len: usize) -> Result<i32, LimitError> {
+    len.try_into().map_err(|_| LimitError::TooManyArgs(len))
+}
+
+/// Converts a number of arguments into `argc`, saturating at `i32::MAX`. C
+/// then only sees the first `i32::MAX` arguments, and `argv[argc]` isn't the
+/// terminating NULL pointer (which is only found further in the list).
+pub(crate) fn saturating_argc(len: usize) -> i32 {
+    try_argc(len).unwrap_or(i32::MAX)
+}
+
+/// Error returned when an environment variable can't be passed to C as a
+/// `KEY=VALUE` string, see [`Env::try_new`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    #[error("variable {index} has an empty name")]
+    EmptyKey { index: usize },
+    /// The name contains a `=`, which would be read as the end of the name.
+    #[error("the name of variable {index} contains '=' at position {position}")]
+    KeyWithEquals { index: usize, position: usize },
+    /// The name or value contains a NUL byte, at `nul_position` of the
+    /// `KEY=VALUE` string.
+    #[error("variable {index} contains a NUL byte at position {nul_position}")]
+    Nul { index: usize, nul_position: usize },
+}
+
+/// Error returned by [`Args::from_cmdline`] for a malformed command line.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum CmdlineError {
+    #[error("unbalanced {0} quote")]
+    UnbalancedQuote(char),
+    #[error("trailing backslash")]
+    TrailingBackslash,
+    #[error(transparent)]
+    Nul(#[from] NulError),
+}
 
 /// Create a clone of command line arguments, encoded into [`CString`]s.
 pub struct Args(Vec<CString>);
 
 impl Args {
+    /// # Panics
+    ///
+    /// Panics if any of the arguments contains a NUL byte, see
+    /// [`Args::try_new`].
     pub fn new(args: impl IntoIterator<Item = String>) -> Self {
-        Self(args.into_iter().map(CString::new).collect::<Result<_, _>>().unwrap())
+        Self::try_new(args).unwrap()
+    }
+
+    /// Returns an error identifying the first argument containing a NUL byte,
+    /// if any.
+    pub fn try_new(args: impl IntoIterator<Item = String>) -> Result<Self, NulError> {
+        Self::try_from_bytes(args.into_iter().map(String::into_bytes))
+    }
+
+    /// Creates the arguments from [`OsString`]s (e.g. returned by
+    /// [env::args_os](std::env::args_os)), which may not be valid UTF-8 but
+    /// are passed to C unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the arguments contains a NUL byte, see
+    /// [`Args::try_from_os`].
+    #[cfg(unix)]
+    pub fn from_os(args: impl IntoIterator<Item = OsString>) -> Self {
+        Self::try_from_os(args).unwrap()
+    }
+
+    /// Like [`Args::from_os`], but returns an error identifying the first
+    /// argument containing a NUL byte, if any.
+    #[cfg(unix)]
+    pub fn try_from_os(args: impl IntoIterator<Item = OsString>) -> Result<Self, NulError> {
+        Self::try_from_bytes(args.into_iter().map(OsString::into_vec))
+    }
+
+    fn try_from_bytes(args: impl Iterator<Item = Vec<u8>>) -> Result<Self, NulError> {
+        args.enumerate()
+            .map(|(index, arg)| CString::new(arg).map_err(|err| NulError { index, nul_position: err.nul_position() }))
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    /// Splits a command line (e.g. EAL options read from a config file, such
+    /// as `-l 0-3 -n 4 --no-huge`) into arguments like a POSIX shell would,
+    /// i.e. on unquoted whitespace, honoring single quotes, double quotes
+    /// and backslash escapes. No expansion of any kind is performed.
+    pub fn from_cmdline(cmdline: &str) -> Result<Self, CmdlineError> {
+        let mut args = vec![];
+        // `None` between words, so that `''` still makes an (empty) argument
+        let mut arg: Option<String> = None;
+        let mut chars = cmdline.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                c if c.is_ascii_whitespace() => args.extend(arg.take()),
+                '\\' => match chars.next().ok_or(CmdlineError::TrailingBackslash)? {
+                    // line continuation
+                    '\n' => {}
+                    c => arg.get_or_insert_with(String::new).push(c),
+                },
+                '\'' => {
+                    let arg = arg.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next().ok_or(CmdlineError::UnbalancedQuote('\''))? {
+                            '\'' => break,
+                            c => arg.push(c),
+                        }
+                    }
+                }
+                '"' => {
+                    let arg = arg.get_or_insert_with(String::new);
+                    loop {
+                        match chars.next().ok_or(CmdlineError::UnbalancedQuote('"'))? {
+                            '"' => break,
+                            // within double quotes, backslashes only escape these
+                            '\\' => match chars.next().ok_or(CmdlineError::UnbalancedQuote('"'))? {
+                                c @ ('"' | '\\' | '$' | '`') => arg.push(c),
+                                '\n' => {}
+                                c => arg.extend(['\\', c]),
+                            },
+                            c => arg.push(c),
+                        }
+                    }
+                }
+                c => arg.get_or_insert_with(String::new).push(c),
+            }
+        }
+        args.extend(arg);
+
+        Ok(Self::try_new(args)?)
     }
 
-    pub fn as_ptrs(&mut self) -> ArgPtrs {
+    /// Removes any NUL bytes from the arguments, rather than failing.
+    pub fn new_lossy(args: impl IntoIterator<Item = String>) -> Self {
+        Self(
+            args.into_iter()
+                .map(|arg| {
+                    let mut arg = arg.into_bytes();
+                    arg.retain(|&b| b != 0);
+                    CString::new(arg).unwrap()
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns the number of arguments, i.e. `argc`.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the memory taken by the arguments once passed to C, i.e. the
+    /// strings (including their NUL bytes) and the (NUL-terminated) `argv`
+    /// pointer list, as counted against e.g. `ARG_MAX` by `execve`.
+    pub fn size(&self) -> usize {
+        let strings = self.0.iter().map(|arg| arg.as_bytes_with_nul().len()).sum::<usize>();
+        strings + (self.0.len() + 1) * mem::size_of::<*const c_char>()
+    }
+
+    /// Checks that the number of arguments fits in `argc`, and that their
+    /// [size](Args::size) is at most `max_size` (if any), so that callers can
+    /// reject them before the FFI call rather than relying on `argc()`
+    /// saturating.
+    pub fn check_limits(&self, max_size: Option<usize>) -> Result<(), LimitError> {
+        try_argc(self.len())?;
+        match (self.size(), max_size) {
+            (size, Some(max_size)) if size > max_size => Err(LimitError::TooLarge { size, max_size }),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn as_ptrs(&mut self) -> ArgPtrs<'_> {
         ArgPtrs::new(self)
     }
+
+    /// See: [`MutArgv`]
+    pub fn into_mut(self) -> MutArgv {
+        MutArgv::new(self)
+    }
+}
+
+/// A builder composing a list of arguments (e.g. EAL options) before freezing
+/// it into [`Args`].
+///
+/// ```
+/// # use argv::ArgsBuilder;
+/// let args = ArgsBuilder::new("app")
+///     .push_pair("-l", "0-3")
+///     .push_if(true, "--no-huge")
+///     .push_pair_if(false, "--file-prefix", "app")
+///     .extend(["--", "--verbose"])
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ArgsBuilder(Vec<String>);
+
+impl ArgsBuilder {
+    /// Starts the arguments with the program name, i.e. `argv[0]`.
+    pub fn new(prog: impl Into<String>) -> Self {
+        Self(vec![prog.into()])
+    }
+
+    pub fn push(mut self, arg: impl Into<String>) -> Self {
+        self.0.push(arg.into());
+        self
+    }
+
+    /// Pushes an option followed by its value, e.g. `("-n", 4)`.
+    pub fn push_pair(self, key: impl Into<String>, value: impl ToString) -> Self {
+        self.push(key).push(value.to_string())
+    }
+
+    pub fn push_if(self, cond: bool, arg: impl Into<String>) -> Self {
+        if cond {
+            self.push(arg)
+        } else {
+            self
+        }
+    }
+
+    pub fn push_pair_if(self, cond: bool, key: impl Into<String>, value: impl ToString) -> Self {
+        if cond {
+            self.push_pair(key, value)
+        } else {
+            self
+        }
+    }
+
+    pub fn extend<S: Into<String>>(mut self, args: impl IntoIterator<Item = S>) -> Self {
+        self.0.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Returns an error identifying the first argument containing a NUL byte,
+    /// if any.
+    pub fn build(self) -> Result<Args, NulError> {
+        Args::try_new(self.0)
+    }
 }
 
 /// A list of pointers pointing to a list of [`CString`]s contained in an
@@ -85,12 +366,14 @@ impl Args {
 /// Created by calling [`Args::as_ptrs`].
 pub struct ArgPtrs<'a> {
     args: &'a mut Args,
-    ptrs: Vec<*const c_char>,
+    ptrs: Vec<*mut c_char>,
 }
 
 impl<'a> ArgPtrs<'a> {
     fn new(args: &'a mut Args) -> Self {
-        let mut ptrs = args.0.iter_mut().map(|arg| arg.as_ptr()).collect::<Vec<_>>();
+        // the strings are only ever read (C may permute the list, though), so
+        // their shared provenance is enough
+        let mut ptrs = args.0.iter().map(|arg| arg.as_ptr().cast_mut()).collect::<Vec<_>>();
         ptrs.push(ptr::null_mut());
 
         Self { args, ptrs }
@@ -114,19 +397,222 @@ impl<'a, 'p> Argv<'a, 'p> {
     /// This function returns a raw pointer that is not tied via lifetimes to
     /// any provenance, the caller must ensure that this pointer is not used
     /// after the lifetime of `&mut self` has ended.
-    pub unsafe fn argv(&mut self) -> *mut *mut i8 {
-        self.ptrs.ptrs.as_mut_ptr() as *mut _
+    pub unsafe fn argv(&mut self) -> *mut *mut c_char {
+        self.ptrs.ptrs.as_mut_ptr()
     }
 
+    /// Saturates at `i32::MAX`, in which case `argv[argc]` isn't NULL, see
+    /// [`Argv::try_argc`].
     pub fn argc(&self) -> i32 {
-        self.ptrs.args.0.len() as i32
+        saturating_argc(self.ptrs.args.len())
+    }
+
+    /// Returns an error if there are more than `i32::MAX` arguments, see
+    /// [`Args::check_limits`].
+    pub fn try_argc(&self) -> Result<i32, LimitError> {
+        try_argc(self.ptrs.args.len())
+    }
+}
+
+/// Create a clone of environment variables (possibly those returned by
+/// [env::vars](std::env::vars)), encoded into `KEY=VALUE` [`CString`]s, for
+/// C functions that also take an `envp` parameter.
+pub struct Env(Vec<CString>);
+
+impl Env {
+    /// # Panics
+    ///
+    /// Panics if any of the variables is invalid, see [`Env::try_new`].
+    pub fn new(vars: impl IntoIterator<Item = (String, String)>) -> Self {
+        Self::try_new(vars).unwrap()
+    }
+
+    /// Returns an error identifying the first invalid variable, if any, i.e.
+    /// with an empty name, a `=` in its name or a NUL byte.
+    pub fn try_new(vars: impl IntoIterator<Item = (String, String)>) -> Result<Self, EnvError> {
+        Self::try_from_bytes(vars.into_iter().map(|(key, value)| (key.into_bytes(), value.into_bytes())))
+    }
+
+    /// Creates the variables from [`OsString`]s (e.g. returned by
+    /// [env::vars_os](std::env::vars_os)), which may not be valid UTF-8 but
+    /// are passed to C unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the variables is invalid, see [`Env::try_new`].
+    #[cfg(unix)]
+    pub fn from_os(vars: impl IntoIterator<Item = (OsString, OsString)>) -> Self {
+        Self::try_from_bytes(vars.into_iter().map(|(key, value)| (key.into_vec(), value.into_vec()))).unwrap()
+    }
+
+    fn try_from_bytes(vars: impl Iterator<Item = (Vec<u8>, Vec<u8>)>) -> Result<Self, EnvError> {
+        vars.enumerate()
+            .map(|(index, (mut key, value))| {
+                if key.is_empty() {
+                    return Err(EnvError::EmptyKey { index });
+                }
+                if let Some(position) = key.iter().position(|&b| b == b'=') {
+                    return Err(EnvError::KeyWithEquals { index, position });
+                }
+                key.push(b'=');
+                key.extend(value);
+                CString::new(key).map_err(|err| EnvError::Nul { index, nul_position: err.nul_position() })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn as_ptrs(&mut self) -> EnvPtrs<'_> {
+        EnvPtrs::new(self)
+    }
+}
+
+/// A NUL-terminated list of pointers pointing to the `KEY=VALUE` strings
+/// contained in an [`Env`] struct, tied to its lifetime like [`ArgPtrs`].
+///
+/// Created by calling [`Env::as_ptrs`].
+pub struct EnvPtrs<'a> {
+    _env: &'a mut Env,
+    ptrs: Vec<*mut c_char>,
+}
+
+impl<'a> EnvPtrs<'a> {
+    fn new(env: &'a mut Env) -> Self {
+        let mut ptrs = env.0.iter().map(|var| var.as_ptr().cast_mut()).collect::<Vec<_>>();
+        ptrs.push(ptr::null_mut());
+
+        Self { _env: env, ptrs }
+    }
+
+    /// # Safety
+    ///
+    /// This function returns a raw pointer that is not tied via lifetimes to
+    /// any provenance, the caller must ensure that this pointer is not used
+    /// after the lifetime of `&mut self` has ended.
+    pub unsafe fn envp(&mut self) -> *mut *mut c_char {
+        self.ptrs.as_mut_ptr()
+    }
+}
+
+/// Owns both a list of arguments and the list of pointers to them, for
+/// passing them to C without keeping track of the borrows between [`Args`],
+/// [`ArgPtrs`] and [`Argv`] (e.g. when calling C from library code).
+///
+/// The pointers are only handed out for the duration of a closure, see
+/// [`ArgvBox::with_argv`].
+pub struct ArgvBox {
+    args: Args,
+    ptrs: Vec<*mut c_char>,
+}
+
+// # Safety
+// The pointers only point into the owned arguments, and are never
+// dereferenced outside of `with_argv`, which requires `&mut self`.
+unsafe impl Send for ArgvBox {}
+unsafe impl Sync for ArgvBox {}
+
+impl ArgvBox {
+    pub fn new(args: Args) -> Self {
+        Self { args, ptrs: Vec::new() }
+    }
+
+    /// Saturates at `i32::MAX`, in which case `argv[argc]` isn't NULL, see
+    /// [`ArgvBox::try_argc`].
+    pub fn argc(&self) -> i32 {
+        saturating_argc(self.args.len())
+    }
+
+    /// Returns an error if there are more than `i32::MAX` arguments, see
+    /// [`Args::check_limits`].
+    pub fn try_argc(&self) -> Result<i32, LimitError> {
+        try_argc(self.args.len())
+    }
+
+    /// Calls `f` with `argc` and a NUL-terminated `argv`, which are valid for
+    /// the duration of the call.
+    ///
+    /// The pointer list is rebuilt for each call, so a permutation of `argv`
+    /// by a previous call is not observed by the next one. The arguments
+    /// themselves must not be modified by `f`.
+    pub fn with_argv<R>(&mut self, f: impl FnOnce(i32, *mut *mut c_char) -> R) -> R {
+        self.ptrs.clear();
+        self.ptrs.extend(self.args.0.iter().map(|arg| arg.as_ptr().cast_mut()));
+        self.ptrs.push(ptr::null_mut());
+
+        f(self.argc(), self.ptrs.as_mut_ptr())
+    }
+}
+
+impl From<Args> for ArgvBox {
+    fn from(args: Args) -> Self {
+        Self::new(args)
+    }
+}
+
+/// A genuinely mutable copy of a list of arguments, for C functions that
+/// permute `argv` or modify the arguments in place (e.g. `getopt` or
+/// `rte_eal_init`), whose changes can then be read back with
+/// [`MutArgv::read_back`].
+///
+/// Created by calling [`Args::into_mut`].
+pub struct MutArgv {
+    // never read directly, owns the buffers pointed to by `ptrs`
+    _bufs: Vec<Box<[u8]>>,
+    ptrs: Vec<*mut c_char>,
+}
+
+// # Safety
+// The pointers only point into the owned buffers, which are only accessed
+// through `&mut self` (or `&self` when reading back).
+unsafe impl Send for MutArgv {}
+unsafe impl Sync for MutArgv {}
+
+impl MutArgv {
+    fn new(args: Args) -> Self {
+        let mut bufs = args.0.into_iter().map(|arg| arg.into_bytes_with_nul().into_boxed_slice()).collect::<Vec<_>>();
+        let mut ptrs = bufs.iter_mut().map(|buf| buf.as_mut_ptr().cast()).collect::<Vec<_>>();
+        ptrs.push(ptr::null_mut());
+
+        Self { _bufs: bufs, ptrs }
+    }
+
+    /// Saturates at `i32::MAX`, in which case `argv[argc]` isn't NULL, see
+    /// [`MutArgv::try_argc`].
+    pub fn argc(&self) -> i32 {
+        saturating_argc(self.ptrs.len() - 1)
+    }
+
+    /// Returns an error if there are more than `i32::MAX` arguments, see
+    /// [`Args::check_limits`].
+    pub fn try_argc(&self) -> Result<i32, LimitError> {
+        try_argc(self.ptrs.len() - 1)
+    }
+
+    /// # Safety
+    ///
+    /// The returned pointer must not be used after `self` is dropped. The C
+    /// side may reorder the pointers and modify the arguments in place
+    /// (without writing past their NUL byte), but any pointer it stores in
+    /// `argv` must point to a NUL-terminated string that outlives `self`, and
+    /// the list must remain NUL-terminated.
+    pub unsafe fn argv(&mut self) -> *mut *mut c_char {
+        self.ptrs.as_mut_ptr()
+    }
+
+    /// Returns the arguments as currently found in `argv`, i.e. reflecting
+    /// any permutation or modification made by the C side. Arguments that
+    /// are not valid UTF-8 are converted lossily.
+    pub fn read_back(&self) -> Vec<String> {
+        self.ptrs
+            .iter()
+            .take_while(|ptr| !ptr.is_null())
+            .map(|&ptr| unsafe { CStr::from_ptr(ptr) }.to_string_lossy().into_owned())
+            .collect()
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::ffi::CStr;
-
     use super::*;
 
     #[test]
@@ -150,4 +636,130 @@ mod tests {
             assert_eq!(args, ARGS);
         }
     }
+
+    #[test]
+    fn test_argv_capture() {
+        let mut args = Args::new(["app", "-v"].map(str::to_string));
+        let mut ptrs = args.as_ptrs();
+        let mut argv = ptrs.as_argv();
+
+        unsafe {
+            assert_eq!(test_support::capture(argv.argc(), argv.argv()), 2);
+            test_support::assert_argv(argv.argc(), argv.argv(), &["app", "-v"]);
+        }
+        assert_eq!(test_support::take_captured().unwrap(), ["app", "-v"]);
+        assert_eq!(test_support::take_captured(), None);
+
+        let mut env = Env::new([("A".to_string(), "1".to_string())]);
+        unsafe { test_support::assert_argv(1, env.as_ptrs().envp(), &["A=1"]) };
+    }
+
+    #[test]
+    fn test_args_limits() {
+        let args = Args::new(["app", "-v"].map(str::to_string));
+        assert_eq!((args.len(), args.is_empty()), (2, false));
+        assert_eq!(args.size(), 4 + 3 + 3 * mem::size_of::<usize>());
+
+        args.check_limits(None).unwrap();
+        args.check_limits(Some(args.size())).unwrap();
+        assert_eq!(args.check_limits(Some(8)).err(), Some(LimitError::TooLarge { size: args.size(), max_size: 8 }));
+        assert_eq!(saturating_argc(usize::MAX), i32::MAX);
+        assert_eq!(try_argc(usize::MAX), Err(LimitError::TooManyArgs(usize::MAX)));
+        assert_eq!(args.into_mut().try_argc(), Ok(2));
+    }
+
+    #[test]
+    fn test_args_builder() {
+        let builder = ArgsBuilder::new("app").push_pair("-n", 4).push_if(false, "--no-huge").extend(["--", "-v"]);
+        assert_eq!(builder, ArgsBuilder(["app", "-n", "4", "--", "-v"].map(str::to_string).to_vec()));
+
+        let err = ArgsBuilder::new("app").push_pair_if(true, "--file-prefix", "a\0").build().err().unwrap();
+        assert_eq!(err.index(), 2);
+    }
+
+    #[test]
+    fn test_env() {
+        let mut env = Env::new([("A", "1"), ("B", "x=y")].map(|(k, v)| (k.to_string(), v.to_string())));
+        let mut ptrs = env.as_ptrs();
+
+        unsafe {
+            let envp = ptrs.envp();
+            assert_eq!(CStr::from_ptr(*envp).to_bytes(), b"A=1");
+            assert_eq!(CStr::from_ptr(*envp.add(1)).to_bytes(), b"B=x=y");
+            assert!((*envp.add(2)).is_null());
+        }
+
+        let try_new = |vars: &[(&str, &str)]| Env::try_new(vars.iter().map(|&(k, v)| (k.to_string(), v.to_string())));
+        assert_eq!(try_new(&[("A", "1"), ("", "1")]).err(), Some(EnvError::EmptyKey { index: 1 }));
+        assert_eq!(try_new(&[("A=B", "1")]).err(), Some(EnvError::KeyWithEquals { index: 0, position: 1 }));
+        assert_eq!(try_new(&[("A", "\0")]).err(), Some(EnvError::Nul { index: 0, nul_position: 2 }));
+    }
+
+    #[test]
+    fn test_args_cmdline() {
+        let args = Args::from_cmdline(r#"  -l 0-3 --vdev 'net_tap0,iface=a b' "x\"y" a\ b '' "#).unwrap();
+        let args = args.0.iter().map(|arg| arg.to_str().unwrap()).collect::<Vec<_>>();
+        assert_eq!(args, ["-l", "0-3", "--vdev", "net_tap0,iface=a b", "x\"y", "a b", ""]);
+
+        assert_eq!(Args::from_cmdline("-l '0-3").err(), Some(CmdlineError::UnbalancedQuote('\'')));
+        assert_eq!(Args::from_cmdline("-l \\").err(), Some(CmdlineError::TrailingBackslash));
+    }
+
+    #[test]
+    fn test_argv_box() {
+        const ARGS: [&str; 2] = ["hello", "world"];
+        let mut argv = ArgvBox::new(Args::new(ARGS.map(str::to_string)));
+
+        // a C function permuting its argv
+        let swap = |argc: i32, argv: *mut *mut c_char| unsafe {
+            assert_eq!(argc, 2);
+            assert!((*argv.add(2)).is_null());
+            ptr::swap(argv, argv.add(1));
+            CStr::from_ptr(*argv).to_str().unwrap().to_string()
+        };
+
+        assert_eq!(argv.with_argv(swap), "world");
+        assert_eq!(argv.with_argv(swap), "world");
+    }
+
+    #[test]
+    fn test_mut_argv() {
+        let mut argv = Args::new(["prog", "-v", "file"].map(str::to_string)).into_mut();
+
+        // a C function moving the non-option arguments first, and modifying
+        // one in place
+        unsafe {
+            assert_eq!(argv.argc(), 3);
+            let ptrs = argv.argv();
+            ptr::swap(ptrs.add(1), ptrs.add(2));
+            *(*ptrs.add(1)).add(1) = b'F' as c_char;
+        }
+
+        assert_eq!(argv.read_back(), ["prog", "fFle", "-v"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_args_os() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let arg = OsString::from_vec(vec![b'a', 0xff, b'z']);
+        let args = Args::from_os([OsString::from("hello"), arg.clone()]);
+        assert_eq!(args.0[1].as_bytes(), arg.as_bytes());
+
+        let err = Args::try_from_os([OsString::from_vec(vec![0xff, 0])]).err().unwrap();
+        assert_eq!((err.index(), err.nul_position()), (0, 1));
+    }
+
+    #[test]
+    fn test_args_nul() {
+        let args = || ["hello", "wo\0rld"].map(str::to_string);
+
+        let err = Args::try_new(args()).err().unwrap();
+        assert_eq!((err.index(), err.nul_position()), (1, 2));
+        assert_eq!(err.to_string(), "argument 1 contains a NUL byte at position 2");
+
+        let args = Args::new_lossy(args());
+        assert_eq!(args.0[1].as_bytes(), b"world");
+    }
 }
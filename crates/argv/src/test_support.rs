@@ -0,0 +1,69 @@
+//! Helpers for testing code passing arguments to C (enabled by the
+//! `test_support` feature), e.g. under Miri, which then reports any use of
+//! the pointers past their lifetime, or any leak of the arguments.
+
+use std::{
+    cell::RefCell,
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+};
+
+thread_local! {
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// A stand-in for a C entry point (`int main(int argc, char* argv[])`),
+/// which checks the arguments (see [`assert_argv_valid`]) and captures a copy
+/// of them for [`take_captured`], returning `argc`.
+///
+/// # Safety
+///
+/// Same as [`assert_argv_valid`].
+pub unsafe extern "C" fn capture(argc: c_int, argv: *mut *mut c_char) -> c_int {
+    let args = assert_argv_valid(argc, argv);
+    CAPTURED.with(|captured| *captured.borrow_mut() = Some(args));
+    argc
+}
+
+/// Returns the arguments captured by the last call to [`capture`] on this
+/// thread, if any.
+pub fn take_captured() -> Option<Vec<String>> {
+    CAPTURED.with(|captured| captured.borrow_mut().take())
+}
+
+/// Asserts that `argv` holds `argc` arguments followed by a NUL pointer, and
+/// that its pointers may be permuted (as done by e.g. `getopt`), returning
+/// (a lossy copy of) the arguments.
+///
+/// # Safety
+///
+/// `argv` must point to at least `argc + 1` pointers, each of them being NUL
+/// or pointing to a NUL-terminated string. Under Miri, violations of this are
+/// reported rather than being undefined behavior.
+pub unsafe fn assert_argv_valid(argc: c_int, argv: *mut *mut c_char) -> Vec<String> {
+    assert!(argc >= 0, "negative argc: {argc}");
+    assert!(!argv.is_null(), "NUL argv");
+
+    let argc = argc as usize;
+    assert!((*argv.add(argc)).is_null(), "argv isn't NUL-terminated after argc arguments");
+
+    (0..argc)
+        .map(|i| {
+            let arg = argv.add(i);
+            assert!(!(*arg).is_null(), "argument {i} is NUL");
+            // write the pointer back, which requires a mutable pointer list
+            arg.write(arg.read());
+            CStr::from_ptr(*arg).to_string_lossy().into_owned()
+        })
+        .collect()
+}
+
+/// Asserts that `argc` and `argv` hold exactly the `expected` arguments, see
+/// [`assert_argv_valid`].
+///
+/// # Safety
+///
+/// Same as [`assert_argv_valid`].
+pub unsafe fn assert_argv(argc: c_int, argv: *mut *mut c_char, expected: &[&str]) {
+    assert_eq!(assert_argv_valid(argc, argv), expected);
+}
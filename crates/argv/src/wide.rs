@@ -0,0 +1,115 @@
+//! Wide-character (UTF-16) arguments for `wmain`-style C entry points on
+//! Windows, i.e.:
+//!
+//! `int wmain(int argc, wchar_t* argv[]);`
+
+use std::{ffi::OsString, os::windows::ffi::OsStrExt, ptr};
+
+use crate::NulError;
+
+/// Create a clone of command line arguments, encoded into NUL-terminated
+/// UTF-16 strings.
+pub struct WideArgs(Vec<Vec<u16>>);
+
+impl WideArgs {
+    /// # Panics
+    ///
+    /// Panics if any of the arguments contains a NUL character, see
+    /// [`WideArgs::try_new`].
+    pub fn new(args: impl IntoIterator<Item = String>) -> Self {
+        Self::try_new(args).unwrap()
+    }
+
+    /// Returns an error identifying the first argument containing a NUL
+    /// character (whose position is in UTF-16 code units), if any.
+    pub fn try_new(args: impl IntoIterator<Item = String>) -> Result<Self, NulError> {
+        Self::try_from_wide(args.into_iter().map(|arg| arg.encode_utf16().collect()))
+    }
+
+    /// Creates the arguments from [`OsString`]s (e.g. returned by
+    /// [env::args_os](std::env::args_os)), which may not be valid UTF-16 but
+    /// are passed to C unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the arguments contains a NUL character.
+    pub fn from_os(args: impl IntoIterator<Item = OsString>) -> Self {
+        Self::try_from_wide(args.into_iter().map(|arg| arg.encode_wide().collect())).unwrap()
+    }
+
+    fn try_from_wide(args: impl Iterator<Item = Vec<u16>>) -> Result<Self, NulError> {
+        args.enumerate()
+            .map(|(index, mut arg)| match arg.iter().position(|&c| c == 0) {
+                Some(nul_position) => Err(NulError { index, nul_position }),
+                None => {
+                    arg.push(0);
+                    Ok(arg)
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn as_ptrs(&mut self) -> WideArgPtrs<'_> {
+        WideArgPtrs::new(self)
+    }
+}
+
+/// A NUL-terminated list of pointers pointing to the strings contained in a
+/// [`WideArgs`] struct, tied to its lifetime like [`ArgPtrs`](crate::ArgPtrs).
+///
+/// Created by calling [`WideArgs::as_ptrs`].
+pub struct WideArgPtrs<'a> {
+    args: &'a mut WideArgs,
+    ptrs: Vec<*mut u16>,
+}
+
+impl<'a> WideArgPtrs<'a> {
+    fn new(args: &'a mut WideArgs) -> Self {
+        let mut ptrs = args.0.iter_mut().map(|arg| arg.as_mut_ptr()).collect::<Vec<_>>();
+        ptrs.push(ptr::null_mut());
+
+        Self { args, ptrs }
+    }
+
+    /// # Safety
+    ///
+    /// This function returns a raw pointer that is not tied via lifetimes to
+    /// any provenance, the caller must ensure that this pointer is not used
+    /// after the lifetime of `&mut self` has ended.
+    pub unsafe fn argv(&mut self) -> *mut *mut u16 {
+        self.ptrs.as_mut_ptr()
+    }
+
+    /// Saturates at `i32::MAX`, like [`Argv::argc`](crate::Argv::argc).
+    pub fn argc(&self) -> i32 {
+        crate::saturating_argc(self.args.0.len())
+    }
+
+    /// Returns an error if there are more than `i32::MAX` arguments, like
+    /// [`Argv::try_argc`](crate::Argv::try_argc).
+    pub fn try_argc(&self) -> Result<i32, crate::LimitError> {
+        crate::try_argc(self.args.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wide_args() {
+        let mut args = WideArgs::new(["app", "é"].map(str::to_string));
+        let mut ptrs = args.as_ptrs();
+
+        unsafe {
+            assert_eq!(ptrs.argc(), 2);
+            let argv = ptrs.argv();
+            assert_eq!(std::slice::from_raw_parts(*argv.add(1), 2), [0xe9, 0]);
+            assert!((*argv.add(2)).is_null());
+        }
+
+        let err = WideArgs::try_new(["a\0".to_string()]).err().unwrap();
+        assert_eq!((err.index(), err.nul_position()), (0, 1));
+    }
+}
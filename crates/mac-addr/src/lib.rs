@@ -4,6 +4,7 @@ use std::{
     result, str,
 };
 
+use rand_core::RngCore;
 use zerocopy::{AsBytes, FromBytes, Unaligned};
 
 #[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
@@ -13,9 +14,50 @@ pub struct AddrParseError(());
 pub const ETHER_ADDR_LEN: u8 = 6;
 type MacAddrBuf = [u8; ETHER_ADDR_LEN as usize];
 
+/// The textual representation of a [`MacAddr`], see [`MacAddr::format`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Colon-separated octets, e.g. `18:2b:3c:4d:5e:6f`.
+    #[default]
+    Colon,
+    /// Hyphen-separated octets, e.g. `18-2b-3c-4d-5e-6f`.
+    Hyphen,
+    /// Dot-separated groups of two octets (as used by Cisco), e.g. `182b.3c4d.5e6f`.
+    Dotted,
+    /// Unseparated octets, e.g. `182b3c4d5e6f`.
+    Bare,
+}
+
+/// A [`MacAddr`] rendered in a given [`Format`], see [`MacAddr::format`].
+#[derive(Debug, Copy, Clone)]
+pub struct Formatted {
+    addr: MacAddrBuf,
+    format: Format,
+}
+
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, octet) in self.addr.iter().enumerate() {
+            match self.format {
+                Format::Colon if i > 0 => f.write_str(":")?,
+                Format::Hyphen if i > 0 => f.write_str("-")?,
+                Format::Dotted if i > 0 && i % 2 == 0 => f.write_str(".")?,
+                _ => {}
+            }
+            write!(f, "{:02x}", octet)?;
+        }
+        Ok(())
+    }
+}
+
+const MAX_VALUE: u64 = (1 << 48) - 1;
+
 /// A 48-bit (6 byte) buffer containing the MAC address
-#[derive(Debug, FromBytes, AsBytes, Unaligned, Copy, Clone, Default, PartialEq, Eq, Hash)]
-#[repr(packed)]
+///
+/// The address has an alignment of 1, so it can be embedded in (packed) headers, which should be read and written
+/// using [`MacAddr::read_from_prefix`] and [`MacAddr::write_to_prefix`] rather than by casting pointers.
+#[derive(Debug, FromBytes, AsBytes, Unaligned, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(C, packed)]
 pub struct MacAddr(MacAddrBuf);
 
 impl Deref for MacAddr {
@@ -44,6 +86,126 @@ impl MacAddr {
         MacAddr([a, b, c, d, e, f])
     }
 
+    /// Generates a random locally administered unicast address, like `rte_eth_random_addr`.
+    #[inline]
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> MacAddr {
+        let mut addr = MacAddrBuf::default();
+        rng.fill_bytes(&mut addr);
+        Self::local_unicast(addr)
+    }
+
+    /// Derives a locally administered unicast address from `seed` (e.g. an instance name), which is stable across
+    /// runs and builds, so that a virtual interface keeps its address.
+    #[inline]
+    pub fn derive_from<T: AsRef<[u8]>>(seed: T) -> MacAddr {
+        // 64 bit FNV-1a
+        let hash = seed
+            .as_ref()
+            .iter()
+            .fold(0xcbf2_9ce4_8422_2325_u64, |hash, &b| (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3));
+
+        let mut addr = MacAddrBuf::default();
+        addr.copy_from_slice(&hash.to_be_bytes()[..ETHER_ADDR_LEN as usize]);
+        Self::local_unicast(addr)
+    }
+
+    fn local_unicast(mut addr: MacAddrBuf) -> MacAddr {
+        // clear the I/G bit and set the U/L bit
+        addr[0] = (addr[0] & !0x01) | 0x02;
+        MacAddr(addr)
+    }
+
+    /// Parses an address in any of the [`Format`]s in a const context, see also [`mac!`].
+    ///
+    /// # Panics
+    /// Panics (i.e. fails to compile, when evaluated at compile time) if the address is invalid.
+    #[inline]
+    pub const fn parse_const(s: &str) -> MacAddr {
+        match parse(s.as_bytes()) {
+            Some(addr) => MacAddr(addr),
+            None => panic!("invalid MAC address syntax"),
+        }
+    }
+
+    /// Reads an address from the first 6 bytes of `bytes` (which need not be aligned), returning `None` if it's too
+    /// short.
+    #[inline]
+    pub fn read_from_prefix(bytes: &[u8]) -> Option<MacAddr> {
+        FromBytes::read_from_prefix(bytes)
+    }
+
+    /// Writes the address to the first 6 bytes of `bytes` (which need not be aligned), returning `None` if it's too
+    /// short.
+    #[inline]
+    pub fn write_to_prefix(&self, bytes: &mut [u8]) -> Option<()> {
+        AsBytes::write_to_prefix(self, bytes)
+    }
+
+    /// Creates an address from the lower 48 bits of `value` in big-endian (i.e. network) byte order, e.g.
+    /// `0x182b3c4d5e6f` for `18:2b:3c:4d:5e:6f`.
+    #[inline]
+    pub const fn from_u64(value: u64) -> MacAddr {
+        let b = value.to_be_bytes();
+        MacAddr([b[2], b[3], b[4], b[5], b[6], b[7]])
+    }
+
+    /// Returns the address as the lower 48 bits of a `u64` in big-endian byte order, see [`MacAddr::from_u64`].
+    #[inline]
+    pub const fn to_u64(&self) -> u64 {
+        let [a, b, c, d, e, f] = self.0;
+        u64::from_be_bytes([0, 0, a, b, c, d, e, f])
+    }
+
+    /// Creates an address from the lower 48 bits of `value` in little-endian byte order (e.g. as stored in some NIC
+    /// registers), e.g. `0x6f5e4d3c2b18` for `18:2b:3c:4d:5e:6f`.
+    #[inline]
+    pub const fn from_u64_le(value: u64) -> MacAddr {
+        let b = value.to_le_bytes();
+        MacAddr([b[0], b[1], b[2], b[3], b[4], b[5]])
+    }
+
+    /// Returns the address as the lower 48 bits of a `u64` in little-endian byte order, see
+    /// [`MacAddr::from_u64_le`].
+    #[inline]
+    pub const fn to_u64_le(&self) -> u64 {
+        let [a, b, c, d, e, f] = self.0;
+        u64::from_le_bytes([a, b, c, d, e, f, 0, 0])
+    }
+
+    /// Returns the organizationally unique identifier (i.e. vendor prefix) of the address, i.e. its first 3 octets.
+    #[inline]
+    pub const fn oui(&self) -> [u8; 3] {
+        let [a, b, c, ..] = self.0;
+        [a, b, c]
+    }
+
+    /// Checks whether the first `len_bits` bits of the address match those of `prefix`, e.g. with a length of 24 for
+    /// matching the address' [OUI](MacAddr::oui).
+    ///
+    /// # Panics
+    /// Panics if `len_bits` is larger than 48.
+    #[inline]
+    pub fn matches_prefix(&self, prefix: &MacAddr, len_bits: u8) -> bool {
+        assert!(len_bits <= 48, "invalid prefix length");
+        let mask = MAX_VALUE & !(MAX_VALUE >> len_bits);
+        self.to_u64() & mask == prefix.to_u64() & mask
+    }
+
+    /// Returns the address following this one, or `None` if this is the last (i.e. broadcast) address.
+    #[inline]
+    pub const fn checked_next(&self) -> Option<MacAddr> {
+        self.checked_add(1)
+    }
+
+    /// Returns the address `offset` addresses after this one, or `None` if it would exceed the 48 bit address space.
+    #[inline]
+    pub const fn checked_add(&self, offset: u64) -> Option<MacAddr> {
+        match self.to_u64().checked_add(offset) {
+            Some(value) if value <= MAX_VALUE => Some(MacAddr::from_u64(value)),
+            _ => None,
+        }
+    }
+
     /// Returns the six eight-bit integers that make up this address.
     #[inline]
     pub const fn octets(&self) -> MacAddrBuf {
@@ -60,6 +222,12 @@ impl MacAddr {
         Self::default()
     }
 
+    /// Returns a value rendering this address in the given format when displayed.
+    #[inline]
+    pub fn format(&self, format: Format) -> Formatted {
+        Formatted { addr: self.0, format }
+    }
+
     /// Check if an Ethernet address is filled with zeros.
     #[inline]
     pub fn is_zero(&self) -> bool {
@@ -70,15 +238,49 @@ impl MacAddr {
     pub fn is_broadcast(&self) -> bool {
         *self == Self::BROADCAST
     }
+
+    /// Check if an Ethernet address is a multicast (group) address, i.e. if the I/G bit is set.
+    #[inline]
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Check if an Ethernet address is a unicast (individual) address, i.e. if the I/G bit is clear.
+    #[inline]
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+
+    /// Check if an Ethernet address is universally administered (i.e. assigned by its manufacturer), i.e. if the U/L
+    /// bit is clear.
+    #[inline]
+    pub fn is_universal(&self) -> bool {
+        !self.is_local()
+    }
+
+    /// Check if an Ethernet address is locally administered, i.e. if the U/L bit is set.
+    #[inline]
+    pub fn is_local(&self) -> bool {
+        self.0[0] & 0x02 != 0
+    }
+
+    /// Check if an Ethernet address is one of the link-local group addresses reserved by IEEE 802.1D (i.e.
+    /// `01:80:c2:00:00:00` to `01:80:c2:00:00:0f`, e.g. used by STP or LLDP), which bridges don't forward.
+    #[inline]
+    pub fn is_link_local(&self) -> bool {
+        matches!(self.0, [0x01, 0x80, 0xc2, 0x00, 0x00, last] if last <= 0x0f)
+    }
+
+    /// Check if an Ethernet address is a valid address assigned to an interface, i.e. a non-zero unicast address.
+    #[inline]
+    pub fn is_valid_assigned(&self) -> bool {
+        self.is_unicast() && !self.is_zero()
+    }
 }
 
 impl fmt::Display for MacAddr {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(
-            f,
-            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
-        )
+        fmt::Display::fmt(&self.format(Format::Colon), f)
     }
 }
 
@@ -88,18 +290,183 @@ impl From<MacAddrBuf> for MacAddr {
     }
 }
 
+/// A half-open range of sequential MAC addresses, e.g. for allocating the addresses of VFs or vdevs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacAddrRange {
+    start: u64,
+    end: u64,
+}
+
+impl MacAddrRange {
+    /// Creates the range of addresses from `start` up to (but excluding) `end`.
+    #[inline]
+    pub fn new(start: MacAddr, end: MacAddr) -> Self {
+        Self { start: start.to_u64(), end: end.to_u64().max(start.to_u64()) }
+    }
+
+    /// Creates the range of `len` addresses starting at `start`, or returns `None` if it would exceed the 48 bit
+    /// address space.
+    #[inline]
+    pub fn with_len(start: MacAddr, len: u64) -> Option<Self> {
+        let start = start.to_u64();
+        let end = start.checked_add(len).filter(|&end| end <= MAX_VALUE + 1)?;
+        Some(Self { start, end })
+    }
+
+    #[inline]
+    pub fn contains(&self, addr: &MacAddr) -> bool {
+        (self.start..self.end).contains(&addr.to_u64())
+    }
+}
+
+impl Iterator for MacAddrRange {
+    type Item = MacAddr;
+
+    #[inline]
+    fn next(&mut self) -> Option<MacAddr> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        Some(MacAddr::from_u64(self.start - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.end - self.start) as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for MacAddrRange {
+    #[inline]
+    fn next_back(&mut self) -> Option<MacAddr> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        Some(MacAddr::from_u64(self.end))
+    }
+}
+
+impl ExactSizeIterator for MacAddrRange {}
+
+/// Parses a MAC address in any of the [`Format`]s, e.g. `18:2b:3c:4d:5e:6f`, `18-2b-3c-4d-5e-6f`, `182b.3c4d.5e6f` or
+/// `182b3c4d5e6f`. The octets of the colon format may also have a single digit, e.g. `2:0:0:0:0:1`.
 impl str::FromStr for MacAddr {
     type Err = AddrParseError;
 
     fn from_str(s: &str) -> result::Result<Self, Self::Err> {
-        let addr: Vec<u8> = s
-            .split(':')
-            .map(|part| u8::from_str_radix(part, 16))
-            .collect::<result::Result<_, _>>()
-            .map_err(|_| AddrParseError(()))?;
+        parse(s.as_bytes()).map(Self).ok_or(AddrParseError(()))
+    }
+}
 
-        MacAddr::from_bytes(addr.as_slice())
+const fn parse(s: &[u8]) -> Option<MacAddrBuf> {
+    let (sep, group_len) = match s.len() {
+        11..=17 if s[1] == b':' || s[2] == b':' => return parse_colon(s),
+        17 if s[2] == b'-' => (b'-', 2),
+        14 => (b'.', 4),
+        12 => (0, 12),
+        _ => return None,
+    };
+
+    let mut addr = [0; ETHER_ADDR_LEN as usize];
+    let mut nibble = 0;
+    let mut i = 0;
+    while i < s.len() {
+        let c = s[i];
+        if i % (group_len + 1) == group_len {
+            if c != sep {
+                return None;
+            }
+        } else {
+            let Some(digit) = hex_digit(c) else { return None };
+            addr[nibble / 2] |= digit << (4 * (1 - nibble % 2));
+            nibble += 1;
+        }
+        i += 1;
     }
+
+    Some(addr)
+}
+
+/// Parses the colon format, whose octets may also have a single digit (e.g. `1:2:3:4:5:6`).
+const fn parse_colon(s: &[u8]) -> Option<MacAddrBuf> {
+    let mut addr = [0; ETHER_ADDR_LEN as usize];
+    let (mut octet, mut digits) = (0, 0);
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] == b':' {
+            if digits == 0 || octet == addr.len() - 1 {
+                return None;
+            }
+            octet += 1;
+            digits = 0;
+        } else {
+            let Some(digit) = hex_digit(s[i]) else { return None };
+            if digits == 2 {
+                return None;
+            }
+            addr[octet] = addr[octet] << 4 | digit;
+            digits += 1;
+        }
+        i += 1;
+    }
+
+    if octet != addr.len() - 1 || digits == 0 {
+        return None;
+    }
+    Some(addr)
+}
+
+const fn hex_digit(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl From<ffi::rte_ether_addr> for MacAddr {
+    fn from(addr: ffi::rte_ether_addr) -> MacAddr {
+        MacAddr(addr.addr_bytes)
+    }
+}
+
+#[cfg(feature = "ffi")]
+impl From<MacAddr> for ffi::rte_ether_addr {
+    fn from(addr: MacAddr) -> ffi::rte_ether_addr {
+        ffi::rte_ether_addr { addr_bytes: addr.0 }
+    }
+}
+
+/// Expands the address to an EUI-64 by inserting `ff:fe` between the OUI and the NIC specific part.
+///
+/// Note that IPv6 interface identifiers use the modified EUI-64, which additionally inverts the U/L bit.
+impl From<MacAddr> for [u8; 8] {
+    fn from(addr: MacAddr) -> [u8; 8] {
+        let [a, b, c, d, e, f] = addr.0;
+        [a, b, c, 0xff, 0xfe, d, e, f]
+    }
+}
+
+/// Creates a [`MacAddr`] constant from a string literal in any of the [`Format`]s, failing to compile if the literal
+/// is invalid.
+///
+/// ```
+/// use mac_addr::{mac, MacAddr};
+///
+/// const GATEWAY: MacAddr = mac!("18:2b:3c:4d:5e:6f");
+/// assert_eq!(GATEWAY, MacAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f));
+/// ```
+#[macro_export]
+macro_rules! mac {
+    ($s:literal) => {{
+        const ADDR: $crate::MacAddr = $crate::MacAddr::parse_const($s);
+        ADDR
+    }};
 }
 
 #[cfg(test)]
@@ -119,8 +486,159 @@ mod tests {
         assert_eq!(addr, MacAddr::from_str("18:2b:3c:4d:5e:6f").unwrap());
 
         MacAddr::from_str("18:2b:3c:4d:5e:6f:XX").unwrap_err();
+        MacAddr::from_str("18:2b").unwrap_err();
 
         assert!(!addr.is_zero());
         assert!(MacAddr::zeroed().is_zero());
     }
+
+    #[test]
+    fn test_macaddr_formats() {
+        let addr = MacAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f);
+
+        for (format, s) in [
+            (Format::Colon, "18:2b:3c:4d:5e:6f"),
+            (Format::Hyphen, "18-2b-3c-4d-5e-6f"),
+            (Format::Dotted, "182b.3c4d.5e6f"),
+            (Format::Bare, "182b3c4d5e6f"),
+        ] {
+            assert_eq!(addr.format(format).to_string(), s);
+            assert_eq!(MacAddr::from_str(s).unwrap(), addr);
+            assert_eq!(MacAddr::from_str(&s.to_uppercase()).unwrap(), addr);
+        }
+
+        for s in ["18:2b:3c-4d:5e:6f", "1:2b:3c:4d:5e:6ff", "182b:3c4d:5e6f", "182b.3c4d.5e6", "+8:2b:3c:4d:5e:6f", ""]
+        {
+            MacAddr::from_str(s).unwrap_err();
+        }
+
+        // octets of the colon format may have a single digit
+        assert_eq!(MacAddr::from_str("1:2:3:a:b:c").unwrap(), MacAddr::new(1, 2, 3, 0xa, 0xb, 0xc));
+        assert_eq!(MacAddr::from_str("18:2b:3c:4d:5e:f").unwrap(), MacAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0xf));
+        for s in ["1:2:3:4:5", "1:2:3:4:5:6:7", "1::3:4:5:6", "1:2:3:4:5:", "1-2-3-4-5-6"] {
+            MacAddr::from_str(s).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn test_macaddr_conversions() {
+        const ADDR: MacAddr = mac!("18:2b:3c:4d:5e:6f");
+
+        assert_eq!(ADDR, MacAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f));
+        assert_eq!(mac!("182b.3c4d.5e6f"), ADDR);
+
+        assert_eq!(ADDR.to_u64(), 0x182b_3c4d_5e6f);
+        assert_eq!(MacAddr::from_u64(0xffff_182b_3c4d_5e6f), ADDR);
+        assert_eq!(MacAddr::from_u64(ADDR.to_u64()), ADDR);
+
+        assert_eq!(ADDR.to_u64_le(), 0x6f5e_4d3c_2b18);
+        assert_eq!(MacAddr::from_u64_le(0x6f5e_4d3c_2b18), ADDR);
+
+        #[derive(FromBytes, AsBytes, Unaligned)]
+        #[repr(C, packed)]
+        struct Hdr {
+            kind: u8,
+            addr: MacAddr,
+        }
+
+        let mut buf = [0; 8];
+        ADDR.write_to_prefix(&mut buf[1..]).unwrap();
+        assert_eq!(MacAddr::read_from_prefix(&buf[1..]), Some(ADDR));
+        assert_eq!({ Hdr::read_from_prefix(&buf[..]).unwrap().addr }, ADDR);
+        assert!(MacAddr::read_from_prefix(&buf[3..]).is_none());
+        assert!(ADDR.write_to_prefix(&mut buf[3..]).is_none());
+
+        assert_eq!(<[u8; 8]>::from(ADDR), [0x18, 0x2b, 0x3c, 0xff, 0xfe, 0x4d, 0x5e, 0x6f]);
+    }
+
+    #[test]
+    fn test_macaddr_sequence() {
+        let addr = mac!("02:00:00:00:00:ff");
+
+        assert_eq!(addr.checked_next(), Some(mac!("02:00:00:00:01:00")));
+        assert_eq!(addr.checked_add(0x101), Some(mac!("02:00:00:00:02:00")));
+        assert_eq!(MacAddr::BROADCAST.checked_next(), None);
+        assert_eq!(addr.checked_add(u64::MAX), None);
+        assert!(addr < addr.checked_next().unwrap());
+
+        let range = MacAddrRange::with_len(addr, 3).unwrap();
+        assert_eq!(range.len(), 3);
+        assert!(range.contains(&mac!("02:00:00:00:01:01")) && !range.contains(&mac!("02:00:00:00:01:02")));
+        assert_eq!(
+            range.rev().collect::<Vec<_>>(),
+            [mac!("02:00:00:00:01:01"), mac!("02:00:00:00:01:00"), mac!("02:00:00:00:00:ff")]
+        );
+
+        assert_eq!(MacAddrRange::with_len(MacAddr::BROADCAST, 1).unwrap().collect::<Vec<_>>(), [MacAddr::BROADCAST]);
+        assert!(MacAddrRange::with_len(MacAddr::BROADCAST, 2).is_none());
+        assert_eq!(MacAddrRange::new(addr, MacAddr::zeroed()).count(), 0);
+    }
+
+    #[test]
+    fn test_macaddr_prefix() {
+        let addr = mac!("18:2b:3c:4d:5e:6f");
+
+        assert_eq!(addr.oui(), [0x18, 0x2b, 0x3c]);
+        assert!(addr.matches_prefix(&mac!("18:2b:3c:00:00:00"), 24));
+        assert!(!addr.matches_prefix(&mac!("18:2b:3d:00:00:00"), 24));
+        assert!(addr.matches_prefix(&mac!("18:2b:3d:00:00:00"), 23));
+        assert!(addr.matches_prefix(&MacAddr::BROADCAST, 0));
+        assert!(addr.matches_prefix(&addr, 48) && !addr.matches_prefix(&mac!("18:2b:3c:4d:5e:6e"), 48));
+    }
+
+    #[test]
+    fn test_macaddr_generation() {
+        struct Rng(u64);
+
+        impl RngCore for Rng {
+            fn next_u32(&mut self) -> u32 {
+                self.next_u64() as u32
+            }
+
+            fn next_u64(&mut self) -> u64 {
+                self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                self.0
+            }
+
+            fn fill_bytes(&mut self, dest: &mut [u8]) {
+                rand_core::impls::fill_bytes_via_next(self, dest)
+            }
+
+            fn try_fill_bytes(&mut self, dest: &mut [u8]) -> result::Result<(), rand_core::Error> {
+                self.fill_bytes(dest);
+                Ok(())
+            }
+        }
+
+        let mut rng = Rng(0);
+        for _ in 0..16 {
+            let addr = MacAddr::random(&mut rng);
+            assert!(addr.is_unicast() && addr.is_local());
+        }
+        assert_ne!(MacAddr::random(&mut rng), MacAddr::random(&mut rng));
+
+        let addr = MacAddr::derive_from("vdev0");
+        assert!(addr.is_unicast() && addr.is_local());
+        // derived addresses must not change across releases
+        assert_eq!(addr, MacAddr::from_str("92:3c:b9:bd:da:4a").unwrap());
+        assert_eq!(addr, MacAddr::derive_from(b"vdev0"));
+        assert_ne!(addr, MacAddr::derive_from("vdev1"));
+    }
+
+    #[test]
+    fn test_macaddr_classification() {
+        let addr = MacAddr::new(0x18, 0x2b, 0x3c, 0x4d, 0x5e, 0x6f);
+        assert!(addr.is_unicast() && addr.is_universal() && addr.is_valid_assigned());
+        assert!(!addr.is_multicast() && !addr.is_local() && !addr.is_link_local());
+
+        let addr = MacAddr::new(0x02, 0, 0, 0, 0, 1);
+        assert!(addr.is_unicast() && addr.is_local());
+
+        let addr = MacAddr::new(0x01, 0x80, 0xc2, 0, 0, 0x0e);
+        assert!(addr.is_multicast() && addr.is_link_local() && !addr.is_valid_assigned());
+        assert!(!MacAddr::new(0x01, 0x80, 0xc2, 0, 0, 0x10).is_link_local());
+
+        assert!(MacAddr::BROADCAST.is_broadcast() && MacAddr::BROADCAST.is_multicast());
+        assert!(!MacAddr::zeroed().is_valid_assigned());
+    }
 }
@@ -0,0 +1,181 @@
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+    result, str,
+};
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid IP network syntax")]
+pub struct NetParseError(());
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[error("invalid IP prefix length")]
+pub struct PrefixLenError(());
+
+macro_rules! ip_net {
+    ($(#[$attr:meta])* $name:ident, $addr:ty, $bits:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name {
+            addr: $addr,
+            prefix_len: u8,
+        }
+
+        impl $name {
+            /// The maximal prefix length, i.e. the length of an address in bits.
+            pub const MAX_PREFIX_LEN: u8 = <$bits>::BITS as u8;
+
+            /// Creates a network from an address (whose host bits are kept, see [`Self::trunc`]) and a prefix length.
+            #[inline]
+            pub const fn new(addr: $addr, prefix_len: u8) -> result::Result<Self, PrefixLenError> {
+                if prefix_len > Self::MAX_PREFIX_LEN {
+                    return Err(PrefixLenError(()));
+                }
+                Ok(Self { addr, prefix_len })
+            }
+
+            /// Returns the address the network was created with, which may have host bits set.
+            #[inline]
+            pub const fn addr(&self) -> $addr {
+                self.addr
+            }
+
+            #[inline]
+            pub const fn prefix_len(&self) -> u8 {
+                self.prefix_len
+            }
+
+            /// Returns the mask of the network's prefix, e.g. `255.255.255.0` for a `/24`.
+            #[inline]
+            pub fn netmask(&self) -> $addr {
+                <$addr>::from(self.mask())
+            }
+
+            /// Returns the network address, i.e. the address with its host bits cleared, as used (along with the
+            /// prefix length) as the key of a route or rule.
+            #[inline]
+            pub fn network(&self) -> $addr {
+                <$addr>::from(<$bits>::from(self.addr) & self.mask())
+            }
+
+            /// Returns the network with its host bits cleared.
+            #[inline]
+            pub fn trunc(&self) -> Self {
+                Self { addr: self.network(), prefix_len: self.prefix_len }
+            }
+
+            /// Checks whether `addr` is part of the network.
+            #[inline]
+            pub fn contains(&self, addr: &$addr) -> bool {
+                <$bits>::from(*addr) & self.mask() == <$bits>::from(self.network())
+            }
+
+            /// Checks whether `other` is a (not necessarily strict) subnet of the network.
+            #[inline]
+            pub fn contains_net(&self, other: &Self) -> bool {
+                other.prefix_len >= self.prefix_len && self.contains(&other.addr)
+            }
+
+            fn mask(&self) -> $bits {
+                <$bits>::MAX.checked_shl(u32::from(Self::MAX_PREFIX_LEN - self.prefix_len)).unwrap_or(0)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}/{}", self.addr, self.prefix_len)
+            }
+        }
+
+        /// Parses a network in CIDR notation, e.g. `10.0.0.0/8`.
+        impl str::FromStr for $name {
+            type Err = NetParseError;
+
+            fn from_str(s: &str) -> result::Result<Self, Self::Err> {
+                let (addr, prefix_len) = s.split_once('/').ok_or(NetParseError(()))?;
+                if !prefix_len.bytes().all(|c| c.is_ascii_digit()) {
+                    return Err(NetParseError(()));
+                }
+
+                let addr = addr.parse().map_err(|_| NetParseError(()))?;
+                let prefix_len = prefix_len.parse().map_err(|_| NetParseError(()))?;
+                Self::new(addr, prefix_len).map_err(|_| NetParseError(()))
+            }
+        }
+
+        /// Returns the network address and the prefix length.
+        impl From<$name> for ($addr, u8) {
+            fn from(net: $name) -> ($addr, u8) {
+                (net.network(), net.prefix_len)
+            }
+        }
+
+        /// Returns the network made of the single address.
+        impl From<$addr> for $name {
+            fn from(addr: $addr) -> $name {
+                $name { addr, prefix_len: $name::MAX_PREFIX_LEN }
+            }
+        }
+    };
+}
+
+ip_net!(
+    /// An IPv4 network, i.e. an address with a prefix length, e.g. `10.0.0.0/8`.
+    Ipv4Net,
+    Ipv4Addr,
+    u32
+);
+
+ip_net!(
+    /// An IPv6 network, i.e. an address with a prefix length, e.g. `fd00::/8`.
+    Ipv6Net,
+    Ipv6Addr,
+    u128
+);
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_ipv4net() {
+        let net = Ipv4Net::from_str("10.1.2.3/16").unwrap();
+
+        assert_eq!(net.addr(), Ipv4Addr::new(10, 1, 2, 3));
+        assert_eq!(net.prefix_len(), 16);
+        assert_eq!(net.netmask(), Ipv4Addr::new(255, 255, 0, 0));
+        assert_eq!(net.network(), Ipv4Addr::new(10, 1, 0, 0));
+        assert_eq!(net.trunc().to_string(), "10.1.0.0/16");
+        assert_eq!(<(Ipv4Addr, u8)>::from(net), (Ipv4Addr::new(10, 1, 0, 0), 16));
+
+        assert!(net.contains(&Ipv4Addr::new(10, 1, 255, 255)));
+        assert!(!net.contains(&Ipv4Addr::new(10, 2, 0, 0)));
+        assert!(net.contains_net(&Ipv4Net::from_str("10.1.128.0/17").unwrap()));
+        assert!(!net.contains_net(&Ipv4Net::from_str("10.0.0.0/8").unwrap()));
+
+        let any = Ipv4Net::from_str("0.0.0.0/0").unwrap();
+        assert_eq!(any.netmask(), Ipv4Addr::UNSPECIFIED);
+        assert!(any.contains(&Ipv4Addr::BROADCAST));
+        assert_eq!(Ipv4Net::from(Ipv4Addr::LOCALHOST).netmask(), Ipv4Addr::BROADCAST);
+
+        for s in ["10.0.0.0/33", "10.0.0.0", "10.0.0.0/+8", "10.0.0/8", "fd00::/8"] {
+            Ipv4Net::from_str(s).unwrap_err();
+        }
+        Ipv4Net::new(Ipv4Addr::UNSPECIFIED, 33).unwrap_err();
+    }
+
+    #[test]
+    fn test_ipv6net() {
+        let net = Ipv6Net::from_str("fd00:1::1/32").unwrap();
+
+        assert_eq!(net.network(), Ipv6Addr::new(0xfd00, 1, 0, 0, 0, 0, 0, 0));
+        assert_eq!(net.netmask(), Ipv6Addr::new(0xffff, 0xffff, 0, 0, 0, 0, 0, 0));
+        assert!(net.contains(&Ipv6Addr::new(0xfd00, 1, 0xffff, 0, 0, 0, 0, 1)));
+        assert!(!net.contains(&Ipv6Addr::LOCALHOST));
+        assert_eq!(net.to_string(), "fd00:1::1/32");
+
+        Ipv6Net::from_str("fd00::/129").unwrap_err();
+    }
+}
@@ -0,0 +1,43 @@
+use std::{env, ffi::OsString, path::PathBuf};
+
+/// DPDK's installation prefix (e.g. `/opt/dpdk-aarch64`), when not found by pkg-config's default search path.
+pub const PREFIX_ENV: &str = "DPDK_PREFIX";
+
+/// Reads the target-specific variant of an environment variable (e.g. `PKG_CONFIG_SYSROOT_DIR_aarch64-unknown-linux-gnu`
+/// or `PKG_CONFIG_SYSROOT_DIR_aarch64_unknown_linux_gnu`), falling back to the variable itself, like pkg-config does.
+fn targeted_env_var(name: &str) -> Option<OsString> {
+    let target = env::var("TARGET").unwrap();
+    [format!("{name}_{target}"), format!("{name}_{}", target.replace('-', "_")), name.to_owned()].into_iter().find_map(
+        |var| {
+            println!("cargo:rerun-if-env-changed={var}");
+            env::var_os(var)
+        },
+    )
+}
+
+/// Points pkg-config at the DPDK under [`PREFIX_ENV`] (if set), allowing it even when cross-compiling as the prefix
+/// then holds the target's DPDK. pkg-config itself handles `PKG_CONFIG_SYSROOT_DIR` (prefixing the include and link
+/// search paths with it) and picks up a cross pkg-config binary from `PKG_CONFIG`, both possibly target-specific.
+pub fn configure_pkg_config() {
+    println!("cargo:rerun-if-env-changed={PREFIX_ENV}");
+    let Some(prefix) = env::var_os(PREFIX_ENV).map(PathBuf::from) else { return };
+
+    let mut paths = vec![prefix.join("lib/pkgconfig"), prefix.join("lib64/pkgconfig")];
+    // i.e. Debian's multiarch directory, e.g. lib/aarch64-linux-gnu
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap();
+    let target_env = env::var("CARGO_CFG_TARGET_ENV").unwrap();
+    paths.push(prefix.join(format!("lib/{arch}-linux-{target_env}/pkgconfig")));
+    paths.extend(env::var_os("PKG_CONFIG_PATH").iter().flat_map(env::split_paths));
+
+    env::set_var("PKG_CONFIG_PATH", env::join_paths(paths).unwrap());
+    env::set_var("PKG_CONFIG_ALLOW_CROSS", "1");
+}
+
+/// Returns the flags to compile DPDK's headers against the sysroot pkg-config uses, if any, so that their own
+/// dependencies (e.g. libc's headers) are found there too.
+pub fn sysroot_args() -> Vec<String> {
+    match targeted_env_var("PKG_CONFIG_SYSROOT_DIR") {
+        Some(sysroot) => vec![format!("--sysroot={}", sysroot.to_str().unwrap())],
+        None => vec![],
+    }
+}
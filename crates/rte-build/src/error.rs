@@ -0,0 +1,79 @@
+use std::{env, fmt, path::PathBuf};
+
+use crate::{cross, version::Version};
+
+/// The pkg-config directories DPDK is commonly installed to (by its `meson install`, or distro packages), looked through
+/// for candidates when pkg-config doesn't find it.
+const PKG_CONFIG_DIRS: &[&str] = &["/usr/local/lib/pkgconfig", "/usr/local/lib64/pkgconfig", "/usr/lib/pkgconfig"];
+
+/// Why DPDK couldn't be bound, with guidance on fixing it.
+#[derive(Debug)]
+pub enum BuildError {
+    /// pkg-config doesn't find DPDK (or DPDK's own dependencies).
+    NotFound { supported: String, source: Box<pkg_config::Error> },
+    /// pkg-config's version of DPDK isn't a DPDK version.
+    InvalidVersion(String),
+    /// The installed DPDK isn't one of the releases enabled by the `dpdk-*` features.
+    UnsupportedVersion { found: Version, supported: String },
+    /// pkg-config doesn't find the libraries to link, e.g. DPDK's static ones when it was only built as shared ones.
+    Link(Box<pkg_config::Error>),
+}
+
+/// Returns the `libdpdk.pc` files in the common installation directories, which pkg-config may not be searching.
+fn candidates() -> Vec<PathBuf> {
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let multiarch =
+        [format!("/usr/local/lib/{arch}-linux-gnu/pkgconfig"), format!("/usr/lib/{arch}-linux-gnu/pkgconfig")];
+
+    PKG_CONFIG_DIRS
+        .iter()
+        .map(PathBuf::from)
+        .chain(multiarch.iter().map(PathBuf::from))
+        .map(|dir| dir.join("libdpdk.pc"))
+        .filter(|pc| pc.exists())
+        .collect()
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound { supported, source } => {
+                writeln!(f, "couldn't find DPDK (rte-sys supports versions {supported}): {source}")?;
+                let candidates = candidates();
+                if !candidates.is_empty() {
+                    writeln!(f, "\nDPDK seems to be installed at:")?;
+                    for candidate in &candidates {
+                        writeln!(f, "    {}", candidate.display())?;
+                    }
+                }
+                write!(
+                    f,
+                    "\nadd the directory of DPDK's libdpdk.pc to PKG_CONFIG_PATH, or set {} to its installation \
+                     prefix, or enable the vendored feature to build it",
+                    cross::PREFIX_ENV,
+                )
+            }
+            Self::InvalidVersion(version) => write!(f, "couldn't parse the version of DPDK: {version:?}"),
+            Self::UnsupportedVersion { found, supported } => write!(
+                f,
+                "found DPDK {found}, but rte-sys is built for versions {supported} (see its dpdk-* features), point \
+                 PKG_CONFIG_PATH or {} at a supported release",
+                cross::PREFIX_ENV,
+            ),
+            Self::Link(source) => write!(
+                f,
+                "couldn't link DPDK: {source}\n\nenable the dynamic feature to link its shared libraries, or build it \
+                 with meson's --default-library=static for the static ones"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::NotFound { source, .. } | Self::Link(source) => Some(&**source),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Finding the installed DPDK from build scripts, the way rte-sys does, e.g. for a crate to only build its DPDK backend
+//! when DPDK is available:
+//! ```rust,ignore
+//! // build.rs
+//! println!("cargo:rustc-check-cfg=cfg(dpdk)");
+//! match rte_build::try_probe() {
+//!     Ok(_) => println!("cargo:rustc-cfg=dpdk"),
+//!     Err(err) => println!("cargo:warning=building without DPDK: {err}"),
+//! }
+//! ```
+//!
+//! Failures are returned (with guidance on fixing them) rather than panicking, so that the caller decides whether DPDK
+//! is required.
+//!
+//! DPDK can then be linked like rte-sys links it, with the driver libraries tuned for the deployed NICs (also through
+//! [`KEEP_DRIVERS_ENV`] and [`DROP_DRIVERS_ENV`]):
+//! ```rust,ignore
+//! let drivers = rte_build::Drivers::new().keep_driver("rte_net_i40e").drop_driver_glob("rte_event_*");
+//! rte_build::link_static(&drivers.with_env_overrides())?;
+//! ```
+
+mod cross;
+mod drivers;
+mod error;
+mod link;
+mod version;
+
+pub use cross::{configure_pkg_config, sysroot_args, PREFIX_ENV};
+pub use drivers::{Drivers, DROP_DRIVERS_ENV, KEEP_DRIVERS_ENV};
+pub use error::BuildError;
+pub use link::{link_dynamic, link_static};
+pub use version::{Version, LTS_RELEASES};
+
+/// The installed DPDK.
+#[derive(Debug)]
+pub struct Dpdk {
+    /// What pkg-config found, i.e. the include paths and the libraries to link.
+    pub library: pkg_config::Library,
+    pub version: Version,
+}
+
+/// Finds the installed DPDK (under [`PREFIX_ENV`] if set), accepting any release rte-sys supports.
+pub fn try_probe() -> Result<Dpdk, BuildError> {
+    let (oldest, newest) = (LTS_RELEASES[0], LTS_RELEASES[LTS_RELEASES.len() - 1]);
+    probe(oldest, Version(newest.0, newest.1, u32::MAX))
+}
+
+/// Finds the installed DPDK (under [`PREFIX_ENV`] if set), checking that its version is in `min..=max`.
+pub fn probe(min: Version, max: Version) -> Result<Dpdk, BuildError> {
+    configure_pkg_config();
+    let supported = format!("{}.{} to {}.{}", min.0, min.1, max.0, max.1);
+
+    let library = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .env_metadata(false)
+        .probe("libdpdk")
+        .map_err(|source| BuildError::NotFound { supported: supported.clone(), source: Box::new(source) })?;
+    let version =
+        Version::parse(&library.version).ok_or_else(|| BuildError::InvalidVersion(library.version.clone()))?;
+
+    if !(min..=max).contains(&version) {
+        return Err(BuildError::UnsupportedVersion { found: version, supported });
+    }
+    Ok(Dpdk { library, version })
+}
@@ -0,0 +1,96 @@
+use std::{collections::HashSet, fmt};
+
+use crate::{BuildError, Drivers};
+
+enum LinkType {
+    Static,
+    Dynamic,
+}
+
+struct LibLink<'l> {
+    name: &'l str,
+    link_type: LinkType,
+}
+
+impl<'l> LibLink<'l> {
+    fn is_static(&self) -> bool {
+        matches!(self.link_type, LinkType::Static)
+    }
+}
+
+impl<'l> fmt::Display for LibLink<'l> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("cargo:rustc-link-lib=")?;
+
+        if self.is_static() {
+            // see https://msazure.visualstudio.com/One/_git/Networking-DDoS-Datapath/pullrequest/5783930 for some historic
+            // context regarding the "whole-archive" and "bundle" link modifiers
+            f.write_str("static:+whole-archive,-bundle=")?;
+        }
+
+        f.write_str(self.name)?;
+
+        Ok(())
+    }
+}
+
+/// Links DPDK's static libraries, leaving out the driver ones which aren't in `drivers`. The version of DPDK isn't
+/// checked, see [`probe`](crate::probe).
+pub fn link_static(drivers: &Drivers) -> Result<(), BuildError> {
+    let pkg = pkg_config::Config::new()
+        .statik(true)
+        .cargo_metadata(false)
+        .probe("libdpdk")
+        .map_err(|err| BuildError::Link(Box::new(err)))?;
+
+    for path in pkg.link_paths {
+        println!("cargo:rustc-link-search=native={}", path.to_str().unwrap());
+    }
+
+    // pkg-config returns a list of libs, where static libs are specified as
+    // ":librte_mempool_ring.a" and dynamic ones like "rte_mempool", so we'll use that
+    // to parse them into two lists
+    let (mut static_libs, mut dyn_libs) = pkg
+        .libs
+        .iter()
+        .map(|lib| {
+            lib.strip_prefix(":lib")
+                .and_then(|lib| lib.strip_suffix(".a"))
+                .map(|lib| LibLink { name: lib, link_type: LinkType::Static })
+                .unwrap_or(LibLink { name: lib, link_type: LinkType::Dynamic })
+        })
+        .partition::<Vec<_>, _>(LibLink::is_static);
+
+    static_libs.retain(|LibLink { name, .. }| drivers.is_linked(name));
+
+    // some libraries appear as both static and dynamic, de-dup
+    let static_lib_names = static_libs.iter().map(|LibLink { name, .. }| name).collect::<HashSet<_>>();
+    dyn_libs.retain(|LibLink { name, .. }| !static_lib_names.contains(name));
+
+    for link in static_libs.into_iter().chain(dyn_libs) {
+        println!("{link}");
+    }
+
+    Ok(())
+}
+
+/// Links DPDK's shared libraries (e.g. with rte-sys's `dynamic` feature) rather than its static ones, which is much
+/// faster and allows using a distro-packaged DPDK. Its drivers are then loaded at runtime (from the driver directory DPDK was built
+/// with, or through EAL's `-d` option), so [`Drivers`] don't apply. Note that a DPDK installed outside of the linker's
+/// default paths must also be found at runtime, e.g. through `LD_LIBRARY_PATH`.
+pub fn link_dynamic() -> Result<(), BuildError> {
+    let pkg = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe("libdpdk")
+        .map_err(|err| BuildError::Link(Box::new(err)))?;
+
+    for path in pkg.link_paths {
+        println!("cargo:rustc-link-search=native={}", path.to_str().unwrap());
+    }
+
+    for lib in &pkg.libs {
+        println!("{}", LibLink { name: lib, link_type: LinkType::Dynamic });
+    }
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use std::fmt;
+
+/// The DPDK LTS releases the bindings can be built against, each enabled by a `dpdk-<year>-<month>` feature of rte-sys.
+///
+/// A release is only listed once rte-sys's `src/stub.c` and the `rte` wrappers handle its API changes (e.g. 23.11
+/// changed the signatures of `rte_pcapng_copy` and of the `rte_security` session functions).
+pub const LTS_RELEASES: &[Version] = &[Version(22, 11, 0)];
+
+/// A DPDK version, e.g. `22.11.1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version(pub u32, pub u32, pub u32);
+
+impl Version {
+    pub(crate) fn parse(version: &str) -> Option<Self> {
+        let mut parts = version.split('.').map(|part| part.parse().ok());
+        let (year, month) = (parts.next()??, parts.next()??);
+        Some(Self(year, month, parts.next().flatten().unwrap_or(0)))
+    }
+
+    /// Returns the LTS release, e.g. `22_11`.
+    pub fn lts(&self) -> String {
+        format!("{}_{}", self.0, self.1)
+    }
+
+    /// Returns rte-sys's feature enabling the LTS release, e.g. `dpdk-22-11`.
+    pub fn feature(&self) -> String {
+        format!("dpdk-{}-{}", self.0, self.1)
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
@@ -1,9 +1,16 @@
 use std::{
     ffi::CString,
     io::{self, BufRead, BufReader},
-    mem,
-    os::unix::{net::UnixStream, prelude::AsRawFd},
-    thread,
+    os::{
+        raw::c_char,
+        unix::{net::UnixStream, prelude::IntoRawFd},
+    },
+    ptr::{self, NonNull},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, Once,
+    },
+    thread::{self, JoinHandle},
 };
 
 use rte_error::ReturnValue as _;
@@ -16,31 +23,75 @@ pub enum Error {
 
     #[error(transparent)]
     Rte(#[from] rte_error::Error),
+
+    /// EAL printed its usage (to stdout) because of invalid arguments, see [`install_exit_hooks`].
+    #[error("invalid EAL arguments")]
+    Usage,
+}
+
+/// The bridge of RTE logs into the global logging mechanism: the stream RTE writes its logs to (instead of stderr),
+/// and the thread reading them from the other end of a unix stream.
+struct LogReader {
+    stream: NonNull<libc::FILE>,
+    thread: JoinHandle<()>,
 }
 
-/// Set up unix stream for RTE logs (instead of stderr), spawn a thread for reading logs
-/// and writing them through global logging mechanism.
-fn init_log_reader() -> Result<(), Error> {
-    let (tx, rx) = UnixStream::pair()?;
+// Safety: the stream is only used by RTE, and closed once it no longer uses it
+unsafe impl Send for LogReader {}
+
+/// The log reader of the initialized EAL, torn down by [`cleanup`].
+static LOG_READER: Mutex<Option<LogReader>> = Mutex::new(None);
+
+impl LogReader {
+    /// Sets up the stream for RTE logs, and spawns the thread reading them and writing them through the global logging
+    /// mechanism.
+    fn new() -> Result<Self, Error> {
+        let (tx, rx) = UnixStream::pair()?;
 
-    unsafe {
+        // the stream owns the fd, which is closed along with it
+        let fd = tx.into_raw_fd();
         let mode = CString::new("w").unwrap();
-        let fd = libc::fdopen(tx.as_raw_fd(), mode.as_ptr());
-        ffi::rte_openlog_stream(fd as *mut _).rte_ok()?;
-    }
+        let Some(stream) = NonNull::new(unsafe { libc::fdopen(fd, mode.as_ptr()) }) else {
+            let err = io::Error::last_os_error();
+            unsafe { libc::close(fd) };
+            return Err(err.into());
+        };
+
+        if let Err(err) = unsafe { ffi::rte_openlog_stream(stream.as_ptr().cast()) }.rte_ok() {
+            unsafe { libc::fclose(stream.as_ptr()) };
+            return Err(err.into());
+        }
 
-    // prevent the fd rte uses for logging from being dropped, which would
-    // cause it to be closed
-    mem::forget(tx);
+        let thread = thread::spawn(|| {
+            let mut logs = BufReader::new(rx).lines();
+            while let Some(Ok(log)) = logs.next() {
+                info!(target: "ddosd::rte", "{log}");
+            }
+        });
+
+        Ok(Self { stream, thread })
+    }
 
-    thread::spawn(|| {
-        let mut logs = BufReader::new(rx).lines();
-        while let Some(Ok(log)) = logs.next() {
-            info!(target: "ddosd::rte", "{log}");
+    /// Restores the default stream of RTE logs and closes ours (flushing it), which ends the reading thread once it
+    /// has read the remaining logs.
+    fn close(self) {
+        unsafe {
+            ffi::rte_openlog_stream(ptr::null_mut());
+            libc::fclose(self.stream.as_ptr());
         }
-    });
+        let _ = self.thread.join();
+    }
 
-    Ok(())
+    /// Flushes our stream and shuts down its writing end, which ends the reading thread once it has read the remaining
+    /// logs. Unlike [`LogReader::close`], the stream is left open (and RTE still writing to it), as lcores may still be
+    /// logging through it, in which case their logs are dropped.
+    fn shutdown(self) {
+        unsafe {
+            libc::fflush(self.stream.as_ptr());
+            libc::shutdown(libc::fileno(self.stream.as_ptr()), libc::SHUT_WR);
+        }
+        let _ = self.thread.join();
+    }
 }
 
 /// Initializes EAL by calling [`rte_eal_init`](https://doc.dpdk.org/api/rte__eal_8h.html#a5c3f4dddc25e38c5a186ecd8a69260e3),
@@ -52,7 +103,16 @@ where
     A: IntoIterator<Item = S>,
     S: Into<String>,
 {
-    init_log_reader()?;
+    // EAL may already be initialized, in which case its logs are already read (and it fails with EALREADY). The lock
+    // isn't held during the initialization, as EAL may exit (running the hook which closes the reader) meanwhile.
+    let installed = {
+        let mut log_reader = LOG_READER.lock().unwrap();
+        let installed = log_reader.is_none();
+        if installed {
+            *log_reader = Some(LogReader::new()?);
+        }
+        installed
+    };
 
     let args = args.into_iter().map(S::into).collect::<Vec<_>>();
 
@@ -61,8 +121,81 @@ where
         let mut arg_ptrs = args.as_ptrs();
         let mut argv = arg_ptrs.as_argv();
 
-        unsafe { ffi::rte_eal_init(argv.argc(), argv.argv()) }.rte_ok()?
+        match unsafe { ffi::rte_eal_init(argv.argc(), argv.argv()) }.rte_ok() {
+            Ok(args_read) => args_read,
+            Err(err) => {
+                if installed {
+                    if let Some(log_reader) = LOG_READER.lock().unwrap().take() {
+                        log_reader.close();
+                    }
+                }
+                if USAGE_PRINTED.swap(false, Ordering::Relaxed) {
+                    return Err(Error::Usage);
+                }
+                return Err(err.into());
+            }
+        }
     };
 
     Ok(args.into_iter().skip(args_read as usize))
 }
+
+/// Releases the resources of EAL by calling [`rte_eal_cleanup`](https://doc.dpdk.org/api/rte__eal_8h.html)
+/// (after which no DPDK function may be called), and stops reading its logs.
+pub fn cleanup() -> Result<(), Error> {
+    unsafe { ffi::rte_eal_cleanup() }.rte_ok()?;
+
+    if let Some(log_reader) = LOG_READER.lock().unwrap().take() {
+        log_reader.close();
+    }
+    Ok(())
+}
+
+/// Set by the usage hook of EAL, which it calls when printing its usage.
+static USAGE_PRINTED: AtomicBool = AtomicBool::new(false);
+
+static EXIT_HOOKS: Once = Once::new();
+
+/// Installs hooks for EAL exiting early (and out of the control of Rust, i.e. without running destructors), to be
+/// called before [`init`]:
+/// - Invalid arguments are reported as [`Error::Usage`] rather than [`Error::Rte`] (EAL printing its usage to stdout
+///   in that case). Note that EAL exits the process (successfully) when given `--help`.
+/// - The logs of EAL are flushed and read through by the global logging mechanism when the process exits, e.g. by
+///   `rte_exit` (which EAL calls on some initialization failures) or a call to [`std::process::exit`].
+///
+/// It doesn't cover the process aborting, e.g. by `rte_panic`.
+pub fn install_exit_hooks() {
+    EXIT_HOOKS.call_once(|| unsafe {
+        ffi::rte_set_application_usage_hook(Some(usage_hook));
+        if libc::atexit(shutdown_log_reader) != 0 {
+            warn!("Could not register the hook flushing the RTE logs on exit");
+        }
+    });
+}
+
+unsafe extern "C" fn usage_hook(_prgname: *const c_char) {
+    USAGE_PRINTED.store(true, Ordering::Relaxed);
+}
+
+extern "C" fn shutdown_log_reader() {
+    // the lock may only be held by another thread, which is left as is rather than deadlocking the exit
+    if let Some(log_reader) = LOG_READER.try_lock().ok().and_then(|mut log_reader| log_reader.take()) {
+        log_reader.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_init_usage() {
+        install_exit_hooks();
+
+        let err = init(["test", "--no-such-option"]).err().unwrap();
+        assert!(matches!(err, Error::Usage), "{err}");
+        // torn down, so that EAL can be initialized again
+        assert!(LOG_READER.lock().unwrap().is_none());
+        assert!(!USAGE_PRINTED.load(Ordering::Relaxed));
+    }
+}
@@ -1,31 +1,298 @@
-use std::{error, ffi::CStr, fmt, os::raw::c_int, ptr::NonNull};
+use std::{
+    borrow::Cow,
+    error, fmt, io,
+    os::raw::c_int,
+    thread,
+    time::{Duration, Instant},
+};
+#[cfg(feature = "ffi")]
+use std::{ffi::CStr, ptr::NonNull};
 
 /// Error returned from call to RTE library function.
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Error(pub i32);
 
+impl Error {
+    /// Returns the category of the error, for matching on common causes.
+    #[inline]
+    pub fn kind(&self) -> ErrorKind {
+        match self.0 {
+            libc::ENOMEM => ErrorKind::NoMemory,
+            libc::EINVAL => ErrorKind::InvalidArgument,
+            libc::ENODEV => ErrorKind::NoDevice,
+            libc::EAGAIN => ErrorKind::WouldBlock,
+            libc::ENOTSUP => ErrorKind::NotSupported,
+            libc::EEXIST => ErrorKind::AlreadyExists,
+            libc::ENOENT => ErrorKind::NotFound,
+            libc::EBUSY => ErrorKind::Busy,
+            libc::EIO => ErrorKind::Io,
+            libc::ENOSPC => ErrorKind::NoSpace,
+            E_RTE_SECONDARY => ErrorKind::Secondary,
+            E_RTE_NO_CONFIG => ErrorKind::NoConfig,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Returns the description of the error (as given by `rte_strerror`),
+    /// which doesn't call into the RTE library for common error numbers (and
+    /// never does without the `ffi` feature).
+    pub fn description(&self) -> Cow<'static, str> {
+        let description = match self.0 {
+            libc::EPERM => "Operation not permitted",
+            libc::ENOENT => "No such file or directory",
+            libc::EINTR => "Interrupted system call",
+            libc::EIO => "Input/output error",
+            libc::EAGAIN => "Resource temporarily unavailable",
+            libc::ENOMEM => "Cannot allocate memory",
+            libc::EBUSY => "Device or resource busy",
+            libc::EEXIST => "File exists",
+            libc::ENODEV => "No such device",
+            libc::EINVAL => "Invalid argument",
+            libc::ENOSPC => "No space left on device",
+            libc::ERANGE => "Numerical result out of range",
+            libc::ENOTSUP => "Operation not supported",
+            E_RTE_SECONDARY => "Invalid call in secondary process",
+            E_RTE_NO_CONFIG => "Missing rte_config structure",
+            #[cfg(feature = "ffi")]
+            errno => return unsafe { CStr::from_ptr(ffi::rte_strerror(errno)) }.to_string_lossy().into_owned().into(),
+            #[cfg(not(feature = "ffi"))]
+            errno => return format!("Unknown error {errno}").into(),
+        };
+        description.into()
+    }
+
+    /// Returns whether the operation may succeed if retried, i.e. on `EAGAIN`,
+    /// `EBUSY` or `EINTR`.
+    #[inline]
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.0, libc::EAGAIN | libc::EBUSY | libc::EINTR)
+    }
+}
+
 impl error::Error for Error {}
 
+/// Maps the error number through [`io::Error::from_raw_os_error`], except for
+/// RTE-specific errors (which aren't OS errors) that are wrapped instead.
+impl From<Error> for io::Error {
+    #[inline]
+    fn from(error: Error) -> Self {
+        match error.0 {
+            E_RTE_SECONDARY | E_RTE_NO_CONFIG => io::Error::other(error),
+            errno => io::Error::from_raw_os_error(errno),
+        }
+    }
+}
+
+/// Operation not allowed in a secondary process (`E_RTE_SECONDARY`, following
+/// `__ELASTERROR` in `rte_errno.h`).
+pub const E_RTE_SECONDARY: i32 = 1001;
+/// Missing rte_config (`E_RTE_NO_CONFIG`).
+pub const E_RTE_NO_CONFIG: i32 = 1002;
+
+/// The category of an [`Error`], see [`Error::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// `ENOMEM`
+    NoMemory,
+    /// `EINVAL`
+    InvalidArgument,
+    /// `ENODEV`
+    NoDevice,
+    /// `EAGAIN`
+    WouldBlock,
+    /// `ENOTSUP`
+    NotSupported,
+    /// `EEXIST`
+    AlreadyExists,
+    /// `ENOENT`
+    NotFound,
+    /// `EBUSY`
+    Busy,
+    /// `EIO`
+    Io,
+    /// `ENOSPC`
+    NoSpace,
+    /// [`E_RTE_SECONDARY`]
+    Secondary,
+    /// [`E_RTE_NO_CONFIG`]
+    NoConfig,
+    /// Any other error number.
+    Other,
+}
+
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let description = unsafe { CStr::from_ptr(ffi::rte_strerror(self.0)) };
-
-        f.debug_struct("RteError").field("code", &self.0).field("description", &description.to_string_lossy()).finish()
+        f.debug_struct("RteError").field("code", &self.0).field("description", &self.description()).finish()
     }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        fmt::Debug::fmt(self, f)
+        write!(f, "{} (error {})", self.description(), self.0)
+    }
+}
+
+/// An [`Error`] along with a description of the failed operation, e.g.
+/// `rte_eth_dev_start on port 3`, see [`ErrorExt`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ContextError {
+    error: Error,
+    context: String,
+}
+
+impl ContextError {
+    #[inline]
+    pub fn new(error: Error, context: impl Into<String>) -> Self {
+        Self { error, context: context.into() }
+    }
+
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    #[inline]
+    pub fn context(&self) -> &str {
+        &self.context
+    }
+}
+
+impl error::Error for ContextError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.context, self.error)
+    }
+}
+
+/// Drops the context, e.g. for propagating the error with `?` from a function
+/// returning a plain [`Error`].
+impl From<ContextError> for Error {
+    #[inline]
+    fn from(error: ContextError) -> Self {
+        error.error
+    }
+}
+
+/// Keeps the context, with the [kind](io::Error::kind) of the underlying
+/// error.
+impl From<ContextError> for io::Error {
+    #[inline]
+    fn from(error: ContextError) -> Self {
+        io::Error::new(io::Error::from(error.error).kind(), error)
+    }
+}
+
+/// Trait for attaching a description of the failed operation to an [`Error`],
+/// so that propagated errors identify which device, queue or function failed.
+pub trait ErrorExt<T> {
+    fn context(self, context: impl Into<String>) -> Result<T, ContextError>;
+
+    /// Like [`ErrorExt::context`], but only builds the context on error.
+    fn with_context<C: Into<String>>(self, f: impl FnOnce() -> C) -> Result<T, ContextError>;
+}
+
+impl<T> ErrorExt<T> for Result<T, Error> {
+    #[inline]
+    fn context(self, context: impl Into<String>) -> Result<T, ContextError> {
+        self.map_err(|error| ContextError::new(error, context))
+    }
+
+    #[inline]
+    fn with_context<C: Into<String>>(self, f: impl FnOnce() -> C) -> Result<T, ContextError> {
+        self.map_err(|error| ContextError::new(error, f()))
+    }
+}
+
+/// Trait for retrying an operation (e.g. stopping a device, or getting objects
+/// from a mempool) while it fails with a [retryable](Error::is_retryable)
+/// error, implemented for closures performing the operation:
+///
+/// ```ignore
+/// (|| dev.stop()).retry_n(3)?;
+/// ```
+///
+/// Between attempts, the thread [yields](thread::yield_now).
+pub trait Retryable<T> {
+    /// Retries the operation up to `times` times, i.e. attempts it at most
+    /// `times + 1` times, returning the last error if none succeeds.
+    fn retry_n(self, times: usize) -> Result<T, Error>;
+
+    /// Retries the operation until `duration` has elapsed since the first
+    /// attempt, returning the last error if none succeeds.
+    fn retry_for(self, duration: Duration) -> Result<T, Error>;
+}
+
+impl<T, F> Retryable<T> for F
+where
+    F: FnMut() -> Result<T, Error>,
+{
+    fn retry_n(mut self, times: usize) -> Result<T, Error> {
+        let mut retries = 0;
+        loop {
+            match self() {
+                Err(err) if err.is_retryable() && retries < times => retries += 1,
+                res => return res,
+            }
+            thread::yield_now();
+        }
+    }
+
+    fn retry_for(mut self, duration: Duration) -> Result<T, Error> {
+        let start = Instant::now();
+        loop {
+            match self() {
+                Err(err) if err.is_retryable() && start.elapsed() < duration => {}
+                res => return res,
+            }
+            thread::yield_now();
+        }
     }
 }
 
 /// Error number value, stored per-thread, which can be queried after
 /// calls to certain functions to determine why those functions failed.
+///
+/// It must be read right after the failing call, as any other call into the
+/// RTE library (e.g. logging, or freeing memory) may overwrite it, see
+/// [`check!`].
+#[cfg(feature = "ffi")]
 pub fn rte_error() -> Error {
     Error(unsafe { ffi::_rte_errno() })
 }
 
+/// Checks the return value of a call through FFI to the RTE library, capturing
+/// [`rte_error`] right after the call (before anything else can overwrite it).
+///
+/// With a single argument, the return value is checked with
+/// [`ReturnValue::rte_ok`]. Otherwise, the second argument is a predicate on a
+/// reference to the return value telling whether the call failed, e.g. for
+/// functions returning an invalid id on error:
+///
+/// ```ignore
+/// let id = check!(unsafe { ffi::rte_graph_create(name, &mut param) }, |&id| id == ffi::rte_graph_t::MAX)?;
+/// ```
+#[cfg(feature = "ffi")]
+#[macro_export]
+macro_rules! check {
+    ($call:expr) => {
+        $crate::ReturnValue::rte_ok($call)
+    };
+    ($call:expr, $is_err:expr) => {{
+        let ret = $call;
+        if ($is_err)(&ret) {
+            ::std::result::Result::Err($crate::rte_error())
+        } else {
+            ::std::result::Result::Ok(ret)
+        }
+    }};
+}
+
 /// Trait for checking the return value from a call through FFI to the RTE library.
 pub trait ReturnValue {
     type Ok;
@@ -34,11 +301,16 @@ pub trait ReturnValue {
 
 /// Returns `Ok` if the pointer is non-null, otherwise uses [`rte_error`]
 /// to return the error.
+#[cfg(feature = "ffi")]
 impl<T> ReturnValue for *mut T {
     type Ok = NonNull<T>;
 
     fn rte_ok(self) -> Result<Self::Ok, Error> {
-        NonNull::new(self).ok_or_else(rte_error)
+        // errno is read eagerly, before anything else can overwrite it
+        match NonNull::new(self) {
+            Some(ptr) => Ok(ptr),
+            None => Err(rte_error()),
+        }
     }
 }
 
@@ -57,8 +329,6 @@ impl ReturnValue for c_int {
 
 #[cfg(test)]
 mod tests {
-    use std::ptr;
-
     use super::*;
 
     #[test]
@@ -70,8 +340,84 @@ mod tests {
         assert_eq!(ret, Error(1));
     }
 
+    #[test]
+    fn check_error_kind() {
+        assert_eq!((-libc::ENOMEM).rte_ok().unwrap_err().kind(), ErrorKind::NoMemory);
+        assert_eq!(Error(E_RTE_SECONDARY).kind(), ErrorKind::Secondary);
+        assert_eq!(Error(i32::MAX).kind(), ErrorKind::Other);
+    }
+
+    #[test]
+    fn check_error_context() {
+        let port_id = 3;
+        let err = (-libc::EIO).rte_ok().with_context(|| format!("rte_eth_dev_start on port {port_id}")).unwrap_err();
+        assert_eq!(err.context(), "rte_eth_dev_start on port 3");
+        assert_eq!(Error::from(err), Error(libc::EIO));
+    }
+
+    #[test]
+    fn check_io_error() {
+        fn assert_send_sync<T: error::Error + Send + Sync + 'static>() {}
+        assert_send_sync::<Error>();
+        assert_send_sync::<ContextError>();
+
+        let err = io::Error::from(Error(libc::ENOENT));
+        assert_eq!((err.kind(), err.raw_os_error()), (io::ErrorKind::NotFound, Some(libc::ENOENT)));
+        assert_eq!(io::Error::from(Error(E_RTE_NO_CONFIG)).raw_os_error(), None);
+
+        let err = io::Error::from(Err::<(), _>(Error(libc::EEXIST)).context("rte_ring_create").unwrap_err());
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn check_retry() {
+        let mut attempts = 0;
+        let busy_twice = || {
+            attempts += 1;
+            if attempts <= 2 {
+                Err(Error(libc::EBUSY))
+            } else {
+                Ok(attempts)
+            }
+        };
+        assert_eq!(busy_twice.retry_n(2), Ok(3));
+
+        let mut attempts = 0;
+        let res = (|| {
+            attempts += 1;
+            Err::<(), _>(Error(libc::EAGAIN))
+        })
+        .retry_n(2);
+        assert_eq!((res, attempts), (Err(Error(libc::EAGAIN)), 3));
+
+        // non-retryable errors are returned right away
+        let mut attempts = 0;
+        let res = (|| {
+            attempts += 1;
+            Err::<(), _>(Error(libc::EINVAL))
+        })
+        .retry_for(Duration::from_secs(60));
+        assert_eq!((res, attempts), (Err(Error(libc::EINVAL)), 1));
+    }
+
+    #[cfg(feature = "ffi")]
+    #[test]
+    fn check_macro() {
+        assert_eq!(check!(-libc::EINVAL), Err(Error(libc::EINVAL)));
+        assert_eq!(check!(u16::MAX - 1, |&id| id == u16::MAX), Ok(u16::MAX - 1));
+    }
+
+    #[test]
+    fn check_display() {
+        assert_eq!(Error(libc::ENOMEM).to_string(), "Cannot allocate memory (error 12)");
+        assert_eq!(Error(E_RTE_NO_CONFIG).description(), "Missing rte_config structure");
+    }
+
+    #[cfg(feature = "ffi")]
     #[test]
     fn check_ptr_result() {
+        use std::ptr;
+
         let mut alloc = Box::new(());
         let ret = (&mut *alloc as *mut ()).rte_ok().unwrap();
         assert_eq!(ret.as_ptr() as *const _, &*alloc as *const _);
@@ -0,0 +1,134 @@
+//! The bindings of the EAL (into `eal.rs`), then of each subsystem (into e.g. `ip_frag.rs`, which is empty unless the
+//! subsystem is enabled), then of the extra stubs (into `extra.rs`).
+//!
+//! Each header is bound on its own, with the items of the headers it includes, so each binding leaves out the items
+//! bound before it (e.g. `rte_mbuf` is only bound into `mbuf.rs`, the subsystems depending on it referring to it
+//! through the crate's root). Top-level anonymous types are numbered per binding (e.g. `_bindgen_ty_1`), so those are
+//! kept in every binding using them.
+
+use std::{collections::HashSet, fs, path::Path};
+
+use quote::ToTokens;
+use syn::{ForeignItem, Item, UseTree};
+
+use crate::{stubs::ExtraStubs, subsystems};
+
+pub fn generate(out_dir: &Path, cflags: &[String], enabled: &[&str], extra_stubs: Option<&ExtraStubs>) {
+    let mut bound = HashSet::new();
+    generate_one("src/headers/eal.h", &out_dir.join("eal.rs"), cflags, &mut bound);
+
+    for subsystem in subsystems::SUBSYSTEMS {
+        let module = subsystems::module(subsystem);
+        let generated_path = out_dir.join(format!("{module}.rs"));
+        if enabled.contains(subsystem) {
+            generate_one(&format!("src/headers/{module}.h"), &generated_path, cflags, &mut bound);
+        } else {
+            fs::write(generated_path, "").expect("Couldn't write bindings!");
+        }
+    }
+
+    let generated_path = out_dir.join("extra.rs");
+    match extra_stubs {
+        Some(extra_stubs) => generate_one(extra_stubs.header.to_str().unwrap(), &generated_path, cflags, &mut bound),
+        None => fs::write(generated_path, "").expect("Couldn't write bindings!"),
+    }
+}
+
+/// Binds `header` into `generated_path`, leaving out the items already `bound`, and adding the ones it binds.
+fn generate_one(header: &str, generated_path: &Path, cflags: &[String], bound: &mut HashSet<String>) {
+    let bindings = bindgen::Builder::default()
+        .header(header)
+        .generate_comments(true)
+        .generate_inline_functions(true)
+        // treat as opaque as per issue w/ combining align/packed:
+        // https://github.com/rust-lang/rust-bindgen/issues/1538
+        .opaque_type(r"rte_arp_ipv4|rte_arp_hdr")
+        // and this struct per this issue:
+        // https://github.com/rust-lang/rust-bindgen/issues/2179
+        .opaque_type("rte_l2tpv2_combined_msg_hdr")
+        .allowlist_type(r"(rte|eth|DDOS)_.*")
+        .allowlist_function(r"(_rte|rte|eth)_.*")
+        .allowlist_var(r"(_?RTE|EXT|DEV|ETH|MEMPOOL|PKT|LCORE|RING|rte)_.*")
+        .derive_copy(true)
+        .derive_debug(true)
+        .derive_default(true)
+        .derive_partialeq(true)
+        .default_enum_style(bindgen::EnumVariation::ModuleConsts)
+        .clang_arg("-finline-functions")
+        .clang_args(cflags)
+        .rustfmt_bindings(false)
+        .layout_tests(false)
+        .generate()
+        .expect("Unable to generate bindings");
+
+    let mut file = syn::parse_file(&bindings.to_string()).expect("Couldn't parse the generated bindings");
+    let mut newly_bound = HashSet::new();
+    file.items.retain_mut(|item| {
+        if let Item::ForeignMod(foreign_mod) = item {
+            foreign_mod.items.retain(|item| is_unbound(foreign_item_name(item), bound, &mut newly_bound));
+            return !foreign_mod.items.is_empty();
+        }
+        is_unbound(item_name(item), bound, &mut newly_bound)
+    });
+    bound.extend(newly_bound);
+
+    fs::write(generated_path, file.into_token_stream().to_string()).expect("Couldn't write bindings!");
+}
+
+/// Returns whether an item (or an `impl` of it) wasn't bound yet, in which case it's bound now. Unnamed items are
+/// always kept.
+fn is_unbound(name: Option<String>, bound: &HashSet<String>, newly_bound: &mut HashSet<String>) -> bool {
+    let Some(name) = name else { return true };
+    if bound.contains(&name) {
+        return false;
+    }
+    if !is_anonymous(&name) {
+        newly_bound.insert(name);
+    }
+    true
+}
+
+/// Returns whether `name` is the one bindgen gives to a top-level anonymous type, e.g. `_bindgen_ty_1`.
+fn is_anonymous(name: &str) -> bool {
+    name.strip_prefix("_bindgen_ty_").is_some_and(|n| n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn item_name(item: &Item) -> Option<String> {
+    let ident = match item {
+        Item::Const(item) => &item.ident,
+        Item::Enum(item) => &item.ident,
+        Item::Fn(item) => &item.sig.ident,
+        Item::Mod(item) => &item.ident,
+        Item::Static(item) => &item.ident,
+        Item::Struct(item) => &item.ident,
+        Item::Type(item) => &item.ident,
+        Item::Union(item) => &item.ident,
+        // the `impl`s of a type (e.g. its bitfields' accessors) go along with it
+        Item::Impl(item) => match &*item.self_ty {
+            syn::Type::Path(ty) => &ty.path.segments.last()?.ident,
+            _ => return None,
+        },
+        Item::Use(item) => return use_name(&item.tree),
+        _ => return None,
+    };
+    Some(ident.to_string())
+}
+
+fn foreign_item_name(item: &ForeignItem) -> Option<String> {
+    match item {
+        ForeignItem::Fn(item) => Some(item.sig.ident.to_string()),
+        ForeignItem::Static(item) => Some(item.ident.to_string()),
+        ForeignItem::Type(item) => Some(item.ident.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns the name a `use` brings in, e.g. `rte_flow_t` for `use self::rte_flow as rte_flow_t`.
+fn use_name(tree: &UseTree) -> Option<String> {
+    match tree {
+        UseTree::Path(path) => use_name(&path.tree),
+        UseTree::Name(name) => Some(name.ident.to_string()),
+        UseTree::Rename(rename) => Some(rename.rename.to_string()),
+        UseTree::Glob(_) | UseTree::Group(_) => None,
+    }
+}
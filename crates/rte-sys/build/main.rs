@@ -1,50 +1,76 @@
-mod linker;
+mod bindings;
+mod probe;
+mod stubs;
+mod subsystems;
+mod target;
+mod vendored;
+mod version;
 
-use std::{env, path::PathBuf};
+use std::{env, fmt, path::PathBuf};
 
-const GENERATED_FILE: &str = "dpdk_bindings.rs";
+use rte_build::BuildError;
 
-fn bind() {
-    cc::Build::new()
-        .file("src/stub.c")
-        .flag("-mssse3")
-        .compile("rte_stub");
+/// Generates the bindings and links DPDK, returning why DPDK couldn't be found (or linked) rather than panicking.
+fn try_bind() -> Result<(), BuildError> {
+    if env::var_os("CARGO_FEATURE_VENDORED").is_some() {
+        // the vendored DPDK is then found like any other installation prefix
+        env::set_var(rte_build::PREFIX_ENV, vendored::build_dpdk());
+    }
+    let dpdk = version::probe_dpdk()?.library;
+    let apis = probe::probe_apis(&dpdk.include_paths);
+    emit_api_cfgs(&apis);
+
+    let mut cflags = target::cflags();
+    cflags.extend(rte_build::sysroot_args());
+    let subsystems = subsystems::enabled(&apis);
+    cflags.extend(subsystems::cflags(&subsystems));
 
     let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let extra_stubs = stubs::generate(&out_dir);
+
+    let mut stub = cc::Build::new();
+    for flag in &cflags {
+        stub.flag(flag);
+    }
+    stub.includes(&dpdk.include_paths).file("src/stub.c");
+    if let Some(extra_stubs) = &extra_stubs {
+        stub.file(&extra_stubs.source);
+    }
+    stub.compile("rte_stub");
+
+    cflags.extend(dpdk.include_paths.iter().map(|path| format!("-I{}", path.display())));
+
+    bindings::generate(&out_dir, &cflags, &subsystems, extra_stubs.as_ref());
 
-    let generated_path = out_dir.join(GENERATED_FILE);
-
-    bindgen::Builder::default()
-        .header("src/dpdk_bindings.h")
-        .generate_comments(true)
-        .generate_inline_functions(true)
-        // treat as opaque as per issue w/ combining align/packed:
-        // https://github.com/rust-lang/rust-bindgen/issues/1538
-        .opaque_type(r"rte_arp_ipv4|rte_arp_hdr")
-        // and this struct per this issue:
-        // https://github.com/rust-lang/rust-bindgen/issues/2179
-        .opaque_type("rte_l2tpv2_combined_msg_hdr")
-        .allowlist_type(r"(rte|eth|DDOS)_.*")
-        .allowlist_function(r"(_rte|rte|eth)_.*")
-        .allowlist_var(r"(_?RTE|EXT|DEV|ETH|MEMPOOL|PKT|LCORE|rte)_.*")
-        .derive_copy(true)
-        .derive_debug(true)
-        .derive_default(true)
-        .derive_partialeq(true)
-        .default_enum_style(bindgen::EnumVariation::ModuleConsts)
-        .clang_arg("-finline-functions")
-        .rustfmt_bindings(true)
-        .layout_tests(false)
-        .generate()
-        .expect("Unable to generate bindings")
-        .write_to_file(&generated_path)
-        .expect("Couldn't write bindings!");
-
-    linker::link_dpdk();
+    if env::var_os("CARGO_FEATURE_DYNAMIC").is_some() {
+        rte_build::link_dynamic()
+    } else {
+        rte_build::link_static(&rte_build::Drivers::new().with_env_overrides())
+    }
 }
 
-fn main() {
-    bind();
+/// Enables a `dpdk_has_<api>` cfg for each optional API the installed DPDK provides, also published to dependents (as
+/// `DEP_DPDK_HAS_<API>`) so that their wrappers can be compiled conditionally as well.
+fn emit_api_cfgs(apis: &[&str]) {
+    for api in probe::all_apis() {
+        println!("cargo:rustc-check-cfg=cfg(dpdk_has_{api})");
+    }
+    for api in apis {
+        println!("cargo:rustc-cfg=dpdk_has_{api}");
+        println!("cargo:has_{api}=1");
+    }
+}
+
+/// A [`BuildError`] returned from `main`, printed with its guidance rather than as a debug structure.
+struct Report(BuildError);
+
+impl fmt::Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
 
+fn main() -> Result<(), Report> {
     println!("cargo:rerun-if-changed=src/");
+    try_bind().map_err(Report)
 }
@@ -0,0 +1,28 @@
+use std::{fs, path::PathBuf};
+
+/// DPDK's optional libraries and APIs (which depend on its build options and release), as the `cfg` they enable, the
+/// header declaring them and the symbol to look for in it (if the header isn't enough).
+const OPTIONAL_APIS: &[(&str, &str, Option<&str>)] = &[
+    ("dmadev", "rte_dmadev.h", None),
+    ("flow_async", "rte_flow.h", Some("rte_flow_async_create")),
+    ("graph", "rte_graph.h", None),
+];
+
+/// Returns the `cfg` names of all the optional APIs, i.e. the ones [`probe_apis`] may return.
+pub fn all_apis() -> impl Iterator<Item = &'static str> {
+    OPTIONAL_APIS.iter().map(|&(api, ..)| api)
+}
+
+/// Returns which of the optional APIs the installed DPDK provides, by looking through its headers.
+pub fn probe_apis(include_paths: &[PathBuf]) -> Vec<&'static str> {
+    OPTIONAL_APIS
+        .iter()
+        .filter(|(_, header, symbol)| {
+            include_paths.iter().map(|path| path.join(header)).any(|path| match symbol {
+                Some(symbol) => fs::read_to_string(path).is_ok_and(|header| header.contains(symbol)),
+                None => path.exists(),
+            })
+        })
+        .map(|&(api, ..)| api)
+        .collect()
+}
@@ -0,0 +1,106 @@
+//! Stubs for DPDK's `static inline` functions beyond the ones in `src/stub.c`, requested through [`EXTRA_STUBS_ENV`]
+//! (e.g. set in a workspace's `.cargo/config.toml`) rather than by forking rte-sys. It points at a list of the headers
+//! to include and of the functions' prototypes, one per line, e.g.:
+//!
+//! ```text
+//! // the flow metadata dynamic field
+//! #include <rte_flow.h>
+//! int rte_flow_dynf_metadata_avail(void)
+//! void rte_flow_dynf_metadata_set(struct rte_mbuf *m, uint32_t v)
+//! ```
+//!
+//! Each function then gets a `_`-prefixed stub (e.g. `ffi::_rte_flow_dynf_metadata_avail`), just like the built-in
+//! ones.
+
+use std::{
+    env, fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// The path of the list of additional stubs.
+const EXTRA_STUBS_ENV: &str = "RTE_SYS_EXTRA_STUBS";
+
+/// A parsed prototype, e.g. `uint16_t rte_eth_rx_burst(uint16_t port_id, ...)`.
+struct Prototype<'p> {
+    return_type: &'p str,
+    name: &'p str,
+    params: Vec<&'p str>,
+}
+
+impl<'p> Prototype<'p> {
+    fn parse(line: &'p str) -> Option<Self> {
+        let (head, params) = line.trim_end_matches(';').trim_end().strip_suffix(')')?.split_once('(')?;
+        let name_start = head.rfind(|c: char| !(c.is_alphanumeric() || c == '_'))? + 1;
+        let (return_type, name) = (head[..name_start].trim(), &head[name_start..]);
+        if return_type.is_empty() || !name.starts_with("rte_") {
+            return None;
+        }
+
+        let params = match params.trim() {
+            "" | "void" => vec![],
+            params => params.split(',').map(str::trim).collect(),
+        };
+        Some(Self { return_type, name, params })
+    }
+
+    /// The name of a parameter, i.e. its last identifier (e.g. `rx_pkts` for `struct rte_mbuf **rx_pkts`).
+    fn param_name(param: &str) -> &str {
+        let param = param.split('[').next().unwrap().trim_end();
+        let start = param.rfind(|c: char| !(c.is_alphanumeric() || c == '_')).map_or(0, |i| i + 1);
+        &param[start..]
+    }
+
+    fn declaration(&self) -> String {
+        let params = if self.params.is_empty() { "void".to_owned() } else { self.params.join(", ") };
+        format!("{} _{}({params})", self.return_type, self.name)
+    }
+}
+
+impl fmt::Display for Prototype<'_> {
+    /// Writes the stub's definition.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let args = self.params.iter().map(|param| Self::param_name(param)).collect::<Vec<_>>();
+        let ret = if self.return_type == "void" { "" } else { "return " };
+        writeln!(f, "{} {{", self.declaration())?;
+        writeln!(f, "    {ret}{}({});", self.name, args.join(", "))?;
+        writeln!(f, "}}")
+    }
+}
+
+/// The generated stubs, to be compiled along with `src/stub.c` and bound into `ffi::extra`.
+pub struct ExtraStubs {
+    pub source: PathBuf,
+    pub header: PathBuf,
+}
+
+/// Generates the stubs listed in the file under [`EXTRA_STUBS_ENV`] (if set) into `out_dir`.
+pub fn generate(out_dir: &Path) -> Option<ExtraStubs> {
+    println!("cargo:rerun-if-env-changed={EXTRA_STUBS_ENV}");
+    let list_path = PathBuf::from(env::var_os(EXTRA_STUBS_ENV)?);
+    println!("cargo:rerun-if-changed={}", list_path.display());
+    let list = fs::read_to_string(&list_path)
+        .unwrap_or_else(|err| panic!("couldn't read the extra stubs from {}: {err}", list_path.display()));
+
+    let (mut includes, mut prototypes) = (vec![], vec![]);
+    for line in list.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with("//")) {
+        if line.starts_with("#include") {
+            includes.push(line);
+        } else {
+            prototypes.push(Prototype::parse(line).unwrap_or_else(|| {
+                panic!(
+                    "invalid extra stub {line:?} in {}, expected the prototype of an rte_* function",
+                    list_path.display()
+                )
+            }));
+        }
+    }
+
+    let includes = includes.join("\n");
+    let declarations = prototypes.iter().map(|prototype| format!("{};\n", prototype.declaration())).collect::<String>();
+    let definitions = prototypes.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n");
+
+    let stubs = ExtraStubs { source: out_dir.join("extra_stubs.c"), header: out_dir.join("extra_stubs.h") };
+    fs::write(&stubs.header, format!("{includes}\n\n{declarations}")).unwrap();
+    fs::write(&stubs.source, format!("#include \"extra_stubs.h\"\n\n{definitions}")).unwrap();
+    Some(stubs)
+}
@@ -0,0 +1,56 @@
+use std::env;
+
+use crate::probe;
+
+/// DPDK's optional subsystems, each enabled by the feature of the same name (e.g. `ip-frag`), compiled into the stubs
+/// as `RTE_SYS_<SUBSYSTEM>` (e.g. `RTE_SYS_IP_FRAG`) and bound from `src/headers/<module>.h` into a module of its own
+/// (e.g. `ffi::ip_frag`). They're bound in this order, i.e. after the ones they depend on. The EAL (along with lcores,
+/// rings, memory allocation and errno) is always bound.
+pub const SUBSYSTEMS: &[&str] = &[
+    "mbuf",
+    "net",
+    "ethdev",
+    "acl",
+    "bpf",
+    "crypto",
+    "distributor",
+    "dmadev",
+    "eventdev",
+    "fib",
+    "graph",
+    "hash",
+    "ip-frag",
+    "meter",
+    "metrics",
+    "pdump",
+    "rcu",
+    "reorder",
+    "stack",
+    "telemetry",
+    "timer",
+];
+
+/// Returns the enabled subsystems (in the order of [`SUBSYSTEMS`]), leaving out the optional ones DPDK was built
+/// without (i.e. not among the probed `apis`).
+pub fn enabled(apis: &[&str]) -> Vec<&'static str> {
+    SUBSYSTEMS
+        .iter()
+        .filter(|subsystem| apis.contains(subsystem) || !probe::all_apis().any(|api| api == **subsystem))
+        .filter(|subsystem| env::var_os(format!("CARGO_FEATURE_{}", feature_name(subsystem))).is_some())
+        .copied()
+        .collect()
+}
+
+/// Returns the defines selecting the `enabled` subsystems' stubs, e.g. `-DRTE_SYS_ETHDEV`.
+pub fn cflags(enabled: &[&str]) -> Vec<String> {
+    enabled.iter().map(|subsystem| format!("-DRTE_SYS_{}", feature_name(subsystem))).collect()
+}
+
+/// Returns the name of the module (and of the header) binding a subsystem, e.g. `ip_frag`.
+pub fn module(subsystem: &str) -> String {
+    subsystem.replace('-', "_")
+}
+
+fn feature_name(subsystem: &str) -> String {
+    module(subsystem).to_uppercase()
+}
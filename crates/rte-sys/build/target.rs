@@ -0,0 +1,51 @@
+use std::env;
+
+/// Overrides the flags passed to the C compiler (for the stubs) and to clang (for bindgen), whitespace-separated.
+const CFLAGS_ENV: &str = "RTE_SYS_CFLAGS";
+
+/// The x86 target features enabled for the Rust code (e.g. with `-C target-cpu=native`) that DPDK's headers have code
+/// paths for, along with the corresponding compiler flag.
+const X86_FEATURES: &[(&str, &str)] = &[
+    ("sse4.1", "-msse4.1"),
+    ("sse4.2", "-msse4.2"),
+    ("popcnt", "-mpopcnt"),
+    ("aes", "-maes"),
+    ("pclmulqdq", "-mpclmul"),
+    ("avx", "-mavx"),
+    ("avx2", "-mavx2"),
+    ("bmi1", "-mbmi"),
+    ("bmi2", "-mbmi2"),
+    ("avx512f", "-mavx512f"),
+    ("avx512bw", "-mavx512bw"),
+    ("avx512vl", "-mavx512vl"),
+    ("gfni", "-mgfni"),
+];
+
+/// Returns the CPU flags to compile DPDK's headers with, derived from the target's architecture and features (so that
+/// the stubs match the Rust code), unless overridden by [`CFLAGS_ENV`].
+pub fn cflags() -> Vec<String> {
+    println!("cargo:rerun-if-env-changed={CFLAGS_ENV}");
+    if let Ok(cflags) = env::var(CFLAGS_ENV) {
+        return cflags.split_whitespace().map(str::to_owned).collect();
+    }
+
+    let features = env::var("CARGO_CFG_TARGET_FEATURE").unwrap_or_default();
+    let features = features.split(',').collect::<Vec<_>>();
+
+    match env::var("CARGO_CFG_TARGET_ARCH").unwrap().as_str() {
+        "x86_64" => {
+            // DPDK's headers (e.g. rte_memcpy.h) require at least SSSE3
+            let mut cflags = vec!["-mssse3".to_owned()];
+            cflags.extend(
+                X86_FEATURES
+                    .iter()
+                    .filter(|(feature, _)| features.contains(feature))
+                    .map(|(_, flag)| (*flag).to_owned()),
+            );
+            cflags
+        }
+        // NEON is part of the baseline, but DPDK's headers also use the CRC32 extension when available
+        "aarch64" if features.contains(&"crc") => vec!["-march=armv8-a+crc".to_owned()],
+        _ => vec![],
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::version;
+
+/// The pinned patch release built for each LTS release, along with the SHA-256 of its tarball.
+const PINNED_RELEASES: &[(u32, u32, &str, &str)] = &[
+    // TODO - fill in the SHA-256 of the tarball published on dpdk.org, the build fails until then
+    (22, 11, "22.11.4", ""),
+];
+
+/// A local DPDK source tree to build instead of downloading the pinned release, e.g. for offline builds.
+const SOURCE_ENV: &str = "RTE_SYS_VENDORED_SOURCE";
+
+fn run(command: &mut Command) {
+    let status = command.status().unwrap_or_else(|err| panic!("couldn't run {command:?}: {err}"));
+    if !status.success() {
+        panic!("{command:?} failed with {status}");
+    }
+}
+
+/// Builds DPDK (with the `vendored` feature) into `OUT_DIR`, i.e. the pinned patch release of the newest enabled LTS
+/// release (downloaded from dpdk.org with curl, and checked with sha256sum), or the source tree under [`SOURCE_ENV`]. This requires meson and
+/// ninja (along with DPDK's own build dependencies), and isn't supported when cross-compiling.
+///
+/// Returns the installation prefix, which is only built once per `OUT_DIR`.
+pub fn build_dpdk() -> PathBuf {
+    if env::var("TARGET").unwrap() != env::var("HOST").unwrap() {
+        panic!("the vendored DPDK can't be cross-compiled, use DPDK_PREFIX instead");
+    }
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let prefix = out_dir.join("dpdk");
+    if prefix.join("lib/pkgconfig/libdpdk.pc").exists() {
+        return prefix;
+    }
+
+    println!("cargo:rerun-if-env-changed={SOURCE_ENV}");
+    let source = match env::var_os(SOURCE_ENV) {
+        Some(source) => PathBuf::from(source),
+        None => download(&out_dir),
+    };
+
+    let build = out_dir.join("dpdk-build");
+    run(Command::new("meson")
+        .arg("setup")
+        .arg(&build)
+        .arg(&source)
+        .arg(format!("--prefix={}", prefix.display()))
+        .args(["--libdir=lib", "--default-library=static", "-Dtests=false", "-Denable_docs=false"])
+        // i.e. for any CPU of the target's architecture, rather than the build machine's
+        .arg("-Dplatform=generic"));
+    run(Command::new("ninja").arg("-C").arg(&build).arg("install"));
+
+    prefix
+}
+
+/// Downloads and extracts the pinned release, returning its source tree.
+fn download(out_dir: &Path) -> PathBuf {
+    let (_, newest) = version::supported_range();
+    let Some(&(_, _, release, sha256)) =
+        PINNED_RELEASES.iter().find(|(year, month, ..)| (*year, *month) == (newest.0, newest.1))
+    else {
+        panic!("no pinned DPDK release for {}", newest.lts());
+    };
+
+    let tarball = out_dir.join(format!("dpdk-{release}.tar.xz"));
+    run(Command::new("curl")
+        .args(["--fail", "--location", "--silent", "--show-error", "--output"])
+        .arg(&tarball)
+        .arg(format!("https://fast.dpdk.org/rel/dpdk-{release}.tar.xz")));
+    verify(&tarball, sha256);
+    run(Command::new("tar").arg("-xJf").arg(&tarball).arg("-C").arg(out_dir));
+
+    // e.g. dpdk-stable-22.11.4 for patch releases
+    let source = out_dir.join(format!("dpdk-stable-{release}"));
+    if source.exists() {
+        source
+    } else {
+        out_dir.join(format!("dpdk-{release}"))
+    }
+}
+
+/// Fails the build (removing `tarball`) unless its SHA-256 is `sha256`.
+fn verify(tarball: &Path, sha256: &str) {
+    let mut command = Command::new("sha256sum");
+    command.arg(tarball);
+    let output = command.output().unwrap_or_else(|err| panic!("couldn't run {command:?}: {err}"));
+    if !output.status.success() {
+        panic!("{command:?} failed with {}", output.status);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let actual = stdout.split_whitespace().next().unwrap_or_default();
+    if !actual.eq_ignore_ascii_case(sha256) {
+        let _ = fs::remove_file(tarball);
+        panic!("{} has SHA-256 {actual}, expected {sha256}", tarball.display());
+    }
+}
@@ -0,0 +1,29 @@
+use std::env;
+
+use rte_build::{BuildError, Dpdk, Version, LTS_RELEASES};
+
+/// Returns the range of DPDK versions accepted by the enabled `dpdk-*` features, i.e. any (patch) release from the
+/// oldest enabled LTS release to the newest one.
+pub fn supported_range() -> (Version, Version) {
+    let enabled = LTS_RELEASES
+        .iter()
+        .filter(|release| env::var_os(format!("CARGO_FEATURE_DPDK_{}_{}", release.0, release.1)).is_some())
+        .collect::<Vec<_>>();
+
+    match (enabled.first(), enabled.last()) {
+        (Some(&&oldest), Some(&&newest)) => (oldest, Version(newest.0, newest.1, u32::MAX)),
+        _ => {
+            let features = LTS_RELEASES.iter().map(Version::feature).collect::<Vec<_>>();
+            panic!("no DPDK release selected, enable any of the {} features", features.join("/"))
+        }
+    }
+}
+
+/// Finds the installed DPDK, checking that it's in the range supported by the enabled features, and publishes the
+/// detected version to dependents (as `DEP_DPDK_VERSION`).
+pub fn probe_dpdk() -> Result<Dpdk, BuildError> {
+    let (min, max) = supported_range();
+    let dpdk = rte_build::probe(min, max)?;
+    println!("cargo:version={}", dpdk.version);
+    Ok(dpdk)
+}
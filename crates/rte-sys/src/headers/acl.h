@@ -0,0 +1,3 @@
+// The `acl` subsystem, bound into `ffi::acl` (and re-exported from the crate's root).
+
+#include <rte_acl.h>
@@ -0,0 +1,7 @@
+// The `bpf` subsystem, bound into `ffi::bpf` (and re-exported from the crate's root).
+
+#include <rte_bpf.h>
+#include <rte_bpf_ethdev.h>
+
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+const uint32_t _RTE_BPF_ETH_F_JIT =                 RTE_BPF_ETH_F_JIT;
@@ -0,0 +1,59 @@
+// The `crypto` subsystem, bound into `ffi::crypto` (and re-exported from the crate's root).
+
+#include <rte_cryptodev.h>
+#include <rte_ipsec.h>
+#include <rte_ipsec_sad.h>
+#include <rte_security.h>
+
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+const uint32_t _RTE_IPSEC_SAD_SPI_ONLY =            RTE_IPSEC_SAD_SPI_ONLY;
+const uint32_t _RTE_IPSEC_SAD_SPI_DIP =             RTE_IPSEC_SAD_SPI_DIP;
+const uint32_t _RTE_IPSEC_SAD_SPI_DIP_SIP =         RTE_IPSEC_SAD_SPI_DIP_SIP;
+
+/**
+ * Allocates a crypto op of the given type from a crypto op pool, returning NULL on failure.
+ */
+struct rte_crypto_op *_rte_crypto_op_alloc(struct rte_mempool *mempool, enum rte_crypto_op_type type);
+
+/**
+ * Frees a crypto op back to its pool (without freeing its mbufs).
+ */
+void _rte_crypto_op_free(struct rte_crypto_op *op);
+
+/**
+ * Attaches a symmetric session to a crypto op.
+ */
+int _rte_crypto_op_attach_sym_session(struct rte_crypto_op *op, void *sess);
+
+/**
+ * Returns the size of the private area of the crypto ops in a crypto op pool.
+ */
+uint16_t _rte_crypto_op_get_priv_data_size(struct rte_mempool *mempool);
+
+/**
+ * Enqueues a burst of crypto ops on a queue pair, returning the number of enqueued ops.
+ */
+uint16_t _rte_cryptodev_enqueue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops);
+
+/**
+ * Dequeues a burst of processed crypto ops from a queue pair, returning the number of dequeued ops.
+ */
+uint16_t _rte_cryptodev_dequeue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops);
+
+/**
+ * Prepares a crypto op for each mbuf of an IPsec session with lookaside crypto, returning the number of prepared
+ * mbufs. Mbufs that could not be prepared are moved to the end of the array.
+ */
+uint16_t _rte_ipsec_pkt_crypto_prepare(const struct rte_ipsec_session *ss, struct rte_mbuf *mb[],
+                                       struct rte_crypto_op *cop[], uint16_t num);
+
+/**
+ * Finalizes the processing of mbufs of an IPsec session, returning the number of successfully processed mbufs. Mbufs
+ * that failed processing are moved to the end of the array.
+ */
+uint16_t _rte_ipsec_pkt_process(const struct rte_ipsec_session *ss, struct rte_mbuf *mb[], uint16_t num);
+
+/**
+ * Sets the security session metadata of an mbuf, to be processed by an inline security session on transmission.
+ */
+int _rte_security_set_pkt_metadata(struct rte_security_ctx *instance, void *sess, struct rte_mbuf *mb, void *params);
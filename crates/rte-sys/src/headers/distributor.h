@@ -0,0 +1,3 @@
+// The `distributor` subsystem, bound into `ffi::distributor` (and re-exported from the crate's root).
+
+#include <rte_distributor.h>
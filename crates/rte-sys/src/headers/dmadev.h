@@ -0,0 +1,29 @@
+// The `dmadev` subsystem, bound into `ffi::dmadev` (and re-exported from the crate's root).
+
+#include <rte_dmadev.h>
+
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+const uint64_t _RTE_DMA_OP_FLAG_FENCE =             RTE_DMA_OP_FLAG_FENCE;
+const uint64_t _RTE_DMA_OP_FLAG_SUBMIT =            RTE_DMA_OP_FLAG_SUBMIT;
+const uint64_t _RTE_DMA_OP_FLAG_LLC =               RTE_DMA_OP_FLAG_LLC;
+
+/**
+ * Enqueues a copy operation on a DMA vchan, returning its ring index (or a negative errno).
+ */
+int _rte_dma_copy(int16_t dev_id, uint16_t vchan, rte_iova_t src, rte_iova_t dst, uint32_t length, uint64_t flags);
+
+/**
+ * Submits the enqueued operations of a DMA vchan to the hardware.
+ */
+int _rte_dma_submit(int16_t dev_id, uint16_t vchan);
+
+/**
+ * Returns the number of successfully completed operations of a DMA vchan, up to `nb_cpls`.
+ */
+uint16_t _rte_dma_completed(int16_t dev_id, uint16_t vchan, const uint16_t nb_cpls, uint16_t *last_idx,
+                            bool *has_error);
+
+/**
+ * Returns the number of operations that can currently be enqueued on a DMA vchan.
+ */
+uint16_t _rte_dma_burst_capacity(int16_t dev_id, uint16_t vchan);
@@ -0,0 +1,75 @@
+// The EAL, lcores, rings, memory allocation, logging and errno, which are always bound (into the crate's root).
+// known issues:
+// 1. https://github.com/rust-lang/rust/issues/54341
+
+#include <rte_cycles.h>
+#include <rte_eal.h>
+#include <rte_errno.h>
+#include <rte_lcore.h>
+#include <rte_log.h>
+#include <rte_malloc.h>
+#include <rte_ring.h>
+
+// Used for testing to initialize lcore ids for all threads while running in parallel
+void _rte_set_mock_lcore(uint32_t lcore_id);
+
+// bindgen can't generate bindings for static functions defined in C
+// header files. these shims are necessary to expose them to FFI.
+
+unsigned _rte_lcore_id(void);
+
+/**
+ * Error number value, stored per-thread, which can be queried after
+ * calls to certain functions to determine why those functions failed.
+ */
+int _rte_errno(void);
+
+/**
+ * Enqueue one object on a ring.
+ */
+int _rte_ring_enqueue_elem(struct rte_ring *r, void *obj, unsigned int esize);
+
+/**
+ * Enqueue several objects on a ring, either all of them or none.
+ */
+unsigned int _rte_ring_enqueue_bulk_elem(struct rte_ring *r, const void *obj_table, unsigned int esize, unsigned int n, unsigned int *free_space);
+
+/**
+ * Enqueue up to `n` objects on a ring.
+ */
+unsigned int _rte_ring_enqueue_burst_elem(struct rte_ring *r, const void *obj_table, unsigned int esize, unsigned int n, unsigned int *free_space);
+
+/**
+ * Dequeue one object from a ring.
+ */
+int _rte_ring_dequeue_elem(struct rte_ring *r, void *obj_p, unsigned int esize);
+
+/**
+ * Dequeue several objects from a ring, either all of them or none.
+ */
+unsigned int _rte_ring_dequeue_bulk_elem(struct rte_ring *r, void *obj_table, unsigned int esize, unsigned int n, unsigned int *available);
+
+/**
+ * Dequeue up to `n` objects from a ring.
+ */
+unsigned int _rte_ring_dequeue_burst_elem(struct rte_ring *r, void *obj_table, unsigned int esize, unsigned int n, unsigned int *available);
+
+/**
+ * Return the number of entries in a ring.
+ */
+unsigned int _rte_ring_count(const struct rte_ring *r);
+
+/**
+ * Return the number of free entries in a ring.
+ */
+unsigned int _rte_ring_free_count(const struct rte_ring *r);
+
+/**
+ * Return the number of elements which can be stored in the ring.
+ */
+unsigned int _rte_ring_get_capacity(const struct rte_ring *r);
+
+/**
+ * Read the TSC register.
+ */
+uint64_t _rte_rdtsc(void);
@@ -1,6 +1,10 @@
+// The `ethdev` subsystem, bound into `ffi::ethdev` (and re-exported from the crate's root).
 
-/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+#include <rte_eth_ring.h>
+#include <rte_ethdev.h>
+#include <rte_mtr.h>
 
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
 const uint64_t _RTE_ETH_RX_OFFLOAD_VLAN_STRIP           = RTE_ETH_RX_OFFLOAD_VLAN_STRIP;
 const uint64_t _RTE_ETH_RX_OFFLOAD_IPV4_CKSUM           = RTE_ETH_RX_OFFLOAD_IPV4_CKSUM;
 const uint64_t _RTE_ETH_RX_OFFLOAD_UDP_CKSUM            = RTE_ETH_RX_OFFLOAD_UDP_CKSUM;
@@ -77,3 +81,37 @@ const uint32_t _RTE_ETH_RSS_PFCP =                  RTE_ETH_RSS_PFCP;
 const uint32_t _RTE_ETH_RSS_PPPOE =                 RTE_ETH_RSS_PPPOE;
 const uint32_t _RTE_ETH_RSS_ECPRI =                 RTE_ETH_RSS_ECPRI;
 const uint32_t _RTE_ETH_RSS_MPLS =                  RTE_ETH_RSS_MPLS;
+
+/**
+ * Retrieve a burst of input packets from a receive queue of an Ethernet
+ * device. The retrieved packets are stored in *rte_mbuf* structures whose
+ * pointers are supplied in the *rx_pkts* array.
+ */
+uint16_t _rte_eth_rx_burst(uint16_t port_id, uint16_t queue_id, struct rte_mbuf **rx_pkts, const uint16_t nb_pkts);
+
+/**
+ * Send a burst of output packets on a transmit queue of an Ethernet device.
+ */
+uint16_t _rte_eth_tx_burst(uint16_t port_id, uint16_t queue_id, struct rte_mbuf **tx_pkts, uint16_t nb_pkts);
+
+/**
+ * Buffer a single packet for future transmission on a transmit queue, sending the buffered packets once the buffer
+ * is full.
+ */
+uint16_t _rte_eth_tx_buffer(uint16_t port_id, uint16_t queue_id, struct rte_eth_dev_tx_buffer *buffer,
+                            struct rte_mbuf *tx_pkt);
+
+/**
+ * Send any packets queued up for transmission on a transmit queue.
+ */
+uint16_t _rte_eth_tx_buffer_flush(uint16_t port_id, uint16_t queue_id, struct rte_eth_dev_tx_buffer *buffer);
+
+/**
+ * Check the status of a Tx descriptor in the queue.
+ */
+int _rte_eth_tx_descriptor_status(uint16_t port_id, uint16_t queue_id, uint16_t offset);
+
+/**
+ * Get the number of used descriptors of a Rx queue.
+ */
+int _rte_eth_rx_queue_count(uint16_t port_id, uint16_t queue_id);
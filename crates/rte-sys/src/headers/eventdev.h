@@ -0,0 +1,16 @@
+// The `eventdev` subsystem, bound into `ffi::eventdev` (and re-exported from the crate's root).
+
+#include <rte_event_eth_rx_adapter.h>
+#include <rte_eventdev.h>
+
+/**
+ * Enqueues a burst of events on an event port, returning the number of enqueued events.
+ */
+uint16_t _rte_event_enqueue_burst(uint8_t dev_id, uint8_t port_id, const struct rte_event ev[], uint16_t nb_events);
+
+/**
+ * Dequeues a burst of events from an event port, waiting up to timeout_ticks for events to arrive. Returns the number
+ * of dequeued events.
+ */
+uint16_t _rte_event_dequeue_burst(uint8_t dev_id, uint8_t port_id, struct rte_event ev[], uint16_t nb_events,
+                                  uint64_t timeout_ticks);
@@ -0,0 +1,6 @@
+// The `fib` subsystem, bound into `ffi::fib` (and re-exported from the crate's root).
+
+#include <rte_fib.h>
+#include <rte_fib6.h>
+#include <rte_rib.h>
+#include <rte_rib6.h>
@@ -0,0 +1,30 @@
+// The `graph` subsystem, bound into `ffi::graph` (and re-exported from the crate's root).
+
+#include <rte_graph.h>
+#include <rte_graph_worker.h>
+
+/**
+ * Registers a node, returning its id (or `RTE_NODE_ID_INVALID` on failure). The registration is copied, so it may be
+ * freed once this returns.
+ */
+rte_node_t _rte_node_register(const struct rte_node_register *node);
+
+/**
+ * Walks a graph once, processing its source nodes and then every node with pending objects.
+ */
+void _rte_graph_walk(struct rte_graph *graph);
+
+/**
+ * Enqueues objects to the node at edge `next` of a node.
+ */
+void _rte_node_enqueue(struct rte_graph *graph, struct rte_node *node, rte_edge_t next, void **objs, uint16_t nb_objs);
+
+/**
+ * Enqueues a single object to the node at edge `next` of a node.
+ */
+void _rte_node_enqueue_x1(struct rte_graph *graph, struct rte_node *node, rte_edge_t next, void *obj);
+
+/**
+ * Moves all of the objects of a node to the node at edge `next`.
+ */
+void _rte_node_next_stream_move(struct rte_graph *graph, struct rte_node *src, rte_edge_t next);
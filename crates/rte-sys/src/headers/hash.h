@@ -0,0 +1,3 @@
+// The `hash` subsystem, bound into `ffi::hash` (and re-exported from the crate's root).
+
+#include <rte_hash.h>
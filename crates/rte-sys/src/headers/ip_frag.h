@@ -0,0 +1,13 @@
+// The `ip-frag` subsystem, bound into `ffi::ip_frag` (and re-exported from the crate's root).
+
+#include <rte_ip_frag.h>
+
+/**
+ * Check if the IPv4 packet is fragmented.
+ */
+int _rte_ipv4_frag_pkt_is_fragmented(const struct rte_ipv4_hdr *hdr);
+
+/**
+ * Return a pointer to the packet's fragment header, if found.
+ */
+struct rte_ipv6_fragment_ext *_rte_ipv6_frag_get_ipv6_fragment_header(struct rte_ipv6_hdr *hdr);
@@ -0,0 +1,55 @@
+// The `mbuf` subsystem, bound into `ffi::mbuf` (and re-exported from the crate's root).
+
+#include <rte_mbuf.h>
+#include <rte_mempool.h>
+
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+const uint32_t _RTE_MBUF_L2_LEN_BITS =              RTE_MBUF_L2_LEN_BITS;
+const uint32_t _RTE_MBUF_L3_LEN_BITS =              RTE_MBUF_L3_LEN_BITS;
+const uint32_t _RTE_MBUF_OUTL2_LEN_BITS =           RTE_MBUF_OUTL2_LEN_BITS;
+const uint32_t _RTE_MBUF_OUTL3_LEN_BITS =           RTE_MBUF_OUTL3_LEN_BITS;
+
+/**
+ * Prepend len bytes to an mbuf data area.
+ */
+char *_rte_pktmbuf_prepend(struct rte_mbuf *m, uint16_t len);
+
+/**
+ * Remove len bytes at the beginning of an mbuf.
+ */
+char *_rte_pktmbuf_adj(struct rte_mbuf *m, uint16_t len);
+
+/**
+ * Linearize data in mbuf, copying all segments into the first one.
+ */
+int _rte_pktmbuf_linearize(struct rte_mbuf *mbuf);
+
+/**
+ * Allocate a new mbuf from a mempool.
+ */
+struct rte_mbuf *_rte_pktmbuf_alloc(struct rte_mempool *mp);
+
+/**
+ * Free a packet mbuf back into its original mempool.
+ */
+void _rte_pktmbuf_free(struct rte_mbuf *m);
+
+/**
+ * Put several objects back in the mempool.
+ */
+void _rte_mempool_put_bulk(struct rte_mempool *mp, void *const *obj_table, unsigned int n);
+
+/**
+ * Get the application private size of mbufs stored in a pktmbuf_pool.
+ */
+uint16_t _rte_pktmbuf_priv_size(struct rte_mempool *mp);
+
+/**
+ * Get the data room size of mbufs stored in a pktmbuf_pool.
+ */
+uint16_t _rte_pktmbuf_data_room_size(struct rte_mempool *mp);
+
+/**
+ * Returns the IOVA of the start of an mbuf's data.
+ */
+rte_iova_t _rte_mbuf_data_iova(const struct rte_mbuf *mb);
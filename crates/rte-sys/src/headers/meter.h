@@ -0,0 +1,27 @@
+// The `meter` subsystem, bound into `ffi::meter` (and re-exported from the crate's root).
+
+#include <rte_meter.h>
+
+/**
+ * Colors a packet using a single rate three color marker, ignoring its current color.
+ */
+enum rte_color _rte_meter_srtcm_color_blind_check(struct rte_meter_srtcm *m, struct rte_meter_srtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len);
+
+/**
+ * Colors a packet using a single rate three color marker, taking its current color into account.
+ */
+enum rte_color _rte_meter_srtcm_color_aware_check(struct rte_meter_srtcm *m, struct rte_meter_srtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len, enum rte_color pkt_color);
+
+/**
+ * Colors a packet using a two rate three color marker, ignoring its current color.
+ */
+enum rte_color _rte_meter_trtcm_color_blind_check(struct rte_meter_trtcm *m, struct rte_meter_trtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len);
+
+/**
+ * Colors a packet using a two rate three color marker, taking its current color into account.
+ */
+enum rte_color _rte_meter_trtcm_color_aware_check(struct rte_meter_trtcm *m, struct rte_meter_trtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len, enum rte_color pkt_color);
@@ -0,0 +1,5 @@
+// The `metrics` subsystem, bound into `ffi::metrics` (and re-exported from the crate's root).
+
+#include <rte_bitrate.h>
+#include <rte_latencystats.h>
+#include <rte_metrics.h>
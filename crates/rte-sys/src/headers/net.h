@@ -0,0 +1,58 @@
+// The `net` subsystem, bound into `ffi::net` (and re-exported from the crate's root).
+
+#include <rte_arp.h>
+#include <rte_geneve.h>
+#include <rte_icmp.h>
+#include <rte_ip.h>
+#include <rte_net.h>
+#include <rte_tcp.h>
+#include <rte_thash.h>
+#include <rte_udp.h>
+#include <rte_vxlan.h>
+
+/**
+ * Generic implementation of the Toeplitz hash function, as used by NICs for RSS.
+ */
+uint32_t _rte_softrss(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key);
+
+/**
+ * Optimized implementation of the Toeplitz hash function, which requires a key converted by
+ * `_rte_convert_rss_key` (i.e. whose 32-bit words are in host byte order, rather than in the big-endian byte order of
+ * the NIC's key). Like `_rte_softrss`, it takes an input tuple of 32-bit words in host byte order.
+ */
+uint32_t _rte_softrss_be(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key);
+
+/**
+ * Prepare an RSS key for use with `_rte_softrss_be`, converting each of its 32-bit words to host byte order.
+ */
+void _rte_convert_rss_key(const uint32_t *orig, uint32_t *targ, int len);
+
+/**
+ * Process the non-complemented checksum of a buffer.
+ */
+uint16_t _rte_raw_cksum(const void *buf, size_t len);
+
+/**
+ * Process the IPv4 header checksum. The checksum field must be set to 0 by the caller.
+ */
+uint16_t _rte_ipv4_cksum(const struct rte_ipv4_hdr *ipv4_hdr);
+
+/**
+ * Process the pseudo-header checksum of an IPv4 header, as required by NICs offloading L4 checksums.
+ */
+uint16_t _rte_ipv4_phdr_cksum(const struct rte_ipv4_hdr *ipv4_hdr, uint64_t ol_flags);
+
+/**
+ * Process the IPv4 UDP or TCP checksum. The checksum field must be set to 0 by the caller.
+ */
+uint16_t _rte_ipv4_udptcp_cksum(const struct rte_ipv4_hdr *ipv4_hdr, const void *l4_hdr);
+
+/**
+ * Process the pseudo-header checksum of an IPv6 header, as required by NICs offloading L4 checksums.
+ */
+uint16_t _rte_ipv6_phdr_cksum(const struct rte_ipv6_hdr *ipv6_hdr, uint64_t ol_flags);
+
+/**
+ * Process the IPv6 UDP or TCP checksum. The checksum field must be set to 0 by the caller.
+ */
+uint16_t _rte_ipv6_udptcp_cksum(const struct rte_ipv6_hdr *ipv6_hdr, const void *l4_hdr);
@@ -0,0 +1,9 @@
+// The `pdump` subsystem, bound into `ffi::pdump` (and re-exported from the crate's root).
+
+#include <rte_pcapng.h>
+#include <rte_pdump.h>
+
+/** Workaround for https://github.com/rust-lang/rust-bindgen/issues/753 **/
+const uint32_t _RTE_PDUMP_FLAG_RX =                 RTE_PDUMP_FLAG_RX;
+const uint32_t _RTE_PDUMP_FLAG_TX =                 RTE_PDUMP_FLAG_TX;
+const uint32_t _RTE_PDUMP_FLAG_PCAPNG =             RTE_PDUMP_FLAG_PCAPNG;
@@ -0,0 +1,29 @@
+// The `rcu` subsystem, bound into `ffi::rcu` (and re-exported from the crate's root).
+
+#include <rte_rcu_qsbr.h>
+
+/**
+ * Marks a registered reader thread as online, i.e. accessing shared data.
+ */
+void _rte_rcu_qsbr_thread_online(struct rte_rcu_qsbr *v, unsigned int thread_id);
+
+/**
+ * Marks a registered reader thread as offline, i.e. no longer accessing shared data.
+ */
+void _rte_rcu_qsbr_thread_offline(struct rte_rcu_qsbr *v, unsigned int thread_id);
+
+/**
+ * Reports the quiescent state of a registered reader thread.
+ */
+void _rte_rcu_qsbr_quiescent(struct rte_rcu_qsbr *v, unsigned int thread_id);
+
+/**
+ * Starts a grace period, returning the token to check its end with.
+ */
+uint64_t _rte_rcu_qsbr_start(struct rte_rcu_qsbr *v);
+
+/**
+ * Checks (optionally waiting) whether all the online reader threads went through a quiescent state since the token
+ * was started, returning 1 if they did and 0 otherwise.
+ */
+int _rte_rcu_qsbr_check(struct rte_rcu_qsbr *v, uint64_t t, bool wait);
@@ -0,0 +1,9 @@
+// The `reorder` subsystem, bound into `ffi::reorder` (and re-exported from the crate's root).
+
+#include <rte_reorder.h>
+
+/**
+ * Returns a pointer to the reorder sequence number of an mbuf, stored in a dynamic field which is registered by
+ * rte_reorder_create.
+ */
+rte_reorder_seqn_t *_rte_reorder_seqn(struct rte_mbuf *mbuf);
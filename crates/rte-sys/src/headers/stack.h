@@ -0,0 +1,23 @@
+// The `stack` subsystem, bound into `ffi::stack` (and re-exported from the crate's root).
+
+#include <rte_stack.h>
+
+/**
+ * Pushes either all `n` objects onto a stack or none of them, returning the number of pushed objects.
+ */
+unsigned int _rte_stack_push(struct rte_stack *s, void *const *obj_table, unsigned int n);
+
+/**
+ * Pops either `n` objects from a stack or none of them, returning the number of popped objects.
+ */
+unsigned int _rte_stack_pop(struct rte_stack *s, void **obj_table, unsigned int n);
+
+/**
+ * Returns the number of objects in a stack.
+ */
+unsigned int _rte_stack_count(struct rte_stack *s);
+
+/**
+ * Returns the number of free slots in a stack.
+ */
+unsigned int _rte_stack_free_count(struct rte_stack *s);
@@ -0,0 +1,3 @@
+// The `telemetry` subsystem, bound into `ffi::telemetry` (and re-exported from the crate's root).
+
+#include <rte_telemetry.h>
@@ -0,0 +1,3 @@
+// The `timer` subsystem, bound into `ffi::timer` (and re-exported from the crate's root).
+
+#include <rte_timer.h>
@@ -1,10 +1,72 @@
 //! Generated FFI bindings for DPDK.
+//!
+//! DPDK's static libraries are linked with the `+whole-archive,-bundle` modifiers (so that its drivers' constructors
+//! aren't dropped by the linker), hence dependents need neither a build script nor any generated file of their own.
+//!
+//! The EAL (along with lcores, rings, memory allocation and errno) is bound into the crate's root, and each of DPDK's
+//! other subsystems into a module of its own (e.g. [`ethdev`]), enabled by the feature of the same name and
+//! re-exported from the crate's root.
+//!
+//! DPDK's `static inline` functions are exposed through `_`-prefixed stubs (e.g. `_rte_eth_rx_burst`), to which more
+//! can be added without forking rte-sys by listing their prototypes in a file under `RTE_SYS_EXTRA_STUBS` (see
+//! `build/stubs.rs` for its format).
 
 #![allow(clippy::all)]
 #![allow(dead_code)]
 #![allow(non_upper_case_globals)]
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
+// the anonymous types of the headers of several subsystems (e.g. `_bindgen_ty_1`) are bound by each of them
+#![allow(ambiguous_glob_reexports)]
 #![cfg(target_os = "linux")]
 
-include!(concat!(env!("OUT_DIR"), "/dpdk_bindings.rs"));
+include!(concat!(env!("OUT_DIR"), "/eal.rs"));
+
+macro_rules! subsystems {
+    ($($feature:literal => $module:ident,)*) => {$(
+        #[cfg(feature = $feature)]
+        pub mod $module {
+            #[allow(unused_imports)]
+            use super::*;
+
+            include!(concat!(env!("OUT_DIR"), "/", stringify!($module), ".rs"));
+        }
+
+        #[cfg(feature = $feature)]
+        pub use $module::*;
+    )*};
+}
+
+subsystems! {
+    "mbuf" => mbuf,
+    "net" => net,
+    "ethdev" => ethdev,
+    "acl" => acl,
+    "bpf" => bpf,
+    "crypto" => crypto,
+    "distributor" => distributor,
+    "dmadev" => dmadev,
+    "eventdev" => eventdev,
+    "fib" => fib,
+    "graph" => graph,
+    "hash" => hash,
+    "ip-frag" => ip_frag,
+    "meter" => meter,
+    "metrics" => metrics,
+    "pdump" => pdump,
+    "rcu" => rcu,
+    "reorder" => reorder,
+    "stack" => stack,
+    "telemetry" => telemetry,
+    "timer" => timer,
+}
+
+/// The stubs listed under `RTE_SYS_EXTRA_STUBS`, if any.
+pub mod extra {
+    #[allow(unused_imports)]
+    use super::*;
+
+    include!(concat!(env!("OUT_DIR"), "/extra.rs"));
+}
+
+pub use extra::*;
@@ -1,7 +1,58 @@
+#include <rte_cycles.h>
 #include <rte_errno.h>
-#include <rte_ethdev.h>
+#include <rte_ring.h>
+
+#ifdef RTE_SYS_MBUF
 #include <rte_mbuf.h>
 #include <rte_mempool.h>
+#endif
+
+#ifdef RTE_SYS_NET
+#include <rte_ip.h>
+#include <rte_thash.h>
+#endif
+
+#ifdef RTE_SYS_ETHDEV
+#include <rte_ethdev.h>
+#endif
+
+#ifdef RTE_SYS_CRYPTO
+#include <rte_cryptodev.h>
+#include <rte_ipsec.h>
+#include <rte_security.h>
+#endif
+
+#ifdef RTE_SYS_DMADEV
+#include <rte_dmadev.h>
+#endif
+
+#ifdef RTE_SYS_EVENTDEV
+#include <rte_eventdev.h>
+#endif
+
+#ifdef RTE_SYS_GRAPH
+#include <rte_graph_worker.h>
+#endif
+
+#ifdef RTE_SYS_IP_FRAG
+#include <rte_ip_frag.h>
+#endif
+
+#ifdef RTE_SYS_METER
+#include <rte_meter.h>
+#endif
+
+#ifdef RTE_SYS_RCU
+#include <rte_rcu_qsbr.h>
+#endif
+
+#ifdef RTE_SYS_REORDER
+#include <rte_reorder.h>
+#endif
+
+#ifdef RTE_SYS_STACK
+#include <rte_stack.h>
+#endif
 
 void _rte_set_mock_lcore(uint32_t lcore_id)
 {
@@ -18,11 +69,73 @@ int _rte_errno(void)
     return rte_errno;
 }
 
+int _rte_ring_enqueue_elem(struct rte_ring *r, void *obj, unsigned int esize)
+{
+    return rte_ring_enqueue_elem(r, obj, esize);
+}
+
+unsigned int _rte_ring_enqueue_bulk_elem(struct rte_ring *r, const void *obj_table, unsigned int esize, unsigned int n, unsigned int *free_space)
+{
+    return rte_ring_enqueue_bulk_elem(r, obj_table, esize, n, free_space);
+}
+
+unsigned int _rte_ring_enqueue_burst_elem(struct rte_ring *r, const void *obj_table, unsigned int esize, unsigned int n, unsigned int *free_space)
+{
+    return rte_ring_enqueue_burst_elem(r, obj_table, esize, n, free_space);
+}
+
+int _rte_ring_dequeue_elem(struct rte_ring *r, void *obj_p, unsigned int esize)
+{
+    return rte_ring_dequeue_elem(r, obj_p, esize);
+}
+
+unsigned int _rte_ring_dequeue_bulk_elem(struct rte_ring *r, void *obj_table, unsigned int esize, unsigned int n, unsigned int *available)
+{
+    return rte_ring_dequeue_bulk_elem(r, obj_table, esize, n, available);
+}
+
+unsigned int _rte_ring_dequeue_burst_elem(struct rte_ring *r, void *obj_table, unsigned int esize, unsigned int n, unsigned int *available)
+{
+    return rte_ring_dequeue_burst_elem(r, obj_table, esize, n, available);
+}
+
+unsigned int _rte_ring_count(const struct rte_ring *r)
+{
+    return rte_ring_count(r);
+}
+
+unsigned int _rte_ring_free_count(const struct rte_ring *r)
+{
+    return rte_ring_free_count(r);
+}
+
+unsigned int _rte_ring_get_capacity(const struct rte_ring *r)
+{
+    return rte_ring_get_capacity(r);
+}
+
+uint64_t _rte_rdtsc(void)
+{
+    return rte_rdtsc();
+}
+
+#ifdef RTE_SYS_MBUF
+
 char *_rte_pktmbuf_prepend(struct rte_mbuf *m, uint16_t len)
 {
     return rte_pktmbuf_prepend(m, len);
 }
 
+char *_rte_pktmbuf_adj(struct rte_mbuf *m, uint16_t len)
+{
+    return rte_pktmbuf_adj(m, len);
+}
+
+int _rte_pktmbuf_linearize(struct rte_mbuf *mbuf)
+{
+    return rte_pktmbuf_linearize(mbuf);
+}
+
 struct rte_mbuf *_rte_pktmbuf_alloc(struct rte_mempool *mp)
 {
     return rte_pktmbuf_alloc(mp);
@@ -38,6 +151,25 @@ void _rte_mempool_put_bulk(struct rte_mempool *mp, void *const *obj_table, unsig
     rte_mempool_put_bulk(mp, obj_table, n);
 }
 
+uint16_t _rte_pktmbuf_priv_size(struct rte_mempool *mp)
+{
+    return rte_pktmbuf_priv_size(mp);
+}
+
+uint16_t _rte_pktmbuf_data_room_size(struct rte_mempool *mp)
+{
+    return rte_pktmbuf_data_room_size(mp);
+}
+
+rte_iova_t _rte_mbuf_data_iova(const struct rte_mbuf *mb)
+{
+    return rte_mbuf_data_iova(mb);
+}
+
+#endif
+
+#ifdef RTE_SYS_ETHDEV
+
 uint16_t _rte_eth_rx_burst(uint16_t port_id, uint16_t queue_id, struct rte_mbuf **rx_pkts, const uint16_t nb_pkts)
 {
     return rte_eth_rx_burst(port_id, queue_id, rx_pkts, nb_pkts);
@@ -48,12 +180,297 @@ uint16_t _rte_eth_tx_burst(uint16_t port_id, uint16_t queue_id, struct rte_mbuf
     return rte_eth_tx_burst(port_id, queue_id, tx_pkts, nb_pkts);
 }
 
-uint16_t _rte_pktmbuf_priv_size(struct rte_mempool *mp)
+uint16_t _rte_eth_tx_buffer(uint16_t port_id, uint16_t queue_id, struct rte_eth_dev_tx_buffer *buffer,
+                            struct rte_mbuf *tx_pkt)
 {
-    return rte_pktmbuf_priv_size(mp);
+    return rte_eth_tx_buffer(port_id, queue_id, buffer, tx_pkt);
 }
 
-uint16_t _rte_pktmbuf_data_room_size(struct rte_mempool *mp)
+uint16_t _rte_eth_tx_buffer_flush(uint16_t port_id, uint16_t queue_id, struct rte_eth_dev_tx_buffer *buffer)
 {
-    return rte_pktmbuf_data_room_size(mp);
+    return rte_eth_tx_buffer_flush(port_id, queue_id, buffer);
+}
+
+int _rte_eth_tx_descriptor_status(uint16_t port_id, uint16_t queue_id, uint16_t offset)
+{
+    return rte_eth_tx_descriptor_status(port_id, queue_id, offset);
+}
+
+int _rte_eth_rx_queue_count(uint16_t port_id, uint16_t queue_id)
+{
+    return rte_eth_rx_queue_count(port_id, queue_id);
+}
+
+#endif
+
+#ifdef RTE_SYS_NET
+
+uint32_t _rte_softrss(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key)
+{
+    return rte_softrss(input_tuple, input_len, rss_key);
+}
+
+uint32_t _rte_softrss_be(uint32_t *input_tuple, uint32_t input_len, const uint8_t *rss_key)
+{
+    return rte_softrss_be(input_tuple, input_len, rss_key);
+}
+
+void _rte_convert_rss_key(const uint32_t *orig, uint32_t *targ, int len)
+{
+    rte_convert_rss_key(orig, targ, len);
+}
+
+uint16_t _rte_raw_cksum(const void *buf, size_t len)
+{
+    return rte_raw_cksum(buf, len);
+}
+
+uint16_t _rte_ipv4_cksum(const struct rte_ipv4_hdr *ipv4_hdr)
+{
+    return rte_ipv4_cksum(ipv4_hdr);
+}
+
+uint16_t _rte_ipv4_phdr_cksum(const struct rte_ipv4_hdr *ipv4_hdr, uint64_t ol_flags)
+{
+    return rte_ipv4_phdr_cksum(ipv4_hdr, ol_flags);
+}
+
+uint16_t _rte_ipv4_udptcp_cksum(const struct rte_ipv4_hdr *ipv4_hdr, const void *l4_hdr)
+{
+    return rte_ipv4_udptcp_cksum(ipv4_hdr, l4_hdr);
+}
+
+uint16_t _rte_ipv6_phdr_cksum(const struct rte_ipv6_hdr *ipv6_hdr, uint64_t ol_flags)
+{
+    return rte_ipv6_phdr_cksum(ipv6_hdr, ol_flags);
+}
+
+uint16_t _rte_ipv6_udptcp_cksum(const struct rte_ipv6_hdr *ipv6_hdr, const void *l4_hdr)
+{
+    return rte_ipv6_udptcp_cksum(ipv6_hdr, l4_hdr);
+}
+
+#endif
+
+#ifdef RTE_SYS_IP_FRAG
+
+int _rte_ipv4_frag_pkt_is_fragmented(const struct rte_ipv4_hdr *hdr)
+{
+    return rte_ipv4_frag_pkt_is_fragmented(hdr);
+}
+
+struct rte_ipv6_fragment_ext *_rte_ipv6_frag_get_ipv6_fragment_header(struct rte_ipv6_hdr *hdr)
+{
+    return rte_ipv6_frag_get_ipv6_fragment_header(hdr);
+}
+
+#endif
+
+#ifdef RTE_SYS_REORDER
+
+rte_reorder_seqn_t *_rte_reorder_seqn(struct rte_mbuf *mbuf)
+{
+    return rte_reorder_seqn(mbuf);
+}
+
+#endif
+
+#ifdef RTE_SYS_METER
+
+enum rte_color _rte_meter_srtcm_color_blind_check(struct rte_meter_srtcm *m, struct rte_meter_srtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len)
+{
+    return rte_meter_srtcm_color_blind_check(m, p, time, pkt_len);
+}
+
+enum rte_color _rte_meter_srtcm_color_aware_check(struct rte_meter_srtcm *m, struct rte_meter_srtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len, enum rte_color pkt_color)
+{
+    return rte_meter_srtcm_color_aware_check(m, p, time, pkt_len, pkt_color);
+}
+
+enum rte_color _rte_meter_trtcm_color_blind_check(struct rte_meter_trtcm *m, struct rte_meter_trtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len)
+{
+    return rte_meter_trtcm_color_blind_check(m, p, time, pkt_len);
+}
+
+enum rte_color _rte_meter_trtcm_color_aware_check(struct rte_meter_trtcm *m, struct rte_meter_trtcm_profile *p,
+                                                  uint64_t time, uint32_t pkt_len, enum rte_color pkt_color)
+{
+    return rte_meter_trtcm_color_aware_check(m, p, time, pkt_len, pkt_color);
+}
+
+#endif
+
+#ifdef RTE_SYS_EVENTDEV
+
+uint16_t _rte_event_enqueue_burst(uint8_t dev_id, uint8_t port_id, const struct rte_event ev[], uint16_t nb_events)
+{
+    return rte_event_enqueue_burst(dev_id, port_id, ev, nb_events);
+}
+
+uint16_t _rte_event_dequeue_burst(uint8_t dev_id, uint8_t port_id, struct rte_event ev[], uint16_t nb_events,
+                                  uint64_t timeout_ticks)
+{
+    return rte_event_dequeue_burst(dev_id, port_id, ev, nb_events, timeout_ticks);
+}
+
+#endif
+
+#ifdef RTE_SYS_CRYPTO
+
+struct rte_crypto_op *_rte_crypto_op_alloc(struct rte_mempool *mempool, enum rte_crypto_op_type type)
+{
+    return rte_crypto_op_alloc(mempool, type);
+}
+
+void _rte_crypto_op_free(struct rte_crypto_op *op)
+{
+    rte_crypto_op_free(op);
+}
+
+int _rte_crypto_op_attach_sym_session(struct rte_crypto_op *op, void *sess)
+{
+    return rte_crypto_op_attach_sym_session(op, sess);
+}
+
+uint16_t _rte_crypto_op_get_priv_data_size(struct rte_mempool *mempool)
+{
+    return __rte_crypto_op_get_priv_data_size(mempool);
+}
+
+uint16_t _rte_cryptodev_enqueue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops)
+{
+    return rte_cryptodev_enqueue_burst(dev_id, qp_id, ops, nb_ops);
+}
+
+uint16_t _rte_cryptodev_dequeue_burst(uint8_t dev_id, uint16_t qp_id, struct rte_crypto_op **ops, uint16_t nb_ops)
+{
+    return rte_cryptodev_dequeue_burst(dev_id, qp_id, ops, nb_ops);
+}
+
+uint16_t _rte_ipsec_pkt_crypto_prepare(const struct rte_ipsec_session *ss, struct rte_mbuf *mb[],
+                                       struct rte_crypto_op *cop[], uint16_t num)
+{
+    return rte_ipsec_pkt_crypto_prepare(ss, mb, cop, num);
+}
+
+uint16_t _rte_ipsec_pkt_process(const struct rte_ipsec_session *ss, struct rte_mbuf *mb[], uint16_t num)
+{
+    return rte_ipsec_pkt_process(ss, mb, num);
+}
+
+int _rte_security_set_pkt_metadata(struct rte_security_ctx *instance, void *sess, struct rte_mbuf *mb, void *params)
+{
+    return rte_security_set_pkt_metadata(instance, sess, mb, params);
+}
+
+#endif
+
+#ifdef RTE_SYS_GRAPH
+
+rte_node_t _rte_node_register(const struct rte_node_register *node)
+{
+    return __rte_node_register(node);
 }
+
+void _rte_graph_walk(struct rte_graph *graph)
+{
+    rte_graph_walk(graph);
+}
+
+void _rte_node_enqueue(struct rte_graph *graph, struct rte_node *node, rte_edge_t next, void **objs, uint16_t nb_objs)
+{
+    rte_node_enqueue(graph, node, next, objs, nb_objs);
+}
+
+void _rte_node_enqueue_x1(struct rte_graph *graph, struct rte_node *node, rte_edge_t next, void *obj)
+{
+    rte_node_enqueue_x1(graph, node, next, obj);
+}
+
+void _rte_node_next_stream_move(struct rte_graph *graph, struct rte_node *src, rte_edge_t next)
+{
+    rte_node_next_stream_move(graph, src, next);
+}
+
+#endif
+
+#ifdef RTE_SYS_RCU
+
+void _rte_rcu_qsbr_thread_online(struct rte_rcu_qsbr *v, unsigned int thread_id)
+{
+    rte_rcu_qsbr_thread_online(v, thread_id);
+}
+
+void _rte_rcu_qsbr_thread_offline(struct rte_rcu_qsbr *v, unsigned int thread_id)
+{
+    rte_rcu_qsbr_thread_offline(v, thread_id);
+}
+
+void _rte_rcu_qsbr_quiescent(struct rte_rcu_qsbr *v, unsigned int thread_id)
+{
+    rte_rcu_qsbr_quiescent(v, thread_id);
+}
+
+uint64_t _rte_rcu_qsbr_start(struct rte_rcu_qsbr *v)
+{
+    return rte_rcu_qsbr_start(v);
+}
+
+int _rte_rcu_qsbr_check(struct rte_rcu_qsbr *v, uint64_t t, bool wait)
+{
+    return rte_rcu_qsbr_check(v, t, wait);
+}
+
+#endif
+
+#ifdef RTE_SYS_STACK
+
+unsigned int _rte_stack_push(struct rte_stack *s, void *const *obj_table, unsigned int n)
+{
+    return rte_stack_push(s, obj_table, n);
+}
+
+unsigned int _rte_stack_pop(struct rte_stack *s, void **obj_table, unsigned int n)
+{
+    return rte_stack_pop(s, obj_table, n);
+}
+
+unsigned int _rte_stack_count(struct rte_stack *s)
+{
+    return rte_stack_count(s);
+}
+
+unsigned int _rte_stack_free_count(struct rte_stack *s)
+{
+    return rte_stack_free_count(s);
+}
+
+#endif
+
+#ifdef RTE_SYS_DMADEV
+
+int _rte_dma_copy(int16_t dev_id, uint16_t vchan, rte_iova_t src, rte_iova_t dst, uint32_t length, uint64_t flags)
+{
+    return rte_dma_copy(dev_id, vchan, src, dst, length, flags);
+}
+
+int _rte_dma_submit(int16_t dev_id, uint16_t vchan)
+{
+    return rte_dma_submit(dev_id, vchan);
+}
+
+uint16_t _rte_dma_completed(int16_t dev_id, uint16_t vchan, const uint16_t nb_cpls, uint16_t *last_idx,
+                            bool *has_error)
+{
+    return rte_dma_completed(dev_id, vchan, nb_cpls, last_idx, has_error);
+}
+
+uint16_t _rte_dma_burst_capacity(int16_t dev_id, uint16_t vchan)
+{
+    return rte_dma_burst_capacity(dev_id, vchan);
+}
+
+#endif
@@ -5,19 +5,52 @@ use quote::quote;
 
 /// Run a test after an EAL environment was initialized.
 ///
-/// Invoke as `#[rte_test(mock_lcore)]` to mock the current lcore when running the test.
+/// Invoke as `#[rte_test(mock_lcore)]` to mock the current lcore when running the test, and as
+/// `#[rte_test(eal_args = "--no-huge -m 2048 --vdev=net_null0")]` to initialize EAL with other arguments than
+/// `rte::test_utils::DEFAULT_EAL_ARGS`. EAL being initialized once per process (by the first test to run), tests with
+/// other arguments fail unless they run in their own test binary, see `rte::test_utils::init_test_env_with`.
+///
+/// Invoke as `#[rte_test(workers = 4)]` to initialize EAL with as many worker lcores, and run the test on the (mocked)
+/// main lcore, launching the workers with `rte::test_utils::run_on_workers`. As the workers are part of the EAL
+/// arguments, such tests must run in their own test binary, along with the tests using as many workers only.
 #[proc_macro_attribute]
 pub fn rte_test(args: TokenStream, item: TokenStream) -> TokenStream {
     let syn::ItemFn { attrs, vis, sig, block } = syn::parse_macro_input!(item as syn::ItemFn);
     let args = syn::parse_macro_input!(args as syn::AttributeArgs);
-    let mock_lcore = match &args[..] {
-        [] => false,
-        [syn::NestedMeta::Meta(syn::Meta::Path(path))]
-            if path.get_ident().map(ToString::to_string).as_deref() == Some("mock_lcore") =>
-        {
-            true
+
+    let mut mock_lcore = false;
+    let mut eal_args = None;
+    let mut workers = None;
+    for arg in &args {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("mock_lcore") => mock_lcore = true,
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path, lit: syn::Lit::Str(lit), ..
+            })) if path.is_ident("eal_args") => eal_args = Some(lit.value()),
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path, lit: syn::Lit::Int(lit), ..
+            })) if path.is_ident("workers") => {
+                workers = Some(lit.base10_parse::<u32>().expect("`workers` must be a number of lcores"))
+            }
+            _ => panic!(
+                "Possible arguments to `rte_test` are \"mock_lcore\", \"eal_args = \\\"...\\\"\" and \"workers = N\"."
+            ),
         }
-        _ => panic!("Only possible argument to `rte_test` is \"mock_lcore\"."),
+    }
+    if mock_lcore && workers.is_some() {
+        panic!("`rte_test` runs tests with workers on the main lcore, which can't be combined with \"mock_lcore\".");
+    }
+
+    let eal_args = match eal_args {
+        Some(eal_args) => quote! { #eal_args },
+        None => quote! { rte::test_utils::DEFAULT_EAL_ARGS },
+    };
+    let init = match workers {
+        Some(workers) => quote! {
+            rte::test_utils::init_test_env_with(&rte::test_utils::eal_args_with_workers(#eal_args, #workers));
+            rte::test_utils::mock_main_lcore();
+        },
+        None => quote! { rte::test_utils::init_test_env_with(#eal_args); },
     };
 
     let mock_lcore = mock_lcore.then(|| {
@@ -30,7 +63,7 @@ pub fn rte_test(args: TokenStream, item: TokenStream) -> TokenStream {
         #[test]
         #(#attrs)*
         #vis #sig {
-            rte::test_utils::init_test_env();
+            #init
             #mock_lcore;
 
             #block;
@@ -38,3 +71,39 @@ pub fn rte_test(args: TokenStream, item: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Run a benchmark (e.g. a criterion function) after an EAL environment was initialized.
+///
+/// EAL is initialized with `rte::bench_utils::DEFAULT_EAL_ARGS`, or with other arguments if invoked as
+/// `#[rte_bench(eal_args = "--no-huge -m 2048")]`, either being overridden by the `RTE_BENCH_EAL_ARGS` environment
+/// variable (EAL being initialized once per process, by the first benchmark to run).
+#[proc_macro_attribute]
+pub fn rte_bench(args: TokenStream, item: TokenStream) -> TokenStream {
+    let syn::ItemFn { attrs, vis, sig, block } = syn::parse_macro_input!(item as syn::ItemFn);
+    let args = syn::parse_macro_input!(args as syn::AttributeArgs);
+
+    let mut eal_args = None;
+    for arg in &args {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path, lit: syn::Lit::Str(lit), ..
+            })) if path.is_ident("eal_args") => eal_args = Some(lit.value()),
+            _ => panic!("The only possible argument to `rte_bench` is \"eal_args = \\\"...\\\"\"."),
+        }
+    }
+
+    let eal_args = match eal_args {
+        Some(eal_args) => quote! { #eal_args },
+        None => quote! { rte::bench_utils::DEFAULT_EAL_ARGS },
+    };
+
+    quote! {
+        #(#attrs)*
+        #vis #sig {
+            rte::bench_utils::init_eal_with(#eal_args);
+
+            #block
+        }
+    }
+    .into()
+}
@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rte::{
+    bench_utils::{self, rte_bench},
+    dispatch::RssDispatcher,
+    mbuf::{MBuf, PacketBatch},
+    mempool::MemoryPool,
+    net::{parse_headers, IPPROTO_UDP},
+};
+
+const BURST: usize = 32;
+const WORKERS: usize = 4;
+/// The bursts held at once (outside of the measurements), which must fit in the memory pool.
+const BATCH: BatchSize = BatchSize::NumIterations(64);
+
+/// Returns a UDP packet of the flow with the given source port.
+fn udp_packet(src_port: u16) -> [u8; 64] {
+    let mut packet = [0; 64];
+    packet[12..14].copy_from_slice(&[0x08, 0x00]);
+    packet[14] = 0x45;
+    packet[23] = IPPROTO_UDP;
+    packet[26..34].copy_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+    packet[34..36].copy_from_slice(&src_port.to_be_bytes());
+    packet[36..38].copy_from_slice(&53u16.to_be_bytes());
+    packet
+}
+
+/// Compares dispatching a burst of packets of distinct flows in software (i.e. hashing and partitioning them), with
+/// dispatching them by the hashes a NIC with RSS would have computed (i.e. only partitioning them).
+#[rte_bench]
+fn rss_dispatch(c: &mut Criterion) {
+    let mempool = bench_utils::mempool("bench_rss_dispatch", 4095);
+    let mempool = &mempool;
+    let dispatcher = RssDispatcher::new(WORKERS as u16);
+
+    let packets = (0..BURST as u16).map(|flow| udp_packet(1024 + flow)).collect::<Vec<_>>();
+    let nic_hashes =
+        packets.iter().map(|packet| dispatcher.hash(&parse_headers(packet).unwrap()).unwrap()).collect::<Vec<_>>();
+    let burst = || -> PacketBatch<&MemoryPool, BURST> {
+        packets.iter().map(|packet| MBuf::new_with_provider_and_data(&mempool, packet)).collect()
+    };
+
+    let mut group = c.benchmark_group("rss_dispatch");
+    group.throughput(Throughput::Elements(BURST as u64));
+    group.bench_function("software", |b| {
+        b.iter_batched(
+            burst,
+            |pkts| {
+                let mut batches = [(); WORKERS].map(|_| PacketBatch::new());
+                dispatcher.dispatch(pkts, &mut batches);
+                batches
+            },
+            BATCH,
+        )
+    });
+    group.bench_function("nic", |b| {
+        b.iter_batched(
+            burst,
+            |pkts| {
+                let mut hashes = nic_hashes.iter();
+                pkts.classify::<WORKERS, _>(|_| Some(usize::from(dispatcher.worker_of_hash(*hashes.next()?))))
+            },
+            BATCH,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, rss_dispatch);
+criterion_main!(benches);
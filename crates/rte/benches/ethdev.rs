@@ -0,0 +1,102 @@
+use std::ptr;
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use rte::{
+    bench_utils::{self, rte_bench},
+    mbuf::PacketBatch,
+    mempool::MemoryPool,
+};
+
+const BURST: usize = 32;
+/// The bursts held at once (outside of the measurements), which must fit in the memory pools.
+const BATCH: BatchSize = BatchSize::NumIterations(64);
+
+/// Mbufs handled through the FFI, the ones in `start..end` being freed when dropped.
+struct RawBurst {
+    pkts: [*mut ffi::rte_mbuf; BURST],
+    start: usize,
+    end: usize,
+}
+
+impl RawBurst {
+    fn new(pkts: PacketBatch<&MemoryPool, BURST>) -> Self {
+        let mut raw = Self { pkts: [ptr::null_mut(); BURST], start: 0, end: pkts.len() };
+        for (slot, pkt) in raw.pkts.iter_mut().zip(pkts) {
+            *slot = pkt.into_raw().as_ptr();
+        }
+        raw
+    }
+}
+
+impl Drop for RawBurst {
+    fn drop(&mut self) {
+        let pkts = &mut self.pkts[self.start..self.end];
+        unsafe { ffi::rte_pktmbuf_free_bulk(pkts.as_mut_ptr(), pkts.len() as u32) }
+    }
+}
+
+#[rte_bench]
+fn rx_burst(c: &mut Criterion) {
+    let mempool = bench_utils::mempool("bench_rx_burst", 4095);
+    let mut port = bench_utils::null_port("bench_rx", &mempool);
+    let port_id = port.dev().port_id();
+
+    let mut group = c.benchmark_group("rx_burst");
+    group.throughput(Throughput::Elements(BURST as u64));
+    group.bench_function("wrapper", |b| {
+        b.iter_batched(
+            PacketBatch::<_, BURST>::new,
+            |mut pkts| {
+                port.recv(&mut pkts);
+                pkts
+            },
+            BATCH,
+        )
+    });
+    group.bench_function("ffi", |b| {
+        b.iter_batched(
+            || RawBurst { pkts: [ptr::null_mut(); BURST], start: 0, end: 0 },
+            |mut raw| {
+                raw.end = unsafe { ffi::_rte_eth_rx_burst(port_id, 0, raw.pkts.as_mut_ptr(), BURST as u16) } as usize;
+                raw
+            },
+            BATCH,
+        )
+    });
+    group.finish();
+}
+
+#[rte_bench]
+fn tx_burst(c: &mut Criterion) {
+    let mempool = bench_utils::mempool("bench_tx_burst", 4095);
+    let mut port = bench_utils::null_port("bench_tx", &mempool);
+    let port_id = port.dev().port_id();
+
+    let mut group = c.benchmark_group("tx_burst");
+    group.throughput(Throughput::Elements(BURST as u64));
+    group.bench_function("wrapper", |b| {
+        b.iter_batched(
+            || bench_utils::alloc_batch::<BURST>(&mempool, BURST, &[0; 64]),
+            |mut pkts| {
+                port.send(&mut pkts);
+                pkts
+            },
+            BATCH,
+        )
+    });
+    group.bench_function("ffi", |b| {
+        b.iter_batched(
+            || RawBurst::new(bench_utils::alloc_batch(&mempool, BURST, &[0; 64])),
+            |mut raw| {
+                raw.start =
+                    unsafe { ffi::_rte_eth_tx_burst(port_id, 0, raw.pkts.as_mut_ptr(), raw.end as u16) } as usize;
+                raw
+            },
+            BATCH,
+        )
+    });
+    group.finish();
+}
+
+criterion_group!(benches, rx_burst, tx_burst);
+criterion_main!(benches);
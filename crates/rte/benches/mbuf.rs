@@ -0,0 +1,21 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rte::bench_utils::{self, rte_bench};
+
+const BATCH_SIZES: [usize; 3] = [1, 32, 256];
+
+#[rte_bench]
+fn alloc_free(c: &mut Criterion) {
+    let mempool = bench_utils::mempool("bench_mbuf", 1023);
+
+    let mut group = c.benchmark_group("mbuf_alloc_free");
+    for batch_size in BATCH_SIZES {
+        group.throughput(Throughput::Elements(batch_size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(batch_size), &batch_size, |b, &batch_size| {
+            b.iter(|| bench_utils::alloc_batch::<256>(&mempool, batch_size, &[0; 64]))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, alloc_free);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+use std::env;
+
+/// The optional DPDK APIs probed by rte-sys, whose wrappers are only compiled when DPDK provides them.
+const OPTIONAL_APIS: &[&str] = &["dmadev", "flow_async", "graph"];
+
+fn main() {
+    for api in OPTIONAL_APIS {
+        println!("cargo:rustc-check-cfg=cfg(dpdk_has_{api})");
+        // published by rte-sys' build script, see its `probe` module
+        let var = format!("DEP_DPDK_HAS_{}", api.to_uppercase());
+        println!("cargo:rerun-if-env-changed={var}");
+        if env::var_os(var).is_some() {
+            println!("cargo:rustc-cfg=dpdk_has_{api}");
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Minimal run-to-completion forwarders in the spirit of DPDK's `l2fwd` and `l3fwd` samples, built from this crate's
+//! own building blocks: each lcore polls the RX queues of its [`PortPair`]s, processes the received packets (e.g.
+//! [swapping their MAC addresses](swap_macs)) and sends them on the paired TX queues.
+//! ```rust,ignore
+//! let mut a = PortSetup::new(EthDev::new(0), &mempool).start()?;
+//! let mut b = PortSetup::new(EthDev::new(1), &mempool).start()?;
+//! let stats = app::l2fwd(&mut PortPair::cross(&mut a, &mut b, 0), &stop);
+//! ```
+//!
+//! [`l3fwd`] instead [routes](route_ipv4) the received IPv4 packets to the TX queues of their next hops, as looked up
+//! in a [`Fib4`].
+//!
+//! The `rte-l2fwd` binary runs the former on all the ports, as a smoke test of a deployment.
+
+use std::{
+    fmt,
+    ops::AddAssign,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use mac_addr::MacAddr;
+
+use crate::{
+    ethdev::{RxQueue, TxQueue},
+    fib::Fib4,
+    mbuf::{Allocator, MBuf, PacketBatch},
+    mempool::MemoryPool,
+    net::{ipv4_cksum, EtherHdr, Header, Ipv4Hdr, ETHER_TYPE_IPV4},
+    port::Port,
+};
+
+/// The maximum number of packets received at once from a queue.
+pub const BURST: usize = 32;
+
+/// An RX queue whose packets are forwarded to a TX queue (usually of another port).
+pub struct PortPair<'mp> {
+    pub rx: RxQueue<'mp>,
+    pub tx: TxQueue<'mp>,
+}
+
+impl<'mp> PortPair<'mp> {
+    /// Returns the pairs forwarding the packets of queue `queue_id` of each port to the same queue of the other port,
+    /// taking these queues from the ports.
+    ///
+    /// # Panics
+    /// Panics if either port doesn't have the queues, or they were already taken.
+    pub fn cross(a: &mut Port<'mp>, b: &mut Port<'mp>, queue_id: u16) -> [Self; 2] {
+        let take = |port: &mut Port<'mp>| {
+            let rx = port.take_rx_queue(queue_id).expect("no such RX queue, or already taken");
+            let tx = port.take_tx_queue(queue_id).expect("no such TX queue, or already taken");
+            (rx, tx)
+        };
+        let ((a_rx, a_tx), (b_rx, b_tx)) = (take(a), take(b));
+        [Self { rx: a_rx, tx: b_tx }, Self { rx: b_rx, tx: a_tx }]
+    }
+}
+
+/// The packets handled by a forwarder.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ForwardStats {
+    pub rx: u64,
+    pub tx: u64,
+    /// The packets which couldn't be forwarded, e.g. because the TX queue was full.
+    pub dropped: u64,
+}
+
+impl AddAssign for ForwardStats {
+    fn add_assign(&mut self, other: Self) {
+        self.rx += other.rx;
+        self.tx += other.tx;
+        self.dropped += other.dropped;
+    }
+}
+
+impl fmt::Display for ForwardStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} packets received, {} sent, {} dropped", self.rx, self.tx, self.dropped)
+    }
+}
+
+/// Swaps the source and destination MAC addresses of a packet, returning `false` if it's too short to have an Ethernet
+/// header.
+#[inline]
+pub fn swap_macs<A: Allocator>(pkt: &mut MBuf<A>) -> bool {
+    match EtherHdr::mut_from_prefix(pkt) {
+        Some((ether, _)) => {
+            (ether.src_addr, ether.dst_addr) = (ether.dst_addr, ether.src_addr);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Forwards a burst of packets from `pair.rx` to `pair.tx`, processing each of them with `process` first.
+#[inline]
+pub fn forward_burst<'mp, F>(pair: &mut PortPair<'mp>, process: &mut F) -> ForwardStats
+where
+    F: FnMut(&mut MBuf<&'mp MemoryPool>),
+{
+    let mut pkts = PacketBatch::<_, BURST>::new();
+    pair.rx.recv(&mut pkts);
+    let rx = pkts.len();
+    if rx == 0 {
+        return ForwardStats::default();
+    }
+
+    pkts.iter_mut().for_each(&mut *process);
+    pair.tx.send(&mut pkts);
+    // the packets which weren't sent are freed
+    ForwardStats { rx: rx as u64, tx: (rx - pkts.len()) as u64, dropped: pkts.len() as u64 }
+}
+
+/// Forwards the packets of each pair in turn (processing them with `process`) until `stop` is set, returning the
+/// packets handled by each pair.
+pub fn run_forwarder<'mp, F>(pairs: &mut [PortPair<'mp>], stop: &AtomicBool, mut process: F) -> Vec<ForwardStats>
+where
+    F: FnMut(&mut MBuf<&'mp MemoryPool>),
+{
+    let mut stats = vec![ForwardStats::default(); pairs.len()];
+
+    while !stop.load(Ordering::Relaxed) {
+        for (pair, stats) in pairs.iter_mut().zip(&mut stats) {
+            *stats += forward_burst(pair, &mut process);
+        }
+    }
+    stats
+}
+
+/// Forwards the packets of each pair with their MAC addresses swapped until `stop` is set, see [`run_forwarder`].
+pub fn l2fwd(pairs: &mut [PortPair], stop: &AtomicBool) -> Vec<ForwardStats> {
+    run_forwarder(pairs, stop, |pkt| {
+        swap_macs(pkt);
+    })
+}
+
+/// Where the packets routed to a next hop are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NextHop {
+    /// The index of the TX queue the packets are sent on.
+    pub tx: usize,
+    /// The MAC address of the sending port.
+    pub src: MacAddr,
+    /// The MAC address of the next hop.
+    pub dst: MacAddr,
+}
+
+/// Routes an IPv4 packet to the next hop `fib` returns for its destination, i.e. decrements its TTL (updating its
+/// checksum) and rewrites its MAC addresses, returning the index of the next hop in `next_hops`.
+///
+/// Returns `None`, leaving the packet as is, if it isn't a well-formed IPv4 packet, its TTL expires, or it has no route
+/// (its next hop isn't in `next_hops`, e.g. the FIB's default next hop).
+#[inline]
+pub fn route_ipv4<A: Allocator>(pkt: &mut MBuf<A>, fib: &Fib4, next_hops: &[NextHop]) -> Option<usize> {
+    let (ether, l3) = EtherHdr::mut_from_prefix(pkt)?;
+    if ether.ether_type.get() != ETHER_TYPE_IPV4 {
+        return None;
+    }
+    let (ipv4, _) = Ipv4Hdr::ref_from_prefix(l3)?;
+    let header_len = ipv4.header_len();
+    if ipv4.version() != 4 || header_len < Ipv4Hdr::LEN || header_len > l3.len() || ipv4.time_to_live <= 1 {
+        return None;
+    }
+    let hop =
+        fib.lookup(ipv4.dst()).ok().and_then(|hop| usize::try_from(hop).ok()).filter(|&hop| hop < next_hops.len())?;
+
+    let (ipv4, _) = Ipv4Hdr::mut_from_prefix(l3)?;
+    ipv4.time_to_live -= 1;
+    ipv4.hdr_checksum.set(0);
+    let cksum = ipv4_cksum(&l3[..header_len]);
+    Ipv4Hdr::mut_from_prefix(l3)?.0.hdr_checksum.set(cksum);
+
+    (ether.src_addr, ether.dst_addr) = (next_hops[hop].src, next_hops[hop].dst);
+    Some(hop)
+}
+
+/// Routes the packets received on each of `rx_queues` in turn to the TX queues of their next hops (see
+/// [`route_ipv4`]) until `stop` is set, returning the packets handled by each RX queue. The packets which couldn't be
+/// routed are freed, and counted as dropped.
+///
+/// # Panics
+/// Panics if the TX queue of a next hop isn't in `tx_queues`.
+pub fn l3fwd<'mp>(
+    rx_queues: &mut [RxQueue<'mp>],
+    tx_queues: &mut [TxQueue<'mp>],
+    fib: &Fib4,
+    next_hops: &[NextHop],
+    stop: &AtomicBool,
+) -> Vec<ForwardStats> {
+    assert!(next_hops.iter().all(|hop| hop.tx < tx_queues.len()), "no such TX queue");
+    let mut stats = vec![ForwardStats::default(); rx_queues.len()];
+    let mut routed = (0..tx_queues.len()).map(|_| PacketBatch::<_, BURST>::new()).collect::<Vec<_>>();
+
+    while !stop.load(Ordering::Relaxed) {
+        for (rx, stats) in rx_queues.iter_mut().zip(&mut stats) {
+            let mut pkts = PacketBatch::<_, BURST>::new();
+            rx.recv(&mut pkts);
+            stats.rx += pkts.len() as u64;
+
+            for mut pkt in pkts {
+                match route_ipv4(&mut pkt, fib, next_hops) {
+                    Some(hop) => routed[next_hops[hop].tx].push(pkt),
+                    None => stats.dropped += 1,
+                }
+            }
+            for (tx, pkts) in tx_queues.iter_mut().zip(&mut routed).filter(|(_, pkts)| !pkts.is_empty()) {
+                let len = pkts.len();
+                tx.send(pkts);
+                // the packets which weren't sent are freed
+                stats.tx += (len - pkts.len()) as u64;
+                stats.dropped += pkts.len() as u64;
+                pkts.clear();
+            }
+        }
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::{
+        fib::{Config, Ipv4Algorithm, NextHopSize},
+        mbuf::GlobalAllocator,
+        net::{raw_cksum, ETHER_TYPE_ARP},
+    };
+
+    #[test]
+    fn test_swap_macs() {
+        let mut packet = [0; 64];
+        packet[..6].copy_from_slice(&[2, 0, 0, 0, 0, 1]);
+        packet[6..12].copy_from_slice(&[2, 0, 0, 0, 0, 2]);
+
+        let mut pkt = MBuf::<GlobalAllocator>::new_with_data(packet);
+        assert!(swap_macs(&mut pkt));
+        assert_eq!(pkt[..12], [2, 0, 0, 0, 0, 2, 2, 0, 0, 0, 0, 1]);
+
+        let mut short = MBuf::<GlobalAllocator>::new_with_data([0; 13]);
+        assert!(!swap_macs(&mut short));
+    }
+
+    fn ipv4_packet(dst: [u8; 4], ttl: u8) -> MBuf<GlobalAllocator> {
+        let mut packet = [0; 64];
+        let (ether, l3) = EtherHdr::mut_from_prefix(&mut packet).unwrap();
+        ether.ether_type.set(ETHER_TYPE_IPV4);
+        let (ipv4, _) = Ipv4Hdr::mut_from_prefix(l3).unwrap();
+        *ipv4 = Ipv4Hdr {
+            version_ihl: Ipv4Hdr::VERSION_IHL,
+            time_to_live: ttl,
+            src_addr: [10, 0, 0, 1],
+            dst_addr: dst,
+            ..Default::default()
+        };
+        MBuf::new_with_data(packet)
+    }
+
+    #[rte_test]
+    fn test_route_ipv4() {
+        let conf = Config {
+            algorithm: Ipv4Algorithm::Dir24_8 { next_hop_size: NextHopSize::U32, num_tbl8: 16 },
+            // out of the next hops, i.e. no route
+            default_next_hop: 255,
+            max_routes: 16,
+        };
+        let mut fib = Fib4::new("test_route_ipv4", None, &conf).unwrap();
+        fib.add("10.1.0.0/16".parse().unwrap(), 1).unwrap();
+        fib.add("10.2.0.0/16".parse().unwrap(), 2).unwrap();
+
+        let next_hop = |tx| NextHop { tx, src: MacAddr::new(2, 0, 0, 0, 0, 1), dst: MacAddr::new(2, 0, 0, 0, 0, 2) };
+        let next_hops = [next_hop(0), next_hop(1)];
+
+        let mut pkt = ipv4_packet([10, 1, 2, 3], 64);
+        assert_eq!(route_ipv4(&mut pkt, &fib, &next_hops), Some(1));
+        assert_eq!(pkt[..12], [2, 0, 0, 0, 0, 2, 2, 0, 0, 0, 0, 1]);
+        let (ipv4, _) = Ipv4Hdr::ref_from_prefix(&pkt[EtherHdr::LEN..]).unwrap();
+        assert_eq!(ipv4.time_to_live, 63);
+        // the checksum of a valid header sums up to all ones
+        assert_eq!(raw_cksum(&pkt[EtherHdr::LEN..][..Ipv4Hdr::LEN]), 0xffff);
+
+        // the TTL expires
+        assert_eq!(route_ipv4(&mut ipv4_packet([10, 1, 2, 3], 1), &fib, &next_hops), None);
+        // no route, and a next hop without a TX queue
+        assert_eq!(route_ipv4(&mut ipv4_packet([11, 0, 0, 1], 64), &fib, &next_hops), None);
+        assert_eq!(route_ipv4(&mut ipv4_packet([10, 2, 0, 1], 64), &fib, &next_hops), None);
+
+        let mut arp = ipv4_packet([10, 1, 2, 3], 64);
+        arp[12..14].copy_from_slice(&ETHER_TYPE_ARP.to_be_bytes());
+        let original = arp[..].to_vec();
+        assert_eq!(route_ipv4(&mut arp, &fib, &next_hops), None);
+        assert_eq!(arp[..], original);
+    }
+}
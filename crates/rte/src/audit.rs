@@ -0,0 +1,48 @@
+//! Exported, monomorphic instances of the burst wrappers, next to the FFI calls they wrap, so that their codegen can
+//! be compared in the built library, e.g.:
+//! ```sh
+//! cargo build --release --features zero-overhead-audit
+//! objdump -d --no-show-raw-insn --disassemble=rte_audit_rx_burst target/release/librte.rlib
+//! objdump -d --no-show-raw-insn --disassemble=rte_audit_ffi_rx_burst target/release/librte.rlib
+//! ```
+//!
+//! The `zero-overhead-audit` feature also forces the wrappers to be inlined, so that they are audited as they are used.
+
+use crate::{
+    ethdev::{RxQueue, TxQueue},
+    mbuf::PacketBatch,
+    mempool::MemoryPool,
+};
+
+/// The number of packets of the audited bursts.
+pub const BURST: usize = 32;
+
+pub type Burst = PacketBatch<&'static MemoryPool, BURST>;
+
+#[no_mangle]
+#[inline(never)]
+pub fn rte_audit_rx_burst(queue: &mut RxQueue<'static>, pkts: &mut Burst) {
+    queue.recv(pkts)
+}
+
+/// # Safety
+/// See [`ffi::rte_eth_rx_burst`].
+#[no_mangle]
+#[inline(never)]
+pub unsafe fn rte_audit_ffi_rx_burst(port_id: u16, queue_id: u16, pkts: *mut *mut ffi::rte_mbuf) -> u16 {
+    ffi::_rte_eth_rx_burst(port_id, queue_id, pkts, BURST as u16)
+}
+
+#[no_mangle]
+#[inline(never)]
+pub fn rte_audit_tx_burst(queue: &mut TxQueue<'static>, pkts: &mut Burst) {
+    queue.send(pkts)
+}
+
+/// # Safety
+/// See [`ffi::rte_eth_tx_burst`].
+#[no_mangle]
+#[inline(never)]
+pub unsafe fn rte_audit_ffi_tx_burst(port_id: u16, queue_id: u16, pkts: *mut *mut ffi::rte_mbuf, nb_pkts: u16) -> u16 {
+    ffi::_rte_eth_tx_burst(port_id, queue_id, pkts, nb_pkts)
+}
@@ -0,0 +1,66 @@
+//! Fixtures for benchmarks: a one-time EAL initialization, pre-sized memory pools and batches of packets, e.g.:
+//! ```rust,ignore
+//! let mempool = bench_utils::mempool("bench_rx", 4095);
+//! c.bench_function("rx", |b| b.iter(|| process(bench_utils::alloc_batch::<32>(&mempool, 32, &[0; 64]))));
+//! ```
+
+use std::{env, iter, sync::Once};
+
+pub use rte_test_macros::rte_bench;
+
+use crate::{
+    mbuf::{MBuf, PacketBatch},
+    mempool::MemoryPool,
+    port,
+    test_utils::FakePort,
+};
+
+/// The EAL arguments of benchmarks, unless overridden with [`EAL_ARGS_ENV`].
+pub const DEFAULT_EAL_ARGS: &str = "--no-huge -m 1024 --no-shconf";
+
+/// Overrides the (whitespace-separated) EAL arguments of benchmarks, e.g. to benchmark with hugepages.
+pub const EAL_ARGS_ENV: &str = "RTE_BENCH_EAL_ARGS";
+
+static INIT: Once = Once::new();
+
+/// Initializes EAL, once per process.
+pub fn init_eal() {
+    init_eal_with(DEFAULT_EAL_ARGS)
+}
+
+/// Initializes EAL (once per process) with the whitespace-separated arguments, unless overridden with [`EAL_ARGS_ENV`].
+pub fn init_eal_with(eal_args: &str) {
+    INIT.call_once(|| {
+        let eal_args = env::var(EAL_ARGS_ENV).unwrap_or_else(|_| eal_args.to_owned());
+        let _ = rte_eal::init(iter::once("").chain(eal_args.split_whitespace()))
+            .expect("Could not initialize EAL for benchmarks");
+    });
+}
+
+/// Creates a memory pool of `size` mbufs with the default data room (initializing EAL if needed), which should be at
+/// least the number of packets a benchmark holds at once. The pool has no per-lcore cache, so that allocations cost the
+/// same regardless of the lcore (if any) running the benchmark.
+pub fn mempool(name: &str, size: u32) -> MemoryPool {
+    init_eal();
+    MemoryPool::new(name, size, 0, 0, ffi::RTE_MBUF_DEFAULT_BUF_SIZE as u16, None)
+        .expect("Could not create a memory pool for benchmarks")
+}
+
+/// Allocates a batch of `len` copies of `packet` from `mempool`.
+///
+/// # Panics
+/// Panics if `len` exceeds `CAP`, or if the memory pool is exhausted.
+pub fn alloc_batch<'mp, const CAP: usize>(
+    mempool: &'mp MemoryPool,
+    len: usize,
+    packet: &[u8],
+) -> PacketBatch<&'mp MemoryPool, CAP> {
+    iter::repeat_with(|| MBuf::new_with_provider_and_data(&mempool, packet)).take(len).collect()
+}
+
+/// Adds a `net_null_<name>` device and starts it with a single RX and TX queue receiving packets into `mempool`
+/// (initializing EAL if needed), to benchmark bursts without the cost of a driver, see [`FakePort::null`].
+pub fn null_port<'mp>(name: &str, mempool: &'mp MemoryPool) -> FakePort<'mp> {
+    init_eal();
+    FakePort::null(name, port::DEFAULT_NB_RX_DESC, mempool).expect("Could not start a port for benchmarks")
+}
@@ -0,0 +1,81 @@
+//! A smoke test of a deployment: forwards packets between pairs of ports (0 and 1, 2 and 3, ...) with their MAC
+//! addresses swapped, for the number of seconds given after the EAL arguments (10 by default), e.g.:
+//! ```sh
+//! rte-l2fwd -l 0 -a 0000:3b:00.0 -a 0000:3b:00.1 -- 30
+//! ```
+//! It fails unless packets were forwarded by every pair.
+
+use std::{
+    env,
+    process::ExitCode,
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use rte::{
+    app::{self, PortPair},
+    ethdev::EthDev,
+    mempool::MemoryPool,
+    port::PortSetup,
+};
+
+const DEFAULT_DURATION: Duration = Duration::from_secs(10);
+const NB_MBUFS: u32 = 8191;
+const MBUF_CACHE_SIZE: u32 = 256;
+
+static STOP: AtomicBool = AtomicBool::new(false);
+
+fn main() -> ExitCode {
+    let args = rte_eal::init(env::args()).expect("Could not initialize EAL");
+    let duration = args.filter_map(|arg| arg.parse().ok()).next().map_or(DEFAULT_DURATION, Duration::from_secs);
+
+    let devs = EthDev::for_each().collect::<Vec<_>>();
+    if devs.len() < 2 || devs.len() % 2 != 0 {
+        eprintln!("an even number of ports is required, found {}", devs.len());
+        return ExitCode::FAILURE;
+    }
+
+    let mempool = MemoryPool::new(
+        "l2fwd",
+        NB_MBUFS * devs.len() as u32,
+        MBUF_CACHE_SIZE,
+        0,
+        ffi::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+        None,
+    )
+    .expect("Could not create the memory pool");
+    let mut ports = devs
+        .into_iter()
+        .map(|dev| PortSetup::new(dev, &mempool).promiscuous(true).start().expect("Could not set up a port"))
+        .collect::<Vec<_>>();
+    let mut pairs = ports
+        .chunks_mut(2)
+        .flat_map(|pair| {
+            let [a, b] = pair else { unreachable!() };
+            PortPair::cross(a, b, 0)
+        })
+        .collect::<Vec<_>>();
+
+    thread::spawn(move || {
+        thread::sleep(duration);
+        STOP.store(true, Ordering::Relaxed);
+    });
+    let stats = app::l2fwd(&mut pairs, &STOP);
+
+    for (pair, stats) in pairs.iter().zip(&stats) {
+        println!("port {} -> port {}: {stats}", pair.rx.dev().port_id(), pair.tx.dev().port_id());
+    }
+    drop(pairs);
+    for port in ports {
+        // Safety: the queues of the port were dropped along with the pairs
+        let _ = unsafe { port.dev().quiesce(Duration::from_secs(1)) };
+    }
+
+    if stats.iter().all(|stats| stats.tx > 0) {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("some ports didn't forward any packets");
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,94 @@
+//! Based on DPDK's `rte_bitrate.h` API: <https://doc.dpdk.org/api-22.11/rte__bitrate_8h.html>
+//!
+//! Computes the mean, smoothed (EWMA) and peak bitrates of ports from the deltas of their
+//! [stats](crate::ethdev::EthDev::stats), and publishes them through the metrics library (e.g. for `dpdk-proc-info` or
+//! telemetry).
+
+use std::{ffi::CStr, fmt, ptr::NonNull};
+
+use rte_error::ReturnValue as _;
+
+use crate::{ethdev::EthDev, memory::SocketId, Result};
+
+/// The bitrates of a port, in bits per [calculation](Bitrates::calc) interval (i.e. bits per second, when calculated
+/// once per second).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortBitrate {
+    pub mean_in: u64,
+    pub mean_out: u64,
+    pub ewma_in: u64,
+    pub ewma_out: u64,
+    pub peak_in: u64,
+    pub peak_out: u64,
+}
+
+/// The bitrate calculation state of all ports, which is freed when dropped.
+pub struct Bitrates {
+    ptr: NonNull<ffi::rte_stats_bitrates>,
+}
+
+// # Safety
+// The state is only accessed through `&mut self`.
+unsafe impl Send for Bitrates {}
+
+impl Bitrates {
+    /// Creates the calculation state, and registers the bitrate metrics with the metrics library (which is initialized
+    /// on the given socket, if it isn't already).
+    #[inline]
+    pub fn new(socket_id: Option<SocketId>) -> Result<Self> {
+        unsafe { ffi::rte_metrics_init(socket_id.map(|id| id.get() as i32).unwrap_or(-1)) };
+
+        let bitrates = unsafe { ffi::rte_stats_bitrate_create() }.rte_ok().map(|ptr| Self { ptr })?;
+        unsafe { ffi::rte_stats_bitrate_reg(bitrates.ptr.as_ptr()) }.rte_ok()?;
+        Ok(bitrates)
+    }
+
+    /// Updates the bitrates of `dev` from the deltas of its stats since the previous calculation, which should be done
+    /// periodically (usually once per second).
+    #[inline]
+    pub fn calc(&mut self, dev: &EthDev) -> Result<()> {
+        unsafe { ffi::rte_stats_bitrate_calc(self.ptr.as_ptr(), dev.port_id()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the bitrates of `dev` as of its last calculation.
+    #[inline]
+    pub fn get(&self, dev: &EthDev) -> Result<PortBitrate> {
+        let len = unsafe { ffi::rte_metrics_get_names(std::ptr::null_mut(), 0) }.rte_ok()? as usize;
+        let mut names = vec![ffi::rte_metric_name::default(); len];
+        let mut values = vec![ffi::rte_metric_value::default(); len];
+
+        unsafe { ffi::rte_metrics_get_names(names.as_mut_ptr(), len as u16) }.rte_ok()?;
+        let len = unsafe { ffi::rte_metrics_get_values(dev.port_id().into(), values.as_mut_ptr(), len as u16) }
+            .rte_ok()? as usize;
+
+        let mut bitrate = PortBitrate::default();
+        for value in &values[..len.min(values.len())] {
+            let Some(name) = names.get(value.key as usize) else { continue };
+            let field = match unsafe { CStr::from_ptr(name.name.as_ptr()) }.to_bytes() {
+                b"mean_bits_in" => &mut bitrate.mean_in,
+                b"mean_bits_out" => &mut bitrate.mean_out,
+                b"ewma_bits_in" => &mut bitrate.ewma_in,
+                b"ewma_bits_out" => &mut bitrate.ewma_out,
+                b"peak_bits_in" => &mut bitrate.peak_in,
+                b"peak_bits_out" => &mut bitrate.peak_out,
+                _ => continue,
+            };
+            *field = value.value;
+        }
+
+        Ok(bitrate)
+    }
+}
+
+impl fmt::Debug for Bitrates {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Bitrates").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Bitrates {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_stats_bitrate_free(self.ptr.as_ptr()) }
+    }
+}
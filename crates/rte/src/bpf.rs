@@ -0,0 +1,207 @@
+//! Based on DPDK's `rte_bpf.h` API: <https://doc.dpdk.org/api-22.11/rte__bpf_8h.html>
+//!
+//! Loads eBPF programs at runtime (either from an ELF object or from raw instructions), which are validated and
+//! (where supported) JIT-compiled by DPDK, and can then be executed on mbufs or buffers. Programs can also be attached
+//! to the RX or TX queues of a port as filters, see [`EthDev::bpf_rx_load`](crate::ethdev::EthDev::bpf_rx_load).
+//!
+//! External symbols (i.e. helper functions or variables called by the program) are not supported.
+
+use std::{
+    ffi::CString,
+    fmt, mem,
+    os::{raw::c_void, unix::ffi::OsStrExt},
+    path::Path,
+    ptr::NonNull,
+};
+
+use rte_error::ReturnValue as _;
+
+use crate::{
+    mbuf::{Allocator, MBuf},
+    Result,
+};
+
+/// A raw eBPF instruction.
+pub type Insn = ffi::ebpf_insn;
+
+/// The type of a program's argument (i.e. its `R1` register), which determines the memory accesses the validator
+/// allows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arg {
+    /// A 64 bit value, which can't be dereferenced.
+    Raw,
+    /// A pointer to a buffer of `size` bytes.
+    Ptr { size: usize },
+    /// A pointer to an mbuf, whose data (of up to `buf_size` bytes) may be accessed using the `LD_ABS`/`LD_IND`
+    /// instructions.
+    Mbuf { buf_size: usize },
+}
+
+impl Arg {
+    pub(crate) fn as_raw(self) -> ffi::rte_bpf_arg {
+        match self {
+            Self::Raw => ffi::rte_bpf_arg {
+                type_: ffi::rte_bpf_arg_type::RTE_BPF_ARG_RAW,
+                size: mem::size_of::<u64>(),
+                buf_size: 0,
+            },
+            Self::Ptr { size } => ffi::rte_bpf_arg { type_: ffi::rte_bpf_arg_type::RTE_BPF_ARG_PTR, size, buf_size: 0 },
+            Self::Mbuf { buf_size } => ffi::rte_bpf_arg {
+                type_: ffi::rte_bpf_arg_type::RTE_BPF_ARG_PTR_MBUF,
+                size: mem::size_of::<ffi::rte_mbuf>(),
+                buf_size,
+            },
+        }
+    }
+
+    pub(crate) fn as_prm(self, insns: &[Insn]) -> ffi::rte_bpf_prm {
+        ffi::rte_bpf_prm {
+            ins: insns.as_ptr(),
+            nb_ins: insns.len() as u32,
+            prog_arg: self.as_raw(),
+            ..Default::default()
+        }
+    }
+}
+
+/// A loaded (and validated) eBPF program, which is destroyed when dropped.
+pub struct Bpf {
+    ptr: NonNull<ffi::rte_bpf>,
+    arg: Arg,
+}
+
+// # Safety
+// The program is immutable once loaded, and may be executed concurrently.
+unsafe impl Send for Bpf {}
+unsafe impl Sync for Bpf {}
+
+impl Bpf {
+    /// Loads a program from raw instructions.
+    #[inline]
+    pub fn load(insns: &[Insn], arg: Arg) -> Result<Self> {
+        unsafe { ffi::rte_bpf_load(&arg.as_prm(insns)) }.rte_ok().map(|ptr| Self { ptr, arg })
+    }
+
+    /// Loads a program from the given section (e.g. `.text`) of an ELF object file (e.g. compiled by clang).
+    #[inline]
+    pub fn load_elf<P: AsRef<Path>>(path: P, section: &str, arg: Arg) -> Result<Self> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes()).unwrap();
+        let section = CString::new(section).unwrap();
+
+        unsafe { ffi::rte_bpf_elf_load(&arg.as_prm(&[]), path.as_ptr(), section.as_ptr()) }
+            .rte_ok()
+            .map(|ptr| Self { ptr, arg })
+    }
+
+    #[inline]
+    pub fn arg(&self) -> Arg {
+        self.arg
+    }
+
+    /// Returns whether the program was JIT-compiled (otherwise it is interpreted).
+    #[inline]
+    pub fn is_jit(&self) -> bool {
+        let mut jit = ffi::rte_bpf_jit::default();
+        unsafe { ffi::rte_bpf_get_jit(self.ptr.as_ptr(), &mut jit) == 0 && jit.func.is_some() }
+    }
+
+    /// Executes a program taking a [raw](Arg::Raw) argument.
+    ///
+    /// # Panics
+    /// Panics if the program does not take a raw argument.
+    #[inline]
+    pub fn exec_raw(&self, value: u64) -> u64 {
+        assert_eq!(self.arg, Arg::Raw, "program does not take a raw argument");
+        unsafe { ffi::rte_bpf_exec(self.ptr.as_ptr(), value as *mut c_void) }
+    }
+
+    /// Executes a program taking a [pointer](Arg::Ptr) argument on `buf`, which the program may modify.
+    ///
+    /// # Panics
+    /// Panics if the program does not take a pointer argument, or if `buf` is smaller than its size.
+    #[inline]
+    pub fn exec_buf(&self, buf: &mut [u8]) -> u64 {
+        assert!(matches!(self.arg, Arg::Ptr { size } if size <= buf.len()), "invalid buffer for program");
+        unsafe { ffi::rte_bpf_exec(self.ptr.as_ptr(), buf.as_mut_ptr().cast()) }
+    }
+
+    /// Returns the number of bytes of the data of the mbufs the program may access.
+    fn mbuf_buf_size(&self) -> usize {
+        match self.arg {
+            Arg::Mbuf { buf_size } => buf_size,
+            _ => panic!("program does not take an mbuf argument"),
+        }
+    }
+
+    /// Executes a program taking an [mbuf](Arg::Mbuf) argument.
+    ///
+    /// # Panics
+    /// Panics if the program does not take an mbuf argument, or if the data of `mbuf` is shorter than its buffer size.
+    #[inline]
+    pub fn exec_mbuf<A: Allocator>(&self, mbuf: &mut MBuf<A>) -> u64 {
+        assert!(mbuf.len() >= self.mbuf_buf_size(), "mbuf shorter than the buffer size of the program");
+        unsafe { ffi::rte_bpf_exec(self.ptr.as_ptr(), mbuf.as_raw().cast()) }
+    }
+
+    /// Executes a program taking an [mbuf](Arg::Mbuf) argument on a burst of mbufs, writing the return value of each
+    /// execution to `results`.
+    ///
+    /// # Panics
+    /// Panics if the program does not take an mbuf argument, if the data of any of `mbufs` is shorter than its buffer
+    /// size, or if `results` is shorter than `mbufs`.
+    #[inline]
+    pub fn exec_mbuf_burst<A: Allocator>(&self, mbufs: &mut [MBuf<A>], results: &mut [u64]) {
+        let buf_size = self.mbuf_buf_size();
+        assert!(mbufs.iter().all(|mbuf| mbuf.len() >= buf_size), "mbuf shorter than the buffer size of the program");
+        assert!(results.len() >= mbufs.len(), "results shorter than mbufs");
+
+        unsafe {
+            ffi::rte_bpf_exec_burst(
+                self.ptr.as_ptr(),
+                mbufs.as_mut_ptr().cast(),
+                results.as_mut_ptr(),
+                mbufs.len() as u32,
+            )
+        };
+    }
+}
+
+impl fmt::Debug for Bpf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Bpf").field("arg", &self.arg).field("jit", &self.is_jit()).finish()
+    }
+}
+
+impl Drop for Bpf {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_bpf_destroy(self.ptr.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn insn(code: u8, dst_reg: u8, src_reg: u8, off: i16, imm: i32) -> Insn {
+        let mut insn = Insn { code, off, imm, ..Default::default() };
+        insn.set_dst_reg(dst_reg);
+        insn.set_src_reg(src_reg);
+        insn
+    }
+
+    #[test]
+    fn test_exec_buf() {
+        let insns = [
+            // r0 = *(u32 *)(r1 + 4)
+            insn(0x61, 0, 1, 4, 0),
+            // r0 += 1
+            insn(0x07, 0, 0, 0, 1),
+            // exit
+            insn(0x95, 0, 0, 0, 0),
+        ];
+        let bpf = Bpf::load(&insns, Arg::Ptr { size: 8 }).unwrap();
+
+        let mut buf = [0, 0, 0, 0, 41, 0, 0, 0];
+        assert_eq!(bpf.exec_buf(&mut buf), u64::from(u32::from_ne_bytes([41, 0, 0, 0])) + 1);
+    }
+}
@@ -0,0 +1,202 @@
+//! Based on DPDK's `rte_cryptodev.h` API: <https://doc.dpdk.org/api-22.11/rte__cryptodev_8h.html>
+//!
+//! A [`CryptoDev`] (e.g. a QAT device, or an AES-NI software device) processes symmetric [`CryptoOp`]s enqueued to
+//! its queue pairs. Each op refers to a [`Session`] holding the [`Transform`] (algorithm, key, etc.) applied to the
+//! op's mbuf, and is allocated from an [`OpPool`], whose private area holds per-op data such as the IV.
+//!
+//! Sessions are allocated from a [`SessionPool`], whose elements must be large enough for the private session data of
+//! every device using it (see [`CryptoDev::session_size`]).
+
+mod op;
+mod session;
+
+use std::{ffi::CStr, marker::PhantomData, mem};
+
+use arrayvec::ArrayVec;
+use rte_error::{Error, ReturnValue as _};
+
+pub use self::{
+    op::{CryptoOp, OpPool, Status, IV_OFFSET},
+    session::{AeadOp, AuthOp, HmacAlgorithm, Session, SessionPool, Transform},
+};
+use crate::{memory::SocketId, Result};
+
+pub type Info = ffi::rte_cryptodev_info;
+pub type Stats = ffi::rte_cryptodev_stats;
+
+/// A crypto device, identified by its device id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CryptoDev {
+    dev_id: u8,
+}
+
+impl CryptoDev {
+    #[inline]
+    pub fn new(dev_id: u8) -> Self {
+        Self { dev_id }
+    }
+
+    /// Returns the device with the given name, e.g. `crypto_aesni_gcm0`, failing with `ENODEV` if there is none.
+    #[inline]
+    pub fn from_name(name: &CStr) -> Result<Self> {
+        // -1 is returned for unknown names, without setting rte_errno
+        let dev_id = unsafe { ffi::rte_cryptodev_get_dev_id(name.as_ptr()) };
+        let dev_id = u8::try_from(dev_id).map_err(|_| Error(libc::ENODEV))?;
+        Ok(Self::new(dev_id))
+    }
+
+    /// Returns the number of crypto devices.
+    #[inline]
+    pub fn count() -> u8 {
+        unsafe { ffi::rte_cryptodev_count() }
+    }
+
+    #[inline]
+    pub fn dev_id(&self) -> u8 {
+        self.dev_id
+    }
+
+    #[inline]
+    pub fn info(&self) -> Info {
+        let mut info = Info::default();
+        unsafe { ffi::rte_cryptodev_info_get(self.dev_id, &mut info) };
+        info
+    }
+
+    /// Returns the size of the private session data of this device, which must fit in the elements of the
+    /// [`SessionPool`] its sessions are created from.
+    #[inline]
+    pub fn session_size(&self) -> u32 {
+        unsafe { ffi::rte_cryptodev_sym_get_private_session_size(self.dev_id) }
+    }
+
+    /// Configures the device, which must be done (while it is stopped) before setting up its queue pairs.
+    #[inline]
+    pub fn configure(&self, nb_queue_pairs: u16, socket_id: Option<SocketId>) -> Result<()> {
+        let mut config = ffi::rte_cryptodev_config {
+            socket_id: socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            nb_queue_pairs,
+            ..Default::default()
+        };
+        unsafe { ffi::rte_cryptodev_configure(self.dev_id, &mut config) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Sets up a queue pair holding up to `nb_descriptors` in-flight ops, whose sessions are created from
+    /// `session_pool`.
+    #[inline]
+    pub fn queue_pair_setup(
+        &self,
+        qp_id: u16,
+        nb_descriptors: u32,
+        session_pool: &SessionPool,
+        socket_id: Option<SocketId>,
+    ) -> Result<()> {
+        let conf = ffi::rte_cryptodev_qp_conf { nb_descriptors, mp_session: session_pool.0.as_ptr() };
+        let socket_id = socket_id.map(|id| id.get() as i32).unwrap_or(-1);
+
+        unsafe { ffi::rte_cryptodev_queue_pair_setup(self.dev_id, qp_id, &conf, socket_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn start(&self) -> Result<()> {
+        unsafe { ffi::rte_cryptodev_start(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stop(&self) {
+        unsafe { ffi::rte_cryptodev_stop(self.dev_id) }
+    }
+
+    #[inline]
+    pub fn close(&self) -> Result<()> {
+        unsafe { ffi::rte_cryptodev_close(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stats(&self) -> Result<Stats> {
+        let mut stats = Stats::default();
+        unsafe { ffi::rte_cryptodev_stats_get(self.dev_id, &mut stats) }.rte_ok()?;
+        Ok(stats)
+    }
+
+    #[inline]
+    pub fn stats_reset(&self) {
+        unsafe { ffi::rte_cryptodev_stats_reset(self.dev_id) }
+    }
+
+    /// Returns a handle for enqueuing and dequeuing ops on one of the device's queue pairs.
+    ///
+    /// # Safety
+    /// Queue pairs are not thread-safe, so it is up to the caller to guarantee that each queue pair is only used by a
+    /// single lcore (i.e. through a single `QueuePair`) at a time.
+    #[inline]
+    pub unsafe fn queue_pair<'a>(&self, qp_id: u16) -> QueuePair<'a> {
+        QueuePair { dev_id: self.dev_id, qp_id, _marker: PhantomData }
+    }
+}
+
+/// A queue pair of a [`CryptoDev`], see [`CryptoDev::queue_pair`].
+#[derive(Debug)]
+pub struct QueuePair<'a> {
+    dev_id: u8,
+    qp_id: u16,
+    _marker: PhantomData<CryptoOp<'a>>,
+}
+
+impl<'a> QueuePair<'a> {
+    #[inline]
+    pub fn qp_id(&self) -> u16 {
+        self.qp_id
+    }
+
+    /// Enqueues as many ops from the beginning of `ops` as possible, for asynchronous processing.
+    ///
+    /// Ops that have been enqueued are removed from `ops`. Any ops remaining in the array were NOT enqueued (e.g. since
+    /// the queue pair is full). Returns the number of enqueued ops.
+    #[inline]
+    pub fn enqueue_burst<const CAP: usize>(&mut self, ops: &mut ArrayVec<CryptoOp<'a>, CAP>) -> usize {
+        let enqueued = unsafe {
+            ffi::_rte_cryptodev_enqueue_burst(self.dev_id, self.qp_id, ops.as_mut_ptr().cast(), ops.len() as u16)
+        } as usize;
+
+        // the device has assumed ownership of the enqueued ops
+        ops.drain(..enqueued).for_each(mem::forget);
+        enqueued
+    }
+
+    /// Dequeues processed ops into the remaining capacity of `ops`, returning the number of dequeued ops.
+    ///
+    /// The [status](CryptoOp::status) of each op should be checked before using its mbuf.
+    #[inline]
+    pub fn dequeue_burst<const CAP: usize>(&mut self, ops: &mut ArrayVec<CryptoOp<'a>, CAP>) -> usize {
+        let old_len = ops.len();
+
+        unsafe {
+            let dequeued = ffi::_rte_cryptodev_dequeue_burst(
+                self.dev_id,
+                self.qp_id,
+                ops.as_mut_ptr().add(old_len).cast(),
+                ops.remaining_capacity().min(u16::MAX as usize) as u16,
+            ) as usize;
+            ops.set_len(old_len + dequeued);
+            dequeued
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[rte_test]
+    fn test_from_unknown_name() {
+        let name = CStr::from_bytes_with_nul(b"crypto_no_such_dev\0").unwrap();
+        assert_eq!(CryptoDev::from_name(name).err(), Some(Error(libc::ENODEV)));
+    }
+}
@@ -0,0 +1,240 @@
+use std::{ffi::CString, fmt, marker::PhantomData, mem, ops::Range, ptr::NonNull};
+
+use rte_error::ReturnValue as _;
+
+use super::session::{Session, SessionKind};
+use crate::{mbuf::MBuf, memory::SocketId, mempool::MemoryPool, Result};
+
+/// The offset (from the start of an op) of the IV, which is stored in the op's private area, followed by the AAD.
+pub const IV_OFFSET: u16 = (mem::size_of::<ffi::rte_crypto_op>() + mem::size_of::<ffi::rte_crypto_sym_op>()) as u16;
+
+/// The AAD is stored after the IV, aligned to 16 bytes.
+const AAD_ALIGN: usize = 16;
+
+/// The status of a [`CryptoOp`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Success,
+    /// The op was not processed yet.
+    NotProcessed,
+    /// The digest did not match, when verifying it (or decrypting using AEAD).
+    AuthFailed,
+    InvalidSession,
+    InvalidArgs,
+    Error,
+}
+
+impl Status {
+    fn from_raw(status: u8) -> Self {
+        match u32::from(status) {
+            ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_SUCCESS => Self::Success,
+            ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_NOT_PROCESSED => Self::NotProcessed,
+            ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_AUTH_FAILED => Self::AuthFailed,
+            ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_INVALID_SESSION => Self::InvalidSession,
+            ffi::rte_crypto_op_status::RTE_CRYPTO_OP_STATUS_INVALID_ARGS => Self::InvalidArgs,
+            _ => Self::Error,
+        }
+    }
+}
+
+/// A memory pool of symmetric [`CryptoOp`]s.
+pub struct OpPool(NonNull<ffi::rte_mempool>);
+
+// # Safety
+// Memory pool operations are thread-safe, and the pool can only be used to allocate ops.
+unsafe impl Send for OpPool {}
+unsafe impl Sync for OpPool {}
+
+impl OpPool {
+    /// Creates a pool of `size` ops, each with a private area of `priv_size` bytes, which must be large enough to hold
+    /// the IV and AAD of the ops' sessions (the AAD being aligned to 16 bytes).
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(
+        name: S,
+        size: u32,
+        cache_size: u32,
+        priv_size: u16,
+        socket_id: Option<SocketId>,
+    ) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        unsafe {
+            ffi::rte_crypto_op_pool_create(
+                name.as_ptr(),
+                ffi::rte_crypto_op_type::RTE_CRYPTO_OP_TYPE_SYMMETRIC,
+                size,
+                cache_size,
+                priv_size,
+                socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            )
+        }
+        .rte_ok()
+        .map(Self)
+    }
+
+    /// Allocates an op, returning `None` if the pool is exhausted.
+    #[inline]
+    pub fn alloc(&self) -> Option<CryptoOp<'_>> {
+        let ptr = unsafe {
+            ffi::_rte_crypto_op_alloc(self.0.as_ptr(), ffi::rte_crypto_op_type::RTE_CRYPTO_OP_TYPE_SYMMETRIC)
+        };
+        NonNull::new(ptr).map(|ptr| CryptoOp { ptr, _marker: PhantomData })
+    }
+}
+
+impl fmt::Debug for OpPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OpPool").finish_non_exhaustive()
+    }
+}
+
+impl Drop for OpPool {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_mempool_free(self.0.as_ptr()) }
+    }
+}
+
+/// A symmetric crypto op, applying a [`Session`]'s transform to an mbuf.
+///
+/// The op owns its mbuf, which is freed along with the op when it is dropped.
+#[repr(transparent)]
+pub struct CryptoOp<'a> {
+    ptr: NonNull<ffi::rte_crypto_op>,
+    _marker: PhantomData<(&'a OpPool, &'a Session<'a>, MBuf<&'a MemoryPool>)>,
+}
+
+// # Safety
+// The op (and its mbuf) are owned exclusively, and the session it refers to is `Sync`.
+unsafe impl Send for CryptoOp<'_> {}
+
+impl<'a> CryptoOp<'a> {
+    fn sym(&self) -> *mut ffi::rte_crypto_sym_op {
+        // the symmetric op immediately follows the op (see `IV_OFFSET`)
+        unsafe { self.ptr.as_ptr().add(1).cast() }
+    }
+
+    #[inline]
+    pub fn status(&self) -> Status {
+        Status::from_raw(unsafe { (*self.ptr.as_ptr()).status })
+    }
+
+    /// Sets the mbuf processed (in-place) by this op, returning the previous one.
+    #[inline]
+    pub fn set_mbuf(&mut self, mbuf: MBuf<&'a MemoryPool>) -> Option<MBuf<&'a MemoryPool>> {
+        let old = self.take_mbuf();
+        unsafe { (*self.sym()).m_src = mbuf.into_raw().as_ptr() };
+        old
+    }
+
+    /// Takes the mbuf out of this op, e.g. once it was processed.
+    #[inline]
+    pub fn take_mbuf(&mut self) -> Option<MBuf<&'a MemoryPool>> {
+        unsafe {
+            let mbuf = NonNull::new(mem::replace(&mut (*self.sym()).m_src, std::ptr::null_mut()))?;
+            Some(MBuf::from_raw(mbuf))
+        }
+    }
+
+    /// Prepares this op for processing by an AEAD (e.g. [AES-GCM](super::Transform::AesGcm)) `session`.
+    ///
+    /// The bytes of the mbuf in the `data` range are encrypted (or decrypted), and the digest is written to (or
+    /// verified against) the bytes at `digest_offset`. `iv` and `aad` are copied to the op's private area.
+    ///
+    /// # Panics
+    /// Panics if the op has no mbuf, if `session` is not an AEAD session, if the lengths of `iv` or `aad` do not match
+    /// the session, if they do not fit in the op's private area, or if `data` or the digest exceed the mbuf's data
+    /// (the digest must be in its first segment).
+    #[inline]
+    pub fn prepare_aead(
+        &mut self,
+        session: &'a Session<'_>,
+        iv: &[u8],
+        aad: &[u8],
+        data: Range<u32>,
+        digest_offset: u32,
+    ) {
+        let SessionKind::Aead { iv_len, aad_len, digest_len } = session.kind else {
+            panic!("not an AEAD session");
+        };
+        assert_eq!(iv.len(), iv_len.into(), "IV length mismatch");
+        assert_eq!(aad.len(), aad_len.into(), "AAD length mismatch");
+
+        let aad_offset = IV_OFFSET as usize + iv.len().next_multiple_of(AAD_ALIGN);
+        assert!(aad_offset + aad.len() <= IV_OFFSET as usize + self.priv_size(), "private area too small");
+
+        let (digest, digest_iova) = self.digest(data.clone(), digest_offset, digest_len);
+        unsafe {
+            let op = self.ptr.as_ptr().cast::<u8>();
+            op.add(IV_OFFSET.into()).copy_from_nonoverlapping(iv.as_ptr(), iv.len());
+            op.add(aad_offset).copy_from_nonoverlapping(aad.as_ptr(), aad.len());
+
+            let aead = &mut (*self.sym()).__bindgen_anon_2.__bindgen_anon_1.aead;
+            aead.data.offset = data.start;
+            aead.data.length = data.end - data.start;
+            aead.digest.data = digest;
+            aead.digest.phys_addr = digest_iova;
+            aead.aad.data = op.add(aad_offset);
+            aead.aad.phys_addr = (*self.ptr.as_ptr()).phys_addr + aad_offset as u64;
+
+            ffi::_rte_crypto_op_attach_sym_session(self.ptr.as_ptr(), session.as_raw());
+        }
+    }
+
+    /// Prepares this op for processing by an authentication (e.g. [HMAC](super::Transform::Hmac)) `session`.
+    ///
+    /// The digest of the bytes of the mbuf in the `data` range is written to (or verified against) the bytes at
+    /// `digest_offset`.
+    ///
+    /// # Panics
+    /// Panics if the op has no mbuf, if `session` is not an authentication session, or if `data` or the digest exceed
+    /// the mbuf's data (the digest must be in its first segment).
+    #[inline]
+    pub fn prepare_auth(&mut self, session: &'a Session<'_>, data: Range<u32>, digest_offset: u32) {
+        let SessionKind::Auth { digest_len } = session.kind else {
+            panic!("not an authentication session");
+        };
+
+        let (digest, digest_iova) = self.digest(data.clone(), digest_offset, digest_len);
+        unsafe {
+            let auth = &mut (*self.sym()).__bindgen_anon_2.__bindgen_anon_2.auth;
+            auth.data.offset = data.start;
+            auth.data.length = data.end - data.start;
+            auth.digest.data = digest;
+            auth.digest.phys_addr = digest_iova;
+
+            ffi::_rte_crypto_op_attach_sym_session(self.ptr.as_ptr(), session.as_raw());
+        }
+    }
+
+    /// Validates the data range and digest location, returning the digest's address and IOVA.
+    fn digest(&self, data: Range<u32>, digest_offset: u32, digest_len: u16) -> (*mut u8, u64) {
+        unsafe {
+            let mbuf = (*self.sym()).m_src;
+            assert!(!mbuf.is_null(), "op has no mbuf");
+            assert!(data.start <= data.end && data.end <= (*mbuf).pkt_len, "data exceeds the mbuf");
+            let digest_end = digest_offset.checked_add(u32::from(digest_len));
+            assert!(digest_end.is_some_and(|end| end <= (*mbuf).data_len.into()), "digest exceeds the mbuf");
+
+            let ffi::rte_mbuf { buf_addr, data_off, .. } = *mbuf;
+            let digest = buf_addr.cast::<u8>().add(usize::from(data_off) + digest_offset as usize);
+            (digest, ffi::_rte_mbuf_data_iova(mbuf) + u64::from(digest_offset))
+        }
+    }
+
+    fn priv_size(&self) -> usize {
+        unsafe { ffi::_rte_crypto_op_get_priv_data_size((*self.ptr.as_ptr()).mempool) as usize }
+    }
+}
+
+impl fmt::Debug for CryptoOp<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CryptoOp").field("status", &self.status()).finish_non_exhaustive()
+    }
+}
+
+impl Drop for CryptoOp<'_> {
+    fn drop(&mut self) {
+        drop(self.take_mbuf());
+        unsafe { ffi::_rte_crypto_op_free(self.ptr.as_ptr()) }
+    }
+}
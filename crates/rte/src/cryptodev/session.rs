@@ -0,0 +1,195 @@
+use std::{ffi::CString, fmt, marker::PhantomData, os::raw::c_void, ptr::NonNull};
+
+use rte_error::ReturnValue as _;
+
+use super::{CryptoDev, IV_OFFSET};
+use crate::{memory::SocketId, Result};
+
+/// The direction of an AEAD transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AeadOp {
+    Encrypt,
+    Decrypt,
+}
+
+/// The direction of an authentication transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthOp {
+    /// Computes the digest, writing it to the op's digest location.
+    Generate,
+    /// Computes the digest and compares it to the one at the op's digest location, failing the op with
+    /// [`Status::AuthFailed`](super::Status::AuthFailed) if they differ.
+    Verify,
+}
+
+/// The hash function of an HMAC transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HmacAlgorithm {
+    Sha1,
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HmacAlgorithm {
+    fn as_raw(self) -> u32 {
+        match self {
+            Self::Sha1 => ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA1_HMAC,
+            Self::Sha256 => ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA256_HMAC,
+            Self::Sha384 => ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA384_HMAC,
+            Self::Sha512 => ffi::rte_crypto_auth_algorithm::RTE_CRYPTO_AUTH_SHA512_HMAC,
+        }
+    }
+}
+
+/// A symmetric crypto transform, from which a [`Session`] is created.
+///
+/// All lengths are in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transform<'k> {
+    /// AES-GCM authenticated encryption, using a 16, 24 or 32 byte key.
+    AesGcm { op: AeadOp, key: &'k [u8], iv_len: u16, aad_len: u16, digest_len: u16 },
+    /// HMAC authentication, whose digest may be truncated to `digest_len`.
+    Hmac { algorithm: HmacAlgorithm, op: AuthOp, key: &'k [u8], digest_len: u16 },
+}
+
+impl Transform<'_> {
+    pub(crate) fn as_raw(&self) -> ffi::rte_crypto_sym_xform {
+        let mut xform = ffi::rte_crypto_sym_xform::default();
+
+        match *self {
+            Self::AesGcm { op, key, iv_len, aad_len, digest_len } => {
+                xform.type_ = ffi::rte_crypto_sym_xform_type::RTE_CRYPTO_SYM_XFORM_AEAD;
+                xform.__bindgen_anon_1.aead.op = match op {
+                    AeadOp::Encrypt => ffi::rte_crypto_aead_operation::RTE_CRYPTO_AEAD_OP_ENCRYPT,
+                    AeadOp::Decrypt => ffi::rte_crypto_aead_operation::RTE_CRYPTO_AEAD_OP_DECRYPT,
+                };
+                xform.__bindgen_anon_1.aead.algo = ffi::rte_crypto_aead_algorithm::RTE_CRYPTO_AEAD_AES_GCM;
+                xform.__bindgen_anon_1.aead.key.data = key.as_ptr();
+                xform.__bindgen_anon_1.aead.key.length = key.len() as u16;
+                xform.__bindgen_anon_1.aead.iv.offset = IV_OFFSET;
+                xform.__bindgen_anon_1.aead.iv.length = iv_len;
+                xform.__bindgen_anon_1.aead.digest_length = digest_len;
+                xform.__bindgen_anon_1.aead.aad_length = aad_len;
+            }
+            Self::Hmac { algorithm, op, key, digest_len } => {
+                xform.type_ = ffi::rte_crypto_sym_xform_type::RTE_CRYPTO_SYM_XFORM_AUTH;
+                xform.__bindgen_anon_1.auth.op = match op {
+                    AuthOp::Generate => ffi::rte_crypto_auth_operation::RTE_CRYPTO_AUTH_OP_GENERATE,
+                    AuthOp::Verify => ffi::rte_crypto_auth_operation::RTE_CRYPTO_AUTH_OP_VERIFY,
+                };
+                xform.__bindgen_anon_1.auth.algo = algorithm.as_raw();
+                xform.__bindgen_anon_1.auth.key.data = key.as_ptr();
+                xform.__bindgen_anon_1.auth.key.length = key.len() as u16;
+                xform.__bindgen_anon_1.auth.digest_length = digest_len;
+            }
+        }
+
+        xform
+    }
+}
+
+/// A memory pool of symmetric crypto sessions.
+pub struct SessionPool(pub(crate) NonNull<ffi::rte_mempool>);
+
+// # Safety
+// Memory pool operations are thread-safe, and the pool can only be used to create sessions.
+unsafe impl Send for SessionPool {}
+unsafe impl Sync for SessionPool {}
+
+impl SessionPool {
+    /// Creates a pool of `size` sessions, whose elements hold `session_size` bytes of private session data (see
+    /// [`CryptoDev::session_size`]).
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(
+        name: S,
+        size: u32,
+        session_size: u32,
+        cache_size: u32,
+        socket_id: Option<SocketId>,
+    ) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        unsafe {
+            ffi::rte_cryptodev_sym_session_pool_create(
+                name.as_ptr(),
+                size,
+                session_size,
+                cache_size,
+                0,
+                socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            )
+        }
+        .rte_ok()
+        .map(Self)
+    }
+}
+
+impl fmt::Debug for SessionPool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SessionPool").finish_non_exhaustive()
+    }
+}
+
+impl Drop for SessionPool {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_mempool_free(self.0.as_ptr()) }
+    }
+}
+
+/// A symmetric crypto session of a [`CryptoDev`], which is freed (back to its pool) when dropped.
+pub struct Session<'pool> {
+    ptr: NonNull<c_void>,
+    dev_id: u8,
+    pub(super) kind: SessionKind,
+    _marker: PhantomData<&'pool SessionPool>,
+}
+
+/// The parameters of a session required for preparing its ops.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SessionKind {
+    Aead { iv_len: u16, aad_len: u16, digest_len: u16 },
+    Auth { digest_len: u16 },
+}
+
+// # Safety
+// Sessions are immutable once created, and may be used by ops on any queue pair of the device.
+unsafe impl Send for Session<'_> {}
+unsafe impl Sync for Session<'_> {}
+
+impl<'pool> Session<'pool> {
+    /// Creates a session applying `transform`. The key is copied, so it does not need to outlive the session.
+    #[inline]
+    pub fn new(dev: &CryptoDev, transform: &Transform, pool: &'pool SessionPool) -> Result<Self> {
+        let mut xform = transform.as_raw();
+        let kind = match *transform {
+            Transform::AesGcm { iv_len, aad_len, digest_len, .. } => SessionKind::Aead { iv_len, aad_len, digest_len },
+            Transform::Hmac { digest_len, .. } => SessionKind::Auth { digest_len },
+        };
+
+        unsafe { ffi::rte_cryptodev_sym_session_create(dev.dev_id, &mut xform, pool.0.as_ptr()) }
+            .rte_ok()
+            .map(|ptr| Self { ptr, dev_id: dev.dev_id, kind, _marker: PhantomData })
+    }
+
+    #[inline]
+    pub fn dev_id(&self) -> u8 {
+        self.dev_id
+    }
+
+    pub(crate) fn as_raw(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+impl fmt::Debug for Session<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session").field("dev_id", &self.dev_id).field("kind", &self.kind).finish()
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_cryptodev_sym_session_free(self.dev_id, self.ptr.as_ptr()) };
+    }
+}
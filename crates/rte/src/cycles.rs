@@ -0,0 +1,44 @@
+//! Based on DPDK's `rte_cycles.h` API: <https://doc.dpdk.org/api-22.11/rte__cycles_8h.html>
+
+#[cfg(any(test, feature = "test-utils"))]
+use std::cell::Cell;
+use std::time::Duration;
+
+#[cfg(any(test, feature = "test-utils"))]
+thread_local! {
+    /// The TSC value of the current thread's [`MockClock`](crate::test_utils::MockClock), if any.
+    pub(crate) static MOCK_TSC: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+/// Returns the current value of the TSC (timestamp counter).
+///
+/// See also: <https://doc.dpdk.org/api-22.11/rte__cycles_8h.html>
+#[inline]
+pub fn tsc() -> u64 {
+    #[cfg(any(test, feature = "test-utils"))]
+    if let Some(tsc) = MOCK_TSC.with(Cell::get) {
+        return tsc;
+    }
+
+    unsafe { ffi::_rte_rdtsc() }
+}
+
+/// Returns the number of TSC cycles in one second.
+///
+/// **NOTE:** requires the EAL to be initialized.
+#[inline]
+pub fn tsc_hz() -> u64 {
+    unsafe { ffi::rte_get_tsc_hz() }
+}
+
+/// Converts a duration into the equivalent number of TSC cycles.
+#[inline]
+pub fn duration_to_cycles(duration: Duration) -> u64 {
+    (duration.as_nanos() * u128::from(tsc_hz()) / 1_000_000_000) as u64
+}
+
+/// Converts a number of TSC cycles into the equivalent duration.
+#[inline]
+pub fn cycles_to_duration(cycles: u64) -> Duration {
+    Duration::from_nanos((u128::from(cycles) * 1_000_000_000 / u128::from(tsc_hz())) as u64)
+}
@@ -0,0 +1,189 @@
+//! Software RSS: dispatching received packets to workers by the Toeplitz hash of their 5-tuple, the way a NIC would
+//! (see [`thash`](crate::thash)), for ports whose RSS is unavailable or misconfigured, e.g.:
+//! ```rust,ignore
+//! let dispatcher = RssDispatcher::new(workers.len() as u16);
+//! let mut batches = [(); WORKERS].map(|_| PacketBatch::new());
+//! queue.recv(&mut pkts);
+//! dispatcher.dispatch(mem::take(&mut pkts), &mut batches);
+//! for (worker, batch) in workers.iter_mut().zip(&mut batches) {
+//!     worker.enqueue_burst(batch);
+//! }
+//! ```
+
+use crate::{
+    mbuf::{Allocator, MBuf, PacketBatch},
+    net::{Headers, L3Hdr, L4Hdr},
+    thash::{self, BeRssKey, RssKey, Tuple},
+};
+
+/// The number of entries of the redirection table of a dispatcher, unless [set](RssDispatcher::reta) otherwise.
+pub const DEFAULT_RETA_SIZE: usize = ffi::RTE_ETH_RSS_RETA_SIZE_128 as usize;
+
+/// Dispatches packets to workers by their RSS hash and a redirection table, like a NIC with RSS enabled for IP, TCP
+/// and UDP packets: TCP and UDP packets are hashed by their addresses and ports (except for IPv4 fragments, which are
+/// hashed by their addresses only, like other IP packets), and other packets are dispatched as if their hash was 0.
+#[derive(Debug, Clone)]
+pub struct RssDispatcher {
+    key: BeRssKey,
+    reta: Vec<u16>,
+    workers: u16,
+}
+
+impl RssDispatcher {
+    /// Creates a dispatcher to `workers` workers, using the [default key](RssKey::DEFAULT) and a redirection table of
+    /// [`DEFAULT_RETA_SIZE`] entries spreading the hashes evenly across the workers.
+    ///
+    /// # Panics
+    /// Panics if `workers` is 0.
+    pub fn new(workers: u16) -> Self {
+        assert!(workers > 0, "packets must be dispatched to at least one worker");
+        let reta = (0..DEFAULT_RETA_SIZE).map(|entry| (entry % usize::from(workers)) as u16).collect();
+        Self { key: RssKey::DEFAULT.to_be(), reta, workers }
+    }
+
+    /// Sets the key of the hash, e.g. [`RssKey::SYMMETRIC`] for both directions of a flow to go to the same worker.
+    #[inline]
+    pub fn key(mut self, key: &RssKey) -> Self {
+        self.key = key.to_be();
+        self
+    }
+
+    /// Sets the redirection table, from hashes to workers, e.g. to mirror the one of a port.
+    ///
+    /// # Panics
+    /// Panics if `reta` is empty, or any of its entries isn't one of the workers.
+    pub fn reta(mut self, reta: Vec<u16>) -> Self {
+        assert!(!reta.is_empty(), "the redirection table must not be empty");
+        assert!(reta.iter().all(|&worker| worker < self.workers), "the redirection table refers to unknown workers");
+        self.reta = reta;
+        self
+    }
+
+    #[inline]
+    pub fn workers(&self) -> u16 {
+        self.workers
+    }
+
+    /// Returns the RSS hash of a packet, or `None` for non-IP packets.
+    #[inline]
+    pub fn hash(&self, headers: &Headers) -> Option<u32> {
+        let tuple = match (headers.l3?, headers.l4) {
+            (L3Hdr::Ipv4(ipv4), _) if ipv4.is_fragment() => Tuple::ipv4(ipv4.src(), ipv4.dst()),
+            (L3Hdr::Ipv4(ipv4), Some(L4Hdr::Tcp(tcp))) => {
+                Tuple::ipv4_l4(ipv4.src(), ipv4.dst(), tcp.src_port.get(), tcp.dst_port.get())
+            }
+            (L3Hdr::Ipv4(ipv4), Some(L4Hdr::Udp(udp))) => {
+                Tuple::ipv4_l4(ipv4.src(), ipv4.dst(), udp.src_port.get(), udp.dst_port.get())
+            }
+            (L3Hdr::Ipv4(ipv4), _) => Tuple::ipv4(ipv4.src(), ipv4.dst()),
+            (L3Hdr::Ipv6(ipv6), Some(L4Hdr::Tcp(tcp))) => {
+                Tuple::ipv6_l4(ipv6.src(), ipv6.dst(), tcp.src_port.get(), tcp.dst_port.get())
+            }
+            (L3Hdr::Ipv6(ipv6), Some(L4Hdr::Udp(udp))) => {
+                Tuple::ipv6_l4(ipv6.src(), ipv6.dst(), udp.src_port.get(), udp.dst_port.get())
+            }
+            (L3Hdr::Ipv6(ipv6), _) => Tuple::ipv6(ipv6.src(), ipv6.dst()),
+            (L3Hdr::Arp(_), _) => return None,
+        };
+        Some(self.key.hash(&tuple))
+    }
+
+    /// Returns the worker a packet with the given RSS hash is dispatched to.
+    #[inline]
+    pub fn worker_of_hash(&self, hash: u32) -> u16 {
+        thash::reta_queue(hash, &self.reta)
+    }
+
+    /// Returns the worker a packet is dispatched to.
+    #[inline]
+    pub fn worker<A: Allocator>(&self, pkt: &MBuf<A>) -> u16 {
+        let hash = pkt.parse_headers().as_ref().and_then(|headers| self.hash(headers));
+        self.worker_of_hash(hash.unwrap_or(0))
+    }
+
+    /// Moves each of `pkts` to the batch of its worker (in order), returning the number of packets dropped (and freed)
+    /// because the batch of their worker was full, which can't happen if the batches were empty.
+    ///
+    /// # Panics
+    /// Panics if there isn't a batch per worker.
+    pub fn dispatch<A: Allocator, const N: usize>(
+        &self,
+        pkts: PacketBatch<A, N>,
+        batches: &mut [PacketBatch<A, N>],
+    ) -> usize {
+        assert_eq!(batches.len(), usize::from(self.workers), "there must be a batch per worker");
+        let mut dropped = 0;
+        for pkt in pkts {
+            let worker = usize::from(self.worker(&pkt));
+            // the packet is freed if its worker's batch is full
+            if batches[worker].try_push(pkt).is_err() {
+                dropped += 1;
+            }
+        }
+        dropped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use super::*;
+    use crate::{
+        mbuf::GlobalAllocator,
+        net::{IPPROTO_TCP, IPPROTO_UDP},
+    };
+
+    // the test vector of the thash module, from Microsoft's RSS verification suite
+    const SRC: Ipv4Addr = Ipv4Addr::new(66, 9, 149, 187);
+    const DST: Ipv4Addr = Ipv4Addr::new(161, 142, 100, 80);
+    const SRC_PORT: u16 = 2794;
+    const DST_PORT: u16 = 1766;
+    const L4_HASH: u32 = 0x51ccc178;
+
+    fn packet(proto: u8, fragment_offset: u16) -> MBuf<GlobalAllocator> {
+        let mut packet = [0; 54];
+        packet[12..14].copy_from_slice(&[0x08, 0x00]);
+        packet[14] = 0x45;
+        packet[20..22].copy_from_slice(&fragment_offset.to_be_bytes());
+        packet[23] = proto;
+        packet[26..30].copy_from_slice(&SRC.octets());
+        packet[30..34].copy_from_slice(&DST.octets());
+        packet[34..36].copy_from_slice(&SRC_PORT.to_be_bytes());
+        packet[36..38].copy_from_slice(&DST_PORT.to_be_bytes());
+        // the data offset of the TCP header
+        packet[46] = 0x50;
+        MBuf::new_with_data(packet)
+    }
+
+    #[test]
+    fn test_hash() {
+        let dispatcher = RssDispatcher::new(7);
+        let hash = |pkt: MBuf<GlobalAllocator>| dispatcher.hash(&pkt.parse_headers().unwrap());
+
+        assert_eq!(hash(packet(IPPROTO_UDP, 0)), Some(L4_HASH));
+        assert_eq!(hash(packet(IPPROTO_TCP, 0)), Some(L4_HASH));
+        // with the "more fragments" flag set
+        assert_eq!(hash(packet(IPPROTO_UDP, 0x2000)), Some(RssKey::DEFAULT.hash(&Tuple::ipv4(SRC, DST))));
+        assert_eq!(hash(MBuf::new_with_data([0; 14])), None);
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let dispatcher = RssDispatcher::new(7);
+        let worker = usize::from(dispatcher.worker_of_hash(L4_HASH));
+        assert_eq!(worker, (L4_HASH as usize % DEFAULT_RETA_SIZE) % 7);
+
+        let pkts: PacketBatch<_, 4> =
+            [packet(IPPROTO_UDP, 0), MBuf::new_with_data([0; 14]), packet(IPPROTO_TCP, 0)].into_iter().collect();
+        let mut batches = [(); 7].map(|_| PacketBatch::new());
+        assert_eq!(dispatcher.dispatch(pkts, &mut batches), 0);
+
+        let lens = batches.iter().map(|batch| batch.len()).collect::<Vec<_>>();
+        let mut expected = vec![0; 7];
+        expected[worker] += 2;
+        // the non-IP packet goes to the worker of the hash 0
+        expected[0] += 1;
+        assert_eq!(lens, expected);
+    }
+}
@@ -0,0 +1,221 @@
+//! Based on DPDK's `rte_distributor.h` API: <https://doc.dpdk.org/api-22.11/rte__distributor_8h.html>
+//!
+//! A [`Distributor`] dynamically load balances packets from a single [`Dispatcher`] lcore across multiple [`Worker`]
+//! lcores, while preserving flow affinity: packets with the same flow tag (the mbuf's `hash.usr` field, which
+//! overlays the RSS hash) are never processed by two workers at the same time. This is an alternative to statically
+//! assigning flows to lcores using RSS.
+
+use std::{ffi::CString, marker::PhantomData, mem, ptr::NonNull, sync::Arc};
+
+use arrayvec::ArrayVec;
+use rte_error::ReturnValue as _;
+
+use crate::{mbuf::MBuf, memory::SocketId, mempool::MemoryPool, Result};
+
+/// Maximal number of mbufs handed to (or returned by) a worker at once.
+pub const BURST_SIZE: usize = ffi::RTE_DIST_BURST_SIZE as usize;
+
+/// The algorithm used by a [`Distributor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Workers receive a single mbuf at a time.
+    Single,
+    /// Workers receive bursts of up to [`BURST_SIZE`] mbufs, using vector instructions where available.
+    Burst,
+}
+
+impl Algorithm {
+    fn as_raw(self) -> u32 {
+        match self {
+            Self::Single => ffi::rte_distributor_alg_type::RTE_DIST_ALG_SINGLE,
+            Self::Burst => ffi::rte_distributor_alg_type::RTE_DIST_ALG_BURST,
+        }
+    }
+}
+
+/// A wrapper around an [`rte_distributor`](ffi::rte_distributor), which is used by [splitting](Self::split) it into
+/// its dispatcher and workers.
+///
+/// Note that DPDK does not support freeing a distributor, so its memory is leaked when dropped.
+pub struct Distributor<'pool> {
+    ptr: NonNull<ffi::rte_distributor>,
+    num_workers: u32,
+    _marker: PhantomData<MBuf<&'pool MemoryPool>>,
+}
+
+// # Safety
+// The distributor can only be used through its dispatcher and workers, each of which may only be used by a single
+// lcore at a time (as enforced by requiring `&mut self`), and mbufs are handed off between them by ownership.
+unsafe impl Send for Distributor<'_> {}
+unsafe impl Sync for Distributor<'_> {}
+
+impl<'pool> Distributor<'pool> {
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(
+        name: S,
+        num_workers: u32,
+        algorithm: Algorithm,
+        socket_id: Option<SocketId>,
+    ) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        unsafe {
+            ffi::rte_distributor_create(
+                name.as_ptr(),
+                socket_id.map(|id| id.get()).unwrap_or(u32::MAX),
+                num_workers,
+                algorithm.as_raw(),
+            )
+        }
+        .rte_ok()
+        .map(|ptr| Self { ptr, num_workers, _marker: PhantomData })
+    }
+
+    /// Splits this distributor into its dispatcher and workers, which can be sent to different lcores.
+    #[inline]
+    pub fn split(self) -> (Dispatcher<'pool>, Vec<Worker<'pool>>) {
+        let distributor = Arc::new(self);
+        let workers = (0..distributor.num_workers).map(|id| Worker { distributor: distributor.clone(), id }).collect();
+
+        (Dispatcher { distributor }, workers)
+    }
+}
+
+/// The dispatching side of a [`Distributor`].
+pub struct Dispatcher<'pool> {
+    distributor: Arc<Distributor<'pool>>,
+}
+
+impl<'pool> Dispatcher<'pool> {
+    /// Distributes all of `mbufs` between the workers (leaving the array empty), and collects the mbufs returned by
+    /// the workers, which can be retrieved using [`Self::returned`].
+    ///
+    /// Calling this method with an empty array only collects returned mbufs.
+    #[inline]
+    pub fn process<const CAP: usize>(&mut self, mbufs: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>) {
+        let processed = unsafe {
+            ffi::rte_distributor_process(self.distributor.ptr.as_ptr(), mbufs.as_mut_ptr().cast(), mbufs.len() as _)
+        } as usize;
+
+        // the distributor has assumed ownership of the processed mbufs
+        mbufs.drain(..processed).for_each(mem::forget);
+    }
+
+    /// Appends mbufs returned by the workers to `mbufs`, returning the number of returned mbufs.
+    ///
+    /// The order of returned mbufs is not necessarily the order in which they were processed.
+    #[inline]
+    pub fn returned<const CAP: usize>(&mut self, mbufs: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>) -> usize {
+        let old_len = mbufs.len();
+
+        unsafe {
+            let returned = ffi::rte_distributor_returned_pkts(
+                self.distributor.ptr.as_ptr(),
+                mbufs.as_mut_ptr().add(old_len).cast(),
+                mbufs.remaining_capacity() as _,
+            ) as usize;
+            mbufs.set_len(old_len + returned);
+            returned
+        }
+    }
+
+    /// Waits for all in-flight mbufs to be processed by the workers, e.g. before shutting down.
+    ///
+    /// Note that the workers must keep requesting mbufs until this method returns.
+    #[inline]
+    pub fn flush(&mut self) {
+        unsafe { ffi::rte_distributor_flush(self.distributor.ptr.as_ptr()) };
+    }
+}
+
+/// The processing side of a [`Distributor`], with a unique worker id.
+pub struct Worker<'pool> {
+    distributor: Arc<Distributor<'pool>>,
+    id: u32,
+}
+
+impl<'pool> Worker<'pool> {
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Hands the mbufs in `returns` back to the dispatcher (leaving the array empty) and waits (busy-polling) for the
+    /// next mbufs to process, which are appended to `mbufs`.
+    ///
+    /// With the [`Algorithm::Single`] algorithm, at most a single mbuf is exchanged in each direction, and an error is
+    /// returned (leaving both arrays unmodified) if `returns` holds more than one mbuf.
+    ///
+    /// # Panics
+    /// Panics if `mbufs` is not empty.
+    #[inline]
+    pub fn get_pkts(
+        &mut self,
+        returns: &mut ArrayVec<MBuf<&'pool MemoryPool>, BURST_SIZE>,
+        mbufs: &mut ArrayVec<MBuf<&'pool MemoryPool>, BURST_SIZE>,
+    ) -> Result<()> {
+        assert!(mbufs.is_empty());
+
+        let received = unsafe {
+            ffi::rte_distributor_get_pkt(
+                self.distributor.ptr.as_ptr(),
+                self.id,
+                mbufs.as_mut_ptr().cast(),
+                returns.as_mut_ptr().cast(),
+                returns.len() as _,
+            )
+        }
+        .rte_ok()?;
+
+        unsafe { mbufs.set_len(received as usize) };
+        returns.drain(..).for_each(mem::forget);
+        Ok(())
+    }
+
+    /// Hands the mbufs in `returns` back to the dispatcher (leaving the array empty) without requesting any more
+    /// mbufs, e.g. before shutting down.
+    ///
+    /// With the [`Algorithm::Single`] algorithm, an error is returned (leaving the array unmodified) if `returns`
+    /// holds more than one mbuf.
+    #[inline]
+    pub fn return_pkts(&mut self, returns: &mut ArrayVec<MBuf<&'pool MemoryPool>, BURST_SIZE>) -> Result<()> {
+        unsafe {
+            ffi::rte_distributor_return_pkt(
+                self.distributor.ptr.as_ptr(),
+                self.id,
+                returns.as_mut_ptr().cast(),
+                returns.len() as _,
+            )
+        }
+        .rte_ok()?;
+
+        returns.drain(..).for_each(mem::forget);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_error::Error;
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::TestPool;
+
+    #[rte_test]
+    fn test_return_pkts_single() {
+        let mempool = TestPool::new(15);
+        let distributor = Distributor::new("test_return_pkts", 1, Algorithm::Single, None).unwrap();
+        let (_dispatcher, mut workers) = distributor.split();
+        assert_eq!(workers.len(), 1);
+
+        let mut returns = mempool.alloc_packets(&[&[0; 64], &[1; 64]]).into_iter().collect::<ArrayVec<_, BURST_SIZE>>();
+        // the mbufs are kept when rejected, rather than leaked
+        assert_eq!(workers[0].return_pkts(&mut returns), Err(Error(libc::EINVAL)));
+        assert_eq!(returns.len(), 2);
+
+        returns.truncate(1);
+        workers[0].return_pkts(&mut returns).unwrap();
+        assert!(returns.is_empty());
+    }
+}
@@ -0,0 +1,177 @@
+//! Based on DPDK's `rte_dmadev.h` API: <https://doc.dpdk.org/api-22.11/rte__dmadev_8h.html>
+//!
+//! A [`DmaDev`] (e.g. an IOAT or IDXD engine) performs memory copies asynchronously, offloading bulk copies (e.g. of
+//! packet payloads) from the lcores. Copies are enqueued to one of the device's virtual channels ([`VChan`]), submitted
+//! to the hardware, and their completion is then polled.
+
+use std::{ffi::CStr, marker::PhantomData};
+
+use rte_error::ReturnValue as _;
+
+use crate::{flags::DmaOpFlags, Result};
+
+pub type Info = ffi::rte_dma_info;
+pub type Stats = ffi::rte_dma_stats;
+
+/// A DMA device, identified by its device id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DmaDev {
+    dev_id: i16,
+}
+
+impl DmaDev {
+    #[inline]
+    pub fn new(dev_id: i16) -> Self {
+        Self { dev_id }
+    }
+
+    /// Returns the device with the given name, e.g. `0000:00:04.0-ch0`.
+    #[inline]
+    pub fn from_name(name: &CStr) -> Result<Self> {
+        let dev_id = unsafe { ffi::rte_dma_get_dev_id_by_name(name.as_ptr()) }.rte_ok()?;
+        Ok(Self::new(dev_id as i16))
+    }
+
+    /// Returns the number of DMA devices.
+    #[inline]
+    pub fn count() -> u16 {
+        unsafe { ffi::rte_dma_count_avail() }
+    }
+
+    #[inline]
+    pub fn dev_id(&self) -> i16 {
+        self.dev_id
+    }
+
+    #[inline]
+    pub fn info(&self) -> Result<Info> {
+        let mut info = Info::default();
+        unsafe { ffi::rte_dma_info_get(self.dev_id, &mut info) }.rte_ok()?;
+        Ok(info)
+    }
+
+    /// Configures the device, which must be done (while it is stopped) before setting up its vchans.
+    #[inline]
+    pub fn configure(&self, nb_vchans: u16) -> Result<()> {
+        let conf = ffi::rte_dma_conf { nb_vchans, ..Default::default() };
+        unsafe { ffi::rte_dma_configure(self.dev_id, &conf) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Sets up a memory-to-memory vchan holding up to `nb_desc` in-flight operations.
+    #[inline]
+    pub fn vchan_setup(&self, vchan: u16, nb_desc: u16) -> Result<()> {
+        let conf = ffi::rte_dma_vchan_conf {
+            direction: ffi::rte_dma_direction::RTE_DMA_DIR_MEM_TO_MEM,
+            nb_desc,
+            ..Default::default()
+        };
+        unsafe { ffi::rte_dma_vchan_setup(self.dev_id, vchan, &conf) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn start(&self) -> Result<()> {
+        unsafe { ffi::rte_dma_start(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stop(&self) -> Result<()> {
+        unsafe { ffi::rte_dma_stop(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn close(&self) -> Result<()> {
+        unsafe { ffi::rte_dma_close(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stats(&self, vchan: u16) -> Result<Stats> {
+        let mut stats = Stats::default();
+        unsafe { ffi::rte_dma_stats_get(self.dev_id, vchan, &mut stats) }.rte_ok()?;
+        Ok(stats)
+    }
+
+    #[inline]
+    pub fn stats_reset(&self, vchan: u16) -> Result<()> {
+        unsafe { ffi::rte_dma_stats_reset(self.dev_id, vchan) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns a handle for enqueuing operations and polling their completion on one of the device's vchans.
+    ///
+    /// # Safety
+    /// Vchans are not thread-safe, so it is up to the caller to guarantee that each vchan is only used by a single
+    /// lcore (i.e. through a single `VChan`) at a time.
+    #[inline]
+    pub unsafe fn vchan(&self, vchan: u16) -> VChan {
+        VChan { dev_id: self.dev_id, vchan, _marker: PhantomData }
+    }
+}
+
+/// The result of polling the completed operations of a [`VChan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Completed {
+    /// The number of successfully completed operations.
+    pub count: u16,
+    /// The ring index of the last completed operation.
+    pub last_idx: u16,
+    /// Whether an operation failed, in which case the operations following it are only reported by the next poll.
+    pub has_error: bool,
+}
+
+/// A virtual channel of a [`DmaDev`], see [`DmaDev::vchan`].
+#[derive(Debug)]
+pub struct VChan {
+    dev_id: i16,
+    vchan: u16,
+    // vchans may only be used by a single lcore
+    _marker: PhantomData<*mut ()>,
+}
+
+impl VChan {
+    #[inline]
+    pub fn vchan(&self) -> u16 {
+        self.vchan
+    }
+
+    /// Returns the number of operations that can currently be enqueued.
+    #[inline]
+    pub fn burst_capacity(&self) -> u16 {
+        unsafe { ffi::_rte_dma_burst_capacity(self.dev_id, self.vchan) }
+    }
+
+    /// Enqueues a copy of `len` bytes from the IO address `src` to `dst` (e.g. from [`MBuf::data_iova`]), returning
+    /// the operation's ring index. The copy only starts once [submitted](VChan::submit) (or when enqueued with
+    /// [`DmaOpFlags::SUBMIT`]).
+    ///
+    /// # Safety
+    /// Both ranges must be valid (and `dst` must not be accessed) until the copy has completed.
+    ///
+    /// [`MBuf::data_iova`]: crate::mbuf::MBuf::data_iova
+    #[inline]
+    pub unsafe fn copy(&mut self, src: u64, dst: u64, len: u32, flags: DmaOpFlags) -> Result<u16> {
+        let idx = ffi::_rte_dma_copy(self.dev_id, self.vchan, src, dst, len, flags.bits()).rte_ok()?;
+        Ok(idx as u16)
+    }
+
+    /// Submits the enqueued operations to the hardware.
+    #[inline]
+    pub fn submit(&mut self) -> Result<()> {
+        unsafe { ffi::_rte_dma_submit(self.dev_id, self.vchan) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Polls up to `max` completed operations, which complete in the order they were enqueued.
+    #[inline]
+    pub fn completed(&mut self, max: u16) -> Completed {
+        let mut last_idx = 0;
+        let mut has_error = false;
+        let count = unsafe { ffi::_rte_dma_completed(self.dev_id, self.vchan, max, &mut last_idx, &mut has_error) };
+
+        Completed { count, last_idx, has_error }
+    }
+}
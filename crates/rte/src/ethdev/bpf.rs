@@ -0,0 +1,85 @@
+//! Based on DPDK's `rte_bpf_ethdev.h` API: <https://doc.dpdk.org/api-22.11/rte__bpf__ethdev_8h.html>
+
+use std::{ffi::CString, os::unix::ffi::OsStrExt, path::Path};
+
+use rte_error::ReturnValue as _;
+
+use super::EthDev;
+use crate::{bpf::Arg, Result};
+
+impl EthDev {
+    /// Loads an eBPF program from the given section of an ELF object file, and attaches it as a filter to an RX queue
+    /// (replacing any previously attached program): received packets for which the program returns 0 are dropped.
+    ///
+    /// The program is JIT-compiled if `jit` is set (failing if JIT is not supported).
+    #[inline]
+    pub fn bpf_rx_load<P: AsRef<Path>>(
+        &self,
+        queue_id: u16,
+        path: P,
+        section: &str,
+        arg: Arg,
+        jit: bool,
+    ) -> Result<()> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes()).unwrap();
+        let section = CString::new(section).unwrap();
+        let flags = if jit { ffi::_RTE_BPF_ETH_F_JIT } else { 0 };
+
+        unsafe {
+            ffi::rte_bpf_eth_rx_elf_load(
+                self.port_id,
+                queue_id,
+                &arg.as_prm(&[]),
+                path.as_ptr(),
+                section.as_ptr(),
+                flags,
+            )
+        }
+        .rte_ok()?;
+        Ok(())
+    }
+
+    /// Detaches (and unloads) the eBPF program attached to an RX queue, if any.
+    #[inline]
+    pub fn bpf_rx_unload(&self, queue_id: u16) {
+        unsafe { ffi::rte_bpf_eth_rx_unload(self.port_id, queue_id) }
+    }
+
+    /// Loads an eBPF program from the given section of an ELF object file, and attaches it as a filter to a TX queue
+    /// (replacing any previously attached program): packets for which the program returns 0 are dropped instead of
+    /// being transmitted.
+    ///
+    /// The program is JIT-compiled if `jit` is set (failing if JIT is not supported).
+    #[inline]
+    pub fn bpf_tx_load<P: AsRef<Path>>(
+        &self,
+        queue_id: u16,
+        path: P,
+        section: &str,
+        arg: Arg,
+        jit: bool,
+    ) -> Result<()> {
+        let path = CString::new(path.as_ref().as_os_str().as_bytes()).unwrap();
+        let section = CString::new(section).unwrap();
+        let flags = if jit { ffi::_RTE_BPF_ETH_F_JIT } else { 0 };
+
+        unsafe {
+            ffi::rte_bpf_eth_tx_elf_load(
+                self.port_id,
+                queue_id,
+                &arg.as_prm(&[]),
+                path.as_ptr(),
+                section.as_ptr(),
+                flags,
+            )
+        }
+        .rte_ok()?;
+        Ok(())
+    }
+
+    /// Detaches (and unloads) the eBPF program attached to a TX queue, if any.
+    #[inline]
+    pub fn bpf_tx_unload(&self, queue_id: u16) {
+        unsafe { ffi::rte_bpf_eth_tx_unload(self.port_id, queue_id) }
+    }
+}
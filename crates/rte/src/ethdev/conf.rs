@@ -0,0 +1,214 @@
+//! Owned representations of the configurations of a port and its queues, which (with the `serde` feature) can be
+//! loaded from configuration files, e.g. in YAML:
+//! ```yaml
+//! rx_mq_mode: 1 # RSS
+//! mtu: 9000
+//! rss:
+//!   rss_hf: 0x104 # IPv4 and IPv6
+//! ```
+
+use rte_error::{Error, ReturnValue as _};
+
+use super::EthDev;
+use crate::{
+    flags::{DevRxOffload, DevTxOffload, EthLinkSpeed, EthMqRxMode, EthRss},
+    Result,
+};
+
+/// The configuration of a port, see [`ffi::rte_eth_conf`] (of which it covers the commonly used fields).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct EthConf {
+    pub link_speeds: EthLinkSpeed,
+    pub rx_mq_mode: EthMqRxMode,
+    /// The MTU, or 0 for the default one.
+    pub mtu: u32,
+    pub max_lro_pkt_size: u32,
+    /// The RX offloads enabled on all the queues.
+    pub rx_offloads: DevRxOffload,
+    pub tx_offloads: DevTxOffload,
+    pub lpbk_mode: u32,
+    pub rss: EthRssConf,
+    /// Whether to enable the link status change interrupt.
+    pub lsc_intr: bool,
+    /// Whether to enable the RX queue interrupts (e.g. for an [`RxStream`](super::RxStream)).
+    pub rxq_intr: bool,
+}
+
+/// The RSS configuration of a port, see [`ffi::rte_eth_rss_conf`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct EthRssConf {
+    /// The RSS hash key (shorter than 256 bytes), or `None` for the driver's default one.
+    pub rss_key: Option<Vec<u8>>,
+    pub rss_hf: EthRss,
+}
+
+/// The configuration of an RX queue, see [`ffi::rte_eth_rxconf`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct RxQueueConf {
+    pub pthresh: u8,
+    pub hthresh: u8,
+    pub wthresh: u8,
+    pub rx_free_thresh: u16,
+    /// Whether to drop packets when no descriptors are available.
+    pub rx_drop_en: bool,
+    /// Whether the queue isn't started along with the port.
+    pub rx_deferred_start: bool,
+    /// The RX offloads enabled on this queue, in addition to the port's ones.
+    pub offloads: DevRxOffload,
+}
+
+/// The configuration of a TX queue, see [`ffi::rte_eth_txconf`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct TxQueueConf {
+    pub pthresh: u8,
+    pub hthresh: u8,
+    pub wthresh: u8,
+    pub tx_rs_thresh: u16,
+    pub tx_free_thresh: u16,
+    /// Whether the queue isn't started along with the port.
+    pub tx_deferred_start: bool,
+    /// The TX offloads enabled on this queue, in addition to the port's ones.
+    pub offloads: DevTxOffload,
+}
+
+impl EthConf {
+    /// Returns the DPDK configuration, which points to the RSS key of `self` (so mustn't outlive it), failing with
+    /// `EINVAL` if the key is too long.
+    fn to_raw(&self) -> Result<ffi::rte_eth_conf> {
+        let mut conf = ffi::rte_eth_conf::default();
+        conf.link_speeds = self.link_speeds.bits();
+        conf.rxmode.mq_mode = self.rx_mq_mode.bits();
+        conf.rxmode.mtu = self.mtu;
+        conf.rxmode.max_lro_pkt_size = self.max_lro_pkt_size;
+        conf.rxmode.offloads = self.rx_offloads.bits();
+        conf.txmode.offloads = self.tx_offloads.bits();
+        conf.lpbk_mode = self.lpbk_mode;
+
+        let rss_conf = &mut conf.rx_adv_conf.rss_conf;
+        if let Some(key) = &self.rss.rss_key {
+            // DPDK copies the key, and doesn't modify it
+            rss_conf.rss_key = key.as_ptr() as *mut u8;
+            rss_conf.rss_key_len = u8::try_from(key.len()).map_err(|_| Error(libc::EINVAL))?;
+        }
+        rss_conf.rss_hf = self.rss.rss_hf.bits();
+
+        conf.intr_conf.set_lsc(self.lsc_intr.into());
+        conf.intr_conf.set_rxq(self.rxq_intr.into());
+        Ok(conf)
+    }
+}
+
+impl From<&RxQueueConf> for ffi::rte_eth_rxconf {
+    fn from(conf: &RxQueueConf) -> Self {
+        let mut rxconf = ffi::rte_eth_rxconf::default();
+        rxconf.rx_thresh = ffi::rte_eth_thresh { pthresh: conf.pthresh, hthresh: conf.hthresh, wthresh: conf.wthresh };
+        rxconf.rx_free_thresh = conf.rx_free_thresh;
+        rxconf.rx_drop_en = conf.rx_drop_en.into();
+        rxconf.rx_deferred_start = conf.rx_deferred_start.into();
+        rxconf.offloads = conf.offloads.bits();
+        rxconf
+    }
+}
+
+impl From<&TxQueueConf> for ffi::rte_eth_txconf {
+    fn from(conf: &TxQueueConf) -> Self {
+        let mut txconf = ffi::rte_eth_txconf::default();
+        txconf.tx_thresh = ffi::rte_eth_thresh { pthresh: conf.pthresh, hthresh: conf.hthresh, wthresh: conf.wthresh };
+        txconf.tx_rs_thresh = conf.tx_rs_thresh;
+        txconf.tx_free_thresh = conf.tx_free_thresh;
+        txconf.tx_deferred_start = conf.tx_deferred_start.into();
+        txconf.offloads = conf.offloads.bits();
+        txconf
+    }
+}
+
+impl From<&ffi::rte_eth_rxconf> for RxQueueConf {
+    fn from(rxconf: &ffi::rte_eth_rxconf) -> Self {
+        let thresh = &rxconf.rx_thresh;
+        Self {
+            pthresh: thresh.pthresh,
+            hthresh: thresh.hthresh,
+            wthresh: thresh.wthresh,
+            rx_free_thresh: rxconf.rx_free_thresh,
+            rx_drop_en: rxconf.rx_drop_en != 0,
+            rx_deferred_start: rxconf.rx_deferred_start != 0,
+            offloads: DevRxOffload::from_bits_truncate(rxconf.offloads),
+        }
+    }
+}
+
+impl From<&ffi::rte_eth_txconf> for TxQueueConf {
+    fn from(txconf: &ffi::rte_eth_txconf) -> Self {
+        let thresh = &txconf.tx_thresh;
+        Self {
+            pthresh: thresh.pthresh,
+            hthresh: thresh.hthresh,
+            wthresh: thresh.wthresh,
+            tx_rs_thresh: txconf.tx_rs_thresh,
+            tx_free_thresh: txconf.tx_free_thresh,
+            tx_deferred_start: txconf.tx_deferred_start != 0,
+            offloads: DevTxOffload::from_bits_truncate(txconf.offloads),
+        }
+    }
+}
+
+impl EthDev {
+    /// Configures the device like [`EthDev::configure`], from an [`EthConf`].
+    #[inline]
+    pub fn configure_with(&self, nb_rx_queue: u16, nb_tx_queue: u16, conf: &EthConf) -> Result<()> {
+        let conf = conf.to_raw()?;
+        unsafe { ffi::rte_eth_dev_configure(self.port_id, nb_rx_queue, nb_tx_queue, &conf) }.rte_ok()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_raw() {
+        let conf = EthConf {
+            rx_mq_mode: EthMqRxMode::RSS_FLAG,
+            mtu: 9000,
+            rss: EthRssConf { rss_key: Some(vec![0x6d; 40]), rss_hf: EthRss::IPV4 | EthRss::IPV6 },
+            rxq_intr: true,
+            ..Default::default()
+        };
+
+        let raw = conf.to_raw().unwrap();
+        assert_eq!(raw.rxmode.mq_mode, ffi::_RTE_ETH_MQ_RX_RSS_FLAG);
+        assert_eq!(raw.rxmode.mtu, 9000);
+        let rss_conf = raw.rx_adv_conf.rss_conf;
+        assert_eq!(rss_conf.rss_key_len, 40);
+        assert_eq!(rss_conf.rss_hf, (EthRss::IPV4 | EthRss::IPV6).bits());
+        assert_eq!(raw.intr_conf.rxq(), 1);
+        assert_eq!(raw.intr_conf.lsc(), 0);
+
+        let conf = EthConf { rss: EthRssConf { rss_key: Some(vec![0; 256]), ..Default::default() }, ..conf };
+        assert_eq!(conf.to_raw().err(), Some(Error(libc::EINVAL)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let conf = EthConf {
+            rx_mq_mode: EthMqRxMode::RSS_FLAG,
+            tx_offloads: DevTxOffload::IPV4_CKSUM | DevTxOffload::MBUF_FAST_FREE,
+            rss: EthRssConf { rss_key: None, rss_hf: EthRss::IPV4 },
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&conf).unwrap();
+        assert_eq!(serde_json::from_str::<EthConf>(&json).unwrap(), conf);
+
+        let partial = serde_json::from_str::<RxQueueConf>(r#"{"rx_free_thresh": 32, "rx_drop_en": true}"#).unwrap();
+        assert_eq!(partial, RxQueueConf { rx_free_thresh: 32, rx_drop_en: true, ..Default::default() });
+
+        // unknown flags are rejected
+        assert!(serde_json::from_str::<EthConf>(r#"{"rx_mq_mode": 128}"#).is_err());
+    }
+}
@@ -0,0 +1,213 @@
+//! Diagnostics of packet loss, correlating the drop counters of a port (and the state of its memory pool) between two
+//! [`Snapshot`]s into a [`DropReport`] listing the likely causes, e.g. for an operator endpoint:
+//! ```rust,ignore
+//! let report = DropReport::new(&prev, &Snapshot::capture(&dev)?, Some(&mempool));
+//! if !report.is_empty() {
+//!     warn!("{report}");
+//! }
+//! ```
+
+use std::{fmt, time::Duration};
+
+use super::stats::{counter_delta, Snapshot};
+use crate::mempool::MemoryPool;
+
+/// The fraction of the memory pool below which it's considered exhausted.
+const MEMPOOL_LOW_WATERMARK: f64 = 1.0 / 16.0;
+
+/// The fraction of the queue drops above which a single queue is considered to be the culprit.
+const QUEUE_IMBALANCE_RATIO: f64 = 0.9;
+
+/// A likely cause of packet loss.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DropCause {
+    /// The hardware ran out of RX descriptors (`imissed`), i.e. the queues aren't polled fast enough.
+    RxOverflow,
+    /// The driver ran out of mbufs to refill the RX descriptors (`rx_nombuf`), or the memory pool is (almost) empty.
+    MbufExhaustion,
+    /// Erroneous packets were received (`ierrors`), e.g. with bad CRCs.
+    RxErrors,
+    /// Packets failed to be transmitted (`oerrors`).
+    TxErrors,
+    /// Most of the queue drops are on a single RX queue, e.g. because of an unbalanced RSS distribution.
+    QueueImbalance { queue_id: u16 },
+}
+
+impl fmt::Display for DropCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RxOverflow => write!(
+                f,
+                "the RX rings overflowed: the queues aren't polled fast enough, consider more lcores or queues, or \
+                 more RX descriptors to absorb bursts"
+            ),
+            Self::MbufExhaustion => write!(
+                f,
+                "the memory pool is exhausted: it's too small for the RX descriptors and the packets in flight, or \
+                 mbufs are leaked or held for too long"
+            ),
+            Self::RxErrors => write!(f, "erroneous packets were received: check the link, cabling and peer"),
+            Self::TxErrors => write!(f, "transmissions failed: check the link state and the TX queue configuration"),
+            Self::QueueImbalance { queue_id } => write!(
+                f,
+                "RX queue {queue_id} accounts for most of the queue drops: check the RSS configuration, or whether \
+                 its lcore is overloaded"
+            ),
+        }
+    }
+}
+
+/// The state of a memory pool when the report was made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MempoolUsage {
+    pub name: String,
+    pub available: u32,
+    pub size: u32,
+}
+
+/// The packets a port dropped between two snapshots, and their likely causes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropReport {
+    pub interval: Duration,
+    pub imissed: u64,
+    pub ierrors: u64,
+    pub rx_nombuf: u64,
+    pub oerrors: u64,
+    /// The drops of each RX queue which has any (of the ones mapped to stats counters), by queue id.
+    pub queue_drops: Vec<(u16, u64)>,
+    pub mempool: Option<MempoolUsage>,
+    pub causes: Vec<DropCause>,
+}
+
+impl DropReport {
+    /// Correlates the drops of the port between the `prev` and `cur` snapshots, along with the state of the memory pool
+    /// of its RX queues, if given.
+    pub fn new(prev: &Snapshot, cur: &Snapshot, mempool: Option<&MemoryPool>) -> Self {
+        let delta = cur.delta(prev);
+        let queue_drops = prev
+            .stats
+            .q_errors
+            .iter()
+            .zip(cur.stats.q_errors)
+            .map(|(&prev, cur)| counter_delta(prev, cur))
+            .enumerate()
+            .filter(|&(_, drops)| drops > 0)
+            .map(|(queue_id, drops)| (queue_id as u16, drops))
+            .collect::<Vec<_>>();
+        let mempool = mempool.map(|mempool| MempoolUsage {
+            name: String::from_utf8_lossy(mempool.name()).into_owned(),
+            available: mempool.get_available_count(),
+            size: mempool.size(),
+        });
+
+        let mut causes = vec![];
+        if delta.imissed > 0 {
+            causes.push(DropCause::RxOverflow);
+        }
+        let mempool_low = mempool
+            .as_ref()
+            .is_some_and(|usage| f64::from(usage.available) < f64::from(usage.size) * MEMPOOL_LOW_WATERMARK);
+        if delta.rx_nombuf > 0 || mempool_low {
+            causes.push(DropCause::MbufExhaustion);
+        }
+        if delta.ierrors > 0 {
+            causes.push(DropCause::RxErrors);
+        }
+        if delta.oerrors > 0 {
+            causes.push(DropCause::TxErrors);
+        }
+        let total_queue_drops = queue_drops.iter().map(|&(_, drops)| drops).sum::<u64>();
+        if let Some(&(queue_id, drops)) = queue_drops.iter().max_by_key(|&&(_, drops)| drops) {
+            if queue_drops.len() > 1 && drops as f64 > total_queue_drops as f64 * QUEUE_IMBALANCE_RATIO {
+                causes.push(DropCause::QueueImbalance { queue_id });
+            }
+        }
+
+        Self {
+            interval: delta.interval,
+            imissed: delta.imissed,
+            ierrors: delta.ierrors,
+            rx_nombuf: delta.rx_nombuf,
+            oerrors: delta.oerrors,
+            queue_drops,
+            mempool,
+            causes,
+        }
+    }
+
+    /// Returns the total number of dropped packets.
+    #[inline]
+    pub fn drops(&self) -> u64 {
+        self.imissed + self.ierrors + self.rx_nombuf + self.oerrors
+    }
+
+    /// Returns whether no packets were dropped, and nothing hints that some will be.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.drops() == 0 && self.causes.is_empty()
+    }
+}
+
+impl fmt::Display for DropReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} packets dropped in {:?} (imissed {}, ierrors {}, rx_nombuf {}, oerrors {})",
+            self.drops(),
+            self.interval,
+            self.imissed,
+            self.ierrors,
+            self.rx_nombuf,
+            self.oerrors
+        )?;
+        if !self.queue_drops.is_empty() {
+            let queues = self.queue_drops.iter().map(|(queue_id, drops)| format!("{queue_id}: {drops}"));
+            write!(f, "\nRX queue drops: {}", queues.collect::<Vec<_>>().join(", "))?;
+        }
+        if let Some(MempoolUsage { name, available, size }) = &self.mempool {
+            write!(f, "\nmempool {name}: {available}/{size} mbufs available")?;
+        }
+        for cause in &self.causes {
+            write!(f, "\nlikely cause: {cause}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::ethdev::DeviceStats;
+
+    fn snapshot(time: Instant, stats: DeviceStats) -> Snapshot {
+        Snapshot { time, stats, xstats: Default::default() }
+    }
+
+    #[test]
+    fn test_no_drops() {
+        let time = Instant::now();
+        let stats = DeviceStats { ipackets: 1000, imissed: 5, ..Default::default() };
+        let report = DropReport::new(&snapshot(time, stats), &snapshot(time + Duration::from_secs(1), stats), None);
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn test_drop_causes() {
+        let time = Instant::now();
+        let mut prev = DeviceStats::default();
+        prev.q_errors[1] = 10;
+        let mut cur = DeviceStats { imissed: 100, rx_nombuf: 3, ..Default::default() };
+        cur.q_errors[0] = 2;
+        cur.q_errors[1] = 110;
+
+        let report = DropReport::new(&snapshot(time, prev), &snapshot(time + Duration::from_secs(1), cur), None);
+        assert_eq!(report.drops(), 103);
+        assert_eq!(report.queue_drops, [(0, 2), (1, 100)]);
+        assert_eq!(
+            report.causes,
+            [DropCause::RxOverflow, DropCause::MbufExhaustion, DropCause::QueueImbalance { queue_id: 1 }]
+        );
+    }
+}
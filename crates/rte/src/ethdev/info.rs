@@ -0,0 +1,187 @@
+use std::{ffi::CStr, os::raw::c_char};
+
+use rte_error::ReturnValue as _;
+
+use super::{EthDev, RxQueueConf, TxQueueConf};
+use crate::{
+    flags::{DevRxOffload, DevTxOffload, EthLinkSpeed, EthRss},
+    memory::SocketId,
+    Result,
+};
+
+/// The contextual information of a device, see [`ffi::rte_eth_dev_info`].
+///
+/// The fields which drivers may leave unset are optional.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeviceInfo {
+    pub driver_name: Option<String>,
+    /// The name of the generic device (e.g. its PCI address).
+    pub device_name: Option<String>,
+    /// The index of the corresponding kernel interface.
+    pub if_index: Option<u32>,
+    /// The NUMA node the device is attached to.
+    pub numa_node: Option<SocketId>,
+    pub min_mtu: u16,
+    pub max_mtu: u16,
+    pub max_rx_pktlen: u32,
+    pub max_rx_queues: u16,
+    pub max_tx_queues: u16,
+    /// The number of RX queues currently configured.
+    pub nb_rx_queues: u16,
+    /// The number of TX queues currently configured.
+    pub nb_tx_queues: u16,
+    pub rx_desc_lim: DescLimits,
+    pub tx_desc_lim: DescLimits,
+    /// The RX offloads supported by the device, per port or per queue.
+    pub rx_offload_capa: DevRxOffload,
+    /// The RX offloads which can be enabled on individual queues.
+    pub rx_queue_offload_capa: DevRxOffload,
+    /// The TX offloads supported by the device, per port or per queue.
+    pub tx_offload_capa: DevTxOffload,
+    /// The TX offloads which can be enabled on individual queues.
+    pub tx_queue_offload_capa: DevTxOffload,
+    /// The packet types RSS can be computed on.
+    pub flow_type_rss_offloads: EthRss,
+    pub hash_key_size: u8,
+    pub reta_size: u16,
+    pub speed_capa: EthLinkSpeed,
+    pub default_rxconf: RxQueueConf,
+    pub default_txconf: TxQueueConf,
+    /// The preferred RX parameters, if the driver has any.
+    pub default_rxportconf: Option<PortParams>,
+    /// The preferred TX parameters, if the driver has any.
+    pub default_txportconf: Option<PortParams>,
+}
+
+/// The limits on the number of descriptors of a queue, see [`ffi::rte_eth_desc_lim`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DescLimits {
+    pub nb_max: u16,
+    pub nb_min: u16,
+    /// The number of descriptors must be a multiple of it.
+    pub nb_align: u16,
+}
+
+/// The preferred parameters of the queues of a device, see [`ffi::rte_eth_dev_portconf`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PortParams {
+    pub burst_size: u16,
+    pub ring_size: u16,
+    pub nb_queues: u16,
+}
+
+/// Copies a C string, which may be null or not UTF-8.
+///
+/// # Safety
+/// `ptr` must be null or point to a nul-terminated string.
+unsafe fn to_string(ptr: *const c_char) -> Option<String> {
+    if ptr.is_null() {
+        return None;
+    }
+    Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+}
+
+impl From<&ffi::rte_eth_desc_lim> for DescLimits {
+    fn from(lim: &ffi::rte_eth_desc_lim) -> Self {
+        Self { nb_max: lim.nb_max, nb_min: lim.nb_min, nb_align: lim.nb_align }
+    }
+}
+
+impl PortParams {
+    fn new(conf: &ffi::rte_eth_dev_portconf) -> Option<Self> {
+        let params = Self { burst_size: conf.burst_size, ring_size: conf.ring_size, nb_queues: conf.nb_queues };
+        (params != Self::default()).then_some(params)
+    }
+}
+
+impl From<&ffi::rte_eth_dev_info> for DeviceInfo {
+    fn from(info: &ffi::rte_eth_dev_info) -> Self {
+        // the generic device is unset for some virtual devices
+        let (device_name, numa_node) = if info.device.is_null() {
+            (None, None)
+        } else {
+            unsafe {
+                let numa_node = ffi::rte_dev_numa_node(info.device);
+                (to_string(ffi::rte_dev_name(info.device)), u32::try_from(numa_node).ok().and_then(SocketId::new))
+            }
+        };
+
+        Self {
+            driver_name: unsafe { to_string(info.driver_name) },
+            device_name,
+            if_index: (info.if_index != 0).then_some(info.if_index),
+            numa_node,
+            min_mtu: info.min_mtu,
+            max_mtu: info.max_mtu,
+            max_rx_pktlen: info.max_rx_pktlen,
+            max_rx_queues: info.max_rx_queues,
+            max_tx_queues: info.max_tx_queues,
+            nb_rx_queues: info.nb_rx_queues,
+            nb_tx_queues: info.nb_tx_queues,
+            rx_desc_lim: (&info.rx_desc_lim).into(),
+            tx_desc_lim: (&info.tx_desc_lim).into(),
+            rx_offload_capa: DevRxOffload::from_bits_truncate(info.rx_offload_capa),
+            rx_queue_offload_capa: DevRxOffload::from_bits_truncate(info.rx_queue_offload_capa),
+            tx_offload_capa: DevTxOffload::from_bits_truncate(info.tx_offload_capa),
+            tx_queue_offload_capa: DevTxOffload::from_bits_truncate(info.tx_queue_offload_capa),
+            flow_type_rss_offloads: EthRss::from_bits_truncate(info.flow_type_rss_offloads),
+            hash_key_size: info.hash_key_size,
+            reta_size: info.reta_size,
+            speed_capa: EthLinkSpeed::from_bits_truncate(info.speed_capa),
+            default_rxconf: (&info.default_rxconf).into(),
+            default_txconf: (&info.default_txconf).into(),
+            default_rxportconf: PortParams::new(&info.default_rxportconf),
+            default_txportconf: PortParams::new(&info.default_txportconf),
+        }
+    }
+}
+
+impl EthDev {
+    /// Returns the raw contextual information of the device, whose pointers are owned by the driver.
+    #[inline]
+    pub fn raw_info(&self) -> Result<ffi::rte_eth_dev_info> {
+        let mut info = ffi::rte_eth_dev_info::default();
+        unsafe { ffi::rte_eth_dev_info_get(self.port_id, &mut info) }.rte_ok()?;
+        Ok(info)
+    }
+
+    #[inline]
+    pub fn info(&self) -> Result<DeviceInfo> {
+        Ok((&self.raw_info()?).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::{FakePort, TestPool};
+
+    #[test]
+    fn test_from_unset() {
+        let mut raw = ffi::rte_eth_dev_info::default();
+        raw.max_rx_queues = 4;
+        raw.rx_offload_capa = ffi::_RTE_ETH_RX_OFFLOAD_RSS_HASH | (1 << 63);
+
+        let info = DeviceInfo::from(&raw);
+        assert_eq!(info.driver_name, None);
+        assert_eq!(info.device_name, None);
+        assert_eq!(info.if_index, None);
+        assert_eq!(info.numa_node, None);
+        assert_eq!(info.max_rx_queues, 4);
+        assert_eq!(info.rx_offload_capa, DevRxOffload::RSS_HASH);
+        assert_eq!(info.default_rxportconf, None);
+    }
+
+    #[rte_test]
+    fn test_info() {
+        let mempool = TestPool::new(1023);
+        let port = FakePort::null("info", 128, &mempool).unwrap();
+
+        let info = port.dev().info().unwrap();
+        assert_eq!(info.driver_name.as_deref(), Some("net_null"));
+        assert_eq!(info.device_name.as_deref(), Some("net_null_info"));
+        assert!(info.max_rx_queues > 0);
+    }
+}
@@ -1,22 +1,47 @@
+pub mod diagnostics;
+pub mod stats;
+
+mod bpf;
+mod conf;
+mod info;
+mod mtr;
+mod queue;
+mod quiesce;
+mod security;
+#[cfg(feature = "async")]
+mod sink;
+#[cfg(feature = "async")]
+mod stream;
+mod virtio_user;
 mod xstats;
 
 use std::{
-    ffi::CStr,
     iter::from_fn,
-    mem::{self, MaybeUninit},
-    ptr, slice,
+    ptr, thread,
+    time::{Duration, Instant},
 };
 
-use arrayvec::ArrayVec;
 use mac_addr::MacAddr;
 use rte_error::{Error, ReturnValue as _};
 
-pub use self::xstats::XStatsDefs;
-use crate::{mbuf::MBuf, memory::SocketId, mempool::MemoryPool, Result};
+pub use self::{
+    conf::{EthConf, EthRssConf, RxQueueConf, TxQueueConf},
+    info::{DescLimits, DeviceInfo, PortParams},
+    mtr::{MtrAction, MtrCapabilities, MtrProfile, MtrStats},
+    queue::{RxQueue, TxQueue},
+    quiesce::QuiesceReport,
+    virtio_user::{VirtioUser, VirtioUserConfig},
+    xstats::XStatsDefs,
+};
+#[cfg(feature = "async")]
+pub use self::{
+    sink::TxSink,
+    stream::{RxMode, RxStream},
+};
+use crate::{mbuf::PacketBatch, memory::SocketId, mempool::MemoryPool, Result};
 
 pub const MAX_QUEUE: u16 = u16::MAX;
 
-pub type DeviceInfo = ffi::rte_eth_dev_info;
 pub type DeviceStats = ffi::rte_eth_stats;
 pub type Conf = ffi::rte_eth_conf;
 
@@ -40,6 +65,7 @@ impl EthDev {
     /// Configure an Ethernet device.
     /// This function must be invoked first before any other function in the Ethernet API. This function can also be re-invoked when a device is in the stopped state.
     #[inline]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, conf), fields(port_id = self.port_id), err))]
     pub fn configure(&self, nb_rx_queue: u16, nb_tx_queue: u16, conf: &Conf) -> Result<()> {
         unsafe { ffi::rte_eth_dev_configure(self.port_id, nb_rx_queue, nb_tx_queue, conf) }.rte_ok()?;
         Ok(())
@@ -55,14 +81,22 @@ impl EthDev {
     pub fn mac_addr(&self) -> Result<MacAddr> {
         let mut addr: ffi::rte_ether_addr = Default::default();
         unsafe { ffi::rte_eth_macaddr_get(self.port_id, &mut addr) }.rte_ok()?;
-        Ok(MacAddr::from(addr.addr_bytes))
+        Ok(addr.into())
+    }
+
+    /// Sets the default MAC address of the port.
+    #[inline]
+    pub fn set_mac_addr(&self, addr: MacAddr) -> Result<()> {
+        let mut addr = addr.into();
+        unsafe { ffi::rte_eth_dev_default_mac_addr_set(self.port_id, &mut addr) }.rte_ok()?;
+        Ok(())
     }
 
     #[inline]
-    pub fn info(&self) -> Result<DeviceInfo> {
-        let mut info: DeviceInfo = Default::default();
-        unsafe { ffi::rte_eth_dev_info_get(self.port_id, &mut info) }.rte_ok()?;
-        Ok(info)
+    pub fn mtu(&self) -> Result<u16> {
+        let mut mtu = 0;
+        unsafe { ffi::rte_eth_dev_get_mtu(self.port_id, &mut mtu) }.rte_ok()?;
+        Ok(mtu)
     }
 
     #[inline]
@@ -73,7 +107,7 @@ impl EthDev {
     }
 
     #[inline]
-    fn socket_id(&self) -> Result<SocketId> {
+    pub(crate) fn socket_id(&self) -> Result<SocketId> {
         // -1 is returned if the port_id (self) is out of range
         let ret = unsafe { ffi::rte_eth_dev_socket_id(self.port_id) };
         // cast from i32 to u32 (e.g., -1 == u32::MAX)
@@ -82,74 +116,83 @@ impl EthDev {
     }
 
     #[inline]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(port_id = self.port_id), err))]
     pub fn start(&self) -> Result<()> {
         unsafe { ffi::rte_eth_dev_start(self.port_id) }.rte_ok()?;
         Ok(())
     }
 
     #[inline]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(port_id = self.port_id), err))]
     pub fn stop(&self) -> Result<()> {
         unsafe { ffi::rte_eth_dev_stop(self.port_id) }.rte_ok()?;
         Ok(())
     }
 
     #[inline]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(port_id = self.port_id), err))]
     pub fn close(&self) -> Result<()> {
         unsafe { ffi::rte_eth_dev_close(self.port_id) }.rte_ok()?;
         Ok(())
     }
 
+    /// Resets the (stopped) device to its state once probed, after which it must be configured again.
+    #[inline]
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(port_id = self.port_id), err))]
+    pub fn reset(&self) -> Result<()> {
+        unsafe { ffi::rte_eth_dev_reset(self.port_id) }.rte_ok()?;
+        Ok(())
+    }
+
     /// Retrieve a burst of input packets from a receive queue of an Ethernet device.
     ///
-    /// The received packets will be appended to `rx_pkts`. This method uses the array's current capacity
-    /// (i.e. `CAP - rx_pkts.len()`) as a buffer for the DPDK library to write the received packets into,
-    /// so in order to utilize the array's entire capacity, it should be empty when calling this function.
+    /// The received packets will be appended to `rx_pkts`, whose remaining capacity is used as the buffer for the DPDK
+    /// library to write the received packets into, so in order to utilize the batch's entire capacity, it should be
+    /// empty when calling this function.
     ///
     /// # Safety
     /// It is up to the caller to guarantee that `mempool` matches the memory pool
     /// used in the call to [`Self::rx_queue_setup`] for this queue.
-    #[inline]
+    #[cfg_attr(feature = "zero-overhead-audit", inline(always))]
+    #[cfg_attr(not(feature = "zero-overhead-audit"), inline)]
     pub unsafe fn rx_burst<'mempool, const CAP: usize>(
         &self,
         queue_id: u16,
         _mempool: &'mempool MemoryPool,
-        rx_pkts: &mut ArrayVec<MBuf<&'mempool MemoryPool>, CAP>,
+        rx_pkts: &mut PacketBatch<&'mempool MemoryPool, CAP>,
     ) {
-        let old_len = rx_pkts.len();
-
-        // this code was adapted from the Vec::spare_capacity_mut method, which ArrayVec unfortunately does not have
-        let spare_cap = slice::from_raw_parts_mut(
-            rx_pkts.as_mut_ptr().add(old_len) as *mut MaybeUninit<MBuf<&'mempool MemoryPool>>,
-            rx_pkts.remaining_capacity(),
-        );
-
-        let received =
-            ffi::_rte_eth_rx_burst(self.port_id, queue_id, spare_cap.as_mut_ptr() as _, spare_cap.len() as u16)
-                as usize;
-        rx_pkts.set_len(old_len + received);
+        // the received mbufs were allocated from the queue's memory pool
+        rx_pkts.fill_raw(|pkts, spare_cap| {
+            let received = ffi::_rte_eth_rx_burst(self.port_id, queue_id, pkts, spare_cap as u16) as usize;
+            #[cfg(feature = "tracing")]
+            trace_burst("rx", self.port_id, queue_id, spare_cap, received);
+            received
+        });
     }
 
     /// Send a burst of output packets on a transmit queue of an Ethernet device.
     ///
-    /// Packets that have been successfully sent will be removed from `tx_pkts`, any `MBufs` remaining in the array
+    /// Packets that have been successfully sent will be removed from `tx_pkts`, any `MBufs` remaining in the batch
     /// after this method has completed are packets that were NOT sent.
     ///
     /// # Safety
     /// It is up to the caller to guarantee that `mempool` matches the memory pool
     /// used in the call to [`Self::tx_queue_setup`] for this queue.
-    #[inline]
+    #[cfg_attr(feature = "zero-overhead-audit", inline(always))]
+    #[cfg_attr(not(feature = "zero-overhead-audit"), inline)]
     pub unsafe fn tx_burst<'mempool, const CAP: usize>(
         &self,
         queue_id: u16,
         _mempool: &'mempool MemoryPool,
-        tx_pkts: &mut ArrayVec<MBuf<&'mempool MemoryPool>, CAP>,
+        tx_pkts: &mut PacketBatch<&'mempool MemoryPool, CAP>,
     ) {
-        let transmitted =
-            ffi::_rte_eth_tx_burst(self.port_id, queue_id, tx_pkts.as_mut_ptr() as _, tx_pkts.len() as u16) as usize;
-
-        // rte_eth_tx_burst assumes ownership of the mbufs that were successfully transmitted,
-        // so we remove them from tx_pkts and use mem::forget to prevent dropping (and freeing) them ourselves
-        tx_pkts.drain(..transmitted).for_each(mem::forget);
+        // rte_eth_tx_burst assumes ownership of the mbufs that were successfully transmitted
+        tx_pkts.consume_raw(|pkts, len| {
+            let transmitted = ffi::_rte_eth_tx_burst(self.port_id, queue_id, pkts, len as u16) as usize;
+            #[cfg(feature = "tracing")]
+            trace_burst("tx", self.port_id, queue_id, len, transmitted);
+            transmitted
+        });
     }
 
     #[inline]
@@ -158,7 +201,7 @@ impl EthDev {
         rx_queue_id: u16,
         nb_rx_desc: u16,
         rx_conf: Option<ffi::rte_eth_rxconf>,
-        mempool: &mut MemoryPool,
+        mempool: &MemoryPool,
     ) -> Result<()> {
         unsafe {
             ffi::rte_eth_rx_queue_setup(
@@ -171,6 +214,8 @@ impl EthDev {
             )
         }
         .rte_ok()?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(port_id = self.port_id, rx_queue_id, nb_rx_desc, "RX queue set up");
         Ok(())
     }
 
@@ -191,9 +236,37 @@ impl EthDev {
             )
         }
         .rte_ok()?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(port_id = self.port_id, tx_queue_id, nb_tx_desc, "TX queue set up");
         Ok(())
     }
 
+    /// Returns the number of descriptors of an RX queue, as it was set up.
+    #[inline]
+    pub fn rx_queue_desc(&self, queue_id: u16) -> Result<u16> {
+        let mut rxq_info = ffi::rte_eth_rxq_info::default();
+        unsafe { ffi::rte_eth_rx_queue_info_get(self.port_id, queue_id, &mut rxq_info) }.rte_ok()?;
+        Ok(rxq_info.nb_desc)
+    }
+
+    /// Returns the number of used descriptors of an RX queue, i.e. of packets received by the device but not yet by
+    /// the application, failing with `ENOTSUP` if the driver doesn't support counting them.
+    #[inline]
+    pub fn rx_queue_count(&self, queue_id: u16) -> Result<u32> {
+        let count = unsafe { ffi::_rte_eth_rx_queue_count(self.port_id, queue_id) }.rte_ok()?;
+        Ok(count as u32)
+    }
+
+    /// Returns the percentage (from 0 to 100) of used descriptors of an RX queue, the device dropping the packets it
+    /// receives (`imissed`) once it's full, e.g. to shed load before it overflows.
+    ///
+    /// It queries the number of descriptors of the queue, so when polled often, it's cheaper to call
+    /// [`Self::rx_queue_desc`] once and [`rx_queue_fullness`] with each [`Self::rx_queue_count`].
+    #[inline]
+    pub fn rx_queue_fullness(&self, queue_id: u16) -> Result<f64> {
+        Ok(rx_queue_fullness(self.rx_queue_count(queue_id)?, self.rx_queue_desc(queue_id)?))
+    }
+
     #[inline]
     pub fn promiscuous_enable(&self) -> Result<()> {
         unsafe { ffi::rte_eth_promiscuous_enable(self.port_id) }.rte_ok()?;
@@ -212,6 +285,34 @@ impl EthDev {
         Ok(ret.is_positive())
     }
 
+    /// Turns the LED of the port on, failing with `ENOTSUP` if the driver doesn't support controlling it.
+    #[inline]
+    pub fn led_on(&self) -> Result<()> {
+        unsafe { ffi::rte_eth_led_on(self.port_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Turns the LED of the port off, failing with `ENOTSUP` if the driver doesn't support controlling it.
+    #[inline]
+    pub fn led_off(&self) -> Result<()> {
+        unsafe { ffi::rte_eth_led_off(self.port_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Blinks the LED of the port (on and off once per `period`) for `duration`, to physically identify it, blocking
+    /// the calling thread meanwhile. The LED is left off.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(port_id = self.port_id), err))]
+    pub fn identify(&self, duration: Duration, period: Duration) -> Result<()> {
+        let end = Instant::now() + duration;
+        while Instant::now() < end {
+            self.led_on()?;
+            thread::sleep(period / 2);
+            self.led_off()?;
+            thread::sleep(period / 2);
+        }
+        Ok(())
+    }
+
     /// Based on [RTE_ETH_FOREACH_DEV](https://doc.dpdk.org/api-21.08/rte__ethdev_8h.html#ad7b46c67203d37fe3a34f11076d970d6)
     #[inline]
     pub fn for_each() -> impl Iterator<Item = EthDev> {
@@ -228,16 +329,67 @@ impl EthDev {
     }
 }
 
-pub trait DeviceInfoWrapper {
-    fn get_device_name(&self) -> String;
-    fn get_driver_name(&self) -> String;
+/// Returns the percentage (from 0 to 100) of `used` descriptors out of the `nb_desc` of a queue (0 for a queue without
+/// descriptors).
+#[inline]
+pub fn rx_queue_fullness(used: u32, nb_desc: u16) -> f64 {
+    match nb_desc {
+        0 => 0.0,
+        nb_desc => (f64::from(used) * 100.0 / f64::from(nb_desc)).min(100.0),
+    }
+}
+
+/// One in how many bursts (of each thread) gets a trace event.
+#[cfg(feature = "tracing")]
+const BURST_TRACE_INTERVAL: u32 = 1024;
+
+/// Emits a trace event for one in [`BURST_TRACE_INTERVAL`] bursts, so that traces show the activity of queues without
+/// the overhead of an event per burst.
+#[cfg(feature = "tracing")]
+#[inline]
+fn trace_burst(direction: &'static str, port_id: u16, queue_id: u16, requested: usize, done: usize) {
+    use std::cell::Cell;
+
+    thread_local! {
+        static BURSTS: Cell<u32> = const { Cell::new(0) };
+    }
+
+    let bursts = BURSTS.with(|bursts| {
+        bursts.set(bursts.get().wrapping_add(1));
+        bursts.get()
+    });
+    if bursts % BURST_TRACE_INTERVAL == 0 {
+        tracing::trace!(port_id, queue_id, requested, done, "{direction} burst");
+    }
 }
 
-impl DeviceInfoWrapper for DeviceInfo {
-    fn get_device_name(&self) -> String {
-        unsafe { CStr::from_ptr(ffi::rte_dev_name(self.device)).to_str().unwrap().to_string() }
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::{FakePort, TestPool};
+
+    #[test]
+    fn test_rx_queue_fullness() {
+        assert_eq!(rx_queue_fullness(0, 128), 0.0);
+        assert_eq!(rx_queue_fullness(32, 128), 25.0);
+        assert_eq!(rx_queue_fullness(128, 128), 100.0);
+        // a queue without descriptors is never full
+        assert_eq!(rx_queue_fullness(0, 0), 0.0);
+        assert_eq!(rx_queue_fullness(32, 0), 0.0);
+        // the count may exceed the descriptors, e.g. with some drivers counting the ones being refilled
+        assert_eq!(rx_queue_fullness(200, 128), 100.0);
     }
-    fn get_driver_name(&self) -> String {
-        unsafe { CStr::from_ptr(self.driver_name).to_str().unwrap().to_string() }
+
+    #[rte_test]
+    fn test_identify_unsupported() {
+        let mempool = TestPool::new(1023);
+        let port = FakePort::null("identify", 128, &mempool).unwrap();
+
+        // net_null has no LED to control
+        assert_eq!(port.dev().led_on(), Err(Error(libc::ENOTSUP)));
+        assert_eq!(port.dev().led_off(), Err(Error(libc::ENOTSUP)));
+        assert_eq!(port.dev().identify(Duration::from_secs(1), Duration::from_millis(100)), Err(Error(libc::ENOTSUP)));
     }
 }
@@ -0,0 +1,153 @@
+//! Based on DPDK's `rte_mtr.h` API: <https://doc.dpdk.org/api-22.11/rte__mtr_8h.html>
+
+use rte_error::ReturnValue as _;
+
+use super::EthDev;
+use crate::{flags::MtrStatsMask, Result};
+
+pub type MtrCapabilities = ffi::rte_mtr_capabilities;
+pub type MtrStats = ffi::rte_mtr_stats;
+
+/// The metering algorithm (and its parameters) of a meter profile. Rates are in bytes per second (or packets per second
+/// in packet mode), and burst sizes are in bytes (or packets).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtrProfile {
+    /// Single rate three color marker, see [RFC 2697](https://www.rfc-editor.org/rfc/rfc2697).
+    SrTcm { cir: u64, cbs: u64, ebs: u64 },
+    /// Two rate three color marker, see [RFC 2698](https://www.rfc-editor.org/rfc/rfc2698).
+    TrTcm { cir: u64, pir: u64, cbs: u64, pbs: u64 },
+    /// Two rate three color marker, see [RFC 4115](https://www.rfc-editor.org/rfc/rfc4115).
+    TrTcmRfc4115 { cir: u64, eir: u64, cbs: u64, ebs: u64 },
+}
+
+/// The action a meter policy applies to packets of a given color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MtrAction {
+    Pass,
+    Drop,
+}
+
+impl MtrAction {
+    fn as_raw(self) -> &'static [ffi::rte_flow_action] {
+        const END: ffi::rte_flow_action =
+            ffi::rte_flow_action { type_: ffi::rte_flow_action_type::RTE_FLOW_ACTION_TYPE_END, conf: std::ptr::null() };
+        const DROP: ffi::rte_flow_action = ffi::rte_flow_action {
+            type_: ffi::rte_flow_action_type::RTE_FLOW_ACTION_TYPE_DROP,
+            conf: std::ptr::null(),
+        };
+
+        match self {
+            Self::Pass => &[END],
+            Self::Drop => &[DROP, END],
+        }
+    }
+}
+
+impl EthDev {
+    #[inline]
+    pub fn mtr_capabilities(&self) -> Result<MtrCapabilities> {
+        let mut caps = MtrCapabilities::default();
+        unsafe { ffi::rte_mtr_capabilities_get(self.port_id, &mut caps, &mut Default::default()) }.rte_ok()?;
+        Ok(caps)
+    }
+
+    /// Adds a meter profile, which can be shared by multiple meters.
+    #[inline]
+    pub fn mtr_profile_add(&self, profile_id: u32, profile: MtrProfile, packet_mode: bool) -> Result<()> {
+        let mut raw = ffi::rte_mtr_meter_profile { packet_mode: packet_mode.into(), ..Default::default() };
+        match profile {
+            MtrProfile::SrTcm { cir, cbs, ebs } => {
+                raw.alg = ffi::rte_mtr_algorithm::RTE_MTR_SRTCM_RFC2697;
+                raw.__bindgen_anon_1.srtcm_rfc2697.cir = cir;
+                raw.__bindgen_anon_1.srtcm_rfc2697.cbs = cbs;
+                raw.__bindgen_anon_1.srtcm_rfc2697.ebs = ebs;
+            }
+            MtrProfile::TrTcm { cir, pir, cbs, pbs } => {
+                raw.alg = ffi::rte_mtr_algorithm::RTE_MTR_TRTCM_RFC2698;
+                raw.__bindgen_anon_1.trtcm_rfc2698.cir = cir;
+                raw.__bindgen_anon_1.trtcm_rfc2698.pir = pir;
+                raw.__bindgen_anon_1.trtcm_rfc2698.cbs = cbs;
+                raw.__bindgen_anon_1.trtcm_rfc2698.pbs = pbs;
+            }
+            MtrProfile::TrTcmRfc4115 { cir, eir, cbs, ebs } => {
+                raw.alg = ffi::rte_mtr_algorithm::RTE_MTR_TRTCM_RFC4115;
+                raw.__bindgen_anon_1.trtcm_rfc4115.cir = cir;
+                raw.__bindgen_anon_1.trtcm_rfc4115.eir = eir;
+                raw.__bindgen_anon_1.trtcm_rfc4115.cbs = cbs;
+                raw.__bindgen_anon_1.trtcm_rfc4115.ebs = ebs;
+            }
+        }
+
+        unsafe { ffi::rte_mtr_meter_profile_add(self.port_id, profile_id, &mut raw, &mut Default::default()) }
+            .rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn mtr_profile_delete(&self, profile_id: u32) -> Result<()> {
+        unsafe { ffi::rte_mtr_meter_profile_delete(self.port_id, profile_id, &mut Default::default()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Adds a meter policy, which applies an action to packets based on the color assigned to them by a meter.
+    ///
+    /// `actions` are indexed by [`Color`](crate::meter::Color), i.e. green, yellow and red.
+    #[inline]
+    pub fn mtr_policy_add(&self, policy_id: u32, actions: [MtrAction; 3]) -> Result<()> {
+        let mut policy = ffi::rte_mtr_meter_policy_params { actions: actions.map(|action| action.as_raw().as_ptr()) };
+        unsafe { ffi::rte_mtr_meter_policy_add(self.port_id, policy_id, &mut policy, &mut Default::default()) }
+            .rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn mtr_policy_delete(&self, policy_id: u32) -> Result<()> {
+        unsafe { ffi::rte_mtr_meter_policy_delete(self.port_id, policy_id, &mut Default::default()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Creates a (color blind) meter, using a previously added profile and policy. Shared meters may be used by
+    /// multiple flows.
+    ///
+    /// All of the statistics supported by the device are enabled.
+    #[inline]
+    pub fn mtr_create(&self, mtr_id: u32, profile_id: u32, policy_id: u32, shared: bool) -> Result<()> {
+        let mut params = ffi::rte_mtr_params {
+            meter_profile_id: profile_id,
+            meter_policy_id: policy_id,
+            meter_enable: 1,
+            stats_mask: self.mtr_capabilities()?.stats_mask,
+            ..Default::default()
+        };
+        unsafe { ffi::rte_mtr_create(self.port_id, mtr_id, &mut params, shared.into(), &mut Default::default()) }
+            .rte_ok()?;
+        Ok(())
+    }
+
+    /// Destroys a meter, which must not be used by any flow.
+    #[inline]
+    pub fn mtr_destroy(&self, mtr_id: u32) -> Result<()> {
+        unsafe { ffi::rte_mtr_destroy(self.port_id, mtr_id, &mut Default::default()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the statistics of a meter, optionally clearing them, along with the counters which are valid (the
+    /// others being left to 0).
+    #[inline]
+    pub fn mtr_stats(&self, mtr_id: u32, clear: bool) -> Result<(MtrStats, MtrStatsMask)> {
+        let mut stats = MtrStats::default();
+        let mut stats_mask = 0;
+        unsafe {
+            ffi::rte_mtr_stats_read(
+                self.port_id,
+                mtr_id,
+                &mut stats,
+                &mut stats_mask,
+                clear.into(),
+                &mut Default::default(),
+            )
+        }
+        .rte_ok()?;
+        Ok((stats, MtrStatsMask::from_bits_truncate(stats_mask)))
+    }
+}
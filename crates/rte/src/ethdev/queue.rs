@@ -0,0 +1,126 @@
+use rte_error::ReturnValue as _;
+
+use super::EthDev;
+use crate::{mbuf::PacketBatch, mempool::MemoryPool, Result};
+
+/// An RX queue of a (started) device, bound to the memory pool it was [set up](EthDev::rx_queue_setup) with, so that
+/// receiving packets is safe.
+///
+/// A queue must not be polled by several lcores at once, hence there's a single handle to it, receiving through an
+/// exclusive reference.
+pub struct RxQueue<'mp> {
+    dev: EthDev,
+    queue_id: u16,
+    mempool: &'mp MemoryPool,
+}
+
+impl<'mp> RxQueue<'mp> {
+    /// # Safety
+    /// `mempool` must match the memory pool used in the call to [`EthDev::rx_queue_setup`] for this queue, and there
+    /// must be no other handle to the queue.
+    #[inline]
+    pub unsafe fn new(dev: EthDev, queue_id: u16, mempool: &'mp MemoryPool) -> Self {
+        Self { dev, queue_id, mempool }
+    }
+
+    #[inline]
+    pub fn dev(&self) -> &EthDev {
+        &self.dev
+    }
+
+    #[inline]
+    pub fn queue_id(&self) -> u16 {
+        self.queue_id
+    }
+
+    #[inline]
+    pub fn mempool(&self) -> &'mp MemoryPool {
+        self.mempool
+    }
+
+    /// Receives packets from the queue, see [`EthDev::rx_burst`].
+    #[cfg_attr(feature = "zero-overhead-audit", inline(always))]
+    #[cfg_attr(not(feature = "zero-overhead-audit"), inline)]
+    pub fn recv<const CAP: usize>(&mut self, pkts: &mut PacketBatch<&'mp MemoryPool, CAP>) {
+        // Safety: the queue was set up with this memory pool
+        unsafe { self.dev.rx_burst(self.queue_id, self.mempool, pkts) }
+    }
+
+    /// Enables the RX interrupt of the queue (which requires the device to be configured with `intr_conf.rxq` set),
+    /// to be notified of packets through its [event fd](Self::intr_fd) rather than by polling.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__ethdev_8h.html>
+    #[inline]
+    pub fn intr_enable(&self) -> Result<()> {
+        unsafe { ffi::rte_eth_dev_rx_intr_enable(self.dev.port_id(), self.queue_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn intr_disable(&self) -> Result<()> {
+        unsafe { ffi::rte_eth_dev_rx_intr_disable(self.dev.port_id(), self.queue_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the event fd signaled by the RX interrupt of the queue.
+    #[inline]
+    pub fn intr_fd(&self) -> Result<i32> {
+        unsafe { ffi::rte_eth_dev_rx_intr_ctl_q_get_fd(self.dev.port_id(), self.queue_id) }.rte_ok()
+    }
+
+    /// Returns the number of packets received by the device but not yet by the application, see
+    /// [`EthDev::rx_queue_count`].
+    #[inline]
+    pub fn count(&self) -> Result<u32> {
+        self.dev.rx_queue_count(self.queue_id)
+    }
+
+    /// Returns the percentage of used descriptors of the queue, see [`EthDev::rx_queue_fullness`].
+    #[inline]
+    pub fn fullness(&self) -> Result<f64> {
+        self.dev.rx_queue_fullness(self.queue_id)
+    }
+}
+
+/// A TX queue of a (started) device, bound to the memory pool it was [set up](EthDev::tx_queue_setup) with, so that
+/// sending packets is safe.
+///
+/// Like an [`RxQueue`], there's a single handle to the queue, sending through an exclusive reference.
+pub struct TxQueue<'mp> {
+    dev: EthDev,
+    queue_id: u16,
+    mempool: &'mp MemoryPool,
+}
+
+impl<'mp> TxQueue<'mp> {
+    /// # Safety
+    /// `mempool` must match the memory pool used in the call to [`EthDev::tx_queue_setup`] for this queue, and there
+    /// must be no other handle to the queue.
+    #[inline]
+    pub unsafe fn new(dev: EthDev, queue_id: u16, mempool: &'mp MemoryPool) -> Self {
+        Self { dev, queue_id, mempool }
+    }
+
+    #[inline]
+    pub fn dev(&self) -> &EthDev {
+        &self.dev
+    }
+
+    #[inline]
+    pub fn queue_id(&self) -> u16 {
+        self.queue_id
+    }
+
+    #[inline]
+    pub fn mempool(&self) -> &'mp MemoryPool {
+        self.mempool
+    }
+
+    /// Sends packets on the queue, see [`EthDev::tx_burst`].
+    #[cfg_attr(feature = "zero-overhead-audit", inline(always))]
+    #[cfg_attr(not(feature = "zero-overhead-audit"), inline)]
+    pub fn send<const CAP: usize>(&mut self, pkts: &mut PacketBatch<&'mp MemoryPool, CAP>) {
+        // Safety: the queue was set up with this memory pool
+        unsafe { self.dev.tx_burst(self.queue_id, self.mempool, pkts) }
+    }
+}
@@ -0,0 +1,104 @@
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use rte_error::{Error, ReturnValue as _};
+
+use super::EthDev;
+use crate::Result;
+
+/// How often the TX queues are checked while waiting for them to be drained.
+const POLL_INTERVAL: Duration = Duration::from_micros(100);
+
+/// The outcome of [`EthDev::quiesce`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuiesceReport {
+    /// The packets whose transmission completed while waiting, as reported by the driver's TX cleanup (0 if it
+    /// doesn't support it).
+    pub tx_drained: u64,
+    /// The TX queues which still had descriptors in flight when the timeout expired.
+    pub tx_timed_out: Vec<u16>,
+}
+
+impl QuiesceReport {
+    /// Returns whether all the TX queues were drained before the timeout.
+    #[inline]
+    pub fn is_drained(&self) -> bool {
+        self.tx_timed_out.is_empty()
+    }
+}
+
+/// Ignores the errors of operations the driver doesn't implement.
+fn unless_unsupported(res: Result<i32>) -> Result<i32> {
+    match res {
+        Err(Error(libc::ENOTSUP)) => Ok(0),
+        res => res,
+    }
+}
+
+impl EthDev {
+    /// Gracefully stops and closes the (started) device: stops its RX queues, then waits (for up to `timeout`) for the
+    /// packets already handed to its TX queues to be transmitted, before stopping and closing it.
+    ///
+    /// # Safety
+    /// The queues of the device mustn't be used anymore, neither while quiescing nor afterwards, e.g. the
+    /// [`RxQueue`](super::RxQueue)s and [`TxQueue`](super::TxQueue)s handed out by its [`Port`](crate::port::Port)
+    /// must have been dropped.
+    pub unsafe fn quiesce(&self, timeout: Duration) -> Result<QuiesceReport> {
+        let deadline = Instant::now() + timeout;
+        let info = self.info()?;
+
+        for queue_id in 0..info.nb_rx_queues {
+            unless_unsupported(unsafe { ffi::rte_eth_dev_rx_queue_stop(self.port_id, queue_id) }.rte_ok())?;
+        }
+
+        let mut report = QuiesceReport::default();
+        for queue_id in 0..info.nb_tx_queues {
+            let mut txq_info = ffi::rte_eth_txq_info::default();
+            unsafe { ffi::rte_eth_tx_queue_info_get(self.port_id, queue_id, &mut txq_info) }.rte_ok()?;
+            // the descriptor of the last packet handed to the queue
+            let last = txq_info.nb_desc.saturating_sub(1);
+
+            loop {
+                let freed = unsafe { ffi::rte_eth_tx_done_cleanup(self.port_id, queue_id, 0) }.rte_ok();
+                report.tx_drained += unless_unsupported(freed)? as u64;
+
+                let status = unsafe { ffi::_rte_eth_tx_descriptor_status(self.port_id, queue_id, last) };
+                // a driver which can't tell whether the descriptor is done is considered drained
+                if unless_unsupported(status.rte_ok())? != ffi::RTE_ETH_TX_DESC_FULL as i32 {
+                    break;
+                }
+                if Instant::now() >= deadline {
+                    report.tx_timed_out.push(queue_id);
+                    break;
+                }
+                thread::sleep(POLL_INTERVAL);
+            }
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(port_id = self.port_id, ?report, "port quiesced");
+        self.stop()?;
+        self.close()?;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::{FakePort, TestPool};
+
+    #[rte_test]
+    fn test_quiesce() {
+        let mempool = TestPool::new(1023);
+        let port = FakePort::null("quiesce", 128, &mempool).unwrap();
+
+        // Safety: the queue is never used
+        let report = unsafe { port.dev().quiesce(Duration::from_millis(100)) }.unwrap();
+        assert!(report.is_drained());
+    }
+}
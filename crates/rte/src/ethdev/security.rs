@@ -0,0 +1,16 @@
+use std::ptr::NonNull;
+
+use super::EthDev;
+use crate::security::SecurityCtx;
+
+impl EthDev {
+    /// Returns the security context of this device, or `None` if it does not support security offloads.
+    ///
+    /// The context's [capabilities](SecurityCtx::capabilities) list the supported offloads. Note that the
+    /// `RTE_ETH_RX_OFFLOAD_SECURITY` and [`DevTxOffload::SECURITY`](crate::flags::DevTxOffload::SECURITY) offloads must
+    /// be enabled for inline sessions.
+    #[inline]
+    pub fn security_ctx(&self) -> Option<SecurityCtx> {
+        NonNull::new(unsafe { ffi::rte_eth_dev_get_sec_ctx(self.port_id) }.cast()).map(SecurityCtx)
+    }
+}
@@ -0,0 +1,183 @@
+//! [`Sink`]s of packets to send on a tokio runtime, for traffic originated by the control plane (e.g. ARP replies or
+//! routing protocol messages), complementing the [`RxStream`](super::RxStream)s.
+
+use std::{
+    future::Future,
+    mem,
+    os::raw::c_void,
+    pin::Pin,
+    ptr::{self, NonNull},
+    slice,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures_sink::Sink;
+use rte_error::{Error, ReturnValue as _};
+use tokio::time::Sleep;
+
+use super::TxQueue;
+use crate::{mbuf::MBuf, mempool::MemoryPool, Result};
+
+/// The error callback of the TX buffer, keeping the packets the queue was full for (rather than dropping them) in the
+/// `Vec` pointed to by `userdata`.
+unsafe extern "C" fn keep_unsent(pkts: *mut *mut ffi::rte_mbuf, count: u16, userdata: *mut c_void) {
+    let unsent = &mut *userdata.cast::<Vec<NonNull<ffi::rte_mbuf>>>();
+    unsent.extend(slice::from_raw_parts(pkts, count.into()).iter().map(|&pkt| NonNull::new_unchecked(pkt)));
+}
+
+/// A [`Sink`] of packets to send on a TX queue, buffering them into an `rte_eth_dev_tx_buffer` which is sent once
+/// it's full, or once the flush interval elapsed since the first packet was buffered (as checked whenever the sink is
+/// polled), or when the sink is flushed.
+///
+/// When the hardware queue is full, the packets it didn't take are kept and retried (every flush interval) before
+/// accepting new ones, applying backpressure to the senders rather than dropping packets.
+///
+/// Must be polled from within a tokio runtime (with its time driver enabled).
+pub struct TxSink<'mp> {
+    queue: TxQueue<'mp>,
+    buffer: NonNull<ffi::rte_eth_dev_tx_buffer>,
+    /// The packets the queue was full for. Boxed, as its address is given to the error callback of the buffer.
+    #[allow(clippy::box_collection)]
+    unsent: Box<Vec<NonNull<ffi::rte_mbuf>>>,
+    flush_interval: Duration,
+    flush_timer: Option<Pin<Box<Sleep>>>,
+    retry_timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl<'mp> TxQueue<'mp> {
+    /// Turns the queue into a [`Sink`] of packets, buffering up to `size` packets for at most `flush_interval`.
+    pub fn into_sink(self, size: u16, flush_interval: Duration) -> Result<TxSink<'mp>> {
+        let len =
+            mem::size_of::<ffi::rte_eth_dev_tx_buffer>() + usize::from(size) * mem::size_of::<*mut ffi::rte_mbuf>();
+        let socket_id = self.dev().socket_id().map_or(-1, |id| id.get() as i32);
+        // rte_zmalloc doesn't set rte_errno
+        let buffer =
+            NonNull::new(unsafe { ffi::rte_zmalloc_socket(ptr::null(), len, ffi::RTE_CACHE_LINE_SIZE, socket_id) })
+                .ok_or(Error(libc::ENOMEM))?
+                .cast::<ffi::rte_eth_dev_tx_buffer>();
+
+        // frees the buffer if initializing it fails
+        let mut sink = TxSink {
+            queue: self,
+            buffer,
+            unsent: Box::new(Vec::with_capacity(size.into())),
+            flush_interval,
+            flush_timer: None,
+            retry_timer: None,
+        };
+        unsafe {
+            ffi::rte_eth_tx_buffer_init(buffer.as_ptr(), size).rte_ok()?;
+            let unsent: *mut Vec<_> = &mut *sink.unsent;
+            ffi::rte_eth_tx_buffer_set_err_callback(buffer.as_ptr(), Some(keep_unsent), unsent.cast()).rte_ok()?;
+        }
+
+        Ok(sink)
+    }
+}
+
+impl<'mp> TxSink<'mp> {
+    #[inline]
+    pub fn queue(&self) -> &TxQueue<'mp> {
+        &self.queue
+    }
+
+    /// Returns the number of packets waiting to be sent, buffered or kept because the queue was full.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        usize::from(unsafe { self.buffer.as_ref() }.length) + self.unsent.len()
+    }
+
+    fn flush(&mut self) {
+        unsafe {
+            ffi::_rte_eth_tx_buffer_flush(self.queue.dev().port_id(), self.queue.queue_id(), self.buffer.as_ptr())
+        };
+        self.flush_timer = None;
+    }
+
+    /// Retries sending the packets the queue was full for, until it took all of them (which must happen before sending
+    /// any other packet, to keep them in order).
+    fn poll_unsent(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if self.unsent.is_empty() {
+                self.retry_timer = None;
+                return Poll::Ready(());
+            }
+
+            let (port_id, queue_id) = (self.queue.dev().port_id(), self.queue.queue_id());
+            let sent = unsafe {
+                ffi::_rte_eth_tx_burst(port_id, queue_id, self.unsent.as_mut_ptr().cast(), self.unsent.len() as u16)
+            };
+            self.unsent.drain(..usize::from(sent));
+            if self.unsent.is_empty() {
+                continue;
+            }
+
+            // there's no notification of the queue having room again
+            let timer = self.retry_timer.get_or_insert_with(|| Box::pin(tokio::time::sleep(self.flush_interval)));
+            ready!(timer.as_mut().poll(cx));
+            self.retry_timer = None;
+        }
+    }
+}
+
+impl<'mp> Sink<MBuf<&'mp MemoryPool>> for TxSink<'mp> {
+    type Error = Error;
+
+    fn poll_ready(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        ready!(this.poll_unsent(cx));
+        if let Some(timer) = &mut this.flush_timer {
+            if timer.as_mut().poll(cx).is_ready() {
+                this.flush();
+                ready!(this.poll_unsent(cx));
+            }
+        }
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, pkt: MBuf<&'mp MemoryPool>) -> Result<()> {
+        let this = self.get_mut();
+
+        let (port_id, queue_id) = (this.queue.dev().port_id(), this.queue.queue_id());
+        // sends the buffered packets if it fills the buffer
+        unsafe { ffi::_rte_eth_tx_buffer(port_id, queue_id, this.buffer.as_ptr(), pkt.into_raw().as_ptr()) };
+
+        if unsafe { this.buffer.as_ref() }.length == 0 {
+            this.flush_timer = None;
+        } else if this.flush_timer.is_none() {
+            this.flush_timer = Some(Box::pin(tokio::time::sleep(this.flush_interval)));
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        // keeps the packets in order
+        ready!(this.poll_unsent(cx));
+        this.flush();
+        this.poll_unsent(cx).map(Ok)
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        self.poll_flush(cx)
+    }
+}
+
+impl Drop for TxSink<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            let buffer = self.buffer.as_ptr();
+            let buffered = slice::from_raw_parts((*buffer).pkts.as_ptr(), (*buffer).length.into());
+            for &pkt in buffered {
+                drop(MBuf::<&MemoryPool>::from_raw(NonNull::new_unchecked(pkt)));
+            }
+            for &pkt in self.unsent.iter() {
+                drop(MBuf::<&MemoryPool>::from_raw(pkt));
+            }
+            ffi::rte_free(buffer.cast());
+        }
+    }
+}
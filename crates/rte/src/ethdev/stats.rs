@@ -0,0 +1,377 @@
+//! Point-in-time snapshots of a port's [stats](EthDev::stats) and [xstats](EthDev::get_xstats), and the deltas and
+//! rates between two of them, e.g. when polling a port once per second:
+//! ```rust,ignore
+//! let mut prev = Snapshot::capture(&dev)?;
+//! loop {
+//!     thread::sleep(Duration::from_secs(1));
+//!     let snapshot = Snapshot::capture(&dev)?;
+//!     let rates = snapshot.delta(&prev).rates();
+//!     println!("rx {:.0} pps, {:.0} drops/s", rates.rx_packets, rates.rx_drops);
+//!     prev = snapshot;
+//! }
+//! ```
+//!
+//! Or sampled into a history by a [`Poller`] (e.g. on a dedicated thread), which is queried by dashboards or anomaly
+//! detection:
+//! ```rust,ignore
+//! let poller = Poller::new(dev, 60).interval(Duration::from_secs(1)).xstats(["rx_good_packets"])?;
+//! let history = poller.history();
+//! thread::spawn(move || poller.run(&STOP));
+//! // ...
+//! let rates = history.delta(Duration::from_secs(10)).map(|delta| delta.rates());
+//! ```
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use rte_error::Error;
+
+use super::{DeviceStats, EthDev, XStatsDefs};
+use crate::Result;
+
+/// The default interval between the samples of a [`Poller`].
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The widths of hardware counters, some NICs having narrower counters than the 64 bits of the stats.
+const COUNTER_WIDTHS: [u32; 4] = [32, 40, 48, 64];
+/// A counter which decreased is only assumed to have wrapped around if it was in the top 1/16th of its width.
+const WRAP_MARGIN_SHIFT: u32 = 4;
+
+/// Returns the increase of a counter from `prev` to `cur`. A counter which decreased is assumed to have wrapped around
+/// if `prev` was close to the maximum of the narrowest width (out of 32, 40, 48 and 64 bits) holding it, and to have
+/// been reset (e.g. by `rte_eth_stats_reset` or a restart of the port) otherwise.
+pub(super) fn counter_delta(prev: u64, cur: u64) -> u64 {
+    if cur >= prev {
+        return cur - prev;
+    }
+
+    let (prev, cur) = (u128::from(prev), u128::from(cur));
+    match COUNTER_WIDTHS.into_iter().map(|width| 1u128 << width).find(|&max| prev < max) {
+        Some(max) if prev >= max - (max >> WRAP_MARGIN_SHIFT) => (max - prev + cur) as u64,
+        _ => cur as u64,
+    }
+}
+
+/// The stats (and optionally xstats) of a port at a point in time.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub time: Instant,
+    pub stats: DeviceStats,
+    /// The xstats by name, empty unless captured with [`Snapshot::capture_with_xstats`].
+    pub xstats: HashMap<String, u64>,
+}
+
+impl Snapshot {
+    /// Captures the current stats of `dev`.
+    #[inline]
+    pub fn capture(dev: &EthDev) -> Result<Self> {
+        Ok(Self { time: Instant::now(), stats: dev.stats()?, xstats: HashMap::new() })
+    }
+
+    /// Captures the current stats and xstats of `dev`.
+    #[inline]
+    pub fn capture_with_xstats(dev: &EthDev, defs: &XStatsDefs) -> Result<Self> {
+        let xstats = dev.get_xstats(defs)?.into_iter().map(|(name, value)| (name.to_owned(), value)).collect();
+        Ok(Self { time: Instant::now(), stats: dev.stats()?, xstats })
+    }
+
+    /// Returns the increase of the counters since the `prev` snapshot (of the same port), handling counters which
+    /// wrapped around.
+    pub fn delta(&self, prev: &Snapshot) -> Delta {
+        let (cur, old) = (&self.stats, &prev.stats);
+        let xstats = self
+            .xstats
+            .iter()
+            .filter_map(|(name, &value)| Some((name.clone(), counter_delta(*prev.xstats.get(name)?, value))))
+            .collect();
+
+        Delta {
+            interval: self.time.saturating_duration_since(prev.time),
+            ipackets: counter_delta(old.ipackets, cur.ipackets),
+            opackets: counter_delta(old.opackets, cur.opackets),
+            ibytes: counter_delta(old.ibytes, cur.ibytes),
+            obytes: counter_delta(old.obytes, cur.obytes),
+            imissed: counter_delta(old.imissed, cur.imissed),
+            ierrors: counter_delta(old.ierrors, cur.ierrors),
+            oerrors: counter_delta(old.oerrors, cur.oerrors),
+            rx_nombuf: counter_delta(old.rx_nombuf, cur.rx_nombuf),
+            xstats,
+        }
+    }
+}
+
+/// The increase of a port's counters between two [`Snapshot`]s, the fields being named after the ones of
+/// [`DeviceStats`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Delta {
+    pub interval: Duration,
+    pub ipackets: u64,
+    pub opackets: u64,
+    pub ibytes: u64,
+    pub obytes: u64,
+    pub imissed: u64,
+    pub ierrors: u64,
+    pub oerrors: u64,
+    pub rx_nombuf: u64,
+    /// The xstats present in both snapshots, by name.
+    pub xstats: HashMap<String, u64>,
+}
+
+impl Delta {
+    /// Returns the rate of `count` events over the interval, per second (0 for an empty interval).
+    #[inline]
+    pub fn per_sec(&self, count: u64) -> f64 {
+        match self.interval.as_secs_f64() {
+            secs if secs > 0.0 => count as f64 / secs,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the rates of the counters, per second.
+    pub fn rates(&self) -> Rates {
+        Rates {
+            rx_packets: self.per_sec(self.ipackets),
+            tx_packets: self.per_sec(self.opackets),
+            rx_bytes: self.per_sec(self.ibytes),
+            tx_bytes: self.per_sec(self.obytes),
+            rx_drops: self.per_sec(self.imissed + self.ierrors + self.rx_nombuf),
+            tx_drops: self.per_sec(self.oerrors),
+        }
+    }
+}
+
+/// The rates of a port's counters, per second.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rates {
+    pub rx_packets: f64,
+    pub tx_packets: f64,
+    pub rx_bytes: f64,
+    pub tx_bytes: f64,
+    /// Packets dropped by the hardware (for lack of RX descriptors), erroneous, or dropped for lack of mbufs.
+    pub rx_drops: f64,
+    /// Packets which failed to be transmitted.
+    pub tx_drops: f64,
+}
+
+/// Samples the stats (and selected xstats) of a port at a fixed interval into a [`History`].
+///
+/// The poller is driven either by calling [`Self::poll`] often enough, e.g. from a timer callback, a service function
+/// or the main loop of an lcore, or by [running](Self::run) it on a thread of its own.
+pub struct Poller {
+    dev: EthDev,
+    interval: Duration,
+    /// The definitions of the xstats of the port, and the names of the selected ones, if any are selected.
+    xstats: Option<(XStatsDefs, HashSet<String>)>,
+    next_sample: Option<Instant>,
+    history: History,
+}
+
+impl Poller {
+    /// Creates a poller of the port's stats, keeping the latest `capacity` samples (which must not be 0).
+    pub fn new(dev: EthDev, capacity: usize) -> Self {
+        assert!(capacity > 0, "the history of a poller must hold at least one sample");
+        Self { dev, interval: DEFAULT_POLL_INTERVAL, xstats: None, next_sample: None, history: History::new(capacity) }
+    }
+
+    /// Sets the interval between samples ([`DEFAULT_POLL_INTERVAL`] by default).
+    #[inline]
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Selects the xstats to sample (along with the stats) by name, failing with `ENOENT` if the port has no xstat of
+    /// one of the names.
+    pub fn xstats<I, S>(mut self, names: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let defs = self.dev.get_xstats_def()?;
+        let names = names.into_iter().map(S::into).collect::<HashSet<_>>();
+        if !names.iter().all(|name| defs.contains(name)) {
+            return Err(Error(libc::ENOENT));
+        }
+
+        self.xstats = (!names.is_empty()).then_some((defs, names));
+        Ok(self)
+    }
+
+    /// Returns a handle to the history of samples, which can be queried from other threads.
+    #[inline]
+    pub fn history(&self) -> History {
+        self.history.clone()
+    }
+
+    /// Samples the stats if the interval has elapsed since the last sample (or if none was taken yet), returning
+    /// whether it did.
+    pub fn poll(&mut self) -> Result<bool> {
+        if self.next_sample.is_some_and(|next_sample| Instant::now() < next_sample) {
+            return Ok(false);
+        }
+        self.sample()?;
+        Ok(true)
+    }
+
+    /// Samples the stats now, the next sample being due after the interval.
+    pub fn sample(&mut self) -> Result<()> {
+        let snapshot = match &self.xstats {
+            Some((defs, names)) => {
+                let mut snapshot = Snapshot::capture_with_xstats(&self.dev, defs)?;
+                snapshot.xstats.retain(|name, _| names.contains(name));
+                snapshot
+            }
+            None => Snapshot::capture(&self.dev)?,
+        };
+
+        self.next_sample = Some(snapshot.time + self.interval);
+        self.history.push(snapshot);
+        Ok(())
+    }
+
+    /// Samples the stats at each interval (sleeping in between) until `stop` is set, or sampling fails.
+    pub fn run(mut self, stop: &AtomicBool) -> Result<()> {
+        while !stop.load(Ordering::Relaxed) {
+            self.poll()?;
+            if let Some(next_sample) = self.next_sample {
+                thread::sleep(next_sample.saturating_duration_since(Instant::now()));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Poller {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Poller")
+            .field("port_id", &self.dev.port_id())
+            .field("interval", &self.interval)
+            .field("xstats", &self.xstats.as_ref().map(|(_, names)| names))
+            .finish_non_exhaustive()
+    }
+}
+
+/// The latest samples of a [`Poller`], oldest first, older samples being dropped once it's full.
+///
+/// It's a handle which can be cloned and shared across threads, the queries returning copies of the samples.
+#[derive(Debug, Clone)]
+pub struct History {
+    snapshots: Arc<Mutex<VecDeque<Snapshot>>>,
+    capacity: usize,
+}
+
+impl History {
+    fn new(capacity: usize) -> Self {
+        Self { snapshots: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))), capacity }
+    }
+
+    fn push(&self, snapshot: Snapshot) {
+        let mut snapshots = self.snapshots.lock().unwrap();
+        if snapshots.len() == self.capacity {
+            snapshots.pop_front();
+        }
+        snapshots.push_back(snapshot);
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.snapshots.lock().unwrap().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the latest sample.
+    #[inline]
+    pub fn latest(&self) -> Option<Snapshot> {
+        self.snapshots.lock().unwrap().back().cloned()
+    }
+
+    /// Returns all the samples, oldest first.
+    #[inline]
+    pub fn snapshots(&self) -> Vec<Snapshot> {
+        self.snapshots.lock().unwrap().iter().cloned().collect()
+    }
+
+    /// Returns the increase of the counters over (at least) the `window` preceding the latest sample, i.e. since the
+    /// latest sample taken at least `window` before it, or `None` if the history doesn't cover the window.
+    pub fn delta(&self, window: Duration) -> Option<Delta> {
+        let snapshots = self.snapshots.lock().unwrap();
+        let latest = snapshots.back()?;
+        let prev =
+            snapshots.iter().rev().find(|snapshot| latest.time.saturating_duration_since(snapshot.time) >= window)?;
+        Some(latest.delta(prev))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_counter_delta() {
+        assert_eq!(counter_delta(10, 15), 5);
+        assert_eq!(counter_delta(u32::MAX as u64 - 1, 3), 5);
+        assert_eq!(counter_delta((1 << 48) - 1, 0), 1);
+        assert_eq!(counter_delta(u64::MAX, 4), 5);
+        // reset rather than wrapped around
+        assert_eq!(counter_delta(1000, 5), 5);
+        assert_eq!(counter_delta(1 << 31, 7), 7);
+        assert_eq!(counter_delta((1 << 40) + 1, 7), 7);
+    }
+
+    #[test]
+    fn test_delta_rates() {
+        let time = Instant::now();
+        let prev = Snapshot {
+            time,
+            stats: DeviceStats { ipackets: 100, ibytes: 6400, imissed: 1, ..Default::default() },
+            xstats: [("rx_good_packets".to_owned(), 100), ("tx_good_packets".to_owned(), 0)].into(),
+        };
+        let cur = Snapshot {
+            time: time + Duration::from_secs(2),
+            stats: DeviceStats { ipackets: 300, ibytes: 19200, imissed: 3, rx_nombuf: 2, ..Default::default() },
+            xstats: [("rx_good_packets".to_owned(), 300)].into(),
+        };
+
+        let delta = cur.delta(&prev);
+        assert_eq!(delta.ipackets, 200);
+        assert_eq!(delta.xstats, [("rx_good_packets".to_owned(), 200)].into());
+        assert_eq!(delta.rates(), Rates { rx_packets: 100.0, rx_bytes: 6400.0, rx_drops: 2.0, ..Default::default() });
+    }
+
+    #[test]
+    fn test_history() {
+        let time = Instant::now();
+        let history = History::new(3);
+        assert!(history.latest().is_none());
+
+        for (secs, ipackets) in [(0, 0), (1, 10), (2, 30), (3, 60)] {
+            let stats = DeviceStats { ipackets, ..Default::default() };
+            history.push(Snapshot { time: time + Duration::from_secs(secs), stats, xstats: HashMap::new() });
+        }
+
+        // the first sample was dropped
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.latest().unwrap().stats.ipackets, 60);
+        assert_eq!(history.delta(Duration::ZERO).unwrap().ipackets, 0);
+        assert_eq!(history.delta(Duration::from_millis(1500)).unwrap().ipackets, 50);
+        assert_eq!(history.delta(Duration::from_secs(2)).unwrap().ipackets, 50);
+        assert!(history.delta(Duration::from_secs(3)).is_none());
+    }
+}
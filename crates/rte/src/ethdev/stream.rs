@@ -0,0 +1,121 @@
+//! [`Stream`]s of received packets on a tokio runtime, for low-rate ports (e.g. control-plane ones) to be handled by
+//! the same runtime as the rest of the application (e.g. its gRPC or REST servers), rather than by dedicated lcores.
+
+use std::{
+    future::Future,
+    io,
+    os::unix::io::RawFd,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use rte_error::Error;
+use tokio::{io::unix::AsyncFd, time::Sleep};
+
+use super::RxQueue;
+use crate::{mbuf::PacketBatch, mempool::MemoryPool, Result};
+
+/// How an [`RxStream`] waits for packets when its queue is empty.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxMode {
+    /// Waits for the RX interrupt of the queue, which requires the device to be configured with `intr_conf.rxq` set
+    /// (and its driver to support it).
+    Interrupt,
+    /// Polls the queue again after `interval`, bounding the CPU time spent on an idle queue.
+    Polling { interval: Duration },
+}
+
+enum Wait {
+    Interrupt { fd: AsyncFd<RawFd>, armed: bool },
+    Polling { interval: Duration, sleep: Option<Pin<Box<Sleep>>> },
+}
+
+fn io_error(err: io::Error) -> Error {
+    Error(err.raw_os_error().unwrap_or(libc::EIO))
+}
+
+/// A [`Stream`] of the bursts (of up to `BURST` packets) received by an RX queue, see [`RxQueue::into_stream`].
+///
+/// Must be polled from within a tokio runtime (with its IO and time drivers enabled).
+pub struct RxStream<'mp, const BURST: usize> {
+    queue: RxQueue<'mp>,
+    wait: Wait,
+}
+
+impl<'mp> RxQueue<'mp> {
+    /// Turns the queue into a [`Stream`] of the bursts it receives, waiting for packets according to `mode`.
+    pub fn into_stream<const BURST: usize>(self, mode: RxMode) -> Result<RxStream<'mp, BURST>> {
+        let wait = match mode {
+            RxMode::Interrupt => {
+                let fd = AsyncFd::new(self.intr_fd()?).map_err(io_error)?;
+                Wait::Interrupt { fd, armed: false }
+            }
+            RxMode::Polling { interval } => Wait::Polling { interval, sleep: None },
+        };
+
+        Ok(RxStream { queue: self, wait })
+    }
+}
+
+impl<'mp, const BURST: usize> RxStream<'mp, BURST> {
+    #[inline]
+    pub fn queue(&self) -> &RxQueue<'mp> {
+        &self.queue
+    }
+
+    fn recv(&mut self) -> PacketBatch<&'mp MemoryPool, BURST> {
+        let mut pkts = PacketBatch::new();
+        self.queue.recv(&mut pkts);
+        pkts
+    }
+}
+
+impl<'mp, const BURST: usize> Stream for RxStream<'mp, BURST> {
+    type Item = Result<PacketBatch<&'mp MemoryPool, BURST>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let pkts = this.recv();
+            if !pkts.is_empty() {
+                return Poll::Ready(Some(Ok(pkts)));
+            }
+
+            match &mut this.wait {
+                Wait::Polling { interval, sleep } => {
+                    let timer = sleep.get_or_insert_with(|| Box::pin(tokio::time::sleep(*interval)));
+                    ready!(timer.as_mut().poll(cx));
+                    *sleep = None;
+                }
+                Wait::Interrupt { fd, armed } => {
+                    if !*armed {
+                        this.queue.intr_enable()?;
+                        *armed = true;
+                        // packets received before the interrupt was enabled don't trigger it
+                        continue;
+                    }
+
+                    let mut guard = ready!(fd.poll_read_ready(cx)).map_err(io_error)?;
+                    // resets the event fd's counter
+                    let mut events = 0u64;
+                    unsafe { libc::read(*guard.get_inner(), (&mut events as *mut u64).cast(), 8) };
+                    guard.clear_ready();
+
+                    this.queue.intr_disable()?;
+                    *armed = false;
+                }
+            }
+        }
+    }
+}
+
+impl<'mp, const BURST: usize> Drop for RxStream<'mp, BURST> {
+    fn drop(&mut self) {
+        if let Wait::Interrupt { armed: true, .. } = self.wait {
+            let _ = self.queue.intr_disable();
+        }
+    }
+}
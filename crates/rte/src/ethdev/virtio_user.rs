@@ -0,0 +1,126 @@
+//! Based on DPDK's `rte_dev.h` API (hotplugging the virtio-user PMD): <https://doc.dpdk.org/api-22.11/rte__dev_8h.html>
+
+use std::ffi::CString;
+
+use mac_addr::MacAddr;
+use rte_error::ReturnValue as _;
+
+use super::{Conf, EthDev};
+use crate::{mbuf::PacketBatch, mempool::MemoryPool, Result};
+
+/// Configuration used for creating a [`VirtioUser`] device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VirtioUserConfig {
+    /// Name of the DPDK device, which must start with `virtio_user` (e.g. `virtio_user0`).
+    pub name: String,
+    /// Name of the kernel's TAP interface, created by the vhost-net backend.
+    pub iface: String,
+    /// MAC address of the DPDK side of the device, a random address is used if `None`.
+    pub mac: Option<MacAddr>,
+    /// Number of RX and TX queues of the device (i.e. of queue pairs of the TAP interface).
+    pub queues: u16,
+    pub queue_size: u16,
+}
+
+/// An exception path to the kernel's network stack: a virtio-user device backed by vhost-net, which appears to the
+/// kernel as a TAP interface.
+///
+/// Packets sent on the device are received by the kernel on its interface (and vice versa), which allows
+/// control-plane protocols (e.g. ARP, BGP or SSH) to be handled by the kernel while the datapath stays in DPDK.
+///
+/// The device has as many RX and TX queues as [configured](VirtioUserConfig::queues), and is removed when dropped.
+///
+/// Note that KNI, the older alternative to virtio-user, is deprecated as of DPDK 22.11 and is therefore not supported.
+///
+/// See also: <https://doc.dpdk.org/guides-22.11/howto/virtio_user_as_exception_path.html>
+pub struct VirtioUser {
+    dev: EthDev,
+    name: CString,
+    queues: u16,
+}
+
+impl VirtioUser {
+    const BUS: &'static [u8] = b"vdev\0";
+
+    /// Creates the device (requires access to `/dev/vhost-net`), which should then be [set up](Self::setup).
+    #[inline]
+    pub fn new(config: &VirtioUserConfig) -> Result<Self> {
+        let name = CString::new(config.name.as_str()).unwrap();
+        let mut args = format!(
+            "path=/dev/vhost-net,queues={},queue_size={},iface={}",
+            config.queues, config.queue_size, config.iface
+        );
+        if let Some(mac) = config.mac {
+            args += &format!(",mac={mac}");
+        }
+        let args = CString::new(args).unwrap();
+
+        unsafe { ffi::rte_eal_hotplug_add(Self::BUS.as_ptr().cast(), name.as_ptr(), args.as_ptr()) }.rte_ok()?;
+
+        let mut port_id = 0;
+        if let Err(err) = unsafe { ffi::rte_eth_dev_get_port_by_name(name.as_ptr(), &mut port_id) }.rte_ok() {
+            unsafe { ffi::rte_eal_hotplug_remove(Self::BUS.as_ptr().cast(), name.as_ptr()) };
+            return Err(err);
+        }
+
+        Ok(Self { dev: EthDev::new(port_id), name, queues: config.queues })
+    }
+
+    /// Configures the device's queues and starts it, received packets are allocated from `mempool`.
+    #[inline]
+    pub fn setup(&self, nb_desc: u16, mempool: &mut MemoryPool) -> Result<()> {
+        self.dev.configure(self.queues, self.queues, &Conf::default())?;
+        for queue_id in 0..self.queues {
+            self.dev.rx_queue_setup(queue_id, nb_desc, None, mempool)?;
+            self.dev.tx_queue_setup(queue_id, nb_desc, None)?;
+        }
+        self.dev.start()
+    }
+
+    #[inline]
+    pub fn dev(&self) -> &EthDev {
+        &self.dev
+    }
+
+    #[inline]
+    pub fn queues(&self) -> u16 {
+        self.queues
+    }
+
+    /// Sends packets to the kernel on TX queue `queue_id`, see [`EthDev::tx_burst`].
+    ///
+    /// # Safety
+    /// See [`EthDev::tx_burst`].
+    #[inline]
+    pub unsafe fn send<'mempool, const CAP: usize>(
+        &self,
+        queue_id: u16,
+        mempool: &'mempool MemoryPool,
+        pkts: &mut PacketBatch<&'mempool MemoryPool, CAP>,
+    ) {
+        self.dev.tx_burst(queue_id, mempool, pkts)
+    }
+
+    /// Receives packets sent by the kernel on RX queue `queue_id`, see [`EthDev::rx_burst`].
+    ///
+    /// # Safety
+    /// `mempool` must match the memory pool used in the call to [`Self::setup`].
+    #[inline]
+    pub unsafe fn recv<'mempool, const CAP: usize>(
+        &self,
+        queue_id: u16,
+        mempool: &'mempool MemoryPool,
+        pkts: &mut PacketBatch<&'mempool MemoryPool, CAP>,
+    ) {
+        self.dev.rx_burst(queue_id, mempool, pkts)
+    }
+}
+
+impl Drop for VirtioUser {
+    fn drop(&mut self) {
+        // the device may have never been started
+        let _ = self.dev.stop();
+        let _ = self.dev.close();
+        unsafe { ffi::rte_eal_hotplug_remove(Self::BUS.as_ptr().cast(), self.name.as_ptr()) };
+    }
+}
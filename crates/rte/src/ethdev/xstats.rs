@@ -14,6 +14,14 @@ use crate::Result;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct XStatsDefs(Vec<String>);
 
+impl XStatsDefs {
+    /// Returns whether the device has an xstat of the given name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.iter().any(|def| def == name)
+    }
+}
+
 impl EthDev {
     fn get_xstats_count(&self) -> Result<u32> {
         let count = unsafe { ffi::rte_eth_xstats_get_names_by_id(self.port_id, null_mut(), 0, null_mut()) }.rte_ok()?;
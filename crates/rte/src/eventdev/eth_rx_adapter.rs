@@ -0,0 +1,94 @@
+//! Based on DPDK's `rte_event_eth_rx_adapter.h` API:
+//! <https://doc.dpdk.org/api-22.11/rte__event__eth__rx__adapter_8h.html>
+
+use rte_error::ReturnValue as _;
+
+use super::{Event, EventDev, PortConf};
+use crate::{ethdev::EthDev, Result};
+
+/// Injects the packets received on ethdev RX queues into an [`EventDev`], as [`EventType::EthDev`] events carrying
+/// the received mbufs.
+///
+/// Unless the ethdev and eventdev support an internal port, the adapter is run by a service (see
+/// [`Self::service_id`]), which must be mapped to a service lcore. The adapter is stopped and freed when dropped.
+///
+/// [`EventType::EthDev`]: super::EventType::EthDev
+#[derive(Debug)]
+pub struct EthRxAdapter {
+    id: u8,
+}
+
+impl EthRxAdapter {
+    /// Creates an adapter for `dev`, which sets up an event port (using `port_conf`) for enqueuing events, unless
+    /// an internal port is used.
+    #[inline]
+    pub fn new(id: u8, dev: &EventDev, port_conf: &PortConf) -> Result<Self> {
+        let mut port_conf = *port_conf;
+        unsafe { ffi::rte_event_eth_rx_adapter_create(id, dev.dev_id, &mut port_conf) }.rte_ok()?;
+        Ok(Self { id })
+    }
+
+    #[inline]
+    pub fn id(&self) -> u8 {
+        self.id
+    }
+
+    /// Adds an RX queue of `eth_dev` to the adapter, or all of its RX queues if `rx_queue_id` is `None`.
+    ///
+    /// Events are created using the queue id, scheduling type, sub-type and priority of `event`, and the mbuf's RSS
+    /// hash as their flow id. The `servicing_weight` determines how often the queue is polled relative to others (0
+    /// enables interrupt mode, if supported).
+    #[inline]
+    pub fn queue_add(
+        &self,
+        eth_dev: &EthDev,
+        rx_queue_id: Option<u16>,
+        event: Event,
+        servicing_weight: u32,
+    ) -> Result<()> {
+        let conf = ffi::rte_event_eth_rx_adapter_queue_conf { ev: event.0, servicing_weight, ..Default::default() };
+        let rx_queue_id = rx_queue_id.map_or(-1, i32::from);
+
+        unsafe { ffi::rte_event_eth_rx_adapter_queue_add(self.id, eth_dev.port_id(), rx_queue_id, &conf) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Removes an RX queue of `eth_dev` from the adapter, or all of its RX queues if `rx_queue_id` is `None`.
+    #[inline]
+    pub fn queue_del(&self, eth_dev: &EthDev, rx_queue_id: Option<u16>) -> Result<()> {
+        let rx_queue_id = rx_queue_id.map_or(-1, i32::from);
+        unsafe { ffi::rte_event_eth_rx_adapter_queue_del(self.id, eth_dev.port_id(), rx_queue_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the id of the service running the adapter, or `None` if it uses an internal port.
+    #[inline]
+    pub fn service_id(&self) -> Option<u32> {
+        let mut service_id = 0;
+        match unsafe { ffi::rte_event_eth_rx_adapter_service_id_get(self.id, &mut service_id) } {
+            0 => Some(service_id),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub fn start(&self) -> Result<()> {
+        unsafe { ffi::rte_event_eth_rx_adapter_start(self.id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stop(&self) -> Result<()> {
+        unsafe { ffi::rte_event_eth_rx_adapter_stop(self.id) }.rte_ok()?;
+        Ok(())
+    }
+}
+
+impl Drop for EthRxAdapter {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rte_event_eth_rx_adapter_stop(self.id);
+            ffi::rte_event_eth_rx_adapter_free(self.id);
+        }
+    }
+}
@@ -0,0 +1,426 @@
+//! Based on DPDK's `rte_eventdev.h` API: <https://doc.dpdk.org/api-22.11/rte__eventdev_8h.html>
+//!
+//! An [`EventDev`] schedules [`Event`]s (e.g. received packets) enqueued to its queues between the lcores dequeuing
+//! from its ports, according to each queue's [scheduling type](SchedType), which allows building pipelines where each
+//! stage runs on any number of lcores. Packets are injected into the pipeline directly from ethdev RX queues using an
+//! [`EthRxAdapter`].
+
+mod eth_rx_adapter;
+
+use std::{ffi::CStr, fmt, marker::PhantomData, ptr::NonNull};
+
+use arrayvec::ArrayVec;
+use rte_error::ReturnValue as _;
+
+pub use self::eth_rx_adapter::EthRxAdapter;
+use crate::{mbuf::MBuf, mempool::MemoryPool, Result};
+
+pub type Config = ffi::rte_event_dev_config;
+pub type Info = ffi::rte_event_dev_info;
+pub type QueueConf = ffi::rte_event_queue_conf;
+pub type PortConf = ffi::rte_event_port_conf;
+
+/// The scheduling type of events, which determines how events of the same flow are distributed between ports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchedType {
+    /// Events of a flow may be processed concurrently, but are restored to their original order when enqueued to the
+    /// next stage.
+    Ordered,
+    /// Events of a flow are only processed by a single port at a time.
+    Atomic,
+    /// Events are processed concurrently, without any ordering guarantees.
+    Parallel,
+}
+
+impl SchedType {
+    fn as_raw(self) -> u8 {
+        (match self {
+            Self::Ordered => ffi::RTE_SCHED_TYPE_ORDERED,
+            Self::Atomic => ffi::RTE_SCHED_TYPE_ATOMIC,
+            Self::Parallel => ffi::RTE_SCHED_TYPE_PARALLEL,
+        }) as u8
+    }
+
+    fn from_raw(sched_type: u8) -> Self {
+        match u32::from(sched_type) {
+            ffi::RTE_SCHED_TYPE_ORDERED => Self::Ordered,
+            ffi::RTE_SCHED_TYPE_ATOMIC => Self::Atomic,
+            _ => Self::Parallel,
+        }
+    }
+}
+
+/// The operation performed when enqueuing an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    /// Injects a new event into the device.
+    New,
+    /// Forwards a previously dequeued event to another queue (i.e. the next pipeline stage).
+    Forward,
+    /// Releases the flow context of a previously dequeued event (e.g. when it was dropped), without enqueuing it.
+    Release,
+}
+
+impl Op {
+    fn as_raw(self) -> u8 {
+        (match self {
+            Self::New => ffi::RTE_EVENT_OP_NEW,
+            Self::Forward => ffi::RTE_EVENT_OP_FORWARD,
+            Self::Release => ffi::RTE_EVENT_OP_RELEASE,
+        }) as u8
+    }
+
+    fn from_raw(op: u8) -> Self {
+        match u32::from(op) {
+            ffi::RTE_EVENT_OP_NEW => Self::New,
+            ffi::RTE_EVENT_OP_FORWARD => Self::Forward,
+            _ => Self::Release,
+        }
+    }
+}
+
+/// The origin of an event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    /// A packet received by an [`EthRxAdapter`], whose payload is an mbuf.
+    EthDev,
+    /// An event created by an lcore.
+    Cpu,
+    /// Any other event type (e.g. from a timer or crypto adapter).
+    Other(u8),
+}
+
+impl EventType {
+    fn as_raw(self) -> u8 {
+        match self {
+            Self::EthDev => ffi::RTE_EVENT_TYPE_ETHDEV as u8,
+            Self::Cpu => ffi::RTE_EVENT_TYPE_CPU as u8,
+            Self::Other(event_type) => event_type,
+        }
+    }
+
+    fn from_raw(event_type: u8) -> Self {
+        match u32::from(event_type) {
+            ffi::RTE_EVENT_TYPE_ETHDEV => Self::EthDev,
+            ffi::RTE_EVENT_TYPE_CPU => Self::Cpu,
+            _ => Self::Other(event_type),
+        }
+    }
+}
+
+/// A typed wrapper around an [`rte_event`](ffi::rte_event): the event's scheduling metadata, and a 64 bit payload
+/// which is either an integer or an mbuf pointer.
+///
+/// Events are plain data, so an event carrying an mbuf does not free it when dropped: ownership of the mbuf must be
+/// either transferred to the device by enqueuing the event, or taken back using [`Self::take_mbuf`].
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct Event(ffi::rte_event);
+
+impl Event {
+    /// Creates a new ([`Op::New`]) event with a zero payload.
+    #[inline]
+    pub fn new(queue_id: u8, sched_type: SchedType, flow_id: u32, event_type: EventType) -> Self {
+        let mut event = Self(Default::default());
+        event.set_queue_id(queue_id);
+        event.set_sched_type(sched_type);
+        event.set_flow_id(flow_id);
+        event.set_event_type(event_type);
+        event.set_op(Op::New);
+        event
+    }
+
+    /// Creates a new ([`Op::New`], [`EventType::Cpu`]) event carrying `mbuf`, whose ownership is transferred to the
+    /// event.
+    #[inline]
+    pub fn from_mbuf(mbuf: MBuf<&MemoryPool>, queue_id: u8, sched_type: SchedType, flow_id: u32) -> Self {
+        let mut event = Self::new(queue_id, sched_type, flow_id, EventType::Cpu);
+        event.0.__bindgen_anon_2.mbuf = mbuf.into_raw().as_ptr();
+        event
+    }
+
+    #[inline]
+    pub fn queue_id(&self) -> u8 {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.queue_id }
+    }
+
+    /// Sets the queue the event is enqueued to, e.g. when forwarding it to the next stage.
+    #[inline]
+    pub fn set_queue_id(&mut self, queue_id: u8) {
+        self.0.__bindgen_anon_1.__bindgen_anon_1.queue_id = queue_id;
+    }
+
+    #[inline]
+    pub fn priority(&self) -> u8 {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.priority }
+    }
+
+    /// Sets the event's priority (lower values are scheduled first), if supported by the device.
+    #[inline]
+    pub fn set_priority(&mut self, priority: u8) {
+        self.0.__bindgen_anon_1.__bindgen_anon_1.priority = priority;
+    }
+
+    #[inline]
+    pub fn sched_type(&self) -> SchedType {
+        SchedType::from_raw(unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.sched_type() })
+    }
+
+    #[inline]
+    pub fn set_sched_type(&mut self, sched_type: SchedType) {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.set_sched_type(sched_type.as_raw()) };
+    }
+
+    /// The event's flow (20 bits), which determines its ordering and atomicity.
+    #[inline]
+    pub fn flow_id(&self) -> u32 {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.flow_id() }
+    }
+
+    /// Sets the event's flow, truncated to 20 bits.
+    #[inline]
+    pub fn set_flow_id(&mut self, flow_id: u32) {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.set_flow_id(flow_id & 0xfffff) };
+    }
+
+    #[inline]
+    pub fn op(&self) -> Op {
+        Op::from_raw(unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.op() })
+    }
+
+    #[inline]
+    pub fn set_op(&mut self, op: Op) {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.set_op(op.as_raw()) };
+    }
+
+    #[inline]
+    pub fn event_type(&self) -> EventType {
+        EventType::from_raw(unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.event_type() } as u8)
+    }
+
+    #[inline]
+    pub fn set_event_type(&mut self, event_type: EventType) {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.set_event_type(event_type.as_raw().into()) };
+    }
+
+    /// An application defined sub-type (8 bits), e.g. the pipeline stage that produced the event.
+    #[inline]
+    pub fn sub_event_type(&self) -> u8 {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.sub_event_type() as u8 }
+    }
+
+    #[inline]
+    pub fn set_sub_event_type(&mut self, sub_event_type: u8) {
+        unsafe { self.0.__bindgen_anon_1.__bindgen_anon_1.set_sub_event_type(sub_event_type.into()) };
+    }
+
+    #[inline]
+    pub fn u64(&self) -> u64 {
+        unsafe { self.0.__bindgen_anon_2.u64_ }
+    }
+
+    #[inline]
+    pub fn set_u64(&mut self, value: u64) {
+        self.0.__bindgen_anon_2.u64_ = value;
+    }
+
+    /// Takes ownership of the mbuf carried by this event.
+    ///
+    /// # Safety
+    /// The event must carry an mbuf allocated from `mempool` (e.g. an [`EventType::EthDev`] event dequeued from a
+    /// port), and its ownership must not be taken more than once.
+    #[inline]
+    pub unsafe fn take_mbuf<'mempool>(&self, _mempool: &'mempool MemoryPool) -> MBuf<&'mempool MemoryPool> {
+        MBuf::from_raw(NonNull::new(self.0.__bindgen_anon_2.mbuf).expect("event does not carry an mbuf"))
+    }
+}
+
+impl fmt::Debug for Event {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Event")
+            .field("queue_id", &self.queue_id())
+            .field("sched_type", &self.sched_type())
+            .field("flow_id", &self.flow_id())
+            .field("op", &self.op())
+            .field("event_type", &self.event_type())
+            .field("sub_event_type", &self.sub_event_type())
+            .field("priority", &self.priority())
+            .field("u64", &self.u64())
+            .finish()
+    }
+}
+
+/// An event device, identified by its device id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventDev {
+    dev_id: u8,
+}
+
+impl EventDev {
+    #[inline]
+    pub fn new(dev_id: u8) -> Self {
+        Self { dev_id }
+    }
+
+    /// Returns the device with the given name, e.g. `event_sw0`.
+    #[inline]
+    pub fn from_name(name: &CStr) -> Result<Self> {
+        let dev_id = unsafe { ffi::rte_event_dev_get_dev_id(name.as_ptr()) }.rte_ok()?;
+        Ok(Self::new(dev_id as u8))
+    }
+
+    /// Returns the number of event devices.
+    #[inline]
+    pub fn count() -> u8 {
+        unsafe { ffi::rte_event_dev_count() }
+    }
+
+    #[inline]
+    pub fn dev_id(&self) -> u8 {
+        self.dev_id
+    }
+
+    #[inline]
+    pub fn info(&self) -> Result<Info> {
+        let mut info = Info::default();
+        unsafe { ffi::rte_event_dev_info_get(self.dev_id, &mut info) }.rte_ok()?;
+        Ok(info)
+    }
+
+    /// Configures the device, which must be done (while it is stopped) before setting up its queues and ports.
+    #[inline]
+    pub fn configure(&self, config: &Config) -> Result<()> {
+        unsafe { ffi::rte_event_dev_configure(self.dev_id, config) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn queue_default_conf(&self, queue_id: u8) -> Result<QueueConf> {
+        let mut conf = QueueConf::default();
+        unsafe { ffi::rte_event_queue_default_conf_get(self.dev_id, queue_id, &mut conf) }.rte_ok()?;
+        Ok(conf)
+    }
+
+    /// Sets up a queue, using the device's default configuration if `conf` is `None`.
+    #[inline]
+    pub fn queue_setup(&self, queue_id: u8, conf: Option<&QueueConf>) -> Result<()> {
+        let conf = conf.map_or(std::ptr::null(), |conf| conf as *const _);
+        unsafe { ffi::rte_event_queue_setup(self.dev_id, queue_id, conf) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn port_default_conf(&self, port_id: u8) -> Result<PortConf> {
+        let mut conf = PortConf::default();
+        unsafe { ffi::rte_event_port_default_conf_get(self.dev_id, port_id, &mut conf) }.rte_ok()?;
+        Ok(conf)
+    }
+
+    /// Sets up a port, using the device's default configuration if `conf` is `None`.
+    #[inline]
+    pub fn port_setup(&self, port_id: u8, conf: Option<&PortConf>) -> Result<()> {
+        let conf = conf.map_or(std::ptr::null(), |conf| conf as *const _);
+        unsafe { ffi::rte_event_port_setup(self.dev_id, port_id, conf) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Links a port to the given queues (and their service priorities), so that events enqueued to those queues can be
+    /// dequeued from it. Links all queues (with normal priority) if `queues` is empty.
+    #[inline]
+    pub fn port_link(&self, port_id: u8, queues: &[(u8, u8)]) -> Result<()> {
+        let (queue_ids, priorities): (Vec<_>, Vec<_>) = queues.iter().copied().unzip();
+        let (queue_ids, priorities) = if queues.is_empty() {
+            (std::ptr::null(), std::ptr::null())
+        } else {
+            (queue_ids.as_ptr(), priorities.as_ptr())
+        };
+
+        let linked =
+            unsafe { ffi::rte_event_port_link(self.dev_id, port_id, queue_ids, priorities, queues.len() as _) }
+                .rte_ok()?;
+        if !queues.is_empty() && linked as usize != queues.len() {
+            return Err(rte_error::rte_error());
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn start(&self) -> Result<()> {
+        unsafe { ffi::rte_event_dev_start(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn stop(&self) {
+        unsafe { ffi::rte_event_dev_stop(self.dev_id) }
+    }
+
+    #[inline]
+    pub fn close(&self) -> Result<()> {
+        unsafe { ffi::rte_event_dev_close(self.dev_id) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns a handle for enqueuing and dequeuing events on one of the device's ports.
+    ///
+    /// # Safety
+    /// Ports are not thread-safe, so it is up to the caller to guarantee that each port is only used by a single lcore
+    /// (i.e. through a single `EventPort`) at a time.
+    #[inline]
+    pub unsafe fn port(&self, port_id: u8) -> EventPort<'_> {
+        EventPort { dev_id: self.dev_id, port_id, _marker: PhantomData }
+    }
+}
+
+/// A port of an [`EventDev`], see [`EventDev::port`].
+#[derive(Debug)]
+pub struct EventPort<'a> {
+    dev_id: u8,
+    port_id: u8,
+    _marker: PhantomData<&'a EventDev>,
+}
+
+impl EventPort<'_> {
+    #[inline]
+    pub fn port_id(&self) -> u8 {
+        self.port_id
+    }
+
+    /// Enqueues as many events from the beginning of `events` as possible.
+    ///
+    /// Events that have been enqueued are removed from `events`, along with the ownership of any mbufs they carry. Any
+    /// events remaining in the array were NOT enqueued (e.g. due to back pressure). Returns the number of enqueued
+    /// events.
+    #[inline]
+    pub fn enqueue_burst<const CAP: usize>(&mut self, events: &mut ArrayVec<Event, CAP>) -> usize {
+        let enqueued = unsafe {
+            ffi::_rte_event_enqueue_burst(self.dev_id, self.port_id, events.as_ptr().cast(), events.len() as u16)
+        } as usize;
+
+        events.drain(..enqueued);
+        enqueued
+    }
+
+    /// Dequeues events into the remaining capacity of `events`, waiting up to `timeout_ticks` (see
+    /// [`rte_event_dequeue_timeout_ticks`](ffi::rte_event_dequeue_timeout_ticks)) for events to arrive. Returns the
+    /// number of dequeued events.
+    ///
+    /// Dequeuing also implicitly releases the flow contexts of the events previously dequeued from this port (unless
+    /// the port was configured to disable implicit release).
+    #[inline]
+    pub fn dequeue_burst<const CAP: usize>(&mut self, events: &mut ArrayVec<Event, CAP>, timeout_ticks: u64) -> usize {
+        let old_len = events.len();
+
+        unsafe {
+            let dequeued = ffi::_rte_event_dequeue_burst(
+                self.dev_id,
+                self.port_id,
+                events.as_mut_ptr().add(old_len).cast(),
+                events.remaining_capacity().min(u16::MAX as usize) as u16,
+                timeout_ticks,
+            ) as usize;
+            events.set_len(old_len + dequeued);
+            dequeued
+        }
+    }
+}
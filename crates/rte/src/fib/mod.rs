@@ -0,0 +1,522 @@
+//! Based on DPDK's `rte_fib.h` and `rte_fib6.h` APIs: <https://doc.dpdk.org/api-22.11/rte__fib_8h.html>,
+//! <https://doc.dpdk.org/api-22.11/rte__fib6_8h.html>
+//!
+//! A [`Fib`] is a longest-prefix-match routing table, mapping prefixes to (integer) next hops. It is the successor of
+//! the LPM library, with a dataplane structure ([`Ipv4Algorithm::Dir24_8`] or [`Ipv6Algorithm::Trie`]) optimized for
+//! lookups, backed by a [`Rib`] holding the control plane's view of the routes.
+
+mod rib;
+
+use std::{
+    ffi::CString,
+    fmt,
+    mem::ManuallyDrop,
+    net::{Ipv4Addr, Ipv6Addr},
+    os::raw::{c_char, c_int},
+    ptr::NonNull,
+};
+
+use arrayvec::ArrayVec;
+use net_addr::{Ipv4Net, Ipv6Net};
+use rte_error::{Error, ReturnValue as _};
+
+pub use self::rib::{Rib, RibNode};
+use crate::{memory::SocketId, Result};
+
+/// Number of addresses translated into DPDK's representation at once during [`Fib::lookup_bulk`].
+const LOOKUP_CHUNK: usize = 64;
+
+/// Size of the next hop values stored in a [`Ipv4Algorithm::Dir24_8`] table.
+///
+/// Note that the most significant bit of each size is reserved, e.g. [`NextHopSize::U8`] can hold values up to `127`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NextHopSize {
+    U8,
+    U16,
+    U32,
+    U64,
+}
+
+/// Size of the next hop values stored in a [`Ipv6Algorithm::Trie`] table.
+///
+/// Note that the most significant bit of each size is reserved, e.g. [`TrieNextHopSize::U16`] can hold values up to
+/// `32767`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrieNextHopSize {
+    U16,
+    U32,
+    U64,
+}
+
+/// Dataplane algorithm of an IPv4 [`Fib`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv4Algorithm {
+    /// Lookups are performed using the [`Rib`] only, mostly useful as a reference implementation.
+    Dummy,
+    /// A 2^24 entries table, extended with up to `num_tbl8` 2^8 entries tables for prefixes longer than /24.
+    Dir24_8 { next_hop_size: NextHopSize, num_tbl8: u32 },
+}
+
+/// Dataplane algorithm of an IPv6 [`Fib`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ipv6Algorithm {
+    /// Lookups are performed using the [`Rib`] only, mostly useful as a reference implementation.
+    Dummy,
+    /// A multibit trie, using up to `num_tbl8` 2^8 entries tables.
+    Trie { next_hop_size: TrieNextHopSize, num_tbl8: u32 },
+}
+
+/// Configuration used for creating a [`Fib`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config<F: Family> {
+    pub algorithm: F::Algorithm,
+    /// Next hop returned by lookups that did not match any route.
+    pub default_next_hop: u64,
+    pub max_routes: u32,
+}
+
+mod sealed {
+    use std::os::raw::{c_char, c_int};
+
+    use super::Config;
+
+    /// Maps the address family to the matching DPDK functions and types.
+    pub trait Sealed: Copy + Sized {
+        type Key: Copy;
+        type RawFib;
+        type RawRib;
+        type RawRibNode;
+
+        fn to_key(self) -> Self::Key;
+        fn from_key(key: Self::Key) -> Self;
+
+        unsafe fn fib_create(
+            name: *const c_char,
+            socket_id: c_int,
+            max_routes: c_int,
+            conf: &Config<Self>,
+        ) -> *mut Self::RawFib
+        where
+            Self: super::Family;
+        unsafe fn fib_free(fib: *mut Self::RawFib);
+        unsafe fn fib_add(fib: *mut Self::RawFib, key: Self::Key, depth: u8, next_hop: u64) -> c_int;
+        unsafe fn fib_delete(fib: *mut Self::RawFib, key: Self::Key, depth: u8) -> c_int;
+        unsafe fn fib_lookup_bulk(fib: *mut Self::RawFib, keys: *mut Self::Key, next_hops: *mut u64, n: c_int)
+            -> c_int;
+        unsafe fn fib_get_rib(fib: *mut Self::RawFib) -> *mut Self::RawRib;
+
+        unsafe fn rib_create(name: *const c_char, socket_id: c_int, max_nodes: c_int) -> *mut Self::RawRib;
+        unsafe fn rib_free(rib: *mut Self::RawRib);
+        unsafe fn rib_insert(rib: *mut Self::RawRib, key: Self::Key, depth: u8) -> *mut Self::RawRibNode;
+        unsafe fn rib_remove(rib: *mut Self::RawRib, key: Self::Key, depth: u8);
+        unsafe fn rib_lookup(rib: *mut Self::RawRib, key: Self::Key) -> *mut Self::RawRibNode;
+        unsafe fn rib_lookup_exact(rib: *mut Self::RawRib, key: Self::Key, depth: u8) -> *mut Self::RawRibNode;
+        unsafe fn rib_lookup_parent(node: *mut Self::RawRibNode) -> *mut Self::RawRibNode;
+        unsafe fn rib_get_next(
+            rib: *mut Self::RawRib,
+            key: Self::Key,
+            depth: u8,
+            last: *mut Self::RawRibNode,
+        ) -> *mut Self::RawRibNode;
+        unsafe fn rib_node_key(node: *const Self::RawRibNode) -> Self::Key;
+        unsafe fn rib_node_depth(node: *const Self::RawRibNode) -> u8;
+        unsafe fn rib_node_next_hop(node: *const Self::RawRibNode) -> u64;
+        unsafe fn rib_node_set_next_hop(node: *mut Self::RawRibNode, next_hop: u64) -> c_int;
+    }
+}
+
+/// An address family that can be stored in a [`Fib`] or [`Rib`], implemented by [`Ipv4Addr`] and [`Ipv6Addr`].
+pub trait Family: sealed::Sealed + fmt::Debug {
+    /// The dataplane algorithms available for this address family.
+    type Algorithm: fmt::Debug + Clone + Copy + PartialEq + Eq;
+    /// The prefixes of routes, whose host bits are ignored.
+    type Net: fmt::Debug + Clone + Copy + PartialEq + Eq + Into<(Self, u8)>;
+}
+
+impl Family for Ipv4Addr {
+    type Algorithm = Ipv4Algorithm;
+    type Net = Ipv4Net;
+}
+
+impl Family for Ipv6Addr {
+    type Algorithm = Ipv6Algorithm;
+    type Net = Ipv6Net;
+}
+
+impl sealed::Sealed for Ipv4Addr {
+    type Key = u32;
+    type RawFib = ffi::rte_fib;
+    type RawRib = ffi::rte_rib;
+    type RawRibNode = ffi::rte_rib_node;
+
+    fn to_key(self) -> u32 {
+        self.into()
+    }
+
+    fn from_key(key: u32) -> Self {
+        key.into()
+    }
+
+    unsafe fn fib_create(
+        name: *const c_char,
+        socket_id: c_int,
+        max_routes: c_int,
+        conf: &Config<Self>,
+    ) -> *mut ffi::rte_fib {
+        let mut raw_conf = ffi::rte_fib_conf { default_nh: conf.default_next_hop, max_routes, ..Default::default() };
+
+        match conf.algorithm {
+            Ipv4Algorithm::Dummy => raw_conf.type_ = ffi::rte_fib_type::RTE_FIB_DUMMY,
+            Ipv4Algorithm::Dir24_8 { next_hop_size, num_tbl8 } => {
+                raw_conf.type_ = ffi::rte_fib_type::RTE_FIB_DIR24_8;
+                raw_conf.__bindgen_anon_1.dir24_8.nh_sz = match next_hop_size {
+                    NextHopSize::U8 => ffi::rte_fib_dir24_8_nh_sz::RTE_FIB_DIR24_8_1B,
+                    NextHopSize::U16 => ffi::rte_fib_dir24_8_nh_sz::RTE_FIB_DIR24_8_2B,
+                    NextHopSize::U32 => ffi::rte_fib_dir24_8_nh_sz::RTE_FIB_DIR24_8_4B,
+                    NextHopSize::U64 => ffi::rte_fib_dir24_8_nh_sz::RTE_FIB_DIR24_8_8B,
+                };
+                raw_conf.__bindgen_anon_1.dir24_8.num_tbl8 = num_tbl8;
+            }
+        }
+
+        ffi::rte_fib_create(name, socket_id, &mut raw_conf)
+    }
+
+    unsafe fn fib_free(fib: *mut ffi::rte_fib) {
+        ffi::rte_fib_free(fib)
+    }
+
+    unsafe fn fib_add(fib: *mut ffi::rte_fib, key: u32, depth: u8, next_hop: u64) -> c_int {
+        ffi::rte_fib_add(fib, key, depth, next_hop)
+    }
+
+    unsafe fn fib_delete(fib: *mut ffi::rte_fib, key: u32, depth: u8) -> c_int {
+        ffi::rte_fib_delete(fib, key, depth)
+    }
+
+    unsafe fn fib_lookup_bulk(fib: *mut ffi::rte_fib, keys: *mut u32, next_hops: *mut u64, n: c_int) -> c_int {
+        ffi::rte_fib_lookup_bulk(fib, keys, next_hops, n)
+    }
+
+    unsafe fn fib_get_rib(fib: *mut ffi::rte_fib) -> *mut ffi::rte_rib {
+        ffi::rte_fib_get_rib(fib)
+    }
+
+    unsafe fn rib_create(name: *const c_char, socket_id: c_int, max_nodes: c_int) -> *mut ffi::rte_rib {
+        let conf = ffi::rte_rib_conf { ext_sz: 0, max_nodes };
+        ffi::rte_rib_create(name, socket_id, &conf)
+    }
+
+    unsafe fn rib_free(rib: *mut ffi::rte_rib) {
+        ffi::rte_rib_free(rib)
+    }
+
+    unsafe fn rib_insert(rib: *mut ffi::rte_rib, key: u32, depth: u8) -> *mut ffi::rte_rib_node {
+        ffi::rte_rib_insert(rib, key, depth)
+    }
+
+    unsafe fn rib_remove(rib: *mut ffi::rte_rib, key: u32, depth: u8) {
+        ffi::rte_rib_remove(rib, key, depth)
+    }
+
+    unsafe fn rib_lookup(rib: *mut ffi::rte_rib, key: u32) -> *mut ffi::rte_rib_node {
+        ffi::rte_rib_lookup(rib, key)
+    }
+
+    unsafe fn rib_lookup_exact(rib: *mut ffi::rte_rib, key: u32, depth: u8) -> *mut ffi::rte_rib_node {
+        ffi::rte_rib_lookup_exact(rib, key, depth)
+    }
+
+    unsafe fn rib_lookup_parent(node: *mut ffi::rte_rib_node) -> *mut ffi::rte_rib_node {
+        ffi::rte_rib_lookup_parent(node)
+    }
+
+    unsafe fn rib_get_next(
+        rib: *mut ffi::rte_rib,
+        key: u32,
+        depth: u8,
+        last: *mut ffi::rte_rib_node,
+    ) -> *mut ffi::rte_rib_node {
+        ffi::rte_rib_get_nxt(rib, key, depth, last, ffi::rte_rib_get_nxt_flag::RTE_RIB_GET_NXT_ALL as c_int)
+    }
+
+    unsafe fn rib_node_key(node: *const ffi::rte_rib_node) -> u32 {
+        let mut key = 0;
+        ffi::rte_rib_get_ip(node, &mut key);
+        key
+    }
+
+    unsafe fn rib_node_depth(node: *const ffi::rte_rib_node) -> u8 {
+        let mut depth = 0;
+        ffi::rte_rib_get_depth(node, &mut depth);
+        depth
+    }
+
+    unsafe fn rib_node_next_hop(node: *const ffi::rte_rib_node) -> u64 {
+        let mut next_hop = 0;
+        ffi::rte_rib_get_nh(node, &mut next_hop);
+        next_hop
+    }
+
+    unsafe fn rib_node_set_next_hop(node: *mut ffi::rte_rib_node, next_hop: u64) -> c_int {
+        ffi::rte_rib_set_nh(node, next_hop)
+    }
+}
+
+impl sealed::Sealed for Ipv6Addr {
+    type Key = [u8; 16];
+    type RawFib = ffi::rte_fib6;
+    type RawRib = ffi::rte_rib6;
+    type RawRibNode = ffi::rte_rib6_node;
+
+    fn to_key(self) -> [u8; 16] {
+        self.octets()
+    }
+
+    fn from_key(key: [u8; 16]) -> Self {
+        key.into()
+    }
+
+    unsafe fn fib_create(
+        name: *const c_char,
+        socket_id: c_int,
+        max_routes: c_int,
+        conf: &Config<Self>,
+    ) -> *mut ffi::rte_fib6 {
+        let mut raw_conf = ffi::rte_fib6_conf { default_nh: conf.default_next_hop, max_routes, ..Default::default() };
+
+        match conf.algorithm {
+            Ipv6Algorithm::Dummy => raw_conf.type_ = ffi::rte_fib6_type::RTE_FIB6_DUMMY,
+            Ipv6Algorithm::Trie { next_hop_size, num_tbl8 } => {
+                raw_conf.type_ = ffi::rte_fib6_type::RTE_FIB6_TRIE;
+                raw_conf.__bindgen_anon_1.trie.nh_sz = match next_hop_size {
+                    TrieNextHopSize::U16 => ffi::rte_fib_trie_nh_sz::RTE_FIB6_TRIE_2B,
+                    TrieNextHopSize::U32 => ffi::rte_fib_trie_nh_sz::RTE_FIB6_TRIE_4B,
+                    TrieNextHopSize::U64 => ffi::rte_fib_trie_nh_sz::RTE_FIB6_TRIE_8B,
+                };
+                raw_conf.__bindgen_anon_1.trie.num_tbl8 = num_tbl8;
+            }
+        }
+
+        ffi::rte_fib6_create(name, socket_id, &mut raw_conf)
+    }
+
+    unsafe fn fib_free(fib: *mut ffi::rte_fib6) {
+        ffi::rte_fib6_free(fib)
+    }
+
+    unsafe fn fib_add(fib: *mut ffi::rte_fib6, key: [u8; 16], depth: u8, next_hop: u64) -> c_int {
+        ffi::rte_fib6_add(fib, key.as_ptr(), depth, next_hop)
+    }
+
+    unsafe fn fib_delete(fib: *mut ffi::rte_fib6, key: [u8; 16], depth: u8) -> c_int {
+        ffi::rte_fib6_delete(fib, key.as_ptr(), depth)
+    }
+
+    unsafe fn fib_lookup_bulk(fib: *mut ffi::rte_fib6, keys: *mut [u8; 16], next_hops: *mut u64, n: c_int) -> c_int {
+        ffi::rte_fib6_lookup_bulk(fib, keys, next_hops, n)
+    }
+
+    unsafe fn fib_get_rib(fib: *mut ffi::rte_fib6) -> *mut ffi::rte_rib6 {
+        ffi::rte_fib6_get_rib(fib)
+    }
+
+    unsafe fn rib_create(name: *const c_char, socket_id: c_int, max_nodes: c_int) -> *mut ffi::rte_rib6 {
+        let conf = ffi::rte_rib6_conf { ext_sz: 0, max_nodes };
+        ffi::rte_rib6_create(name, socket_id, &conf)
+    }
+
+    unsafe fn rib_free(rib: *mut ffi::rte_rib6) {
+        ffi::rte_rib6_free(rib)
+    }
+
+    unsafe fn rib_insert(rib: *mut ffi::rte_rib6, key: [u8; 16], depth: u8) -> *mut ffi::rte_rib6_node {
+        ffi::rte_rib6_insert(rib, key.as_ptr(), depth)
+    }
+
+    unsafe fn rib_remove(rib: *mut ffi::rte_rib6, key: [u8; 16], depth: u8) {
+        ffi::rte_rib6_remove(rib, key.as_ptr(), depth)
+    }
+
+    unsafe fn rib_lookup(rib: *mut ffi::rte_rib6, key: [u8; 16]) -> *mut ffi::rte_rib6_node {
+        ffi::rte_rib6_lookup(rib, key.as_ptr())
+    }
+
+    unsafe fn rib_lookup_exact(rib: *mut ffi::rte_rib6, key: [u8; 16], depth: u8) -> *mut ffi::rte_rib6_node {
+        ffi::rte_rib6_lookup_exact(rib, key.as_ptr(), depth)
+    }
+
+    unsafe fn rib_lookup_parent(node: *mut ffi::rte_rib6_node) -> *mut ffi::rte_rib6_node {
+        ffi::rte_rib6_lookup_parent(node)
+    }
+
+    unsafe fn rib_get_next(
+        rib: *mut ffi::rte_rib6,
+        key: [u8; 16],
+        depth: u8,
+        last: *mut ffi::rte_rib6_node,
+    ) -> *mut ffi::rte_rib6_node {
+        ffi::rte_rib6_get_nxt(rib, key.as_ptr(), depth, last, ffi::rte_rib6_get_nxt_flag::RTE_RIB6_GET_NXT_ALL as c_int)
+    }
+
+    unsafe fn rib_node_key(node: *const ffi::rte_rib6_node) -> [u8; 16] {
+        let mut key = [0; 16];
+        ffi::rte_rib6_get_ip(node, key.as_mut_ptr());
+        key
+    }
+
+    unsafe fn rib_node_depth(node: *const ffi::rte_rib6_node) -> u8 {
+        let mut depth = 0;
+        ffi::rte_rib6_get_depth(node, &mut depth);
+        depth
+    }
+
+    unsafe fn rib_node_next_hop(node: *const ffi::rte_rib6_node) -> u64 {
+        let mut next_hop = 0;
+        ffi::rte_rib6_get_nh(node, &mut next_hop);
+        next_hop
+    }
+
+    unsafe fn rib_node_set_next_hop(node: *mut ffi::rte_rib6_node, next_hop: u64) -> c_int {
+        ffi::rte_rib6_set_nh(node, next_hop)
+    }
+}
+
+/// An IPv4 [`Fib`].
+pub type Fib4 = Fib<Ipv4Addr>;
+
+/// An IPv6 [`Fib`].
+pub type Fib6 = Fib<Ipv6Addr>;
+
+/// A longest-prefix-match routing table for addresses of family `F` ([`Ipv4Addr`] or [`Ipv6Addr`]).
+pub struct Fib<F: Family> {
+    ptr: NonNull<F::RawFib>,
+    rib: ManuallyDrop<Rib<F>>,
+}
+
+// # Safety
+// Lookups are thread-safe, while modifying the table requires a mutable reference.
+// See also: <https://doc.dpdk.org/guides-22.11/prog_guide/fib_lib.html>
+unsafe impl<F: Family> Send for Fib<F> {}
+unsafe impl<F: Family> Sync for Fib<F> {}
+
+impl<F: Family> Fib<F> {
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__fib_8h.html>
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, socket_id: Option<SocketId>, conf: &Config<F>) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+        let socket_id = socket_id.map(|id| id.get() as c_int).unwrap_or(-1);
+
+        let max_routes = c_int::try_from(conf.max_routes).map_err(|_| Error(libc::EINVAL))?;
+
+        let ptr = unsafe { F::fib_create(name.as_ptr(), socket_id, max_routes, conf) }.rte_ok()?;
+        let rib = unsafe { NonNull::new_unchecked(F::fib_get_rib(ptr.as_ptr())) };
+
+        Ok(Self { ptr, rib: ManuallyDrop::new(Rib::from_raw(rib)) })
+    }
+
+    /// Adds a route to `net`, replacing the next hop of an existing route.
+    #[inline]
+    pub fn add(&mut self, net: F::Net, next_hop: u64) -> Result<()> {
+        let (addr, depth) = net.into();
+        unsafe { F::fib_add(self.ptr.as_ptr(), addr.to_key(), depth, next_hop) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Deletes the route to `net`.
+    #[inline]
+    pub fn delete(&mut self, net: F::Net) -> Result<()> {
+        let (addr, depth) = net.into();
+        unsafe { F::fib_delete(self.ptr.as_ptr(), addr.to_key(), depth) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Looks up the next hop of each address in `addrs`, writing them into the matching index of `next_hops`.
+    ///
+    /// Addresses not matching any route are given the configured [default next hop](Config::default_next_hop).
+    ///
+    /// # Panics
+    /// Panics if `addrs` and `next_hops` are not of the same length.
+    #[inline]
+    pub fn lookup_bulk(&self, addrs: &[F], next_hops: &mut [u64]) -> Result<()> {
+        assert_eq!(addrs.len(), next_hops.len());
+
+        for (addrs, next_hops) in addrs.chunks(LOOKUP_CHUNK).zip(next_hops.chunks_mut(LOOKUP_CHUNK)) {
+            let mut keys = addrs.iter().map(|addr| addr.to_key()).collect::<ArrayVec<_, LOOKUP_CHUNK>>();
+            unsafe {
+                F::fib_lookup_bulk(self.ptr.as_ptr(), keys.as_mut_ptr(), next_hops.as_mut_ptr(), keys.len() as c_int)
+            }
+            .rte_ok()?;
+        }
+
+        Ok(())
+    }
+
+    /// Looks up the next hop of a single address.
+    #[inline]
+    pub fn lookup(&self, addr: F) -> Result<u64> {
+        let mut next_hop = 0;
+        self.lookup_bulk(&[addr], std::slice::from_mut(&mut next_hop))?;
+        Ok(next_hop)
+    }
+
+    /// Returns the [`Rib`] holding the routes of this table.
+    ///
+    /// The returned reference only allows querying the RIB, since routes must be modified through the `Fib` in order
+    /// to keep its dataplane structure in sync.
+    #[inline]
+    pub fn rib(&self) -> &Rib<F> {
+        &self.rib
+    }
+}
+
+impl<F: Family> fmt::Debug for Fib<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Fib").field("ptr", &self.ptr).finish()
+    }
+}
+
+impl<F: Family> Drop for Fib<F> {
+    #[inline]
+    fn drop(&mut self) {
+        // the RIB is owned (and freed) by the FIB
+        unsafe { F::fib_free(self.ptr.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[rte_test]
+    fn test_fib4() {
+        let conf = Config {
+            algorithm: Ipv4Algorithm::Dir24_8 { next_hop_size: NextHopSize::U32, num_tbl8: 16 },
+            default_next_hop: 0,
+            max_routes: 16,
+        };
+        // beyond DPDK's limit, rather than wrapping around
+        let too_many = Config { max_routes: u32::MAX, ..conf };
+        assert_eq!(Fib4::new("test_fib4", None, &too_many).err(), Some(Error(libc::EINVAL)));
+
+        let mut fib = Fib4::new("test_fib4", None, &conf).unwrap();
+
+        fib.add("10.0.0.0/8".parse().unwrap(), 1).unwrap();
+        // the host bits are ignored
+        fib.add("10.1.2.3/16".parse().unwrap(), 2).unwrap();
+
+        let addrs = [Ipv4Addr::new(10, 1, 2, 3), Ipv4Addr::new(10, 2, 3, 4), Ipv4Addr::new(11, 0, 0, 1)];
+        let mut next_hops = [u64::MAX; 3];
+        fib.lookup_bulk(&addrs, &mut next_hops).unwrap();
+        assert_eq!(next_hops, [2, 1, 0]);
+
+        let route = fib.rib().lookup_exact("10.1.0.0/16".parse().unwrap()).unwrap();
+        assert_eq!(route.next_hop(), 2);
+        assert_eq!(route.parent().unwrap().depth(), 8);
+
+        fib.delete("10.1.0.0/16".parse().unwrap()).unwrap();
+        assert_eq!(fib.lookup(Ipv4Addr::new(10, 1, 2, 3)).unwrap(), 1);
+    }
+}
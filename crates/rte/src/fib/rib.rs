@@ -0,0 +1,142 @@
+//! Based on DPDK's `rte_rib.h` and `rte_rib6.h` APIs: <https://doc.dpdk.org/api-22.11/rte__rib_8h.html>,
+//! <https://doc.dpdk.org/api-22.11/rte__rib6_8h.html>
+
+use std::{ffi::CString, fmt, iter::successors, marker::PhantomData, os::raw::c_int, ptr::NonNull};
+
+use rte_error::{Error, ReturnValue as _};
+
+use super::Family;
+use crate::{memory::SocketId, Result};
+
+/// A routing information base: a binary tree of prefixes, each mapped to a next hop.
+///
+/// Unlike a [`Fib`](super::Fib), which is optimized for dataplane lookups, a `Rib` is meant to be used as the control
+/// plane's storage of routes, and also supports exact and covering-prefix queries.
+pub struct Rib<F: Family> {
+    ptr: NonNull<F::RawRib>,
+}
+
+// # Safety
+// Queries only require a shared reference, while modifying the RIB requires a mutable reference.
+unsafe impl<F: Family> Send for Rib<F> {}
+unsafe impl<F: Family> Sync for Rib<F> {}
+
+impl<F: Family> Rib<F> {
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__rib_8h.html>
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, socket_id: Option<SocketId>, max_nodes: u32) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+        let socket_id = socket_id.map(|id| id.get() as c_int).unwrap_or(-1);
+
+        let max_nodes = c_int::try_from(max_nodes).map_err(|_| Error(libc::EINVAL))?;
+
+        unsafe { F::rib_create(name.as_ptr(), socket_id, max_nodes) }.rte_ok().map(Self::from_raw)
+    }
+
+    pub(super) fn from_raw(ptr: NonNull<F::RawRib>) -> Self {
+        Self { ptr }
+    }
+
+    /// Inserts a route to `net`, or updates the next hop of an existing one.
+    #[inline]
+    pub fn insert(&mut self, net: F::Net, next_hop: u64) -> Result<()> {
+        let (addr, depth) = net.into();
+        let key = addr.to_key();
+        let node = match unsafe { self.lookup_exact_raw(key, depth) } {
+            Some(node) => node,
+            None => unsafe { F::rib_insert(self.ptr.as_ptr(), key, depth) }.rte_ok()?,
+        };
+
+        unsafe { F::rib_node_set_next_hop(node.as_ptr(), next_hop) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Removes the route to `net`, if it exists.
+    #[inline]
+    pub fn remove(&mut self, net: F::Net) {
+        let (addr, depth) = net.into();
+        unsafe { F::rib_remove(self.ptr.as_ptr(), addr.to_key(), depth) }
+    }
+
+    /// Returns the longest prefix route matching `addr`.
+    #[inline]
+    pub fn lookup(&self, addr: F) -> Option<RibNode<'_, F>> {
+        NonNull::new(unsafe { F::rib_lookup(self.ptr.as_ptr(), addr.to_key()) }).map(RibNode::new)
+    }
+
+    /// Returns the route to exactly `net`.
+    #[inline]
+    pub fn lookup_exact(&self, net: F::Net) -> Option<RibNode<'_, F>> {
+        let (addr, depth) = net.into();
+        unsafe { self.lookup_exact_raw(addr.to_key(), depth) }.map(RibNode::new)
+    }
+
+    /// Returns an iterator over all routes that are more specific than (i.e. covered by) `net`.
+    #[inline]
+    pub fn subroutes(&self, net: F::Net) -> impl Iterator<Item = RibNode<'_, F>> + '_ {
+        let (addr, depth) = net.into();
+        let key = addr.to_key();
+        let next = move |last: *mut F::RawRibNode| {
+            NonNull::new(unsafe { F::rib_get_next(self.ptr.as_ptr(), key, depth, last) })
+        };
+
+        successors(next(std::ptr::null_mut()), move |last| next(last.as_ptr())).map(RibNode::new)
+    }
+
+    unsafe fn lookup_exact_raw(&self, key: F::Key, depth: u8) -> Option<NonNull<F::RawRibNode>> {
+        NonNull::new(F::rib_lookup_exact(self.ptr.as_ptr(), key, depth))
+    }
+}
+
+impl<F: Family> fmt::Debug for Rib<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Rib").field("ptr", &self.ptr).finish()
+    }
+}
+
+impl<F: Family> Drop for Rib<F> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { F::rib_free(self.ptr.as_ptr()) }
+    }
+}
+
+/// A route stored in a [`Rib`].
+#[derive(Clone, Copy)]
+pub struct RibNode<'a, F: Family> {
+    ptr: NonNull<F::RawRibNode>,
+    _marker: PhantomData<&'a Rib<F>>,
+}
+
+impl<'a, F: Family> RibNode<'a, F> {
+    fn new(ptr: NonNull<F::RawRibNode>) -> Self {
+        Self { ptr, _marker: PhantomData }
+    }
+
+    #[inline]
+    pub fn addr(&self) -> F {
+        F::from_key(unsafe { F::rib_node_key(self.ptr.as_ptr()) })
+    }
+
+    #[inline]
+    pub fn depth(&self) -> u8 {
+        unsafe { F::rib_node_depth(self.ptr.as_ptr()) }
+    }
+
+    #[inline]
+    pub fn next_hop(&self) -> u64 {
+        unsafe { F::rib_node_next_hop(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the longest route covering this one.
+    #[inline]
+    pub fn parent(&self) -> Option<RibNode<'a, F>> {
+        NonNull::new(unsafe { F::rib_lookup_parent(self.ptr.as_ptr()) }).map(RibNode::new)
+    }
+}
+
+impl<F: Family> fmt::Debug for RibNode<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}/{} -> {}", self.addr(), self.depth(), self.next_hop())
+    }
+}
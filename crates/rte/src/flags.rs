@@ -1,7 +1,33 @@
 use bitflags::bitflags;
 
+/// (De)serializes flags as their bits, failing on unknown ones rather than keeping them.
+#[cfg(feature = "serde")]
+macro_rules! serde_bits {
+    ($($flags:ident: $bits:ty),* $(,)?) => {$(
+        impl From<$flags> for $bits {
+            fn from(flags: $flags) -> Self {
+                flags.bits()
+            }
+        }
+
+        impl TryFrom<$bits> for $flags {
+            type Error = String;
+
+            fn try_from(bits: $bits) -> Result<Self, Self::Error> {
+                Self::from_bits(bits)
+                    .ok_or_else(|| format!("unknown {} bits: {:#x}", stringify!($flags), bits & !Self::all().bits()))
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "serde")]
+serde_bits!(EthMqRxMode: u32, DevRxOffload: u64, DevTxOffload: u64, EthRss: u64, EthLinkSpeed: u32);
+
 bitflags! {
     /// A set of values to identify what method is to be used to route packets to multiple queues.
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "u32", try_from = "u32"))]
     pub struct EthMqRxMode: u32 {
         const RSS_FLAG    = ffi::_RTE_ETH_MQ_RX_RSS_FLAG;
         const DCB_FLAG    = ffi::_RTE_ETH_MQ_RX_DCB_FLAG;
@@ -10,6 +36,32 @@ bitflags! {
 }
 
 bitflags! {
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "u64", try_from = "u64"))]
+    pub struct DevRxOffload: u64 {
+        const VLAN_STRIP       = ffi::_RTE_ETH_RX_OFFLOAD_VLAN_STRIP;
+        const IPV4_CKSUM       = ffi::_RTE_ETH_RX_OFFLOAD_IPV4_CKSUM;
+        const UDP_CKSUM        = ffi::_RTE_ETH_RX_OFFLOAD_UDP_CKSUM;
+        const TCP_CKSUM        = ffi::_RTE_ETH_RX_OFFLOAD_TCP_CKSUM;
+        const TCP_LRO          = ffi::_RTE_ETH_RX_OFFLOAD_TCP_LRO;
+        const QINQ_STRIP       = ffi::_RTE_ETH_RX_OFFLOAD_QINQ_STRIP;
+        const OUTER_IPV4_CKSUM = ffi::_RTE_ETH_RX_OFFLOAD_OUTER_IPV4_CKSUM;
+        const MACSEC_STRIP     = ffi::_RTE_ETH_RX_OFFLOAD_MACSEC_STRIP;
+        const VLAN_FILTER      = ffi::_RTE_ETH_RX_OFFLOAD_VLAN_FILTER;
+        const VLAN_EXTEND      = ffi::_RTE_ETH_RX_OFFLOAD_VLAN_EXTEND;
+        const SCATTER          = ffi::_RTE_ETH_RX_OFFLOAD_SCATTER;
+        const TIMESTAMP        = ffi::_RTE_ETH_RX_OFFLOAD_TIMESTAMP;
+        const SECURITY         = ffi::_RTE_ETH_RX_OFFLOAD_SECURITY;
+        const KEEP_CRC         = ffi::_RTE_ETH_RX_OFFLOAD_KEEP_CRC;
+        const SCTP_CKSUM       = ffi::_RTE_ETH_RX_OFFLOAD_SCTP_CKSUM;
+        const OUTER_UDP_CKSUM  = ffi::_RTE_ETH_RX_OFFLOAD_OUTER_UDP_CKSUM;
+        const RSS_HASH         = ffi::_RTE_ETH_RX_OFFLOAD_RSS_HASH;
+    }
+}
+
+bitflags! {
+    #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "u64", try_from = "u64"))]
     pub struct DevTxOffload: u64 {
         const VLAN_INSERT       = ffi::_RTE_ETH_TX_OFFLOAD_VLAN_INSERT;
         const IPV4_CKSUM        = ffi::_RTE_ETH_TX_OFFLOAD_IPV4_CKSUM;
@@ -90,6 +142,7 @@ bitflags! {
 
 bitflags! {
     #[derive(Default)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "u64", try_from = "u64"))]
     pub struct EthRss: u64 {
         const IPV4                  = ffi::_RTE_ETH_RSS_IPV4 as u64;
         const FRAG_IPV4             = ffi::_RTE_ETH_RSS_FRAG_IPV4 as u64;
@@ -127,6 +180,7 @@ bitflags! {
 
 bitflags! {
     /// Device supported speeds bitmap flags
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(into = "u32", try_from = "u32"))]
     pub struct EthLinkSpeed: u32 {
         /// Autonegotiate (all speeds)
         const AUTONEG = ffi::RTE_ETH_LINK_AUTONEG;
@@ -140,3 +194,43 @@ impl Default for EthLinkSpeed {
         EthLinkSpeed::AUTONEG
     }
 }
+
+bitflags! {
+    /// The counters of [`MtrStats`](crate::ethdev::MtrStats) which are valid, as reported by the driver.
+    #[derive(Default)]
+    pub struct MtrStatsMask: u64 {
+        const N_PKTS_GREEN      = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_PKTS_GREEN as u64;
+        const N_PKTS_YELLOW     = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_PKTS_YELLOW as u64;
+        const N_PKTS_RED        = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_PKTS_RED as u64;
+        const N_PKTS_DROPPED    = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_PKTS_DROPPED as u64;
+        const N_BYTES_GREEN     = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_BYTES_GREEN as u64;
+        const N_BYTES_YELLOW    = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_BYTES_YELLOW as u64;
+        const N_BYTES_RED       = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_BYTES_RED as u64;
+        const N_BYTES_DROPPED   = ffi::rte_mtr_stats_type::RTE_MTR_STATS_N_BYTES_DROPPED as u64;
+    }
+}
+
+bitflags! {
+    /// Traffic directions and format of a [`pdump`](crate::pdump) capture.
+    pub struct PdumpFlags: u32 {
+        const RX        = ffi::_RTE_PDUMP_FLAG_RX;
+        const TX        = ffi::_RTE_PDUMP_FLAG_TX;
+        const RXTX      = Self::RX.bits | Self::TX.bits;
+        /// Captured packets are formatted for writing with a [`PcapngWriter`](crate::pdump::PcapngWriter).
+        const PCAPNG    = ffi::_RTE_PDUMP_FLAG_PCAPNG;
+    }
+}
+
+#[cfg(dpdk_has_dmadev)]
+bitflags! {
+    /// Flags of a [`dmadev`](crate::dmadev) operation.
+    #[derive(Default)]
+    pub struct DmaOpFlags: u64 {
+        /// The operation is only started once all of the previous operations (of the vchan) have completed.
+        const FENCE     = ffi::_RTE_DMA_OP_FLAG_FENCE;
+        /// The operation (and all of the previously enqueued ones) is submitted to the hardware immediately.
+        const SUBMIT    = ffi::_RTE_DMA_OP_FLAG_SUBMIT;
+        /// The destination data is written to the last level cache (if supported), rather than only to memory.
+        const LLC       = ffi::_RTE_DMA_OP_FLAG_LLC;
+    }
+}
@@ -0,0 +1,454 @@
+//! A table of per-flow state keyed by 5-tuple, based on DPDK's `rte_hash.h` API:
+//! <https://doc.dpdk.org/api-22.11/rte__hash_8h.html>
+//!
+//! Flows are evicted when they are idle for longer than the table's timeout (lazily when looked up, or by periodic
+//! [sweeps](FlowTable::expire_older_than)), or when the table is full, the least recently used flow making room for
+//! the new one, e.g.:
+//! ```rust,ignore
+//! let mut table = FlowTable::new("flows", None, &Config { capacity: 1 << 16, idle_timeout: Some(TIMEOUT) })?;
+//! table.lookup_bulk(&mut pkts, |pkt, flow| match flow {
+//!     Some(counter) => *counter += 1,
+//!     None => new_flows.push(FlowKey::from_headers(&pkt.parse_headers().unwrap())),
+//! });
+//! ```
+
+use std::{
+    ffi::CString,
+    fmt,
+    mem::size_of,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    os::raw::{c_int, c_void},
+    ptr::NonNull,
+    time::Duration,
+};
+
+use arrayvec::ArrayVec;
+use rte_error::{Error, ReturnValue as _};
+
+use crate::{
+    cycles,
+    mbuf::{Allocator, MBuf},
+    memory::SocketId,
+    net::{Headers, L3Hdr, L4Hdr},
+    Result,
+};
+
+/// Number of packets looked up at once by [`FlowTable::lookup_bulk`].
+const LOOKUP_CHUNK: usize = ffi::RTE_HASH_LOOKUP_BULK_MAX as usize;
+
+/// Marks the absence of a slot in the LRU list.
+const NIL: u32 = u32::MAX;
+
+/// The 5-tuple identifying a flow, IPv4 addresses being stored as IPv4-mapped IPv6 addresses.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct FlowKey {
+    src_addr: [u8; 16],
+    dst_addr: [u8; 16],
+    src_port: u16,
+    dst_port: u16,
+    proto: u8,
+    /// Hashed along with the other fields, so must be zeroed.
+    _pad: [u8; 3],
+}
+
+impl FlowKey {
+    /// Creates the key of a flow, whose ports are 0 for protocols without ports.
+    #[inline]
+    pub fn new(src: IpAddr, dst: IpAddr, proto: u8, src_port: u16, dst_port: u16) -> Self {
+        let to_v6 = |addr: IpAddr| match addr {
+            IpAddr::V4(addr) => addr.to_ipv6_mapped().octets(),
+            IpAddr::V6(addr) => addr.octets(),
+        };
+        Self { src_addr: to_v6(src), dst_addr: to_v6(dst), src_port, dst_port, proto, _pad: [0; 3] }
+    }
+
+    /// Returns the key of the flow of a parsed IP packet, or `None` if it isn't one.
+    #[inline]
+    pub fn from_headers(headers: &Headers) -> Option<Self> {
+        let (src, dst, proto) = match headers.l3? {
+            L3Hdr::Ipv4(ipv4) => (ipv4.src().into(), ipv4.dst().into(), ipv4.next_proto_id),
+            L3Hdr::Ipv6(ipv6) => (ipv6.src().into(), ipv6.dst().into(), ipv6.proto),
+            L3Hdr::Arp(_) => return None,
+        };
+        let (src_port, dst_port) = match headers.l4 {
+            Some(L4Hdr::Tcp(tcp)) => (tcp.src_port.get(), tcp.dst_port.get()),
+            Some(L4Hdr::Udp(udp)) => (udp.src_port.get(), udp.dst_port.get()),
+            _ => (0, 0),
+        };
+        Some(Self::new(src, dst, proto, src_port, dst_port))
+    }
+
+    #[inline]
+    pub fn src(&self) -> IpAddr {
+        Self::addr(self.src_addr)
+    }
+
+    #[inline]
+    pub fn dst(&self) -> IpAddr {
+        Self::addr(self.dst_addr)
+    }
+
+    #[inline]
+    pub fn proto(&self) -> u8 {
+        self.proto
+    }
+
+    #[inline]
+    pub fn src_port(&self) -> u16 {
+        self.src_port
+    }
+
+    #[inline]
+    pub fn dst_port(&self) -> u16 {
+        self.dst_port
+    }
+
+    fn addr(octets: [u8; 16]) -> IpAddr {
+        let addr = Ipv6Addr::from(octets);
+        addr.to_ipv4_mapped().map_or(IpAddr::V6(addr), IpAddr::V4)
+    }
+
+    fn as_ptr(&self) -> *const c_void {
+        (self as *const Self).cast()
+    }
+}
+
+impl From<(Ipv4Addr, Ipv4Addr, u8, u16, u16)> for FlowKey {
+    fn from((src, dst, proto, src_port, dst_port): (Ipv4Addr, Ipv4Addr, u8, u16, u16)) -> Self {
+        Self::new(src.into(), dst.into(), proto, src_port, dst_port)
+    }
+}
+
+/// Configuration used for creating a [`FlowTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// The maximum number of flows (at least 8).
+    pub capacity: u32,
+    /// How long flows are kept without being looked up, or `None` to keep them until they are evicted to make room for
+    /// others.
+    pub idle_timeout: Option<Duration>,
+}
+
+struct Slot<V> {
+    key: FlowKey,
+    value: V,
+    /// The TSC when the flow was last inserted or looked up.
+    last_seen: u64,
+    /// The previous (more recently used) slot.
+    prev: u32,
+    /// The next (less recently used) slot.
+    next: u32,
+}
+
+/// A table of per-flow values, see the [module docs](self).
+///
+/// The values are stored in the table's (Rust) slots, indexed by the positions of their keys in the `rte_hash`.
+pub struct FlowTable<V> {
+    hash: NonNull<ffi::rte_hash>,
+    slots: Vec<Option<Slot<V>>>,
+    /// The most recently used slot.
+    head: u32,
+    /// The least recently used slot, i.e. the first to be evicted.
+    tail: u32,
+    len: usize,
+    /// The idle timeout, in TSC cycles.
+    idle_timeout: Option<u64>,
+}
+
+// # Safety
+// The hash table is only accessed through the FlowTable, and not concurrently since it's only modified through `&mut`.
+unsafe impl<V: Send> Send for FlowTable<V> {}
+unsafe impl<V: Sync> Sync for FlowTable<V> {}
+
+impl<V> FlowTable<V> {
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__hash_8h.html>
+    pub fn new<S: Into<Vec<u8>>>(name: S, socket_id: Option<SocketId>, conf: &Config) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+        let params = ffi::rte_hash_parameters {
+            name: name.as_ptr(),
+            entries: conf.capacity,
+            key_len: size_of::<FlowKey>() as u32,
+            socket_id: socket_id.map(|id| id.get() as c_int).unwrap_or(-1),
+            // so that inserting fails only when all the entries are used, rather than on bucket collisions
+            extra_flag: ffi::RTE_HASH_EXTRA_FLAGS_EXT_TABLE as u8,
+            ..Default::default()
+        };
+
+        let hash = unsafe { ffi::rte_hash_create(&params) }.rte_ok()?;
+        Ok(Self {
+            hash,
+            slots: (0..conf.capacity).map(|_| None).collect(),
+            head: NIL,
+            tail: NIL,
+            len: 0,
+            idle_timeout: conf.idle_timeout.map(cycles::duration_to_cycles),
+        })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Inserts the value of a flow, returning its previous value if it was already in the table.
+    ///
+    /// If the table is full, the least recently used flow is evicted.
+    pub fn insert(&mut self, key: FlowKey, value: V) -> Result<Option<V>> {
+        let now = cycles::tsc();
+        let pos = match unsafe { ffi::rte_hash_add_key(self.hash.as_ptr(), key.as_ptr()) }.rte_ok() {
+            Err(Error(libc::ENOSPC)) if self.tail != NIL => {
+                self.remove_slot(self.tail);
+                unsafe { ffi::rte_hash_add_key(self.hash.as_ptr(), key.as_ptr()) }.rte_ok()?
+            }
+            res => res?,
+        } as u32;
+
+        let prev = self.take_slot(pos).map(|slot| slot.value);
+        self.slots[pos as usize] = Some(Slot { key, value, last_seen: now, prev: NIL, next: NIL });
+        self.push_front(pos);
+        self.len += 1;
+        Ok(prev)
+    }
+
+    /// Returns the value of a flow, unless it's expired (in which case it's removed), marking it as used.
+    pub fn get(&mut self, key: &FlowKey) -> Option<&mut V> {
+        let pos = unsafe { ffi::rte_hash_lookup(self.hash.as_ptr(), key.as_ptr()) }.rte_ok().ok()?;
+        self.touch(pos as u32, cycles::tsc())
+    }
+
+    /// Removes a flow, returning its value.
+    pub fn remove(&mut self, key: &FlowKey) -> Option<V> {
+        let pos = unsafe { ffi::rte_hash_lookup(self.hash.as_ptr(), key.as_ptr()) }.rte_ok().ok()?;
+        Some(self.remove_slot(pos as u32))
+    }
+
+    /// Looks up the flows of a batch of packets at once (keyed by their 5-tuples), calling `f` with each packet and
+    /// the value of its flow, if any, like [`Self::get`].
+    pub fn lookup_bulk<A, F>(&mut self, pkts: &mut [MBuf<A>], mut f: F)
+    where
+        A: Allocator,
+        F: FnMut(&mut MBuf<A>, Option<&mut V>),
+    {
+        let now = cycles::tsc();
+
+        for chunk in pkts.chunks_mut(LOOKUP_CHUNK) {
+            let keys: ArrayVec<_, LOOKUP_CHUNK> =
+                chunk.iter().map(|pkt| pkt.parse_headers().as_ref().and_then(FlowKey::from_headers)).collect();
+            // only the packets of IP flows are looked up
+            let mut key_ptrs: ArrayVec<_, LOOKUP_CHUNK> = keys.iter().flatten().map(FlowKey::as_ptr).collect();
+            let mut positions = [-1; LOOKUP_CHUNK];
+            if !key_ptrs.is_empty() {
+                let ret = unsafe {
+                    ffi::rte_hash_lookup_bulk(
+                        self.hash.as_ptr(),
+                        key_ptrs.as_mut_ptr(),
+                        key_ptrs.len() as u32,
+                        positions.as_mut_ptr(),
+                    )
+                };
+                debug_assert_eq!(ret, 0, "invalid bulk lookup");
+            }
+
+            let mut positions = positions.into_iter();
+            for (pkt, key) in chunk.iter_mut().zip(&keys) {
+                match key.and_then(|_| positions.next()) {
+                    Some(pos) if pos >= 0 => f(pkt, self.touch(pos as u32, now)),
+                    _ => f(pkt, None),
+                }
+            }
+        }
+    }
+
+    /// Removes the flows which weren't inserted or looked up since `tsc` (see [`cycles::tsc`]), returning how many
+    /// were removed.
+    ///
+    /// Meant to be called periodically (e.g. every [`Config::idle_timeout`]), so that idle flows are removed even if
+    /// they aren't looked up anymore.
+    pub fn expire_older_than(&mut self, tsc: u64) -> usize {
+        let mut expired = 0;
+        // the flows are ordered by their last use
+        while let Some(slot) = self.slot(self.tail) {
+            if slot.last_seen >= tsc {
+                break;
+            }
+            self.remove_slot(self.tail);
+            expired += 1;
+        }
+        expired
+    }
+
+    /// Removes all the flows.
+    pub fn clear(&mut self) {
+        unsafe { ffi::rte_hash_reset(self.hash.as_ptr()) };
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        (self.head, self.tail, self.len) = (NIL, NIL, 0);
+    }
+
+    fn slot(&self, pos: u32) -> Option<&Slot<V>> {
+        self.slots.get(pos as usize)?.as_ref()
+    }
+
+    /// Returns the value of a slot and marks it as used, unless it's expired (in which case it's removed).
+    fn touch(&mut self, pos: u32, now: u64) -> Option<&mut V> {
+        let last_seen = self.slot(pos)?.last_seen;
+        if self.idle_timeout.is_some_and(|timeout| now.saturating_sub(last_seen) > timeout) {
+            self.remove_slot(pos);
+            return None;
+        }
+
+        self.unlink(pos);
+        self.push_front(pos);
+        let slot = self.slots[pos as usize].as_mut()?;
+        slot.last_seen = now;
+        Some(&mut slot.value)
+    }
+
+    /// Removes a used slot from the hash table and the LRU list, returning its value.
+    fn remove_slot(&mut self, pos: u32) -> V {
+        let slot = self.take_slot(pos).expect("removing an unused slot");
+        unsafe { ffi::rte_hash_del_key(self.hash.as_ptr(), slot.key.as_ptr()) };
+        slot.value
+    }
+
+    /// Takes a slot out of the LRU list (leaving its key in the hash table).
+    fn take_slot(&mut self, pos: u32) -> Option<Slot<V>> {
+        self.slots[pos as usize].as_ref()?;
+        self.unlink(pos);
+        self.len -= 1;
+        self.slots[pos as usize].take()
+    }
+
+    fn unlink(&mut self, pos: u32) {
+        let Some(slot) = self.slots[pos as usize].as_mut() else { return };
+        let (prev, next) = (slot.prev, slot.next);
+        (slot.prev, slot.next) = (NIL, NIL);
+
+        match self.slots.get_mut(prev as usize).and_then(Option::as_mut) {
+            Some(prev) => prev.next = next,
+            None => self.head = next,
+        }
+        match self.slots.get_mut(next as usize).and_then(Option::as_mut) {
+            Some(next) => next.prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    fn push_front(&mut self, pos: u32) {
+        let head = self.head;
+        if let Some(slot) = self.slots[pos as usize].as_mut() {
+            slot.next = head;
+        }
+        match self.slots.get_mut(head as usize).and_then(Option::as_mut) {
+            Some(head) => head.prev = pos,
+            None => self.tail = pos,
+        }
+        self.head = pos;
+    }
+}
+
+impl<V> fmt::Debug for FlowTable<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FlowTable")
+            .field("len", &self.len)
+            .field("capacity", &self.capacity())
+            .field("idle_timeout", &self.idle_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<V> Drop for FlowTable<V> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { ffi::rte_hash_free(self.hash.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::{mbuf::GlobalAllocator, net::IPPROTO_UDP, test_utils::MockClock};
+
+    fn key(port: u16) -> FlowKey {
+        (Ipv4Addr::new(10, 0, 0, 1), Ipv4Addr::new(10, 0, 0, 2), IPPROTO_UDP, port, 53).into()
+    }
+
+    #[test]
+    fn test_flow_key() {
+        let key = key(1234);
+        assert_eq!(key.src(), IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(key.src_port(), 1234);
+        let v6 = FlowKey::new(Ipv6Addr::LOCALHOST.into(), Ipv6Addr::LOCALHOST.into(), 6, 1, 2);
+        assert_eq!(v6.dst(), IpAddr::V6(Ipv6Addr::LOCALHOST));
+    }
+
+    #[rte_test]
+    fn test_lru_and_expiry() {
+        let clock = MockClock::new(0);
+        let conf = Config { capacity: 8, idle_timeout: Some(Duration::from_secs(10)) };
+        let mut table = FlowTable::new("test_flowtable", None, &conf).unwrap();
+
+        for port in 0..8 {
+            assert_eq!(table.insert(key(port), port).unwrap(), None);
+            clock.advance(Duration::from_secs(1));
+        }
+        assert_eq!(table.insert(key(0), 100).unwrap(), Some(0));
+        assert_eq!(table.len(), 8);
+
+        // the least recently used flow (1) is evicted
+        assert_eq!(table.insert(key(8), 8).unwrap(), None);
+        assert_eq!(table.get(&key(1)), None);
+        assert_eq!(table.get(&key(0)), Some(&mut 100));
+        assert_eq!(table.len(), 8);
+
+        // 2 was inserted at 2s, so is lazily expired at 13s
+        clock.advance(Duration::from_secs(5));
+        assert_eq!(table.get(&key(2)), None);
+        assert_eq!(table.len(), 7);
+
+        // 3..=7 were last seen before 8s
+        assert_eq!(table.expire_older_than(cycles::duration_to_cycles(Duration::from_secs(8))), 5);
+        assert_eq!(table.remove(&key(8)), Some(8));
+        assert_eq!(table.len(), 1);
+    }
+
+    #[rte_test]
+    fn test_lookup_bulk() {
+        let mut table =
+            FlowTable::new("test_flowtable_bulk", None, &Config { capacity: 8, idle_timeout: None }).unwrap();
+        table.insert(key(1000), 0).unwrap();
+
+        let packet = |src_port: u16| {
+            let mut packet = [0; 42];
+            packet[12..14].copy_from_slice(&[0x08, 0x00]);
+            packet[14] = 0x45;
+            packet[23] = IPPROTO_UDP;
+            packet[26..34].copy_from_slice(&[10, 0, 0, 1, 10, 0, 0, 2]);
+            packet[34..36].copy_from_slice(&src_port.to_be_bytes());
+            packet[36..38].copy_from_slice(&53u16.to_be_bytes());
+            MBuf::<GlobalAllocator>::new_with_data(packet)
+        };
+        let mut pkts = [packet(1000), MBuf::new_with_data([0; 20]), packet(1001), packet(1000)];
+
+        let mut found = vec![];
+        table.lookup_bulk(&mut pkts, |_, counter| {
+            found.push(counter.map(|counter| {
+                *counter += 1;
+                *counter
+            }))
+        });
+        assert_eq!(found, [Some(1), None, None, Some(2)]);
+    }
+}
@@ -0,0 +1,303 @@
+//! Based on DPDK's `rte_graph.h` API: <https://doc.dpdk.org/api-22.11/rte__graph_8h.html>
+//!
+//! Packet processing composed as a graph of [`Node`]s, each processing a burst (stream) of objects (usually mbufs) and
+//! enqueuing them to its next nodes. Nodes are registered once (see [`register`]), after which a [`Graph`] made of
+//! (a subset of) the registered nodes is created for each worker lcore, which then repeatedly [walks](Graph::walk) it.
+//!
+//! Nodes defined in Rust may be mixed with the nodes of DPDK's `librte_node` (e.g. `ethdev_rx` or `ip4_lookup`).
+
+use std::{
+    alloc::{self, Layout},
+    ffi::CString,
+    fmt,
+    marker::PhantomData,
+    mem,
+    os::raw::{c_char, c_int, c_void},
+    panic::{catch_unwind, AssertUnwindSafe},
+    process,
+    ptr::{self, NonNull},
+    slice,
+};
+
+use rte_error::{check, rte_error};
+
+use crate::{memory::SocketId, Result};
+
+/// The id of a registered node.
+pub type NodeId = ffi::rte_node_t;
+
+/// An object processed by the nodes of a graph, e.g. an mbuf pointer.
+pub type Object = NonNull<c_void>;
+
+/// A node, i.e. a packet processing stage, which is instantiated in each graph it is part of.
+pub trait Node: Send + Sized + 'static {
+    /// The unique name of the node.
+    const NAME: &'static str;
+    /// The names of the nodes following this node, which are indexed by their edge (e.g. in [`Ctx::enqueue`]).
+    const NEXT_NODES: &'static [&'static str];
+    /// Whether this is a source node (e.g. receiving packets), whose instances are processed at the start of each
+    /// walk, without any input objects.
+    const SOURCE: bool = false;
+
+    /// Creates the state of an instance of this node, when a graph containing it is created.
+    fn init() -> Self;
+
+    /// Processes a stream of objects, each of which must be either enqueued to a next node or released (e.g.
+    /// freed). Returns the number of processed objects (e.g. used by source nodes to report the received objects).
+    ///
+    /// A panic aborts the process, rather than unwinding into DPDK.
+    fn process(&mut self, ctx: &mut Ctx<'_>, objs: &[Object]) -> u16;
+}
+
+/// The context of a node instance's [processing](Node::process), used to enqueue objects to the next nodes.
+pub struct Ctx<'a> {
+    graph: *mut ffi::rte_graph,
+    node: *mut ffi::rte_node,
+    _marker: PhantomData<&'a mut ffi::rte_graph>,
+}
+
+impl Ctx<'_> {
+    /// Enqueues `objs` to the node at edge `next`.
+    ///
+    /// # Safety
+    /// The ownership of the objects (e.g. of the mbufs they point to) is transferred to the next node, which may
+    /// dereference or free them: they must be valid objects of the type the next node expects, owned by this node
+    /// (e.g. its input objects, each enqueued once), and not used anymore.
+    ///
+    /// # Panics
+    /// Panics if `next` is not a valid edge of the node.
+    #[inline]
+    pub unsafe fn enqueue(&mut self, next: u16, objs: &[Object]) {
+        self.check_edge(next);
+        ffi::_rte_node_enqueue(self.graph, self.node, next, objs.as_ptr() as *mut _, objs.len() as u16)
+    }
+
+    /// Enqueues a single object to the node at edge `next`.
+    ///
+    /// # Safety
+    /// See [`enqueue`](Self::enqueue).
+    ///
+    /// # Panics
+    /// Panics if `next` is not a valid edge of the node.
+    #[inline]
+    pub unsafe fn enqueue_x1(&mut self, next: u16, obj: Object) {
+        self.check_edge(next);
+        ffi::_rte_node_enqueue_x1(self.graph, self.node, next, obj.as_ptr())
+    }
+
+    /// Moves all of the node's input objects to the node at edge `next`, which is faster than enqueuing them (when all
+    /// of them go to the same node).
+    ///
+    /// # Panics
+    /// Panics if `next` is not a valid edge of the node.
+    #[inline]
+    pub fn next_stream_move(&mut self, next: u16) {
+        self.check_edge(next);
+        unsafe { ffi::_rte_node_next_stream_move(self.graph, self.node, next) }
+    }
+
+    fn check_edge(&self, next: u16) {
+        assert!(next < unsafe { (*self.node).nb_edges }, "invalid edge");
+    }
+}
+
+/// Runs a callback of a node, aborting the process if it panics, as unwinding into DPDK is undefined behavior (and
+/// the lcore walking the graph couldn't recover from it anyway, like the ones [launched](crate::launch) on a function
+/// which panics).
+fn abort_on_panic<R>(callback: impl FnOnce() -> R) -> R {
+    // the panic's message has already been written to stderr by the panic hook
+    catch_unwind(AssertUnwindSafe(callback)).unwrap_or_else(|_| process::abort())
+}
+
+unsafe extern "C" fn init<N: Node>(_graph: *const ffi::rte_graph, node: *mut ffi::rte_node) -> c_int {
+    let state = Box::into_raw(Box::new(abort_on_panic(N::init)));
+    (*node).ctx.as_mut_ptr().cast::<*mut N>().write_unaligned(state);
+    0
+}
+
+unsafe extern "C" fn fini<N: Node>(_graph: *const ffi::rte_graph, node: *mut ffi::rte_node) {
+    let state = Box::from_raw((*node).ctx.as_ptr().cast::<*mut N>().read_unaligned());
+    abort_on_panic(|| drop(state));
+}
+
+unsafe extern "C" fn process<N: Node>(
+    graph: *mut ffi::rte_graph,
+    node: *mut ffi::rte_node,
+    objs: *mut *mut c_void,
+    nb_objs: u16,
+) -> u16 {
+    let state = &mut *(*node).ctx.as_ptr().cast::<*mut N>().read_unaligned();
+    let objs = if nb_objs == 0 { &[] } else { slice::from_raw_parts(objs.cast::<Object>(), nb_objs.into()) };
+
+    abort_on_panic(|| state.process(&mut Ctx { graph, node, _marker: PhantomData }, objs))
+}
+
+/// Registers the node `N`, which can then be included in graphs.
+///
+/// # Panics
+/// Panics if the name of the node is too long.
+pub fn register<N: Node>() -> Result<NodeId> {
+    let next_nodes: Vec<_> = N::NEXT_NODES.iter().map(|&name| CString::new(name).unwrap()).collect();
+
+    // the registration ends with a flexible array of the next nodes' names
+    let layout = Layout::new::<ffi::rte_node_register>()
+        .extend(Layout::array::<*const c_char>(next_nodes.len()).unwrap())
+        .unwrap()
+        .0
+        .pad_to_align();
+
+    unsafe {
+        let reg = alloc::alloc_zeroed(layout).cast::<ffi::rte_node_register>();
+        assert!(!reg.is_null(), "out of memory");
+
+        assert!(N::NAME.len() < (*reg).name.len(), "node name too long");
+        ptr::copy_nonoverlapping(N::NAME.as_ptr().cast(), (*reg).name.as_mut_ptr(), N::NAME.len());
+        if N::SOURCE {
+            (*reg).flags = ffi::RTE_NODE_SOURCE_F.into();
+        }
+        (*reg).process = Some(process::<N>);
+        (*reg).init = Some(init::<N>);
+        (*reg).fini = Some(fini::<N>);
+        (*reg).nb_edges = next_nodes.len() as _;
+
+        let names = (*reg).next_nodes.as_mut_ptr();
+        for (i, name) in next_nodes.iter().enumerate() {
+            *names.add(i) = name.as_ptr();
+        }
+
+        // the registration is copied by DPDK, and the error must be captured before freeing it
+        // (RTE_NODE_ID_INVALID on error)
+        let id = check!(ffi::_rte_node_register(reg), |&id| id == NodeId::MAX);
+        alloc::dealloc(reg.cast(), layout);
+        id
+    }
+}
+
+/// A graph, made of the registered nodes matching the patterns it was created with (and the nodes following them).
+///
+/// A graph may only be walked by a single lcore at a time, and is destroyed when dropped.
+pub struct Graph {
+    id: ffi::rte_graph_t,
+    ptr: NonNull<ffi::rte_graph>,
+}
+
+// # Safety
+// The graph's memory (and its nodes' state) is only accessed by the lcore owning it.
+unsafe impl Send for Graph {}
+
+impl Graph {
+    /// Creates a graph named `name` (which must be unique) from the nodes whose names match any of `node_patterns`
+    /// (e.g. `ethdev_rx-0-*`), along with all of the nodes reachable from them. The graph's memory (including node
+    /// instances) is allocated on the given socket.
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, node_patterns: &[&str], socket_id: Option<SocketId>) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+        let patterns: Vec<_> = node_patterns.iter().map(|&pattern| CString::new(pattern).unwrap()).collect();
+        let mut pattern_ptrs: Vec<_> = patterns.iter().map(|pattern| pattern.as_ptr()).collect();
+
+        let mut param = ffi::rte_graph_param {
+            socket_id: socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            nb_node_patterns: pattern_ptrs.len() as u16,
+            node_patterns: pattern_ptrs.as_mut_ptr(),
+            ..Default::default()
+        };
+
+        // i.e. RTE_GRAPH_ID_INVALID
+        let id =
+            check!(unsafe { ffi::rte_graph_create(name.as_ptr(), &mut param) }, |&id| id == ffi::rte_graph_t::MAX)?;
+
+        match NonNull::new(unsafe { ffi::rte_graph_lookup(name.as_ptr()) }) {
+            Some(ptr) => Ok(Self { id, ptr }),
+            None => {
+                let err = rte_error();
+                unsafe { ffi::rte_graph_destroy(id) };
+                Err(err)
+            }
+        }
+    }
+
+    #[inline]
+    pub fn id(&self) -> ffi::rte_graph_t {
+        self.id
+    }
+
+    /// Walks the graph once: processes the source nodes, and then every node with pending objects, until all of the
+    /// objects have been consumed.
+    #[inline]
+    pub fn walk(&mut self) {
+        unsafe { ffi::_rte_graph_walk(self.ptr.as_ptr()) }
+    }
+}
+
+impl fmt::Debug for Graph {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Graph").field("id", &self.id).finish_non_exhaustive()
+    }
+}
+
+impl Drop for Graph {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_graph_destroy(self.id) };
+    }
+}
+
+const _: () = assert!(mem::size_of::<*mut c_void>() <= ffi::RTE_NODE_CTX_SZ as usize);
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    /// The objects received by [`Sink`].
+    static SUNK: AtomicUsize = AtomicUsize::new(0);
+
+    struct Source;
+
+    impl Node for Source {
+        const NAME: &'static str = "test_source";
+        const NEXT_NODES: &'static [&'static str] = &["test_sink"];
+        const SOURCE: bool = true;
+
+        fn init() -> Self {
+            Source
+        }
+
+        fn process(&mut self, ctx: &mut Ctx<'_>, _objs: &[Object]) -> u16 {
+            let objs = [NonNull::<u64>::dangling().cast(); 4];
+            // Safety: the sink only counts the objects
+            unsafe { ctx.enqueue(0, &objs) };
+            objs.len() as u16
+        }
+    }
+
+    struct Sink;
+
+    impl Node for Sink {
+        const NAME: &'static str = "test_sink";
+        const NEXT_NODES: &'static [&'static str] = &[];
+
+        fn init() -> Self {
+            Sink
+        }
+
+        fn process(&mut self, _ctx: &mut Ctx<'_>, objs: &[Object]) -> u16 {
+            SUNK.fetch_add(objs.len(), Ordering::Relaxed);
+            objs.len() as u16
+        }
+    }
+
+    #[rte_test]
+    fn test_graph_walk() {
+        register::<Sink>().unwrap();
+        register::<Source>().unwrap();
+
+        // the sink is reachable from the source
+        let mut graph = Graph::new("test_graph", &["test_source"], None).unwrap();
+        graph.walk();
+        graph.walk();
+        assert_eq!(SUNK.load(Ordering::Relaxed), 8);
+    }
+}
@@ -0,0 +1,231 @@
+//! Based on DPDK's `rte_ip_frag.h` API: <https://doc.dpdk.org/api-22.11/rte__ip__frag_8h.html>
+//!
+//! See also: the DPDK documentation on the [IP Fragmentation and Reassembly Library](https://doc.dpdk.org/guides-22.11/prog_guide/ip_fragment_reassembly_lib.html).
+
+use std::{marker::PhantomData, mem, os::raw::c_int, ptr::NonNull, time::Duration};
+
+use arrayvec::ArrayVec;
+use rte_error::{Error, ReturnValue as _};
+
+use crate::{cycles, mbuf::MBuf, memory::SocketId, mempool::MemoryPool, Result};
+
+/// Number of mbufs prefetched ahead when freeing the death row.
+const DEATH_ROW_PREFETCH: u32 = 3;
+
+/// Fragments an IPv4 packet into fragments no larger than `mtu`, appending them to `fragments`.
+///
+/// `pkt` must start with the IPv4 header, i.e. its L2 header must have already been removed (see [`MBuf::adj`]).
+/// Each fragment is an mbuf chain made up of a header mbuf allocated from `direct_pool`, and a payload mbuf allocated
+/// from `indirect_pool`, which references the original packet's buffer. `pkt` itself is consumed, and freed once all
+/// fragments referencing it are freed.
+///
+/// Returns the number of fragments, or an error if `fragments` doesn't have enough spare capacity (`EINVAL`) or if
+/// allocating an mbuf has failed (`ENOMEM`), along with `pkt`, which is then left as is.
+///
+/// See also: <https://doc.dpdk.org/api-22.11/rte__ip__frag_8h.html>
+#[inline]
+pub fn fragment_ipv4<'pool, const CAP: usize>(
+    pkt: MBuf<&'pool MemoryPool>,
+    mtu: u16,
+    direct_pool: &'pool MemoryPool,
+    indirect_pool: &'pool MemoryPool,
+    fragments: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>,
+) -> FragmentResult<'pool> {
+    fragment(ffi::rte_ipv4_fragment_packet, pkt, mtu, direct_pool, indirect_pool, fragments)
+}
+
+/// Fragments an IPv6 packet, see [`fragment_ipv4`].
+#[inline]
+pub fn fragment_ipv6<'pool, const CAP: usize>(
+    pkt: MBuf<&'pool MemoryPool>,
+    mtu: u16,
+    direct_pool: &'pool MemoryPool,
+    indirect_pool: &'pool MemoryPool,
+    fragments: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>,
+) -> FragmentResult<'pool> {
+    fragment(ffi::rte_ipv6_fragment_packet, pkt, mtu, direct_pool, indirect_pool, fragments)
+}
+
+/// The number of fragments, or the error along with the packet which couldn't be fragmented.
+pub type FragmentResult<'pool> = Result<usize, (Error, MBuf<&'pool MemoryPool>)>;
+
+type FragmentFn = unsafe extern "C" fn(
+    *mut ffi::rte_mbuf,
+    *mut *mut ffi::rte_mbuf,
+    u16,
+    u16,
+    *mut ffi::rte_mempool,
+    *mut ffi::rte_mempool,
+) -> i32;
+
+#[inline]
+fn fragment<'pool, const CAP: usize>(
+    fragment_fn: FragmentFn,
+    pkt: MBuf<&'pool MemoryPool>,
+    mtu: u16,
+    direct_pool: &'pool MemoryPool,
+    indirect_pool: &'pool MemoryPool,
+    fragments: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>,
+) -> FragmentResult<'pool> {
+    let old_len = fragments.len();
+
+    let count = unsafe {
+        fragment_fn(
+            pkt.as_raw(),
+            fragments.as_mut_ptr().add(old_len) as *mut *mut ffi::rte_mbuf,
+            fragments.remaining_capacity().min(u16::MAX as usize) as u16,
+            mtu,
+            direct_pool.0.as_ptr(),
+            indirect_pool.0.as_ptr(),
+        )
+    };
+    match count.rte_ok() {
+        Ok(count) => {
+            unsafe { fragments.set_len(old_len + count as usize) };
+            // the fragments hold references to the packet, which is freed along with them
+            Ok(count as usize)
+        }
+        Err(err) => Err((err, pkt)),
+    }
+}
+
+/// A table of fragmented packets awaiting reassembly.
+///
+/// Fragments are held in the table until all fragments of a packet have arrived, or until they have expired. Mbufs
+/// that should be freed (e.g. expired or invalid fragments) are put on a "death row", which is freed in bulk by
+/// [`Self::free_death_row`], so that freeing mbufs doesn't stall the reassembly of packets.
+///
+/// The table is not thread-safe, and is meant to be used by a single lcore.
+pub struct FragTable<'pool> {
+    ptr: NonNull<ffi::rte_ip_frag_tbl>,
+    death_row: Box<ffi::rte_ip_frag_death_row>,
+    _marker: PhantomData<MBuf<&'pool MemoryPool>>,
+}
+
+// # Safety
+// The table is only ever accessed through a mutable reference, so it may be moved to (and used by) another lcore.
+unsafe impl Send for FragTable<'_> {}
+
+impl<'pool> FragTable<'pool> {
+    /// Creates a new table with `bucket_num` buckets of `bucket_entries` entries each, holding at most `max_entries`
+    /// packets at any given time. Fragments of packets that were not reassembled within `ttl` are dropped.
+    ///
+    /// **NOTE:** requires the EAL to be initialized.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__ip__frag_8h.html>
+    #[inline]
+    pub fn new(
+        bucket_num: u32,
+        bucket_entries: u32,
+        max_entries: u32,
+        ttl: Duration,
+        socket_id: Option<SocketId>,
+    ) -> Result<Self> {
+        let ptr = unsafe {
+            ffi::rte_ip_frag_table_create(
+                bucket_num,
+                bucket_entries,
+                max_entries,
+                cycles::duration_to_cycles(ttl),
+                socket_id.map(|id| id.get() as c_int).unwrap_or(-1),
+            )
+        }
+        .rte_ok()?;
+
+        Ok(Self { ptr, death_row: Box::default(), _marker: PhantomData })
+    }
+
+    /// Processes an IPv4 packet, returning the reassembled packet once all of its fragments have arrived.
+    ///
+    /// Packets that are not fragmented, or too short to hold the headers, are returned as is. Otherwise, the fragment is kept in the table and `None` is
+    /// returned until the packet is complete, in which case the returned mbuf is a chain made up of all fragments
+    /// (see [`MBuf::linearize`]).
+    ///
+    /// The mbuf's `l2_len` and `l3_len` fields must be set (see [`MetadataExt`](crate::mbuf::MetadataExt)), and `now`
+    /// is the current [TSC value](cycles::tsc).
+    #[inline]
+    pub fn reassemble_ipv4(&mut self, mbuf: MBuf<&'pool MemoryPool>, now: u64) -> Option<MBuf<&'pool MemoryPool>> {
+        unsafe {
+            let Some(ip_hdr) = Self::l3_header(&mbuf, mem::size_of::<ffi::rte_ipv4_hdr>()) else {
+                return Some(mbuf);
+            };
+            let ip_hdr = ip_hdr as *mut ffi::rte_ipv4_hdr;
+            if ffi::_rte_ipv4_frag_pkt_is_fragmented(ip_hdr) == 0 {
+                return Some(mbuf);
+            }
+
+            let reassembled = ffi::rte_ipv4_frag_reassemble_packet(
+                self.ptr.as_ptr(),
+                &mut *self.death_row,
+                mbuf.into_raw().as_ptr(),
+                now,
+                ip_hdr,
+            );
+
+            NonNull::new(reassembled).map(|ptr| MBuf::from_raw(ptr))
+        }
+    }
+
+    /// Processes an IPv6 packet, see [`Self::reassemble_ipv4`].
+    ///
+    /// Only packets whose fragment header immediately follows the IPv6 header are considered fragmented.
+    #[inline]
+    pub fn reassemble_ipv6(&mut self, mbuf: MBuf<&'pool MemoryPool>, now: u64) -> Option<MBuf<&'pool MemoryPool>> {
+        unsafe {
+            let Some(ip_hdr) = Self::l3_header(&mbuf, mem::size_of::<ffi::rte_ipv6_hdr>()) else {
+                return Some(mbuf);
+            };
+            let ip_hdr = ip_hdr as *mut ffi::rte_ipv6_hdr;
+            let frag_hdr = ffi::_rte_ipv6_frag_get_ipv6_fragment_header(ip_hdr);
+            let headers_len = mem::size_of::<ffi::rte_ipv6_hdr>() + mem::size_of::<ffi::rte_ipv6_fragment_ext>();
+            if frag_hdr.is_null() || Self::l3_header(&mbuf, headers_len).is_none() {
+                return Some(mbuf);
+            }
+
+            let reassembled = ffi::rte_ipv6_frag_reassemble_packet(
+                self.ptr.as_ptr(),
+                &mut *self.death_row,
+                mbuf.into_raw().as_ptr(),
+                now,
+                ip_hdr,
+                frag_hdr,
+            );
+
+            NonNull::new(reassembled).map(|ptr| MBuf::from_raw(ptr))
+        }
+    }
+
+    /// Moves the fragments of all packets that have expired by `now` to the death row.
+    #[inline]
+    pub fn del_expired(&mut self, now: u64) {
+        unsafe { ffi::rte_ip_frag_table_del_expired_entries(self.ptr.as_ptr(), &mut *self.death_row, now) }
+    }
+
+    /// Frees all mbufs on the death row.
+    ///
+    /// Should be called periodically, e.g. after processing each burst of received packets.
+    #[inline]
+    pub fn free_death_row(&mut self) {
+        unsafe { ffi::rte_ip_frag_free_death_row(&mut *self.death_row, DEATH_ROW_PREFETCH) }
+    }
+
+    /// Returns a pointer to the L3 header, based on the mbuf's `l2_len` field, unless the (first segment of the) mbuf is
+    /// too short to hold `l3_len` bytes of L3 headers.
+    unsafe fn l3_header(mbuf: &MBuf<&'pool MemoryPool>, l3_len: usize) -> Option<*mut u8> {
+        let raw = mbuf.as_raw();
+        let l2_len = (*raw).__bindgen_anon_3.__bindgen_anon_1.l2_len() as usize;
+        if l2_len + l3_len > mbuf.len() {
+            return None;
+        }
+        Some(((*raw).buf_addr as *mut u8).add(usize::from((*raw).data_off) + l2_len))
+    }
+}
+
+impl Drop for FragTable<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.free_death_row();
+        // frees the fragments still held in the table
+        unsafe { ffi::rte_ip_frag_table_destroy(self.ptr.as_ptr()) }
+    }
+}
@@ -0,0 +1,308 @@
+//! Based on DPDK's `rte_ipsec.h` API: <https://doc.dpdk.org/api-22.11/rte__ipsec_8h.html>
+//!
+//! ESP encapsulation (outbound) and decapsulation (inbound) of mbufs according to a security association ([`Sa`]),
+//! through an IPsec [`Session`] that binds the SA to the device performing the crypto. With lookaside crypto, packets
+//! are processed in three steps:
+//! 1. [`Session::crypto_prepare`] builds a crypto op for each mbuf (e.g. adding the ESP header and trailer).
+//! 2. The ops are processed by a [`CryptoDev`](crate::cryptodev::CryptoDev) queue pair, after which
+//!    [`Session::crypto_complete`] takes the mbufs back out of the dequeued ops.
+//! 3. [`Session::process`] finalizes the mbufs (e.g. checking the replay window and removing the ESP header and
+//!    trailer).
+//!
+//! With inline crypto (or protocol) offloads, i.e. a [`security::Session`], the NIC performs the crypto as packets are
+//! transmitted or received, so [`Session::process`] is the only step.
+//!
+//! Inbound SAs are usually looked up by SPI (and addresses) using a security association database ([`Sad`]).
+//! Security policies (i.e. which traffic is protected by which SA) are not part of `rte_ipsec`, and are usually
+//! implemented using ACL or flow classification.
+
+mod sad;
+
+use std::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    ptr::{self, NonNull},
+};
+
+use arrayvec::ArrayVec;
+use rte_error::{Error, ReturnValue as _};
+
+pub use self::sad::{Sad, SadConf, SadKey, SadKeyType};
+use crate::{
+    cryptodev::{self, CryptoDev, CryptoOp, Transform},
+    mbuf::MBuf,
+    memory::SocketId,
+    mempool::MemoryPool,
+    security, Result,
+};
+
+/// The direction of an SA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Encapsulates packets.
+    Outbound,
+    /// Decapsulates packets.
+    Inbound,
+}
+
+/// The mode of an SA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode<'a> {
+    /// Protects the payload of the original IP header, whose protocol is replaced by ESP (`proto` being the original
+    /// protocol).
+    Transport { proto: u8 },
+    /// Protects the whole original packet, adding a new (outer) header.
+    ///
+    /// For outbound SAs, `header` is the template of the prepended header (e.g. an IPv4 or IPv6 header, optionally
+    /// preceded by an L2 header), and `l3_offset` is the offset of the IP header in the template. `next_proto` is the
+    /// protocol of the original packet (e.g. `IPPROTO_IPIP`). The template is at most 255 bytes long.
+    Tunnel { ipv6: bool, header: &'a [u8], l3_offset: u8, next_proto: u8 },
+}
+
+/// The parameters of an [`Sa`].
+#[derive(Debug, Clone, Copy)]
+pub struct SaParams<'a> {
+    pub spi: u32,
+    /// The salt of the AEAD nonce (e.g. for AES-GCM).
+    pub salt: u32,
+    pub direction: Direction,
+    pub mode: Mode<'a>,
+    /// The crypto transform, which must match the one of the crypto session the SA is used with.
+    pub transform: Transform<'a>,
+    /// The size of the anti-replay window (in packets), or 0 to disable replay protection.
+    pub replay_window: u32,
+    /// Whether extended (64 bit) sequence numbers are used.
+    pub esn: bool,
+    /// An arbitrary value, e.g. used by the application to identify the SA.
+    pub userdata: u64,
+}
+
+impl SaParams<'_> {
+    /// Fails with `EINVAL` if the tunnel header template is too long.
+    pub(crate) fn as_raw(&self, xform: &mut ffi::rte_crypto_sym_xform) -> Result<ffi::rte_ipsec_sa_prm> {
+        let mut prm = ffi::rte_ipsec_sa_prm { userdata: self.userdata, crypto_xform: xform, ..Default::default() };
+
+        let ipsec = &mut prm.ipsec_xform;
+        ipsec.spi = self.spi;
+        ipsec.salt = self.salt;
+        ipsec.options.set_esn(self.esn.into());
+        ipsec.replay_win_sz = self.replay_window;
+        ipsec.proto = ffi::rte_security_ipsec_sa_protocol::RTE_SECURITY_IPSEC_SA_PROTO_ESP;
+        ipsec.direction = match self.direction {
+            Direction::Outbound => ffi::rte_security_ipsec_sa_direction::RTE_SECURITY_IPSEC_SA_DIR_EGRESS,
+            Direction::Inbound => ffi::rte_security_ipsec_sa_direction::RTE_SECURITY_IPSEC_SA_DIR_INGRESS,
+        };
+
+        match self.mode {
+            Mode::Transport { proto } => {
+                ipsec.mode = ffi::rte_security_ipsec_sa_mode::RTE_SECURITY_IPSEC_SA_MODE_TRANSPORT;
+                prm.__bindgen_anon_1.trs.proto = proto;
+            }
+            Mode::Tunnel { ipv6, header, l3_offset, next_proto } => {
+                ipsec.mode = ffi::rte_security_ipsec_sa_mode::RTE_SECURITY_IPSEC_SA_MODE_TUNNEL;
+                ipsec.tunnel.type_ = match ipv6 {
+                    false => ffi::rte_security_ipsec_tunnel_type::RTE_SECURITY_IPSEC_TUNNEL_IPV4,
+                    true => ffi::rte_security_ipsec_tunnel_type::RTE_SECURITY_IPSEC_TUNNEL_IPV6,
+                };
+                prm.__bindgen_anon_1.tun.hdr = header.as_ptr().cast();
+                prm.__bindgen_anon_1.tun.hdr_len = u8::try_from(header.len()).map_err(|_| Error(libc::EINVAL))?;
+                prm.__bindgen_anon_1.tun.hdr_l3_off = l3_offset;
+                prm.__bindgen_anon_1.tun.next_proto = next_proto;
+
+                // the tunnel endpoints are only used by inline protocol offloads, see `security::Session`
+                let l3 = header.get(usize::from(l3_offset)..).unwrap_or_default();
+                let tunnel = &mut ipsec.tunnel.__bindgen_anon_1;
+                match ipv6 {
+                    false if l3.len() >= 20 => {
+                        tunnel.ipv4.ttl = l3[8];
+                        tunnel.ipv4.src_ip.s_addr = u32::from_ne_bytes(l3[12..16].try_into().unwrap());
+                        tunnel.ipv4.dst_ip.s_addr = u32::from_ne_bytes(l3[16..20].try_into().unwrap());
+                    }
+                    true if l3.len() >= 40 => unsafe {
+                        // `in6_addr` is an opaque union of the address bytes
+                        tunnel.ipv6.hlimit = l3[7];
+                        let src = ptr::addr_of_mut!(tunnel.ipv6.src_addr).cast::<[u8; 16]>();
+                        src.write(l3[8..24].try_into().unwrap());
+                        let dst = ptr::addr_of_mut!(tunnel.ipv6.dst_addr).cast::<[u8; 16]>();
+                        dst.write(l3[24..40].try_into().unwrap());
+                    },
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(prm)
+    }
+}
+
+/// A security association, holding the state (e.g. sequence numbers and replay window) of one direction of an IPsec
+/// connection.
+pub struct Sa {
+    ptr: NonNull<ffi::rte_ipsec_sa>,
+    spi: u32,
+}
+
+// # Safety
+// The SA is owned exclusively, and its state is only modified through the (single) session borrowing it mutably.
+unsafe impl Send for Sa {}
+
+impl Sa {
+    /// Creates an SA, whose memory is allocated on the given socket. The tunnel header template (if any) is copied.
+    #[inline]
+    pub fn new(params: &SaParams, socket_id: Option<SocketId>) -> Result<Self> {
+        let mut xform = params.transform.as_raw();
+        let prm = params.as_raw(&mut xform)?;
+
+        let size = unsafe { ffi::rte_ipsec_sa_size(&prm) }.rte_ok()?;
+        // rte_zmalloc doesn't set rte_errno
+        let ptr = NonNull::new(unsafe {
+            ffi::rte_zmalloc_socket(
+                std::ptr::null(),
+                size as usize,
+                ffi::RTE_CACHE_LINE_SIZE,
+                socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            )
+        })
+        .ok_or(Error(libc::ENOMEM))?
+        .cast();
+
+        if let Err(err) = unsafe { ffi::rte_ipsec_sa_init(ptr.as_ptr(), &prm, size as u32) }.rte_ok() {
+            unsafe { ffi::rte_free(ptr.as_ptr().cast()) };
+            return Err(err);
+        }
+
+        Ok(Self { ptr, spi: params.spi })
+    }
+
+    #[inline]
+    pub fn spi(&self) -> u32 {
+        self.spi
+    }
+}
+
+impl fmt::Debug for Sa {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sa").field("spi", &self.spi).finish_non_exhaustive()
+    }
+}
+
+impl Drop for Sa {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rte_ipsec_sa_fini(self.ptr.as_ptr());
+            ffi::rte_free(self.ptr.as_ptr().cast());
+        }
+    }
+}
+
+/// An IPsec session, binding an [`Sa`] to the device performing its crypto (or offloading its processing).
+pub struct Session<'a> {
+    raw: ffi::rte_ipsec_session,
+    _marker: PhantomData<(&'a mut Sa, &'a cryptodev::Session<'a>, &'a security::Session<'a>)>,
+}
+
+// # Safety
+// The session has exclusive access to its SA, and the crypto (or security) session is `Sync`.
+unsafe impl Send for Session<'_> {}
+
+impl<'a> Session<'a> {
+    /// Creates a session using lookaside crypto, i.e. a crypto `session` of the device `dev`.
+    #[inline]
+    pub fn lookaside(sa: &'a mut Sa, dev: &CryptoDev, session: &'a cryptodev::Session<'_>) -> Result<Self> {
+        let mut raw = ffi::rte_ipsec_session {
+            sa: sa.ptr.as_ptr(),
+            type_: ffi::rte_security_session_action_type::RTE_SECURITY_ACTION_TYPE_NONE,
+            ..Default::default()
+        };
+        raw.__bindgen_anon_1.crypto.ses = session.as_raw().cast();
+        raw.__bindgen_anon_1.crypto.dev_id = dev.dev_id();
+
+        unsafe { ffi::rte_ipsec_session_prepare(&mut raw) }.rte_ok()?;
+        Ok(Self { raw, _marker: PhantomData })
+    }
+
+    /// Creates a session using an inline (NIC) security `session`, for which [`Self::process`] is the only processing
+    /// step, i.e. [`Self::crypto_prepare`] and [`Self::crypto_complete`] must not be used.
+    ///
+    /// For outbound SAs, [`Self::process`] sets the mbufs' security metadata, and the mbufs should then be transmitted
+    /// on a queue of the device owning the session.
+    #[inline]
+    pub fn inline(sa: &'a mut Sa, session: &'a security::Session<'_>) -> Result<Self> {
+        let mut raw =
+            ffi::rte_ipsec_session { sa: sa.ptr.as_ptr(), type_: session.action().as_raw(), ..Default::default() };
+        raw.__bindgen_anon_1.security.ses = session.as_raw().cast();
+        raw.__bindgen_anon_1.security.ctx = session.ctx().0.as_ptr();
+        raw.__bindgen_anon_1.security.ol_flags = session.ctx().flags();
+
+        unsafe { ffi::rte_ipsec_session_prepare(&mut raw) }.rte_ok()?;
+        Ok(Self { raw, _marker: PhantomData })
+    }
+
+    /// Prepares a crypto op for each of `mbufs`, using the ops at the beginning of `ops`. Returns the number of
+    /// prepared mbufs.
+    ///
+    /// The prepared mbufs are moved into the first ops (any mbufs previously held by those ops are freed). Mbufs that
+    /// could not be prepared (e.g. since they are malformed) remain in `mbufs`.
+    ///
+    /// # Panics
+    /// Panics if `ops` holds less ops than `mbufs` holds mbufs.
+    #[inline]
+    pub fn crypto_prepare<'o, const CAP: usize>(
+        &mut self,
+        mbufs: &mut ArrayVec<MBuf<&'o MemoryPool>, CAP>,
+        ops: &mut ArrayVec<CryptoOp<'o>, CAP>,
+    ) -> usize
+    where
+        'a: 'o,
+    {
+        assert!(ops.len() >= mbufs.len(), "not enough crypto ops");
+        ops[..mbufs.len()].iter_mut().for_each(|op| drop(op.take_mbuf()));
+
+        let prepared = unsafe {
+            ffi::_rte_ipsec_pkt_crypto_prepare(
+                &self.raw,
+                mbufs.as_mut_ptr().cast(),
+                ops.as_mut_ptr().cast(),
+                mbufs.len() as u16,
+            )
+        } as usize;
+
+        // the ops have assumed ownership of the prepared mbufs (which were moved to the beginning of the array)
+        mbufs.drain(..prepared).for_each(mem::forget);
+        prepared
+    }
+
+    /// Takes the mbufs out of `ops` (processed by a crypto device) and appends them to `mbufs`, flagging the mbufs
+    /// whose crypto failed so that they are rejected by [`Self::process`]. The ops are freed.
+    ///
+    /// # Panics
+    /// Panics if `mbufs` does not have enough capacity, or if any of the ops has no mbuf.
+    #[inline]
+    pub fn crypto_complete<'o, const CAP: usize, const OPS_CAP: usize>(
+        &mut self,
+        ops: &mut ArrayVec<CryptoOp<'o>, OPS_CAP>,
+        mbufs: &mut ArrayVec<MBuf<&'o MemoryPool>, CAP>,
+    ) {
+        for mut op in ops.drain(..) {
+            let mbuf = op.take_mbuf().expect("crypto op has no mbuf");
+            if op.status() != cryptodev::Status::Success {
+                unsafe { (*mbuf.as_raw()).ol_flags |= ffi::RTE_MBUF_F_RX_SEC_OFFLOAD_FAILED };
+            }
+            mbufs.push(mbuf);
+        }
+    }
+
+    /// Finalizes the processing of `mbufs`, returning the number of successfully processed mbufs, which are moved to
+    /// the beginning of the array. The rest (e.g. failing authentication or the replay check) should be dropped.
+    #[inline]
+    pub fn process<const CAP: usize>(&mut self, mbufs: &mut ArrayVec<MBuf<&MemoryPool>, CAP>) -> usize {
+        unsafe { ffi::_rte_ipsec_pkt_process(&self.raw, mbufs.as_mut_ptr().cast(), mbufs.len() as u16) as usize }
+    }
+}
+
+impl fmt::Debug for Session<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session").finish_non_exhaustive()
+    }
+}
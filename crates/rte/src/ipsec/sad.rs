@@ -0,0 +1,146 @@
+//! Based on DPDK's `rte_ipsec_sad.h` API: <https://doc.dpdk.org/api-22.11/rte__ipsec__sad_8h.html>
+
+use std::{ffi::CString, fmt, marker::PhantomData, net::IpAddr, os::raw::c_void, ptr::NonNull};
+
+use rte_error::ReturnValue as _;
+
+use crate::Result;
+
+/// The configuration of a [`Sad`], i.e. the maximal number of SAs of each [`SadKeyType`] (indexed by
+/// [`SadKeyType::as_index`]), and the `RTE_IPSEC_SAD_FLAG_*` flags (e.g. whether the database holds IPv6 SAs).
+pub type SadConf = ffi::rte_ipsec_sad_conf;
+
+/// The fields of a [`SadKey`] an SA is matched on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SadKeyType {
+    Spi,
+    SpiDip,
+    SpiDipSip,
+}
+
+impl SadKeyType {
+    /// Returns the index of this key type in [`SadConf::max_sa`].
+    #[inline]
+    pub fn as_index(self) -> usize {
+        (match self {
+            Self::Spi => ffi::_RTE_IPSEC_SAD_SPI_ONLY,
+            Self::SpiDip => ffi::_RTE_IPSEC_SAD_SPI_DIP,
+            Self::SpiDipSip => ffi::_RTE_IPSEC_SAD_SPI_DIP_SIP,
+        }) as usize
+    }
+}
+
+/// The key of an inbound packet (or SA), in host byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SadKey {
+    pub spi: u32,
+    pub dip: IpAddr,
+    pub sip: IpAddr,
+}
+
+impl SadKey {
+    fn as_raw(&self, ipv6: bool) -> ffi::rte_ipsec_sad_key {
+        let mut key = ffi::rte_ipsec_sad_key::default();
+
+        match (self.dip, self.sip) {
+            (IpAddr::V4(dip), IpAddr::V4(sip)) if !ipv6 => {
+                key.v4.spi = self.spi.to_be();
+                key.v4.dip = u32::from_ne_bytes(dip.octets());
+                key.v4.sip = u32::from_ne_bytes(sip.octets());
+            }
+            (IpAddr::V6(dip), IpAddr::V6(sip)) if ipv6 => {
+                key.v6.spi = self.spi.to_be();
+                key.v6.dip = dip.octets();
+                key.v6.sip = sip.octets();
+            }
+            _ => panic!("address family mismatch"),
+        }
+
+        key
+    }
+}
+
+/// A security association database, mapping the keys of inbound packets to (references to) their SAs, e.g. an
+/// [`Sa`](super::Sa) or the application's per-SA context.
+///
+/// Lookups return the SA with the most specific matching key.
+pub struct Sad<'a, T> {
+    ptr: NonNull<ffi::rte_ipsec_sad>,
+    ipv6: bool,
+    _marker: PhantomData<&'a T>,
+}
+
+// # Safety
+// The database only holds shared references, and lookups are thread-safe (given the `RW_CONCURRENCY` flag, when
+// concurrent with updates, which require `&mut self`).
+unsafe impl<T: Sync> Send for Sad<'_, T> {}
+unsafe impl<T: Sync> Sync for Sad<'_, T> {}
+
+impl<'a, T> Sad<'a, T> {
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, conf: &SadConf) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+        let ipv6 = conf.flags & ffi::RTE_IPSEC_SAD_FLAG_IPV6 as u8 != 0;
+
+        unsafe { ffi::rte_ipsec_sad_create(name.as_ptr(), conf) }.rte_ok().map(|ptr| Self {
+            ptr,
+            ipv6,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Adds (or replaces) the SA matching `key` according to `key_type`.
+    ///
+    /// # Panics
+    /// Panics if the address family of `key` does not match the database's.
+    #[inline]
+    pub fn add(&mut self, key: &SadKey, key_type: SadKeyType, sa: &'a T) -> Result<()> {
+        let key = key.as_raw(self.ipv6);
+        let sa = sa as *const T as *mut c_void;
+        unsafe { ffi::rte_ipsec_sad_add(self.ptr.as_ptr(), &key, key_type.as_index() as i32, sa) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Removes the SA matching `key` according to `key_type`.
+    ///
+    /// # Panics
+    /// Panics if the address family of `key` does not match the database's.
+    #[inline]
+    pub fn del(&mut self, key: &SadKey, key_type: SadKeyType) -> Result<()> {
+        let key = key.as_raw(self.ipv6);
+        unsafe { ffi::rte_ipsec_sad_del(self.ptr.as_ptr(), &key, key_type.as_index() as i32) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Looks up the SAs of a burst of keys.
+    ///
+    /// # Panics
+    /// Panics if the address family of any of the keys does not match the database's.
+    #[inline]
+    pub fn lookup_bulk<const N: usize>(&self, keys: &[SadKey; N]) -> [Option<&'a T>; N] {
+        let keys = keys.map(|key| key.as_raw(self.ipv6));
+        let mut key_ptrs = keys.each_ref().map(|key| key as *const _);
+        let mut sas = [std::ptr::null_mut(); N];
+
+        unsafe { ffi::rte_ipsec_sad_lookup(self.ptr.as_ptr(), key_ptrs.as_mut_ptr(), sas.as_mut_ptr(), N as u32) };
+        sas.map(|sa| unsafe { sa.cast::<T>().as_ref() })
+    }
+
+    #[inline]
+    pub fn lookup(&self, key: &SadKey) -> Option<&'a T> {
+        let [sa] = self.lookup_bulk(&[*key]);
+        sa
+    }
+}
+
+impl<T> fmt::Debug for Sad<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sad").field("ipv6", &self.ipv6).finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for Sad<'_, T> {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_ipsec_sad_destroy(self.ptr.as_ptr()) }
+    }
+}
@@ -0,0 +1,79 @@
+//! Based on DPDK's `rte_latencystats.h` API: <https://doc.dpdk.org/api-22.11/rte__latencystats_8h.html>
+//!
+//! Measures the latency of packets between their reception and transmission, by timestamping packets in an RX
+//! callback and sampling their latency in a TX callback, on every queue of every port. The latencies are aggregated
+//! over all ports, and published through the metrics library (e.g. for `dpdk-proc-info` or telemetry).
+
+use std::{ffi::CStr, time::Duration};
+
+use rte_error::ReturnValue as _;
+
+use crate::{memory::SocketId, Result};
+
+/// Aggregated packet latencies.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Latency {
+    pub min: Duration,
+    pub avg: Duration,
+    pub max: Duration,
+    pub jitter: Duration,
+}
+
+/// The latency measurement hooks, which are removed when this struct is dropped.
+#[derive(Debug)]
+pub struct LatencyStats(());
+
+impl LatencyStats {
+    /// Installs the latency measurement callbacks on all queues of the (already configured) ports, sampling the
+    /// latency of a packet at most once per `sample_interval`. The metrics library is initialized on the given socket,
+    /// if it isn't already.
+    #[inline]
+    pub fn init(sample_interval: Duration, socket_id: Option<SocketId>) -> Result<Self> {
+        unsafe {
+            ffi::rte_metrics_init(socket_id.map(|id| id.get() as i32).unwrap_or(-1));
+            ffi::rte_latencystats_init(sample_interval.as_nanos() as u64, None)
+        }
+        .rte_ok()?;
+
+        Ok(Self(()))
+    }
+
+    /// Publishes the current latencies to the metrics library.
+    #[inline]
+    pub fn update(&self) -> Result<()> {
+        unsafe { ffi::rte_latencystats_update() }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the current latencies.
+    #[inline]
+    pub fn get(&self) -> Result<Latency> {
+        const LEN: usize = 4;
+
+        let mut names = [ffi::rte_metric_name::default(); LEN];
+        let mut values = [ffi::rte_metric_value::default(); LEN];
+        let len = unsafe { ffi::rte_latencystats_get_names(names.as_mut_ptr(), LEN as u16) }.rte_ok()?;
+        unsafe { ffi::rte_latencystats_get(values.as_mut_ptr(), LEN as u16) }.rte_ok()?;
+
+        let mut latency = Latency::default();
+        for value in &values[..(len as usize).min(LEN)] {
+            let name = unsafe { CStr::from_ptr(names[value.key as usize].name.as_ptr()) };
+            let field = match name.to_bytes() {
+                b"min_latency_ns" => &mut latency.min,
+                b"avg_latency_ns" => &mut latency.avg,
+                b"max_latency_ns" => &mut latency.max,
+                b"jitter_ns" => &mut latency.jitter,
+                _ => continue,
+            };
+            *field = Duration::from_nanos(value.value);
+        }
+
+        Ok(latency)
+    }
+}
+
+impl Drop for LatencyStats {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_latencystats_uninit() };
+    }
+}
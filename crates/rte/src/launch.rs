@@ -80,6 +80,16 @@ impl lcore::Id {
         debug_assert!(lcore::current().is_main());
         unsafe { ffi::rte_eal_get_lcore_state(self.get()) }.into()
     }
+
+    /// Waits for the lcore to finish its job, returning the value returned by its entrypoint (or 0 if it wasn't
+    /// running one).
+    ///
+    /// **NOTE:** should be executed on main lcore only. Will `panic` otherwise, if debug assertions are enabled.
+    #[inline]
+    pub fn wait(self) -> i32 {
+        debug_assert!(lcore::current().is_main());
+        unsafe { ffi::rte_eal_wait_lcore(self.get()) }
+    }
 }
 
 /// **NOTE:** should be executed on main lcore only. Will `panic` otherwise, if debug assertions are enabled.
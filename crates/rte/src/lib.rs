@@ -1,14 +1,49 @@
 #[cfg(test)]
 extern crate self as rte;
 
+#[cfg(feature = "app")]
+pub mod app;
+#[cfg(feature = "zero-overhead-audit")]
+pub mod audit;
+pub mod bitrate;
+pub mod bpf;
+pub mod cryptodev;
+pub mod cycles;
+pub mod dispatch;
+pub mod distributor;
+#[cfg(dpdk_has_dmadev)]
+pub mod dmadev;
 pub mod ethdev;
+pub mod eventdev;
+pub mod fib;
 pub mod flags;
+pub mod flowtable;
+#[cfg(dpdk_has_graph)]
+pub mod graph;
+pub mod ip_frag;
+pub mod ipsec;
+pub mod latencystats;
 pub mod launch;
 pub mod lcore;
 pub mod mbuf;
 pub mod memory;
 pub mod mempool;
+pub mod meter;
+pub mod metrics;
+pub mod net;
+pub mod pdump;
+pub mod pipeline;
+pub mod port;
+pub mod rcu;
+pub mod reorder;
+pub mod ring;
+pub mod security;
+pub mod stack;
+pub mod telemetry;
+pub mod thash;
 
+#[cfg(feature = "bench-utils")]
+pub mod bench_utils;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod test_utils;
 
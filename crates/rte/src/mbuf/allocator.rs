@@ -60,11 +60,15 @@ mod test_pool {
     };
 
     use super::Allocator;
-    use crate::Result;
+    use crate::{mbuf::MBuf, Result};
 
     /// A struct implementing the [`Allocator`] trait using the [global Rust allocator](https://doc.rust-lang.org/stable/std/alloc/index.html).
     ///
-    /// Allows testing code that uses [`MBuf`](crate::mbuf::MBuf)s without having to rely on the RTE memory pool, and without having to initialize the EAL.
+    /// Allows testing code that uses [`MBuf`]s without having to rely on the RTE memory pool, and without having to initialize the EAL.
+    ///
+    /// Its mbufs are initialized like the ones of a memory pool, i.e. with a data buffer of `BUF_SIZE` bytes (including
+    /// [`RTE_PKTMBUF_HEADROOM`](ffi::RTE_PKTMBUF_HEADROOM) bytes of headroom, as by default), a single reference and a
+    /// single segment, so that prepending headers or filling the tailroom behaves just as it does in production.
     #[derive(Default, Clone, Copy)]
     pub struct GlobalAllocator<const BUF_SIZE: usize = { ffi::RTE_MBUF_DEFAULT_BUF_SIZE as usize }>;
 
@@ -72,6 +76,33 @@ mod test_pool {
         fn data_layout() -> Layout {
             Layout::array::<u8>(BUF_SIZE).unwrap()
         }
+
+        /// Allocates a chain of mbufs with a segment per slice of `segments`, e.g. for testing code handling scattered
+        /// packets (as received with scatter RX, or reassembled).
+        ///
+        /// Notice that the chain can't be [linearized](MBuf::linearize), as DPDK would free the merged segments to
+        /// their (missing) memory pool.
+        ///
+        /// # Panics
+        /// Panics if `segments` is empty, or if a segment doesn't fit in the tailroom of an mbuf.
+        pub fn alloc_chain(&self, segments: &[&[u8]]) -> MBuf<Self> {
+            let (first, rest) = segments.split_first().expect("A chain has at least one segment");
+            let mut head = MBuf::new_with_provider_and_data(self, first);
+
+            unsafe {
+                let head = head.as_raw();
+                let mut last = head;
+                for segment in rest {
+                    let seg = MBuf::new_with_provider_and_data(self, segment).into_raw().as_ptr();
+                    (*last).next = seg;
+                    (*head).nb_segs += 1;
+                    (*head).pkt_len += (*seg).pkt_len;
+                    last = seg;
+                }
+            }
+
+            head
+        }
     }
 
     impl<const BUF_SIZE: usize> Allocator for GlobalAllocator<BUF_SIZE> {
@@ -84,6 +115,10 @@ mod test_pool {
                     let mbuf = mbuf.as_mut();
                     mbuf.buf_addr = data;
                     mbuf.buf_len = BUF_SIZE as u16;
+                    // as `rte_pktmbuf_reset_headroom` does
+                    mbuf.data_off = BUF_SIZE.min(ffi::RTE_PKTMBUF_HEADROOM as usize) as u16;
+                    mbuf.refcnt = 1;
+                    mbuf.nb_segs = 1;
                     mbuf.ol_flags &= ffi::RTE_MBUF_F_EXTERNAL;
                     mbuf.port = ffi::RTE_MBUF_PORT_INVALID as u16;
                 }
@@ -92,6 +127,7 @@ mod test_pool {
             }
         }
 
+        /// Clones the first segment of the mbuf, like the [`MemoryPool`](crate::mempool::MemoryPool)'s clone.
         unsafe fn clone(mbuf: NonNull<ffi::rte_mbuf>) -> Result<NonNull<ffi::rte_mbuf>> {
             let mut clone = Self::alloc(&Self)?;
 
@@ -99,17 +135,60 @@ mod test_pool {
                 let clone = clone.as_mut();
                 let mbuf = mbuf.as_ref();
 
-                ptr::copy_nonoverlapping(mbuf.buf_addr, clone.buf_addr, mbuf.data_len.into());
+                clone.data_off = mbuf.data_off;
+                ptr::copy_nonoverlapping(
+                    mbuf.buf_addr.add(mbuf.data_off.into()),
+                    clone.buf_addr.add(clone.data_off.into()),
+                    mbuf.data_len.into(),
+                );
                 clone.data_len = mbuf.data_len;
-                clone.pkt_len = mbuf.pkt_len;
+                clone.pkt_len = mbuf.data_len.into();
             }
 
             Ok(clone)
         }
 
+        /// Frees all the segments of the mbuf, like `rte_pktmbuf_free`.
         unsafe fn free(mbuf: NonNull<ffi::rte_mbuf>) {
-            dealloc(mbuf.as_ref().buf_addr as _, Self::data_layout());
-            dealloc(mbuf.as_ptr() as _, Layout::new::<ffi::rte_mbuf>());
+            let mut seg = Some(mbuf);
+            while let Some(mbuf) = seg {
+                seg = NonNull::new(mbuf.as_ref().next);
+                dealloc(mbuf.as_ref().buf_addr as _, Self::data_layout());
+                dealloc(mbuf.as_ptr() as _, Layout::new::<ffi::rte_mbuf>());
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_headroom() {
+            let mut mbuf = MBuf::<GlobalAllocator>::new_with_data([1; 10]);
+            assert_eq!(
+                mbuf.prepend(ffi::RTE_PKTMBUF_HEADROOM as u16).map(|data| data.iter().all(|&byte| byte == 0)),
+                Some(true)
+            );
+            assert!(mbuf.prepend(1).is_none());
+            assert_eq!(mbuf.len(), 138);
+
+            let clone = mbuf.clone();
+            assert_eq!(&clone[128..], [1; 10]);
+        }
+
+        #[test]
+        #[should_panic]
+        fn test_tailroom() {
+            MBuf::<GlobalAllocator<256>>::new_with_data([0; 129]);
+        }
+
+        #[test]
+        fn test_alloc_chain() {
+            let mbuf = GlobalAllocator::<256>.alloc_chain(&[&[1; 100], &[2; 50], &[3; 10]]);
+            assert_eq!(mbuf.nb_segs(), 3);
+            assert_eq!(mbuf.pkt_len(), 160);
+            assert_eq!(&mbuf[..], [1; 100]);
         }
     }
 }
@@ -0,0 +1,324 @@
+use std::{
+    fmt, mem,
+    ops::{Deref, DerefMut, RangeBounds},
+};
+
+use arrayvec::ArrayVec;
+
+use super::{Allocator, MBuf};
+
+/// A batch of up to `N` packets, e.g. as received or sent in a single burst.
+///
+/// It owns its packets like an `ArrayVec<MBuf<A>, N>` (and dereferences into a slice of them): packets removed from it
+/// without being returned (e.g. by [`Self::retain`]) are freed. Packets are only ever handed over to DPDK through
+/// [`Self::fill_raw`] and [`Self::consume_raw`], which keep track of which of them DPDK took ownership of, so that the
+/// burst APIs (e.g. [`EthDev::tx_burst`](crate::ethdev::EthDev::tx_burst)) neither leak nor double free any of them.
+pub struct PacketBatch<A: Allocator, const N: usize>(ArrayVec<MBuf<A>, N>);
+
+impl<A: Allocator, const N: usize> PacketBatch<A, N> {
+    #[inline]
+    pub const fn new() -> Self {
+        Self(ArrayVec::new_const())
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline]
+    pub fn remaining_capacity(&self) -> usize {
+        self.0.remaining_capacity()
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.0.is_full()
+    }
+
+    /// Appends a packet.
+    ///
+    /// # Panics
+    /// Panics if the batch is full.
+    #[track_caller]
+    #[inline]
+    pub fn push(&mut self, pkt: MBuf<A>) {
+        self.0.push(pkt)
+    }
+
+    /// Appends a packet, returning it back if the batch is full.
+    #[inline]
+    pub fn try_push(&mut self, pkt: MBuf<A>) -> Result<(), MBuf<A>> {
+        self.0.try_push(pkt).map_err(|err| err.element())
+    }
+
+    #[inline]
+    pub fn pop(&mut self) -> Option<MBuf<A>> {
+        self.0.pop()
+    }
+
+    /// Frees all the packets.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// Frees the packets past the first `len` ones.
+    #[inline]
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len)
+    }
+
+    /// Keeps the packets for which `f` returns `true` (in order), freeing the others.
+    #[inline]
+    pub fn retain<F: FnMut(&mut MBuf<A>) -> bool>(&mut self, f: F) {
+        self.0.retain(f)
+    }
+
+    /// Removes the packets in `range`, returning them in an iterator (the ones it doesn't yield are freed).
+    #[inline]
+    pub fn drain<R: RangeBounds<usize>>(&mut self, range: R) -> arrayvec::Drain<'_, MBuf<A>, N> {
+        self.0.drain(range)
+    }
+
+    /// Moves as many packets of `other` (from its beginning) as fit to the end of this batch, leaving the others in
+    /// `other`.
+    #[inline]
+    pub fn append<const M: usize>(&mut self, other: &mut PacketBatch<A, M>) {
+        let moved = self.remaining_capacity().min(other.len());
+        self.0.extend(other.0.drain(..moved));
+    }
+
+    /// Splits the batch in two at `at`, returning the packets from `at` on.
+    ///
+    /// # Panics
+    /// Panics if `at` is greater than the number of packets.
+    #[track_caller]
+    #[inline]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        assert!(at <= self.len(), "`at` ({at}) out of bounds of a batch of {} packets", self.len());
+        self.0.drain(at..).collect()
+    }
+
+    /// Splits the batch into the packets for which `f` returns `true`, and the others (both in order).
+    #[inline]
+    pub fn partition<F: FnMut(&MBuf<A>) -> bool>(self, mut f: F) -> (Self, Self) {
+        let (mut matching, mut others) = (Self::new(), Self::new());
+        for pkt in self.0 {
+            if f(&pkt) {
+                matching.0.push(pkt);
+            } else {
+                others.0.push(pkt);
+            }
+        }
+        (matching, others)
+    }
+
+    /// Distributes the packets into `K` batches by the index `classify` returns for each of them (e.g. an output port),
+    /// freeing the packets it returns `None` for.
+    ///
+    /// # Panics
+    /// Panics if `classify` returns an index greater than or equal to `K`.
+    #[track_caller]
+    #[inline]
+    pub fn classify<const K: usize, F>(self, mut classify: F) -> [Self; K]
+    where
+        F: FnMut(&MBuf<A>) -> Option<usize>,
+    {
+        let mut batches = [(); K].map(|_| Self::new());
+        for pkt in self.0 {
+            if let Some(index) = classify(&pkt) {
+                batches[index].0.push(pkt);
+            }
+        }
+        batches
+    }
+
+    /// Appends the packets written by `fill` into the spare capacity of the batch, e.g. received by
+    /// `rte_eth_rx_burst`. `fill` is given a pointer to the spare capacity and its length, and returns the number of
+    /// packets it wrote.
+    ///
+    /// # Safety
+    /// `fill` must write exactly the number of packets it returns, each of them being an owned mbuf allocated by `A`.
+    #[inline]
+    pub unsafe fn fill_raw<F>(&mut self, fill: F) -> usize
+    where
+        F: FnOnce(*mut *mut ffi::rte_mbuf, usize) -> usize,
+    {
+        let len = self.0.len();
+        let filled = fill(self.0.as_mut_ptr().add(len).cast(), self.0.remaining_capacity());
+        debug_assert!(filled <= self.0.remaining_capacity());
+        self.0.set_len(len + filled);
+        filled
+    }
+
+    /// Hands over the leading packets of the batch to `consume`, e.g. sending them with `rte_eth_tx_burst`. `consume`
+    /// is given a pointer to the packets and their number, and returns the number of them it took ownership of, which
+    /// are removed from the batch without being freed.
+    ///
+    /// # Safety
+    /// `consume` must take ownership of exactly the number of leading packets it returns, and leave the others as is.
+    #[inline]
+    pub unsafe fn consume_raw<F>(&mut self, consume: F) -> usize
+    where
+        F: FnOnce(*mut *mut ffi::rte_mbuf, usize) -> usize,
+    {
+        let consumed = consume(self.0.as_mut_ptr().cast(), self.0.len());
+        debug_assert!(consumed <= self.0.len());
+        if consumed == self.0.len() {
+            self.0.set_len(0);
+        } else {
+            self.0.drain(..consumed).for_each(mem::forget);
+        }
+        consumed
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> ArrayVec<MBuf<A>, N> {
+        self.0
+    }
+}
+
+impl<A: Allocator, const N: usize> Default for PacketBatch<A, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: Allocator, const N: usize> Deref for PacketBatch<A, N> {
+    type Target = [MBuf<A>];
+
+    #[inline]
+    fn deref(&self) -> &[MBuf<A>] {
+        &self.0
+    }
+}
+
+impl<A: Allocator, const N: usize> DerefMut for PacketBatch<A, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [MBuf<A>] {
+        &mut self.0
+    }
+}
+
+impl<A: Allocator, const N: usize> From<ArrayVec<MBuf<A>, N>> for PacketBatch<A, N> {
+    #[inline]
+    fn from(pkts: ArrayVec<MBuf<A>, N>) -> Self {
+        Self(pkts)
+    }
+}
+
+/// # Panics
+/// Panics if the iterator yields more than `N` packets.
+impl<A: Allocator, const N: usize> FromIterator<MBuf<A>> for PacketBatch<A, N> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = MBuf<A>>>(iter: I) -> Self {
+        Self(ArrayVec::from_iter(iter))
+    }
+}
+
+/// # Panics
+/// Panics if the batch overflows.
+impl<A: Allocator, const N: usize> Extend<MBuf<A>> for PacketBatch<A, N> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = MBuf<A>>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+impl<A: Allocator, const N: usize> IntoIterator for PacketBatch<A, N> {
+    type Item = MBuf<A>;
+    type IntoIter = arrayvec::IntoIter<MBuf<A>, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, A: Allocator, const N: usize> IntoIterator for &'a PacketBatch<A, N> {
+    type Item = &'a MBuf<A>;
+    type IntoIter = std::slice::Iter<'a, MBuf<A>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, A: Allocator, const N: usize> IntoIterator for &'a mut PacketBatch<A, N> {
+    type Item = &'a mut MBuf<A>;
+    type IntoIter = std::slice::IterMut<'a, MBuf<A>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+impl<A: Allocator, const N: usize> fmt::Debug for PacketBatch<A, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter().map(|pkt| pkt.len())).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ptr::NonNull;
+
+    use super::*;
+    use crate::mbuf::GlobalAllocator;
+
+    fn batch(packets: &[u8]) -> PacketBatch<GlobalAllocator, 8> {
+        packets.iter().map(|&byte| MBuf::new_with_data([byte])).collect()
+    }
+
+    fn bytes<const N: usize>(batch: &PacketBatch<GlobalAllocator, N>) -> Vec<u8> {
+        batch.iter().map(|pkt| pkt[0]).collect()
+    }
+
+    #[test]
+    fn test_split_and_partition() {
+        let mut pkts = batch(&[0, 1, 2, 3, 4]);
+        let tail = pkts.split_off(3);
+        assert_eq!((bytes(&pkts), bytes(&tail)), (vec![0, 1, 2], vec![3, 4]));
+
+        let (even, odd) = pkts.partition(|pkt| pkt[0] % 2 == 0);
+        assert_eq!((bytes(&even), bytes(&odd)), (vec![0, 2], vec![1]));
+
+        let [low, high] = batch(&[5, 0, 9, 7]).classify(|pkt| match pkt[0] {
+            0 => None,
+            1..=6 => Some(0),
+            _ => Some(1),
+        });
+        assert_eq!((bytes(&low), bytes(&high)), (vec![5], vec![9, 7]));
+    }
+
+    #[test]
+    fn test_raw_ownership() {
+        let mut pkts = batch(&[0, 1, 2]);
+        let mut sent = vec![];
+        let consumed = unsafe {
+            pkts.consume_raw(|ptr, len| {
+                assert_eq!(len, 3);
+                sent.extend((0..2).map(|i| MBuf::<GlobalAllocator>::from_raw(NonNull::new(*ptr.add(i)).unwrap())));
+                2
+            })
+        };
+        assert_eq!(consumed, 2);
+        assert_eq!(bytes(&pkts), [2]);
+
+        let filled = unsafe {
+            pkts.fill_raw(|ptr, spare| {
+                assert_eq!(spare, 7);
+                for (i, pkt) in sent.drain(..).enumerate() {
+                    *ptr.add(i) = pkt.into_raw().as_ptr();
+                }
+                2
+            })
+        };
+        assert_eq!(filled, 2);
+        assert_eq!(bytes(&pkts), [2, 0, 1]);
+    }
+}
@@ -1,6 +1,6 @@
 use std::{marker::PhantomData, ptr::NonNull};
 
-use ffi::_bindgen_ty_13::{RTE_MBUF_L2_LEN_BITS, RTE_MBUF_L3_LEN_BITS};
+use ffi::{_RTE_MBUF_L2_LEN_BITS, _RTE_MBUF_L3_LEN_BITS, _RTE_MBUF_OUTL2_LEN_BITS, _RTE_MBUF_OUTL3_LEN_BITS};
 
 use super::ptr::AsPtr;
 use crate::flags::PktTxOffload;
@@ -24,7 +24,7 @@ pub trait MetadataExt: AsPtr {
     /// Sets the [`l2_len`](https://doc.dpdk.org/api-2.2/structrte__mbuf.html#aa25a7c259438b9eba28bcedc33846620) field.
     #[inline]
     fn set_l2_len(&mut self, len: u64) {
-        assert!(len < 1 << RTE_MBUF_L2_LEN_BITS);
+        assert!(len < 1 << _RTE_MBUF_L2_LEN_BITS);
         unsafe {
             let mbuf = self.as_ptr().as_mut();
             mbuf.__bindgen_anon_3.__bindgen_anon_1.set_l2_len(len);
@@ -34,13 +34,45 @@ pub trait MetadataExt: AsPtr {
     /// Sets the [`l3_len`](https://doc.dpdk.org/api-2.2/structrte__mbuf.html#a82a34cb6d5935a8c0f043f2783d6b42d) field.
     #[inline]
     fn set_l3_len(&mut self, len: u64) {
-        assert!(len < 1 << RTE_MBUF_L3_LEN_BITS);
+        assert!(len < 1 << _RTE_MBUF_L3_LEN_BITS);
         unsafe {
             let mbuf = self.as_ptr().as_mut();
             mbuf.__bindgen_anon_3.__bindgen_anon_1.set_l3_len(len);
         }
     }
 
+    /// Returns the [`l2_len`](https://doc.dpdk.org/api-2.2/structrte__mbuf.html#aa25a7c259438b9eba28bcedc33846620) field.
+    #[inline]
+    fn l2_len(&self) -> u64 {
+        unsafe { self.as_ptr().as_ref().__bindgen_anon_3.__bindgen_anon_1.l2_len() }
+    }
+
+    /// Sets the [`outer_l2_len`](https://doc.dpdk.org/api-22.11/structrte__mbuf.html) field, used by tunnel offloads.
+    #[inline]
+    fn set_outer_l2_len(&mut self, len: u64) {
+        assert!(len < 1 << _RTE_MBUF_OUTL2_LEN_BITS);
+        unsafe {
+            let mbuf = self.as_ptr().as_mut();
+            mbuf.__bindgen_anon_3.__bindgen_anon_1.set_outer_l2_len(len);
+        }
+    }
+
+    /// Sets the [`outer_l3_len`](https://doc.dpdk.org/api-22.11/structrte__mbuf.html) field, used by tunnel offloads.
+    #[inline]
+    fn set_outer_l3_len(&mut self, len: u64) {
+        assert!(len < 1 << _RTE_MBUF_OUTL3_LEN_BITS);
+        unsafe {
+            let mbuf = self.as_ptr().as_mut();
+            mbuf.__bindgen_anon_3.__bindgen_anon_1.set_outer_l3_len(len);
+        }
+    }
+
+    /// Returns the TX offload flags currently enabled in the [`ol_flags`](https://doc.dpdk.org/api-2.2/structrte__mbuf.html#a319d580a6e1ef13692631d7b0d6d5c98) field.
+    #[inline]
+    fn tx_offload_flags(&self) -> PktTxOffload {
+        PktTxOffload::from_bits_truncate(unsafe { self.as_ptr().as_ref() }.ol_flags)
+    }
+
     /// Enables (bitwise-or) the given flags on the [`ol_flags`](https://doc.dpdk.org/api-2.2/structrte__mbuf.html#a319d580a6e1ef13692631d7b0d6d5c98) field.
     ///
     /// See also: [`PktTxOffload`].
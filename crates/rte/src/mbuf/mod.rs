@@ -1,4 +1,5 @@
 mod allocator;
+mod batch;
 mod metadata;
 mod ptr;
 
@@ -11,10 +12,16 @@ use std::{
     slice,
 };
 
+use crate::{
+    flags::PktTxOffload,
+    net::{self, Header, Headers, Ipv4Hdr, Ipv6Hdr, L3Hdr, L4Hdr, PacketType, TcpHdr, UdpHdr},
+};
+
 #[cfg(any(test, feature = "test-utils"))]
 pub use self::allocator::GlobalAllocator;
 pub use self::{
     allocator::Allocator,
+    batch::PacketBatch,
     metadata::{MetadataExt, MetadataPart},
 };
 
@@ -52,7 +59,8 @@ pub use self::{
 /// - The DPDK documentation on the [Mbuf Library](https://doc.dpdk.org/guides-21.08/prog_guide/mbuf_lib.html).
 ///
 /// # Implementation notes
-/// - This wrapper completely ignores all but the first segment of an mbuf.
+/// - This wrapper completely ignores all but the first segment of an mbuf, chained mbufs (e.g. reassembled packets)
+///   can be turned into a single segment using [`MBuf::linearize`].
 #[repr(transparent)]
 pub struct MBuf<A>
 where
@@ -113,6 +121,26 @@ where
         self.ptr.as_ptr()
     }
 
+    /// Consumes the `MBuf`, returning the pointer to the `rte_mbuf` struct without freeing it.
+    ///
+    /// The caller becomes responsible for the mbuf, which can be turned back into an `MBuf` using [`Self::from_raw`].
+    #[inline]
+    pub fn into_raw(self) -> NonNull<ffi::rte_mbuf> {
+        let ptr = self.ptr;
+        mem::forget(self);
+        ptr
+    }
+
+    /// Constructs an `MBuf` from a pointer previously returned by [`Self::into_raw`] (or received from DPDK).
+    ///
+    /// # Safety
+    /// `ptr` must point to a valid mbuf that was allocated by an allocator of type `A`, and must not be owned by anything
+    /// else: the returned `MBuf` will free it when dropped.
+    #[inline]
+    pub unsafe fn from_raw(ptr: NonNull<ffi::rte_mbuf>) -> Self {
+        Self { ptr, _marker: PhantomData }
+    }
+
     /* Helper functions for Deref and DerefMut impls. */
 
     fn data_ptr(&self) -> *mut u8 {
@@ -150,6 +178,177 @@ where
 
         (data, metadata)
     }
+
+    /// Returns the total length of the packet, across all segments of the mbuf chain.
+    #[inline]
+    pub fn pkt_len(&self) -> usize {
+        unsafe { self.ptr.as_ref() }.pkt_len as usize
+    }
+
+    /// Returns the number of segments in the mbuf chain.
+    #[inline]
+    pub fn nb_segs(&self) -> u16 {
+        unsafe { self.ptr.as_ref() }.nb_segs
+    }
+
+    /// Returns the IO address of the start of the (first segment's) data, e.g. for DMA.
+    #[inline]
+    pub fn data_iova(&self) -> u64 {
+        unsafe { ffi::_rte_mbuf_data_iova(self.ptr.as_ptr()) }
+    }
+
+    /// Removes `len` bytes from the beginning of the buffer, e.g. for stripping a header.
+    ///
+    /// Returns `false` (leaving the mbuf unmodified) if `len` is larger than the buffer's length.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__mbuf_8h.html>
+    #[inline]
+    pub fn adj(&mut self, len: u16) -> bool {
+        !unsafe { ffi::_rte_pktmbuf_adj(self.ptr.as_ptr(), len) }.is_null()
+    }
+
+    /// Prepends `len` bytes to the beginning of the buffer using its headroom, e.g. for adding a header, and returns the
+    /// prepended part of the buffer, which is zeroed (as headroom may be uninitialized memory).
+    ///
+    /// Returns `None` (leaving the mbuf unmodified) if there is not enough headroom.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__mbuf_8h.html>
+    #[inline]
+    pub fn prepend(&mut self, len: u16) -> Option<&mut [u8]> {
+        let data = unsafe { ffi::_rte_pktmbuf_prepend(self.ptr.as_ptr(), len) } as *mut u8;
+        (!data.is_null()).then(|| unsafe {
+            data.write_bytes(0, len.into());
+            slice::from_raw_parts_mut(data, len.into())
+        })
+    }
+
+    /// Copies the data of all segments of the mbuf chain into the first segment, so it can be accessed as a single
+    /// contiguous buffer.
+    ///
+    /// Returns `false` if the first segment does not have enough tailroom to hold the entire packet.
+    #[inline]
+    pub fn linearize(&mut self) -> bool {
+        unsafe { ffi::_rte_pktmbuf_linearize(self.ptr.as_ptr()) == 0 }
+    }
+
+    /// Parses the packet's headers, see [`parse_headers`](crate::net::parse_headers).
+    #[inline]
+    pub fn parse_headers(&self) -> Option<Headers<'_>> {
+        net::parse_headers(self)
+    }
+
+    /// Returns a [`Display`](fmt::Display)-able dissection of the packet, see [`Dissect`](crate::net::Dissect).
+    #[inline]
+    pub fn dissect(&self) -> net::Dissect<'_> {
+        net::Dissect::new(self)
+    }
+
+    /// Returns the packet's type as reported by the NIC on RX, which is unknown for NICs (or drivers) which don't support
+    /// packet type parsing.
+    ///
+    /// See also: [`MBuf::classify`].
+    #[inline]
+    pub fn packet_type(&self) -> PacketType {
+        PacketType::from_raw(unsafe { self.ptr.as_ref().__bindgen_anon_2.packet_type })
+    }
+
+    /// Determines the packet's type by parsing its headers in software, giving consistent results regardless of the
+    /// NIC's support for packet type parsing.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__net_8h.html>
+    #[inline]
+    pub fn classify(&self) -> PacketType {
+        let raw = unsafe { ffi::rte_net_get_ptype(self.ptr.as_ptr(), std::ptr::null_mut(), ffi::RTE_PTYPE_ALL_MASK) };
+        PacketType::from_raw(raw)
+    }
+
+    /// Fills in the IPv4 header checksum of the packet, or zeroes it if its computation is offloaded to the NIC (i.e.
+    /// [`PktTxOffload::IP_CKSUM`] is enabled, see [`MetadataExt::enable_ol_flags`]).
+    ///
+    /// Returns `false` (leaving the mbuf unmodified) if the packet is not an IPv4 packet.
+    #[inline]
+    pub fn fill_ipv4_checksum(&mut self) -> bool {
+        let offloaded = self.tx_offload_flags().contains(PktTxOffload::IP_CKSUM);
+        let (l3_offset, l3_len) = match self.parse_headers() {
+            Some(headers @ Headers { l3: Some(L3Hdr::Ipv4(_)), .. }) => (headers.l3_offset(), headers.l3_len),
+            _ => return false,
+        };
+
+        let header = &mut self[l3_offset..l3_offset + l3_len];
+        let set_cksum = |header: &mut [u8], cksum| Ipv4Hdr::mut_from_prefix(header).unwrap().0.hdr_checksum.set(cksum);
+        set_cksum(header, 0);
+        if !offloaded {
+            set_cksum(header, net::ipv4_cksum(header));
+        }
+        true
+    }
+
+    /// Fills in the TCP/UDP checksum of the packet.
+    ///
+    /// If the checksum's computation is offloaded to the NIC (i.e. [`PktTxOffload::TCP_CKSUM`],
+    /// [`PktTxOffload::UDP_CKSUM`] or [`PktTxOffload::TCP_SEG`] are enabled, see [`MetadataExt::enable_ol_flags`]), only
+    /// the pseudo-header checksum is filled in, as required by the NIC.
+    ///
+    /// Returns `false` (leaving the mbuf unmodified) if the packet is not a TCP or UDP over IPv4/IPv6 packet, or if the
+    /// checksum is computed in software but the IP header's length field is larger than the packet.
+    #[inline]
+    pub fn fill_l4_checksum(&mut self) -> bool {
+        let flags = self.tx_offload_flags();
+        let (l3_offset, l4_offset, is_ipv4, is_udp) = match self.parse_headers() {
+            Some(headers) => match (headers.l3, headers.l4) {
+                (Some(l3 @ (L3Hdr::Ipv4(_) | L3Hdr::Ipv6(_))), Some(l4 @ (L4Hdr::Tcp(_) | L4Hdr::Udp(_)))) => (
+                    headers.l3_offset(),
+                    headers.l4_offset(),
+                    matches!(l3, L3Hdr::Ipv4(_)),
+                    matches!(l4, L4Hdr::Udp(_)),
+                ),
+                _ => return false,
+            },
+            None => return false,
+        };
+
+        let l4_cksum_flag = if is_udp { PktTxOffload::UDP_CKSUM } else { PktTxOffload::TCP_CKSUM };
+        let offloaded = flags & PktTxOffload::L4_MASK == l4_cksum_flag || flags.contains(PktTxOffload::TCP_SEG);
+
+        let (l3, l4) = self[l3_offset..].split_at_mut(l4_offset - l3_offset);
+        if !offloaded {
+            let l4_len = if is_ipv4 {
+                let (ipv4, _) = Ipv4Hdr::ref_from_prefix(l3).unwrap();
+                usize::from(ipv4.total_length.get()).saturating_sub(ipv4.header_len())
+            } else {
+                usize::from(Ipv6Hdr::ref_from_prefix(l3).unwrap().0.payload_len.get())
+            };
+            if l4_len > l4.len() {
+                return false;
+            }
+        }
+        let set_cksum = |l4: &mut [u8], cksum| {
+            if is_udp {
+                UdpHdr::mut_from_prefix(l4).unwrap().0.dgram_cksum.set(cksum)
+            } else {
+                TcpHdr::mut_from_prefix(l4).unwrap().0.cksum.set(cksum)
+            }
+        };
+        set_cksum(l4, 0);
+
+        let cksum = if is_ipv4 {
+            let (ipv4, _) = Ipv4Hdr::ref_from_prefix(l3).unwrap();
+            if offloaded {
+                net::ipv4_phdr_cksum(ipv4, flags)
+            } else {
+                net::ipv4_udptcp_cksum(ipv4, l4)
+            }
+        } else {
+            let (ipv6, _) = Ipv6Hdr::ref_from_prefix(l3).unwrap();
+            if offloaded {
+                net::ipv6_phdr_cksum(ipv6, flags)
+            } else {
+                net::ipv6_udptcp_cksum(ipv6, l4)
+            }
+        };
+        set_cksum(l4, cksum);
+        true
+    }
 }
 
 /// These method are equivilent to their [`Vec`] counterparts.
@@ -168,8 +367,8 @@ where
         unsafe {
             let ffi::rte_mbuf { buf_addr, data_off, data_len, buf_len, .. } = *self.ptr.as_ref();
             let spare_cap = buf_addr.add(data_off.into()).add(data_len.into());
-            let buf_end = spare_cap.add(buf_len.into());
-            slice::from_raw_parts_mut(spare_cap as _, spare_cap.offset_from(buf_end) as usize)
+            let tailroom = buf_len - data_off - data_len;
+            slice::from_raw_parts_mut(spare_cap as _, tailroom.into())
         }
     }
 
@@ -274,21 +473,20 @@ where
 }
 
 #[cfg(any(test, feature = "test-utils"))]
-/// Small helper for allocating and collecting an [`ArrayVec<MBuf>`](arrayvec::ArrayVec) from an iterator over byte slices,
+/// Small helper for allocating and collecting a [`PacketBatch`] from an iterator over byte slices,
 /// using a [`GlobalAllocator`] as the mbuf allocator.
 ///
 /// # Example
 /// ```rust
-/// # use arrayvec::ArrayVec;
-/// # use rte::mbuf::{alloc_mbufs, MBuf};
+/// # use rte::mbuf::{alloc_mbufs, PacketBatch};
 /// #
 /// let packets = [b"\x00\x01", b"\x02\x03"];
-/// let mbufs: ArrayVec<MBuf<_>, 2> = alloc_mbufs(packets);
+/// let mbufs: PacketBatch<_, 2> = alloc_mbufs(packets);
 /// assert_eq!(&mbufs[0][..], b"\x00\x01");
 /// assert_eq!(&mbufs[1][..], b"\x02\x03");
 /// ```
 pub fn alloc_mbufs<const CAP: usize, B: AsRef<[u8]>, I: IntoIterator<Item = B>>(
     iter: I,
-) -> arrayvec::ArrayVec<MBuf<GlobalAllocator>, CAP> {
+) -> PacketBatch<GlobalAllocator, CAP> {
     iter.into_iter().map(MBuf::<GlobalAllocator>::new_with_data).collect()
 }
@@ -2,13 +2,14 @@ use std::{
     ffi::CString,
     fmt,
     mem::size_of_val,
+    os::raw::c_uint,
     ptr::{addr_of, NonNull},
     slice,
 };
 
-use rte_error::ReturnValue as _;
+use rte_error::{Error, ReturnValue as _};
 
-use crate::{memory::SocketId, Result};
+use crate::{ethdev::EthDev, memory::SocketId, Result};
 
 #[repr(transparent)]
 pub struct MemoryPool(pub(crate) NonNull<ffi::rte_mempool>);
@@ -52,6 +53,40 @@ impl MemoryPool {
         .map(Self)
     }
 
+    /// Creates a memory pool of `size` mbufs for the RX queues of `dev`, named after its port id (so only one can exist
+    /// per port at a time).
+    ///
+    /// The pool is allocated on the socket of the device (or any socket if it's unknown, with a warning in DPDK's log),
+    /// and its data room fits a frame of the current MTU of the device in a single mbuf. The data room size is derived
+    /// from the MTU rather than supplied by the caller, so there is no caller-supplied size to check.
+    pub fn for_port(dev: &EthDev, size: u32, cache_size: u32) -> Result<Self> {
+        let socket_id = dev.socket_id().ok();
+        if socket_id.is_none() {
+            let format = b"socket of port %u unknown, allocating its memory pool on any socket\n\0";
+            unsafe {
+                ffi::rte_log(
+                    ffi::RTE_LOG_WARNING,
+                    ffi::RTE_LOGTYPE_MEMPOOL,
+                    format.as_ptr().cast(),
+                    c_uint::from(dev.port_id()),
+                )
+            };
+        }
+
+        let info = dev.info()?;
+        // the same L2 overhead as DPDK's
+        let overhead = if info.max_mtu != u16::MAX && info.max_rx_pktlen > u32::from(info.max_mtu) {
+            info.max_rx_pktlen - u32::from(info.max_mtu)
+        } else {
+            ffi::RTE_ETHER_HDR_LEN + ffi::RTE_ETHER_CRC_LEN
+        };
+        let frame_size = ffi::RTE_PKTMBUF_HEADROOM + u32::from(dev.mtu()?) + overhead;
+        let data_room_size =
+            u16::try_from(frame_size.max(ffi::RTE_MBUF_DEFAULT_BUF_SIZE)).map_err(|_| Error(libc::EINVAL))?;
+
+        Self::new(format!("port{}_pool", dev.port_id()), size, cache_size, 0, data_room_size, socket_id)
+    }
+
     #[inline]
     pub fn name(&self) -> &[u8] {
         let name = unsafe {
@@ -81,6 +116,13 @@ impl MemoryPool {
         unsafe { (*self.0.as_ptr()).cache_size }
     }
 
+    /// Returns the socket the memory pool was allocated on, or `None` if it was allocated on any socket.
+    #[inline]
+    pub fn socket_id(&self) -> Option<SocketId> {
+        let socket_id = unsafe { (*self.0.as_ptr()).socket_id };
+        SocketId::new(socket_id as u32)
+    }
+
     /// See also: <https://doc.dpdk.org/api-21.08/rte__mbuf_8h.html#afc63705bb85669e2a1ea17e3279d59ce>
     #[inline]
     pub fn private_data_size(&self) -> u16 {
@@ -114,6 +156,47 @@ impl MemoryPool {
     }
 }
 
+/// The parameters of a memory pool of packets, e.g. loaded from a configuration file (with the `serde` feature).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(default))]
+pub struct MemoryPoolParams {
+    pub name: String,
+    pub size: u32,
+    pub cache_size: u32,
+    pub private_size: u16,
+    pub data_room_size: u16,
+    /// The socket to allocate the memory pool on, or `None` for any.
+    pub socket_id: Option<u32>,
+}
+
+impl Default for MemoryPoolParams {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            size: 0,
+            cache_size: 0,
+            private_size: 0,
+            data_room_size: ffi::RTE_MBUF_DEFAULT_BUF_SIZE as u16,
+            socket_id: None,
+        }
+    }
+}
+
+impl MemoryPoolParams {
+    /// Creates a memory pool with these parameters, see [`MemoryPool::new`].
+    #[inline]
+    pub fn create(&self) -> Result<MemoryPool> {
+        MemoryPool::new(
+            self.name.as_str(),
+            self.size,
+            self.cache_size,
+            self.private_size,
+            self.data_room_size,
+            self.socket_id.and_then(SocketId::new),
+        )
+    }
+}
+
 impl fmt::Debug for MemoryPool {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("MemoryPool")
@@ -133,3 +216,27 @@ impl Drop for MemoryPool {
         unsafe { ffi::rte_mempool_free(self.0.as_ptr()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::{FakePort, TestPool};
+
+    #[rte_test]
+    fn test_for_port() {
+        let mempool = TestPool::new(1023);
+        let port = FakePort::null("for_port", 128, &mempool).unwrap();
+        let dev = port.dev();
+
+        let pool = MemoryPool::for_port(dev, 1023, 0).unwrap();
+        assert_eq!(pool.name(), format!("port{}_pool", dev.port_id()).as_bytes());
+        assert_eq!(pool.socket_id(), dev.socket_id().ok());
+        let frame_size = u32::from(dev.mtu().unwrap()) + ffi::RTE_ETHER_HDR_LEN + ffi::RTE_ETHER_CRC_LEN;
+        assert!(u32::from(pool.data_room_size()) >= ffi::RTE_PKTMBUF_HEADROOM + frame_size);
+
+        // named after the port, so only one can exist at a time
+        assert_eq!(MemoryPool::for_port(dev, 1023, 0).err(), Some(Error(libc::EEXIST)));
+    }
+}
@@ -0,0 +1,254 @@
+//! Based on DPDK's `rte_meter.h` API: <https://doc.dpdk.org/api-22.11/rte__meter_8h.html>
+//!
+//! Software traffic metering, using either the single rate ([RFC 2697](https://www.rfc-editor.org/rfc/rfc2697)) or
+//! the two rate ([RFC 2698](https://www.rfc-editor.org/rfc/rfc2698)) three color marker algorithms. Packets are
+//! colored by a [`Meter`], which can then be used for policing (e.g. dropping red packets).
+//!
+//! Meters are not thread-safe, so a meter is usually kept per flow (or per flow and lcore).
+//! ```no_run
+//! # use rte::{cycles, meter::{Color, Meter, Profile, SrTcm, SrTcmParams}};
+//! // 1 Gbps, with bursts of up to 64 KiB
+//! let profile = Profile::<SrTcm>::new(&SrTcmParams { cir: 125_000_000, cbs: 65_536, ebs: 65_536 }).unwrap();
+//! let mut meter = Meter::new(&profile).unwrap();
+//! assert_eq!(meter.color_blind_check(cycles::tsc(), 1500), Color::Green);
+//! ```
+
+use std::{fmt, os::raw::c_int};
+
+use rte_error::ReturnValue as _;
+
+use crate::Result;
+
+/// Parameters of a single rate three color marker.
+///
+/// The rate is in bytes per second, and the burst sizes are in bytes.
+pub type SrTcmParams = ffi::rte_meter_srtcm_params;
+
+/// Parameters of a two rate three color marker.
+///
+/// The rates are in bytes per second, and the burst sizes are in bytes.
+pub type TrTcmParams = ffi::rte_meter_trtcm_params;
+
+/// The color of a packet, as determined by a [`Meter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Color {
+    Green,
+    Yellow,
+    Red,
+}
+
+impl Color {
+    #[inline]
+    pub(crate) fn as_raw(self) -> u32 {
+        match self {
+            Self::Green => ffi::rte_color::RTE_COLOR_GREEN,
+            Self::Yellow => ffi::rte_color::RTE_COLOR_YELLOW,
+            Self::Red => ffi::rte_color::RTE_COLOR_RED,
+        }
+    }
+
+    #[inline]
+    fn from_raw(color: u32) -> Self {
+        match color {
+            ffi::rte_color::RTE_COLOR_GREEN => Self::Green,
+            ffi::rte_color::RTE_COLOR_YELLOW => Self::Yellow,
+            _ => Self::Red,
+        }
+    }
+}
+
+mod sealed {
+    use std::os::raw::c_int;
+
+    /// Maps the metering algorithm to the matching DPDK functions and types.
+    pub trait Sealed {
+        type Params: Copy;
+        type RawProfile: Copy + Default;
+        type RawMeter: Copy + Default;
+
+        unsafe fn profile_config(profile: *mut Self::RawProfile, params: *mut Self::Params) -> c_int;
+        unsafe fn config(meter: *mut Self::RawMeter, profile: *mut Self::RawProfile) -> c_int;
+        unsafe fn color_blind_check(
+            meter: *mut Self::RawMeter,
+            profile: *mut Self::RawProfile,
+            time: u64,
+            pkt_len: u32,
+        ) -> u32;
+        unsafe fn color_aware_check(
+            meter: *mut Self::RawMeter,
+            profile: *mut Self::RawProfile,
+            time: u64,
+            pkt_len: u32,
+            color: u32,
+        ) -> u32;
+    }
+}
+
+/// A metering algorithm, implemented by [`SrTcm`] and [`TrTcm`].
+pub trait Algorithm: sealed::Sealed {}
+
+/// The single rate three color marker, see [RFC 2697](https://www.rfc-editor.org/rfc/rfc2697).
+#[derive(Debug)]
+pub enum SrTcm {}
+
+/// The two rate three color marker, see [RFC 2698](https://www.rfc-editor.org/rfc/rfc2698).
+#[derive(Debug)]
+pub enum TrTcm {}
+
+impl Algorithm for SrTcm {}
+impl Algorithm for TrTcm {}
+
+impl sealed::Sealed for SrTcm {
+    type Params = SrTcmParams;
+    type RawProfile = ffi::rte_meter_srtcm_profile;
+    type RawMeter = ffi::rte_meter_srtcm;
+
+    unsafe fn profile_config(profile: *mut Self::RawProfile, params: *mut Self::Params) -> c_int {
+        ffi::rte_meter_srtcm_profile_config(profile, params)
+    }
+
+    unsafe fn config(meter: *mut Self::RawMeter, profile: *mut Self::RawProfile) -> c_int {
+        ffi::rte_meter_srtcm_config(meter, profile)
+    }
+
+    unsafe fn color_blind_check(
+        meter: *mut Self::RawMeter,
+        profile: *mut Self::RawProfile,
+        time: u64,
+        pkt_len: u32,
+    ) -> u32 {
+        ffi::_rte_meter_srtcm_color_blind_check(meter, profile, time, pkt_len)
+    }
+
+    unsafe fn color_aware_check(
+        meter: *mut Self::RawMeter,
+        profile: *mut Self::RawProfile,
+        time: u64,
+        pkt_len: u32,
+        color: u32,
+    ) -> u32 {
+        ffi::_rte_meter_srtcm_color_aware_check(meter, profile, time, pkt_len, color)
+    }
+}
+
+impl sealed::Sealed for TrTcm {
+    type Params = TrTcmParams;
+    type RawProfile = ffi::rte_meter_trtcm_profile;
+    type RawMeter = ffi::rte_meter_trtcm;
+
+    unsafe fn profile_config(profile: *mut Self::RawProfile, params: *mut Self::Params) -> c_int {
+        ffi::rte_meter_trtcm_profile_config(profile, params)
+    }
+
+    unsafe fn config(meter: *mut Self::RawMeter, profile: *mut Self::RawProfile) -> c_int {
+        ffi::rte_meter_trtcm_config(meter, profile)
+    }
+
+    unsafe fn color_blind_check(
+        meter: *mut Self::RawMeter,
+        profile: *mut Self::RawProfile,
+        time: u64,
+        pkt_len: u32,
+    ) -> u32 {
+        ffi::_rte_meter_trtcm_color_blind_check(meter, profile, time, pkt_len)
+    }
+
+    unsafe fn color_aware_check(
+        meter: *mut Self::RawMeter,
+        profile: *mut Self::RawProfile,
+        time: u64,
+        pkt_len: u32,
+        color: u32,
+    ) -> u32 {
+        ffi::_rte_meter_trtcm_color_aware_check(meter, profile, time, pkt_len, color)
+    }
+}
+
+/// The configuration of a [`Meter`], which can be shared by any number of meters.
+pub struct Profile<A: Algorithm>(A::RawProfile);
+
+impl<A: Algorithm> Profile<A> {
+    /// Creates a profile, failing if any of the parameters is 0.
+    #[inline]
+    pub fn new(params: &A::Params) -> Result<Self> {
+        let mut profile = A::RawProfile::default();
+        let mut params = *params;
+        unsafe { A::profile_config(&mut profile, &mut params) }.rte_ok()?;
+        Ok(Self(profile))
+    }
+}
+
+impl<A: Algorithm> fmt::Debug for Profile<A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Profile").finish_non_exhaustive()
+    }
+}
+
+/// The run-time state of a three color marker, which colors packets according to its [`Profile`].
+pub struct Meter<'p, A: Algorithm> {
+    meter: A::RawMeter,
+    profile: &'p Profile<A>,
+}
+
+impl<'p, A: Algorithm> Meter<'p, A> {
+    /// Creates a meter, whose buckets start full.
+    #[inline]
+    pub fn new(profile: &'p Profile<A>) -> Result<Self> {
+        let mut meter = A::RawMeter::default();
+        unsafe { A::config(&mut meter, &profile.0 as *const _ as *mut _) }.rte_ok()?;
+        Ok(Self { meter, profile })
+    }
+
+    #[inline]
+    pub fn profile(&self) -> &'p Profile<A> {
+        self.profile
+    }
+
+    /// Colors a packet of `pkt_len` bytes arriving at `time` (in [TSC cycles](crate::cycles::tsc)), ignoring any color
+    /// it was previously assigned.
+    #[inline]
+    pub fn color_blind_check(&mut self, time: u64, pkt_len: u32) -> Color {
+        // the profile is only read by DPDK
+        let profile = &self.profile.0 as *const _ as *mut _;
+        Color::from_raw(unsafe { A::color_blind_check(&mut self.meter, profile, time, pkt_len) })
+    }
+
+    /// Colors a packet of `pkt_len` bytes arriving at `time` (in [TSC cycles](crate::cycles::tsc)), which was
+    /// previously assigned `color` (e.g. by an upstream meter). The resulting color is never "better" than `color`.
+    #[inline]
+    pub fn color_aware_check(&mut self, time: u64, pkt_len: u32, color: Color) -> Color {
+        let profile = &self.profile.0 as *const _ as *mut _;
+        Color::from_raw(unsafe { A::color_aware_check(&mut self.meter, profile, time, pkt_len, color.as_raw()) })
+    }
+}
+
+impl<A: Algorithm> fmt::Debug for Meter<'_, A> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Meter").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::cycles;
+
+    #[rte_test]
+    fn test_srtcm() {
+        let profile = Profile::<SrTcm>::new(&SrTcmParams { cir: 1000, cbs: 1000, ebs: 1000 }).unwrap();
+        let mut meter = Meter::new(&profile).unwrap();
+
+        // the buckets start full, and are refilled at a negligible rate
+        let time = cycles::tsc();
+        assert_eq!(meter.color_blind_check(time, 1000), Color::Green);
+        assert_eq!(meter.color_blind_check(time, 1000), Color::Yellow);
+        assert_eq!(meter.color_blind_check(time, 1000), Color::Red);
+
+        let mut meter = Meter::new(&profile).unwrap();
+        assert_eq!(meter.color_aware_check(time, 100, Color::Yellow), Color::Yellow);
+
+        assert!(Profile::<SrTcm>::new(&SrTcmParams { cir: 0, cbs: 0, ebs: 0 }).is_err());
+    }
+}
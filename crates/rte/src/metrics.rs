@@ -0,0 +1,183 @@
+//! Based on DPDK's `rte_metrics.h` API: <https://doc.dpdk.org/api-22.11/rte__metrics_8h.html>
+//!
+//! Publishes application-defined counters through the metrics library, alongside DPDK's own metrics (e.g. the
+//! [bitrates](crate::bitrate) and [latencies](crate::latencystats)), so that they are read by the same tools (e.g.
+//! `dpdk-proc-info --metrics`) and, if [registered](MetricsGroup::register_telemetry), on the telemetry socket.
+//!
+//! Groups of counters are declared with [`metrics_counters!`](crate::metrics_counters):
+//! ```rust,ignore
+//! rte::metrics_counters! {
+//!     /// The packets dropped by the application.
+//!     pub struct AppDrops {
+//!         acl_denied,
+//!         no_route,
+//!     }
+//! }
+//!
+//! metrics::init(None);
+//! let group = MetricsGroup::<AppDrops>::register()?;
+//! group.register_telemetry("/myapp/drops")?;
+//! group.publish(Some(dev.port_id()), &AppDrops { acl_denied: 1, no_route: 2 })?;
+//! ```
+
+use std::{ffi::CString, fmt, marker::PhantomData, os::raw::c_char, ptr};
+
+use rte_error::{Error, ReturnValue as _};
+
+use crate::{memory::SocketId, telemetry, Result};
+
+/// Declares a struct of `u64` counters implementing [`Counters`], named after its fields.
+#[macro_export]
+macro_rules! metrics_counters {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($(#[$field_meta:meta])* $field:ident),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+        $vis struct $name {
+            $($(#[$field_meta])* pub $field: u64,)*
+        }
+
+        impl $crate::metrics::Counters for $name {
+            const NAMES: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            fn values(&self) -> Vec<u64> {
+                vec![$(self.$field),*]
+            }
+
+            fn from_values(values: &[u64]) -> Self {
+                let mut values = values.iter().copied();
+                Self { $($field: values.next().unwrap_or_default(),)* }
+            }
+        }
+    };
+}
+
+/// A group of counters published together, usually declared with [`metrics_counters!`](crate::metrics_counters).
+pub trait Counters: Sized {
+    /// The names of the counters, in the order of their values.
+    const NAMES: &'static [&'static str];
+
+    fn values(&self) -> Vec<u64>;
+
+    fn from_values(values: &[u64]) -> Self;
+}
+
+/// Initializes the metrics library (on the given socket), if it isn't already.
+#[inline]
+pub fn init(socket_id: Option<SocketId>) {
+    unsafe { ffi::rte_metrics_init(socket_id.map(|id| id.get() as i32).unwrap_or(-1)) }
+}
+
+fn port_or_global(port_id: Option<u16>) -> i32 {
+    port_id.map_or(ffi::RTE_METRICS_GLOBAL, i32::from)
+}
+
+/// The metrics of a group of [`Counters`], registered with the metrics library (whose metrics can't be unregistered,
+/// so each group should be registered once).
+pub struct MetricsGroup<C> {
+    /// The key of the first counter, the others following it.
+    key: u16,
+    _marker: PhantomData<fn() -> C>,
+}
+
+impl<C> Clone for MetricsGroup<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C> Copy for MetricsGroup<C> {}
+
+impl<C: Counters> MetricsGroup<C> {
+    /// Registers the names of the counters (the metrics library must have been [initialized](init)).
+    pub fn register() -> Result<Self> {
+        let names = C::NAMES.iter().map(|&name| CString::new(name).unwrap()).collect::<Vec<_>>();
+        let names = names.iter().map(|name| name.as_ptr()).collect::<Vec<*const c_char>>();
+
+        let key = unsafe { ffi::rte_metrics_reg_names(names.as_ptr(), names.len() as u16) }.rte_ok()?;
+        Ok(Self { key: key as u16, _marker: PhantomData })
+    }
+
+    /// Publishes the values of the counters for a port, or global ones if `port_id` is `None`.
+    #[inline]
+    pub fn publish(&self, port_id: Option<u16>, counters: &C) -> Result<()> {
+        let values = counters.values();
+        unsafe {
+            ffi::rte_metrics_update_values(port_or_global(port_id), self.key, values.as_ptr(), values.len() as u32)
+        }
+        .rte_ok()?;
+        Ok(())
+    }
+
+    /// Returns the values of the counters last published for a port, or the global ones if `port_id` is `None`.
+    pub fn get(&self, port_id: Option<u16>) -> Result<C> {
+        let port_id = port_or_global(port_id);
+        let len = unsafe { ffi::rte_metrics_get_values(port_id, ptr::null_mut(), 0) }.rte_ok()? as usize;
+        let mut metrics = vec![ffi::rte_metric_value::default(); len];
+        let len = unsafe { ffi::rte_metrics_get_values(port_id, metrics.as_mut_ptr(), len as u16) }.rte_ok()? as usize;
+
+        let mut values = vec![0; C::NAMES.len()];
+        for metric in &metrics[..len.min(metrics.len())] {
+            if let Some(value) = (metric.key as usize).checked_sub(self.key.into()).and_then(|i| values.get_mut(i)) {
+                *value = metric.value;
+            }
+        }
+        Ok(C::from_values(&values))
+    }
+
+    /// Registers a telemetry command (e.g. `/myapp/drops`) replying with the counters of the port given as parameter,
+    /// or the global ones without a parameter.
+    pub fn register_telemetry(&self, cmd: &str) -> Result<()>
+    where
+        C: 'static,
+    {
+        let group = *self;
+        telemetry::register(
+            cmd,
+            "Returns the counters of a port, or the global ones. Parameters: [port_id]",
+            move |params, data| {
+                let port_id = match params.trim() {
+                    "" => None,
+                    port_id => Some(port_id.parse().map_err(|_| Error(libc::EINVAL))?),
+                };
+
+                let values = group.get(port_id)?.values();
+                data.start_dict()?;
+                for (name, value) in C::NAMES.iter().zip(values) {
+                    data.add_dict_u64(name, value)?;
+                }
+                Ok(())
+            },
+        )
+    }
+}
+
+impl<C> fmt::Debug for MetricsGroup<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MetricsGroup").field("key", &self.key).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    crate::metrics_counters! {
+        struct TestCounters {
+            test_first,
+            test_second,
+        }
+    }
+
+    #[rte_test]
+    fn test_publish_counters() {
+        init(None);
+        let group = MetricsGroup::<TestCounters>::register().unwrap();
+        assert_eq!(TestCounters::NAMES, ["test_first", "test_second"]);
+
+        let counters = TestCounters { test_first: 1, test_second: 2 };
+        group.publish(None, &counters).unwrap();
+        assert_eq!(group.get(None).unwrap(), counters);
+    }
+}
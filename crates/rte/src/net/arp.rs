@@ -0,0 +1,167 @@
+//! Helpers for constructing and answering ARP packets (IPv4 over Ethernet only).
+
+use std::net::Ipv4Addr;
+
+use mac_addr::{MacAddr, ETHER_ADDR_LEN};
+use zerocopy::{byteorder::network_endian::U16, AsBytes};
+
+use super::{parse_headers, ArpHdr, EtherHdr, Header, L3Hdr, ETHER_TYPE_ARP, ETHER_TYPE_IPV4};
+use crate::mbuf::{Allocator, MBuf};
+
+/// An ARP operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u16)]
+pub enum Op {
+    Request = ffi::RTE_ARP_OP_REQUEST as u16,
+    Reply = ffi::RTE_ARP_OP_REPLY as u16,
+}
+
+/// An IP to MAC address mapping, as advertised by an ARP packet's sender.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Binding {
+    pub ip: Ipv4Addr,
+    pub mac: MacAddr,
+}
+
+impl ArpHdr {
+    /// Creates an ARP header for IPv4 over Ethernet.
+    #[inline]
+    pub fn new(op: Op, sender: Binding, target: Binding) -> Self {
+        Self {
+            arp_hardware: U16::new(ffi::RTE_ARP_HRD_ETHER as u16),
+            arp_protocol: U16::new(ETHER_TYPE_IPV4),
+            arp_hlen: ETHER_ADDR_LEN,
+            arp_plen: 4,
+            arp_opcode: U16::new(op as u16),
+            arp_sha: sender.mac,
+            arp_sip: sender.ip.octets(),
+            arp_tha: target.mac,
+            arp_tip: target.ip.octets(),
+        }
+    }
+
+    /// Returns the header's operation, or `None` if it is neither a request nor a reply.
+    #[inline]
+    pub fn op(&self) -> Option<Op> {
+        match self.arp_opcode.get() {
+            op if op == Op::Request as u16 => Some(Op::Request),
+            op if op == Op::Reply as u16 => Some(Op::Reply),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this header is for IPv4 over Ethernet, the only kind of ARP supported by this module.
+    #[inline]
+    pub fn is_ipv4_over_ether(&self) -> bool {
+        self.arp_hardware.get() == ffi::RTE_ARP_HRD_ETHER as u16
+            && self.arp_protocol.get() == ETHER_TYPE_IPV4
+            && self.arp_hlen == ETHER_ADDR_LEN
+            && self.arp_plen == 4
+    }
+
+    #[inline]
+    pub fn sender(&self) -> Binding {
+        Binding { ip: self.sender_ip(), mac: self.arp_sha }
+    }
+}
+
+/// Appends a (broadcast) ARP request for `target_ip` to `mbuf`, which should be empty.
+#[inline]
+pub fn write_request<A: Allocator>(mbuf: &mut MBuf<A>, sender: Binding, target_ip: Ipv4Addr) {
+    let target = Binding { ip: target_ip, mac: MacAddr::zeroed() };
+    write(mbuf, MacAddr::BROADCAST, ArpHdr::new(Op::Request, sender, target));
+}
+
+/// Appends a gratuitous ARP request to `mbuf` (which should be empty), announcing `sender`'s mapping to the network,
+/// e.g. after an IP address has moved to a different host.
+#[inline]
+pub fn write_gratuitous<A: Allocator>(mbuf: &mut MBuf<A>, sender: Binding) {
+    let target = Binding { ip: sender.ip, mac: MacAddr::zeroed() };
+    write(mbuf, MacAddr::BROADCAST, ArpHdr::new(Op::Request, sender, target));
+}
+
+fn write<A: Allocator>(mbuf: &mut MBuf<A>, dst_addr: MacAddr, arp: ArpHdr) {
+    let ether = EtherHdr { dst_addr, src_addr: arp.arp_sha, ether_type: U16::new(ETHER_TYPE_ARP) };
+    mbuf.extend_from_slice(ether.as_bytes());
+    mbuf.extend_from_slice(arp.as_bytes());
+}
+
+/// Returns the sender's mapping if `packet` is an ARP reply.
+#[inline]
+pub fn parse_reply(packet: &[u8]) -> Option<Binding> {
+    match parse_headers(packet)?.l3? {
+        L3Hdr::Arp(arp) if arp.is_ipv4_over_ether() && arp.op() == Some(Op::Reply) => Some(arp.sender()),
+        _ => None,
+    }
+}
+
+/// Turns `mbuf` into a reply in place if it is an ARP request for `local`'s IP address, in which case it should be
+/// sent back on the port it was received on.
+///
+/// Returns `false` (leaving the mbuf unmodified) if it is not such a request.
+#[inline]
+pub fn reply_in_place<A: Allocator>(mbuf: &mut MBuf<A>, local: Binding) -> bool {
+    let (request, l3_offset) = match mbuf.parse_headers() {
+        Some(headers) => match headers.l3 {
+            Some(L3Hdr::Arp(arp))
+                if arp.is_ipv4_over_ether() && arp.op() == Some(Op::Request) && arp.target_ip() == local.ip =>
+            {
+                (*arp, headers.l3_offset())
+            }
+            _ => return false,
+        },
+        None => return false,
+    };
+
+    let (ether, _) = EtherHdr::mut_from_prefix(mbuf).unwrap();
+    ether.dst_addr = request.arp_sha;
+    ether.src_addr = local.mac;
+
+    let (arp, _) = ArpHdr::mut_from_prefix(&mut mbuf[l3_offset..]).unwrap();
+    *arp = ArpHdr::new(Op::Reply, local, request.sender());
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mbuf::GlobalAllocator;
+
+    const LOCAL: Binding = Binding { ip: Ipv4Addr::new(10, 0, 0, 1), mac: MacAddr::new(2, 0, 0, 0, 0, 1) };
+    const REMOTE: Binding = Binding { ip: Ipv4Addr::new(10, 0, 0, 2), mac: MacAddr::new(2, 0, 0, 0, 0, 2) };
+
+    #[test]
+    fn test_request_reply() {
+        let mut mbuf = MBuf::<GlobalAllocator>::new();
+        write_request(&mut mbuf, REMOTE, LOCAL.ip);
+        assert_eq!(parse_reply(&mbuf), None);
+
+        assert!(!reply_in_place(&mut mbuf, REMOTE));
+        assert!(reply_in_place(&mut mbuf, LOCAL));
+        assert_eq!(parse_reply(&mbuf), Some(LOCAL));
+
+        // replies are not answered
+        assert!(!reply_in_place(&mut mbuf, LOCAL));
+
+        let headers = mbuf.parse_headers().unwrap();
+        assert_eq!(headers.ether.dst_addr, REMOTE.mac);
+        assert!(
+            matches!(headers.l3, Some(L3Hdr::Arp(arp)) if arp.target_ip() == REMOTE.ip && arp.arp_tha == REMOTE.mac)
+        );
+    }
+
+    #[test]
+    fn test_gratuitous() {
+        let mut mbuf = MBuf::<GlobalAllocator>::new();
+        write_gratuitous(&mut mbuf, LOCAL);
+
+        let headers = mbuf.parse_headers().unwrap();
+        assert!(headers.ether.dst_addr.is_broadcast());
+        let arp = match headers.l3 {
+            Some(L3Hdr::Arp(arp)) => arp,
+            l3 => panic!("unexpected L3 header: {l3:?}"),
+        };
+        assert!(arp.is_ipv4_over_ether());
+        assert_eq!((arp.sender(), arp.target_ip()), (LOCAL, LOCAL.ip));
+    }
+}
@@ -0,0 +1,120 @@
+//! Software implementations of the Internet checksums, based on DPDK's `rte_ip.h` API:
+//! <https://doc.dpdk.org/api-22.11/rte__ip_8h.html>
+//!
+//! The checksums are computed over the current value of the checksum field as well, which therefore must be set to 0
+//! by the caller beforehand. All results are in host byte order, as expected by e.g. [`U16::set`](zerocopy::U16::set).
+
+use super::{Header, Ipv4Hdr, Ipv6Hdr};
+use crate::flags::PktTxOffload;
+
+/// Computes the (non-complemented) one's complement sum of `buf`.
+#[inline]
+pub fn raw_cksum(buf: &[u8]) -> u16 {
+    u16::from_be(unsafe { ffi::_rte_raw_cksum(buf.as_ptr().cast(), buf.len()) })
+}
+
+/// Computes the IPv4 header checksum of `header`, which starts with an [`Ipv4Hdr`] followed by its options.
+///
+/// # Panics
+/// Panics if `header` is shorter than the IPv4 header's length (including options).
+#[inline]
+pub fn ipv4_cksum(header: &[u8]) -> u16 {
+    let valid = Ipv4Hdr::ref_from_prefix(header).is_some_and(|(ipv4, _)| ipv4.header_len() <= header.len());
+    assert!(valid, "truncated IPv4 header");
+    u16::from_be(unsafe { ffi::_rte_ipv4_cksum(header.as_ptr().cast()) })
+}
+
+/// Computes the UDP/TCP checksum of `l4` (the L4 header followed by its payload), including the IPv4 pseudo-header.
+///
+/// # Panics
+/// Panics if `l4` is shorter than the L4 length derived from the IPv4 header's `total_length`.
+#[inline]
+pub fn ipv4_udptcp_cksum(ipv4: &Ipv4Hdr, l4: &[u8]) -> u16 {
+    let l4_len = usize::from(ipv4.total_length.get()).saturating_sub(ipv4.header_len());
+    assert!(l4_len <= l4.len(), "L4 data is shorter than the length in the IPv4 header");
+    u16::from_be(unsafe { ffi::_rte_ipv4_udptcp_cksum(ipv4.as_ffi(), l4.as_ptr().cast()) })
+}
+
+/// Computes the UDP/TCP checksum of `l4` (the L4 header followed by its payload), including the IPv6 pseudo-header.
+///
+/// # Panics
+/// Panics if `l4` is shorter than the IPv6 header's `payload_len`.
+#[inline]
+pub fn ipv6_udptcp_cksum(ipv6: &Ipv6Hdr, l4: &[u8]) -> u16 {
+    assert!(usize::from(ipv6.payload_len.get()) <= l4.len(), "L4 data is shorter than the IPv6 payload length");
+    u16::from_be(unsafe { ffi::_rte_ipv6_udptcp_cksum(ipv6.as_ffi(), l4.as_ptr().cast()) })
+}
+
+/// Computes the IPv4 pseudo-header checksum, which NICs expect in the L4 checksum field when offloading it.
+///
+/// `flags` should be the packet's offload flags, as the pseudo-header does not include the L4 length when
+/// [`PktTxOffload::TCP_SEG`] is set.
+#[inline]
+pub fn ipv4_phdr_cksum(ipv4: &Ipv4Hdr, flags: PktTxOffload) -> u16 {
+    u16::from_be(unsafe { ffi::_rte_ipv4_phdr_cksum(ipv4.as_ffi(), flags.bits()) })
+}
+
+/// IPv6 version of [`ipv4_phdr_cksum`].
+#[inline]
+pub fn ipv6_phdr_cksum(ipv6: &Ipv6Hdr, flags: PktTxOffload) -> u16 {
+    u16::from_be(unsafe { ffi::_rte_ipv6_phdr_cksum(ipv6.as_ffi(), flags.bits()) })
+}
+
+impl Ipv4Hdr {
+    fn as_ffi(&self) -> *const ffi::rte_ipv4_hdr {
+        (self as *const Self).cast()
+    }
+}
+
+impl Ipv6Hdr {
+    fn as_ffi(&self) -> *const ffi::rte_ipv6_hdr {
+        (self as *const Self).cast()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::{byteorder::network_endian::U16, AsBytes};
+
+    use super::*;
+    use crate::net::IPPROTO_UDP;
+
+    #[test]
+    fn test_ipv4_cksum() {
+        // example header from https://en.wikipedia.org/wiki/Internet_checksum
+        let header = b"\x45\x00\x00\x73\x00\x00\x40\x00\x40\x11\x00\x00\xc0\xa8\x00\x01\xc0\xa8\x00\xc7";
+        assert_eq!(ipv4_cksum(header), 0xb861);
+        assert_eq!(raw_cksum(header), !0xb861);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ipv4_cksum_truncated() {
+        let ipv4 = Ipv4Hdr { version_ihl: 0x46, ..Default::default() };
+        ipv4_cksum(ipv4.as_bytes());
+    }
+
+    #[test]
+    fn test_udptcp_cksum() {
+        let mut l4 = [0; 12];
+        l4[4..6].copy_from_slice(&12u16.to_be_bytes());
+        l4[8..].copy_from_slice(b"data");
+
+        let ipv4 = Ipv4Hdr {
+            version_ihl: Ipv4Hdr::VERSION_IHL,
+            total_length: U16::new(32),
+            next_proto_id: IPPROTO_UDP,
+            src_addr: [10, 0, 0, 1],
+            dst_addr: [10, 0, 0, 2],
+            ..Default::default()
+        };
+        let cksum = ipv4_udptcp_cksum(&ipv4, &l4);
+
+        assert_ne!(cksum, 0);
+
+        // the checksum of a packet with a correct checksum is 0, which is reported as 0xffff (as 0 means "no checksum"
+        // for UDP)
+        l4[6..8].copy_from_slice(&cksum.to_be_bytes());
+        assert_eq!(ipv4_udptcp_cksum(&ipv4, &l4), 0xffff);
+    }
+}
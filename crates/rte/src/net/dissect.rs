@@ -0,0 +1,279 @@
+//! Human-readable dissections of packets for debugging, with their personal data optionally redacted.
+
+use std::{
+    fmt::{self, Display},
+    net::IpAddr,
+};
+
+use bitflags::bitflags;
+use mac_addr::MacAddr;
+
+use super::*;
+
+/// Default value of [`Dissect::max_dump_len`].
+pub const DEFAULT_MAX_DUMP_LEN: usize = 256;
+
+bitflags! {
+    /// Parts of a packet that [`Dissect`] omits from its output, e.g. to avoid logging personal data.
+    #[derive(Default)]
+    pub struct Redact: u8 {
+        const MAC_ADDRS = 1 << 0;
+        const IP_ADDRS  = 1 << 1;
+        /// The data following the parsed headers (or the entire packet, if it could not be parsed).
+        const PAYLOAD   = 1 << 2;
+        const ALL       = Self::MAC_ADDRS.bits | Self::IP_ADDRS.bits | Self::PAYLOAD.bits;
+    }
+}
+
+/// A [`Display`]-able dissection of a packet, rendering each of its headers on a separate line, followed by a
+/// hexdump of the remaining data (in the format of `rte_hexdump`).
+///
+/// Meant for debugging (e.g. of malformed packets) through logs:
+/// ```rust
+/// # use rte::net::{Dissect, Redact};
+/// let packet = [0xff; 20];
+/// let dissect = Dissect::new(&packet).redact(Redact::MAC_ADDRS);
+/// assert_eq!(
+///     dissect.to_string(),
+///     "ether <redacted> > <redacted> type 0xffff\n\
+///      payload 6 bytes:\n\
+///      00000000: FF FF FF FF FF FF                               | ......"
+/// );
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Dissect<'a> {
+    packet: &'a [u8],
+    redact: Redact,
+    max_dump_len: usize,
+}
+
+impl<'a> Dissect<'a> {
+    #[inline]
+    pub fn new(packet: &'a [u8]) -> Self {
+        Self { packet, redact: Redact::empty(), max_dump_len: DEFAULT_MAX_DUMP_LEN }
+    }
+
+    /// Sets the parts of the packet omitted from the output.
+    #[inline]
+    pub fn redact(mut self, redact: Redact) -> Self {
+        self.redact = redact;
+        self
+    }
+
+    /// Sets the maximal number of bytes included in the hexdump, defaults to [`DEFAULT_MAX_DUMP_LEN`].
+    #[inline]
+    pub fn max_dump_len(mut self, len: usize) -> Self {
+        self.max_dump_len = len;
+        self
+    }
+
+    fn mac(&self, addr: MacAddr) -> Redacted<MacAddr> {
+        Redacted(addr, self.redact.contains(Redact::MAC_ADDRS))
+    }
+
+    fn ip(&self, addr: impl Into<IpAddr>) -> Redacted<IpAddr> {
+        Redacted(addr.into(), self.redact.contains(Redact::IP_ADDRS))
+    }
+
+    fn fmt_headers(&self, f: &mut fmt::Formatter, headers: &Headers) -> fmt::Result {
+        let ether = headers.ether;
+        write!(
+            f,
+            "ether {} > {} type {:#06x}",
+            self.mac(ether.src_addr),
+            self.mac(ether.dst_addr),
+            ether.ether_type.get()
+        )?;
+        for vlan in &headers.vlans {
+            write!(f, "\nvlan {} pcp {} type {:#06x}", vlan.vid(), vlan.pcp(), vlan.eth_proto.get())?;
+        }
+
+        match headers.l3 {
+            Some(L3Hdr::Ipv4(ipv4)) => write!(
+                f,
+                "\nipv4 {} > {} len {} id {} frag {:#06x} ttl {} proto {} cksum {:#06x}",
+                self.ip(ipv4.src()),
+                self.ip(ipv4.dst()),
+                ipv4.total_length.get(),
+                ipv4.packet_id.get(),
+                ipv4.fragment_offset.get(),
+                ipv4.time_to_live,
+                ipv4.next_proto_id,
+                ipv4.hdr_checksum.get()
+            )?,
+            Some(L3Hdr::Ipv6(ipv6)) => write!(
+                f,
+                "\nipv6 {} > {} len {} hlim {} proto {}",
+                self.ip(ipv6.src()),
+                self.ip(ipv6.dst()),
+                ipv6.payload_len.get(),
+                ipv6.hop_limits,
+                ipv6.proto
+            )?,
+            Some(L3Hdr::Arp(arp)) => write!(
+                f,
+                "\narp op {} {} ({}) > {} ({})",
+                arp.arp_opcode.get(),
+                self.ip(arp.sender_ip()),
+                self.mac(arp.arp_sha),
+                self.ip(arp.target_ip()),
+                self.mac(arp.arp_tha)
+            )?,
+            None => {}
+        }
+
+        match headers.l4 {
+            Some(L4Hdr::Tcp(tcp)) => write!(
+                f,
+                "\ntcp {} > {} seq {} ack {} flags {:#04x} win {} cksum {:#06x}",
+                tcp.src_port.get(),
+                tcp.dst_port.get(),
+                tcp.sent_seq.get(),
+                tcp.recv_ack.get(),
+                tcp.tcp_flags,
+                tcp.rx_win.get(),
+                tcp.cksum.get()
+            ),
+            Some(L4Hdr::Udp(udp)) => write!(
+                f,
+                "\nudp {} > {} len {} cksum {:#06x}",
+                udp.src_port.get(),
+                udp.dst_port.get(),
+                udp.dgram_len.get(),
+                udp.dgram_cksum.get()
+            ),
+            Some(L4Hdr::Icmp(icmp)) => write!(
+                f,
+                "\nicmp type {} code {} id {} seq {} cksum {:#06x}",
+                icmp.icmp_type,
+                icmp.icmp_code,
+                icmp.icmp_ident.get(),
+                icmp.icmp_seq_nb.get(),
+                icmp.icmp_cksum.get()
+            ),
+            None => Ok(()),
+        }
+    }
+
+    fn fmt_dump(&self, f: &mut fmt::Formatter, data: &[u8]) -> fmt::Result {
+        if self.redact.contains(Redact::PAYLOAD) {
+            return write!(f, "<redacted>");
+        }
+
+        let len = data.len().min(self.max_dump_len);
+        for (i, line) in data[..len].chunks(16).enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{:08X}:", i * 16)?;
+            for byte in line {
+                write!(f, " {byte:02X}")?;
+            }
+            write!(f, "{:width$} | ", "", width = (16 - line.len()) * 3)?;
+            for &byte in line {
+                let c = if byte.is_ascii_graphic() || byte == b' ' { byte as char } else { '.' };
+                write!(f, "{c}")?;
+            }
+        }
+        if len < data.len() {
+            write!(f, "\n... {} more bytes", data.len() - len)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Dissect<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let payload = match parse_headers(self.packet) {
+            Some(headers) => {
+                self.fmt_headers(f, &headers)?;
+                match &self.packet[headers.payload_offset()..] {
+                    [] => return Ok(()),
+                    payload => {
+                        writeln!(f)?;
+                        payload
+                    }
+                }
+            }
+            None => self.packet,
+        };
+
+        writeln!(f, "payload {} bytes:", payload.len())?;
+        self.fmt_dump(f, payload)
+    }
+}
+
+struct Redacted<T>(T, bool);
+
+impl<T: Display> Display for Redacted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self(_, true) => write!(f, "<redacted>"),
+            Self(value, false) => value.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::{byteorder::network_endian::U16, AsBytes};
+
+    use super::*;
+
+    fn udp_packet() -> Vec<u8> {
+        let ether = EtherHdr {
+            dst_addr: MacAddr::new(2, 0, 0, 0, 0, 2),
+            src_addr: MacAddr::new(2, 0, 0, 0, 0, 1),
+            ether_type: U16::new(ETHER_TYPE_IPV4),
+        };
+        let ipv4 = Ipv4Hdr {
+            version_ihl: Ipv4Hdr::VERSION_IHL,
+            total_length: U16::new(33),
+            time_to_live: 64,
+            next_proto_id: IPPROTO_UDP,
+            src_addr: [10, 0, 0, 1],
+            dst_addr: [10, 0, 0, 2],
+            ..Default::default()
+        };
+        let udp =
+            UdpHdr { src_port: U16::new(1234), dst_port: U16::new(53), dgram_len: U16::new(13), ..Default::default() };
+
+        [ether.as_bytes(), ipv4.as_bytes(), udp.as_bytes(), b"hello"].concat()
+    }
+
+    #[test]
+    fn test_dissect() {
+        let packet = udp_packet();
+        assert_eq!(
+            Dissect::new(&packet).to_string(),
+            "ether 02:00:00:00:00:01 > 02:00:00:00:00:02 type 0x0800\n\
+             ipv4 10.0.0.1 > 10.0.0.2 len 33 id 0 frag 0x0000 ttl 64 proto 17 cksum 0x0000\n\
+             udp 1234 > 53 len 13 cksum 0x0000\n\
+             payload 5 bytes:\n\
+             00000000: 68 65 6C 6C 6F                                  | hello"
+        );
+
+        let redacted = Dissect::new(&packet).redact(Redact::ALL).to_string();
+        assert!(!redacted.contains("02:00") && !redacted.contains("10.0.0") && !redacted.contains("hello"));
+        assert!(redacted.ends_with("payload 5 bytes:\n<redacted>"));
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let packet: Vec<u8> = (0..40).collect();
+        assert_eq!(
+            Dissect::new(&packet[..10]).to_string(),
+            "payload 10 bytes:\n00000000: 00 01 02 03 04 05 06 07 08 09                   | .........."
+        );
+        assert_eq!(
+            Dissect::new(&packet).max_dump_len(20).to_string().lines().collect::<Vec<_>>(),
+            [
+                "ether 06:07:08:09:0a:0b > 00:01:02:03:04:05 type 0x0c0d",
+                "payload 26 bytes:",
+                "00000000: 0E 0F 10 11 12 13 14 15 16 17 18 19 1A 1B 1C 1D | ................",
+                "00000010: 1E 1F 20 21                                     | .. !",
+                "... 6 more bytes",
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use mac_addr::MacAddr;
+use zerocopy::{
+    byteorder::network_endian::{U16, U32},
+    AsBytes, FromBytes, Unaligned,
+};
+
+/// Ethernet header, see [`rte_ether_hdr`](ffi::rte_ether_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct EtherHdr {
+    pub dst_addr: MacAddr,
+    pub src_addr: MacAddr,
+    pub ether_type: U16,
+}
+
+/// 802.1Q VLAN tag, following the [`EtherHdr`] (whose `ether_type` is [`ETHER_TYPE_VLAN`](super::ETHER_TYPE_VLAN)).
+///
+/// See [`rte_vlan_hdr`](ffi::rte_vlan_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct VlanHdr {
+    pub vlan_tci: U16,
+    /// The ether type of the encapsulated packet.
+    pub eth_proto: U16,
+}
+
+impl VlanHdr {
+    /// VLAN identifier (12 bits).
+    #[inline]
+    pub fn vid(&self) -> u16 {
+        self.vlan_tci.get() & 0xfff
+    }
+
+    /// Priority code point (3 bits).
+    #[inline]
+    pub fn pcp(&self) -> u8 {
+        (self.vlan_tci.get() >> 13) as u8
+    }
+}
+
+/// IPv4 header (without options), see [`rte_ipv4_hdr`](ffi::rte_ipv4_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct Ipv4Hdr {
+    pub version_ihl: u8,
+    pub type_of_service: u8,
+    pub total_length: U16,
+    pub packet_id: U16,
+    pub fragment_offset: U16,
+    pub time_to_live: u8,
+    pub next_proto_id: u8,
+    pub hdr_checksum: U16,
+    pub src_addr: [u8; 4],
+    pub dst_addr: [u8; 4],
+}
+
+impl Ipv4Hdr {
+    /// Value of `version_ihl` for a header without options.
+    pub const VERSION_IHL: u8 = 0x45;
+
+    #[inline]
+    pub fn version(&self) -> u8 {
+        self.version_ihl >> 4
+    }
+
+    /// Length of the header in bytes, including options.
+    #[inline]
+    pub fn header_len(&self) -> usize {
+        usize::from(self.version_ihl & 0xf) * 4
+    }
+
+    #[inline]
+    pub fn src(&self) -> Ipv4Addr {
+        self.src_addr.into()
+    }
+
+    #[inline]
+    pub fn dst(&self) -> Ipv4Addr {
+        self.dst_addr.into()
+    }
+
+    /// Returns `true` if this header belongs to a fragment, i.e. either the "more fragments" flag or a fragment
+    /// offset is set.
+    ///
+    /// See also: `rte_ipv4_frag_pkt_is_fragmented`.
+    #[inline]
+    pub fn is_fragment(&self) -> bool {
+        self.fragment_offset.get() & 0x3fff != 0
+    }
+
+    /// Offset (in bytes) of this fragment's data within the original packet.
+    #[inline]
+    pub fn fragment_data_offset(&self) -> usize {
+        usize::from(self.fragment_offset.get() & 0x1fff) * 8
+    }
+}
+
+/// IPv6 header (without extension headers), see [`rte_ipv6_hdr`](ffi::rte_ipv6_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct Ipv6Hdr {
+    /// IP version, traffic class & flow label.
+    pub vtc_flow: U32,
+    pub payload_len: U16,
+    pub proto: u8,
+    pub hop_limits: u8,
+    pub src_addr: [u8; 16],
+    pub dst_addr: [u8; 16],
+}
+
+impl Ipv6Hdr {
+    #[inline]
+    pub fn version(&self) -> u8 {
+        (self.vtc_flow.get() >> 28) as u8
+    }
+
+    #[inline]
+    pub fn src(&self) -> Ipv6Addr {
+        self.src_addr.into()
+    }
+
+    #[inline]
+    pub fn dst(&self) -> Ipv6Addr {
+        self.dst_addr.into()
+    }
+}
+
+/// TCP header (without options), see [`rte_tcp_hdr`](ffi::rte_tcp_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct TcpHdr {
+    pub src_port: U16,
+    pub dst_port: U16,
+    pub sent_seq: U32,
+    pub recv_ack: U32,
+    pub data_off: u8,
+    pub tcp_flags: u8,
+    pub rx_win: U16,
+    pub cksum: U16,
+    pub tcp_urp: U16,
+}
+
+impl TcpHdr {
+    /// Length of the header in bytes, including options.
+    #[inline]
+    pub fn header_len(&self) -> usize {
+        usize::from(self.data_off >> 4) * 4
+    }
+}
+
+/// UDP header, see [`rte_udp_hdr`](ffi::rte_udp_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct UdpHdr {
+    pub src_port: U16,
+    pub dst_port: U16,
+    pub dgram_len: U16,
+    pub dgram_cksum: U16,
+}
+
+/// ARP header for IPv4 over Ethernet, see [`rte_arp_hdr`](ffi::rte_arp_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct ArpHdr {
+    pub arp_hardware: U16,
+    pub arp_protocol: U16,
+    pub arp_hlen: u8,
+    pub arp_plen: u8,
+    pub arp_opcode: U16,
+    /// Sender hardware address.
+    pub arp_sha: MacAddr,
+    /// Sender IP address.
+    pub arp_sip: [u8; 4],
+    /// Target hardware address.
+    pub arp_tha: MacAddr,
+    /// Target IP address.
+    pub arp_tip: [u8; 4],
+}
+
+impl ArpHdr {
+    #[inline]
+    pub fn sender_ip(&self) -> Ipv4Addr {
+        self.arp_sip.into()
+    }
+
+    #[inline]
+    pub fn target_ip(&self) -> Ipv4Addr {
+        self.arp_tip.into()
+    }
+}
+
+/// ICMP header (the echo request/reply variant), see [`rte_icmp_hdr`](ffi::rte_icmp_hdr).
+///
+/// Also used for ICMPv6, which shares the same layout for echo messages.
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct IcmpHdr {
+    pub icmp_type: u8,
+    pub icmp_code: u8,
+    pub icmp_cksum: U16,
+    pub icmp_ident: U16,
+    pub icmp_seq_nb: U16,
+}
+
+/// VXLAN header, see [`rte_vxlan_hdr`](ffi::rte_vxlan_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct VxlanHdr {
+    pub vx_flags: U32,
+    /// The VNI, in the upper 24 bits.
+    pub vx_vni: U32,
+}
+
+impl VxlanHdr {
+    /// The "I" flag, which must be set for the VNI to be valid.
+    pub const FLAG_VNI: u32 = 0x0800_0000;
+
+    #[inline]
+    pub fn new(vni: u32) -> Self {
+        Self { vx_flags: U32::new(Self::FLAG_VNI), vx_vni: U32::new(vni << 8) }
+    }
+
+    #[inline]
+    pub fn vni(&self) -> u32 {
+        self.vx_vni.get() >> 8
+    }
+}
+
+/// GENEVE header (without options), see [`rte_geneve_hdr`](ffi::rte_geneve_hdr).
+#[derive(FromBytes, AsBytes, Unaligned, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(C)]
+pub struct GeneveHdr {
+    /// Version (2 bits) and length of the options in 4-byte words (6 bits).
+    pub ver_opt_len: u8,
+    /// OAM & critical options flags.
+    pub flags: u8,
+    /// Ether type of the encapsulated packet.
+    pub proto: U16,
+    pub vni: [u8; 3],
+    pub reserved: u8,
+}
+
+impl GeneveHdr {
+    #[inline]
+    pub fn version(&self) -> u8 {
+        self.ver_opt_len >> 6
+    }
+
+    /// Length of the header in bytes, including options.
+    #[inline]
+    pub fn header_len(&self) -> usize {
+        std::mem::size_of::<Self>() + usize::from(self.ver_opt_len & 0x3f) * 4
+    }
+
+    #[inline]
+    pub fn vni(&self) -> u32 {
+        let [a, b, c] = self.vni;
+        u32::from_be_bytes([0, a, b, c])
+    }
+}
@@ -0,0 +1,178 @@
+//! Helpers for answering ICMP and ICMPv6 echo requests (i.e. pings) directly from the datapath.
+
+use std::mem;
+
+use super::{
+    ipv6_udptcp_cksum, parse_headers, raw_cksum, EtherHdr, Header, Headers, IcmpHdr, Ipv4Hdr, Ipv6Hdr, L3Hdr, L4Hdr,
+};
+use crate::mbuf::{Allocator, MBuf};
+
+pub const ECHO_REQUEST: u8 = ffi::RTE_IP_ICMP_ECHO_REQUEST as u8;
+pub const ECHO_REPLY: u8 = ffi::RTE_IP_ICMP_ECHO_REPLY as u8;
+pub const ICMP6_ECHO_REQUEST: u8 = 128;
+pub const ICMP6_ECHO_REPLY: u8 = 129;
+
+/// An ICMP or ICMPv6 echo request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Echo<'a> {
+    pub ident: u16,
+    pub seq_nb: u16,
+    pub data: &'a [u8],
+}
+
+/// Returns the echo request contained in `packet`, if it is one.
+#[inline]
+pub fn parse_echo_request(packet: &[u8]) -> Option<Echo<'_>> {
+    let headers = parse_headers(packet)?;
+    let (icmp, end, _) = echo_request(&headers, packet.len())?;
+    Some(Echo {
+        ident: icmp.icmp_ident.get(),
+        seq_nb: icmp.icmp_seq_nb.get(),
+        data: &packet[headers.payload_offset()..end],
+    })
+}
+
+/// Turns `mbuf` into an echo reply in place if it is an echo request, by swapping its MAC and IP addresses and
+/// updating the ICMP header, in which case it should be sent back on the port it was received on.
+///
+/// The IPv4 header checksum remains valid, as swapping addresses does not change it.
+///
+/// Returns `false` (leaving the mbuf unmodified) if it is not an echo request.
+#[inline]
+pub fn reply_in_place<A: Allocator>(mbuf: &mut MBuf<A>) -> bool {
+    let (l3_offset, l4_offset, end, is_ipv4) = match mbuf.parse_headers() {
+        Some(headers) => match echo_request(&headers, mbuf.len()) {
+            Some((_, end, is_ipv4)) => (headers.l3_offset(), headers.l4_offset(), end, is_ipv4),
+            None => return false,
+        },
+        None => return false,
+    };
+
+    let (ether, _) = EtherHdr::mut_from_prefix(mbuf).unwrap();
+    mem::swap(&mut ether.src_addr, &mut ether.dst_addr);
+
+    let (l3, l4) = mbuf[l3_offset..end].split_at_mut(l4_offset - l3_offset);
+    let (icmp, _) = IcmpHdr::mut_from_prefix(l4).unwrap();
+    icmp.icmp_cksum.set(0);
+
+    let cksum = if is_ipv4 {
+        let (ipv4, _) = Ipv4Hdr::mut_from_prefix(l3).unwrap();
+        mem::swap(&mut ipv4.src_addr, &mut ipv4.dst_addr);
+        icmp.icmp_type = ECHO_REPLY;
+        !raw_cksum(l4)
+    } else {
+        let (ipv6, _) = Ipv6Hdr::mut_from_prefix(l3).unwrap();
+        mem::swap(&mut ipv6.src_addr, &mut ipv6.dst_addr);
+        icmp.icmp_type = ICMP6_ECHO_REPLY;
+        ipv6_udptcp_cksum(ipv6, l4)
+    };
+
+    IcmpHdr::mut_from_prefix(l4).unwrap().0.icmp_cksum.set(cksum);
+    true
+}
+
+/// Returns the ICMP header of an echo request, the offset its L3 payload ends at (excluding any L2 padding), and
+/// whether it is an IPv4 packet. IPv4 fragments only carry part of a request, and aren't answered.
+fn echo_request<'a>(headers: &Headers<'a>, packet_len: usize) -> Option<(&'a IcmpHdr, usize, bool)> {
+    let (icmp, end, is_ipv4) = match (headers.l3?, headers.l4?) {
+        (L3Hdr::Ipv4(ipv4), L4Hdr::Icmp(icmp)) if icmp.icmp_type == ECHO_REQUEST && !ipv4.is_fragment() => {
+            (icmp, headers.l3_offset() + usize::from(ipv4.total_length.get()), true)
+        }
+        (L3Hdr::Ipv6(ipv6), L4Hdr::Icmp(icmp)) if icmp.icmp_type == ICMP6_ECHO_REQUEST => {
+            (icmp, headers.l4_offset() + usize::from(ipv6.payload_len.get()), false)
+        }
+        _ => return None,
+    };
+
+    (icmp.icmp_code == 0 && end >= headers.payload_offset() && end <= packet_len).then_some((icmp, end, is_ipv4))
+}
+
+#[cfg(test)]
+mod tests {
+    use mac_addr::MacAddr;
+    use zerocopy::{
+        byteorder::network_endian::{U16, U32},
+        AsBytes,
+    };
+
+    use super::*;
+    use crate::{
+        mbuf::GlobalAllocator,
+        net::{ipv4_cksum, ETHER_TYPE_IPV4, ETHER_TYPE_IPV6, IPPROTO_ICMP, IPPROTO_ICMPV6},
+    };
+
+    const SRC_MAC: MacAddr = MacAddr::new(2, 0, 0, 0, 0, 1);
+    const DATA: &[u8] = b"abcdefgh";
+
+    fn request_mbuf(ether_type: u16, l3: &[u8]) -> MBuf<GlobalAllocator> {
+        let ether = EtherHdr { src_addr: SRC_MAC, ether_type: U16::new(ether_type), ..Default::default() };
+        let icmp = IcmpHdr {
+            icmp_type: if ether_type == ETHER_TYPE_IPV4 { ECHO_REQUEST } else { ICMP6_ECHO_REQUEST },
+            icmp_ident: U16::new(1),
+            icmp_seq_nb: U16::new(2),
+            ..Default::default()
+        };
+
+        let mut mbuf = MBuf::new();
+        mbuf.extend_from_slice(ether.as_bytes());
+        mbuf.extend_from_slice(l3);
+        mbuf.extend_from_slice(icmp.as_bytes());
+        mbuf.extend_from_slice(DATA);
+        // ethernet padding
+        mbuf.extend_from_slice(&[0; 4]);
+        mbuf
+    }
+
+    #[test]
+    fn test_ipv4_echo() {
+        let mut ipv4 = Ipv4Hdr {
+            version_ihl: Ipv4Hdr::VERSION_IHL,
+            total_length: U16::new((Ipv4Hdr::LEN + IcmpHdr::LEN + DATA.len()) as u16),
+            next_proto_id: IPPROTO_ICMP,
+            src_addr: [10, 0, 0, 1],
+            dst_addr: [10, 0, 0, 2],
+            ..Default::default()
+        };
+        ipv4.hdr_checksum.set(ipv4_cksum(ipv4.as_bytes()));
+        let mut mbuf = request_mbuf(ETHER_TYPE_IPV4, ipv4.as_bytes());
+
+        assert_eq!(parse_echo_request(&mbuf), Some(Echo { ident: 1, seq_nb: 2, data: DATA }));
+        assert!(reply_in_place(&mut mbuf));
+        assert_eq!(parse_echo_request(&mbuf), None);
+
+        let headers = mbuf.parse_headers().unwrap();
+        assert_eq!(headers.ether.dst_addr, SRC_MAC);
+        assert!(matches!(headers.l3, Some(L3Hdr::Ipv4(ip)) if ip.dst_addr == [10, 0, 0, 1]));
+        assert_eq!(raw_cksum(&mbuf[headers.l3_offset()..headers.l4_offset()]), 0xffff);
+        assert!(matches!(headers.l4, Some(L4Hdr::Icmp(icmp)) if icmp.icmp_type == ECHO_REPLY));
+        assert_eq!(raw_cksum(&mbuf[headers.l4_offset()..headers.payload_offset() + DATA.len()]), 0xffff);
+
+        // the first fragment of a request, with the "more fragments" flag set
+        ipv4.fragment_offset.set(0x2000);
+        ipv4.hdr_checksum.set(0);
+        ipv4.hdr_checksum.set(ipv4_cksum(ipv4.as_bytes()));
+        let mut mbuf = request_mbuf(ETHER_TYPE_IPV4, ipv4.as_bytes());
+        assert_eq!(parse_echo_request(&mbuf), None);
+        assert!(!reply_in_place(&mut mbuf));
+    }
+
+    #[test]
+    fn test_ipv6_echo() {
+        let ipv6 = Ipv6Hdr {
+            vtc_flow: U32::new(6 << 28),
+            payload_len: U16::new((IcmpHdr::LEN + DATA.len()) as u16),
+            proto: IPPROTO_ICMPV6,
+            src_addr: [1; 16],
+            dst_addr: [2; 16],
+            ..Default::default()
+        };
+        let mut mbuf = request_mbuf(ETHER_TYPE_IPV6, ipv6.as_bytes());
+
+        assert_eq!(parse_echo_request(&mbuf), Some(Echo { ident: 1, seq_nb: 2, data: DATA }));
+        assert!(reply_in_place(&mut mbuf));
+
+        let headers = mbuf.parse_headers().unwrap();
+        assert!(matches!(headers.l3, Some(L3Hdr::Ipv6(ip)) if ip.dst_addr == [1; 16]));
+        assert!(matches!(headers.l4, Some(L4Hdr::Icmp(icmp)) if icmp.icmp_type == ICMP6_ECHO_REPLY));
+    }
+}
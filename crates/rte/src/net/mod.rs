@@ -0,0 +1,74 @@
+//! Typed, zero-copy views of common network protocol headers, and a parser for locating them within a packet.
+//!
+//! The header structs mirror their DPDK counterparts (e.g. [`rte_ipv4_hdr`](ffi::rte_ipv4_hdr)), but use
+//! [`zerocopy`]'s network-endian integer types and have an alignment of 1, so they can be safely viewed directly over
+//! packet data:
+//! ```rust
+//! # use rte::net::{EtherHdr, Header, ETHER_TYPE_IPV4};
+//! let mut packet = [0; 64];
+//! let (ether, _payload) = EtherHdr::mut_from_prefix(&mut packet).unwrap();
+//! ether.ether_type.set(ETHER_TYPE_IPV4);
+//! assert_eq!(&packet[12..14], b"\x08\x00");
+//! ```
+
+pub mod arp;
+pub mod icmp;
+pub mod tunnel;
+
+mod cksum;
+mod dissect;
+mod headers;
+mod parse;
+mod ptype;
+
+use zerocopy::{AsBytes, FromBytes, LayoutVerified, Unaligned};
+
+pub use self::{
+    cksum::{ipv4_cksum, ipv4_phdr_cksum, ipv4_udptcp_cksum, ipv6_phdr_cksum, ipv6_udptcp_cksum, raw_cksum},
+    dissect::{Dissect, Redact, DEFAULT_MAX_DUMP_LEN},
+    headers::{ArpHdr, EtherHdr, GeneveHdr, IcmpHdr, Ipv4Hdr, Ipv6Hdr, TcpHdr, UdpHdr, VlanHdr, VxlanHdr},
+    parse::{parse_headers, Headers, L3Hdr, L4Hdr, MAX_VLANS},
+    ptype::{L2Type, L3Type, L4Type, PacketType, TunnelType},
+};
+
+pub const ETHER_TYPE_IPV4: u16 = ffi::RTE_ETHER_TYPE_IPV4 as u16;
+pub const ETHER_TYPE_IPV6: u16 = ffi::RTE_ETHER_TYPE_IPV6 as u16;
+pub const ETHER_TYPE_ARP: u16 = ffi::RTE_ETHER_TYPE_ARP as u16;
+pub const ETHER_TYPE_VLAN: u16 = ffi::RTE_ETHER_TYPE_VLAN as u16;
+pub const ETHER_TYPE_QINQ: u16 = ffi::RTE_ETHER_TYPE_QINQ as u16;
+
+pub const IPPROTO_ICMP: u8 = 1;
+pub const IPPROTO_TCP: u8 = 6;
+pub const IPPROTO_UDP: u8 = 17;
+pub const IPPROTO_ICMPV6: u8 = 58;
+
+/// A network protocol header, which can be viewed in place over a byte buffer.
+pub trait Header: FromBytes + AsBytes + Unaligned + Sized {
+    /// Length of the header in bytes (not including any options).
+    const LEN: usize = std::mem::size_of::<Self>();
+
+    /// Views the beginning of `bytes` as this header, returning it along with the rest of the buffer.
+    ///
+    /// Returns `None` if `bytes` is too short to contain the header.
+    #[inline]
+    fn ref_from_prefix(bytes: &[u8]) -> Option<(&Self, &[u8])> {
+        LayoutVerified::<_, Self>::new_unaligned_from_prefix(bytes).map(|(hdr, rest)| (hdr.into_ref(), rest))
+    }
+
+    /// Mutable version of [`Header::ref_from_prefix`].
+    #[inline]
+    fn mut_from_prefix(bytes: &mut [u8]) -> Option<(&mut Self, &mut [u8])> {
+        LayoutVerified::<_, Self>::new_unaligned_from_prefix(bytes).map(|(hdr, rest)| (hdr.into_mut(), rest))
+    }
+}
+
+impl Header for ArpHdr {}
+impl Header for EtherHdr {}
+impl Header for GeneveHdr {}
+impl Header for IcmpHdr {}
+impl Header for Ipv4Hdr {}
+impl Header for Ipv6Hdr {}
+impl Header for TcpHdr {}
+impl Header for UdpHdr {}
+impl Header for VlanHdr {}
+impl Header for VxlanHdr {}
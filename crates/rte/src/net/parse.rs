@@ -0,0 +1,236 @@
+use arrayvec::ArrayVec;
+
+use super::*;
+
+/// Maximal number of (stacked) VLAN tags parsed by [`parse_headers`].
+pub const MAX_VLANS: usize = 2;
+
+/// The network layer header of a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L3Hdr<'a> {
+    Ipv4(&'a Ipv4Hdr),
+    Ipv6(&'a Ipv6Hdr),
+    Arp(&'a ArpHdr),
+}
+
+/// The transport layer header of a packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum L4Hdr<'a> {
+    Tcp(&'a TcpHdr),
+    Udp(&'a UdpHdr),
+    /// Either ICMP or ICMPv6, depending on the L3 header.
+    Icmp(&'a IcmpHdr),
+}
+
+/// A layered view of a packet's headers, as returned by [`parse_headers`].
+///
+/// Parsing stops at the first layer which is either unsupported or truncated, in which case it and all following
+/// layers are `None`. The `*_len` fields follow the same semantics as the mbuf's fields of the same name, and are 0 for
+/// missing layers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Headers<'a> {
+    pub ether: &'a EtherHdr,
+    /// VLAN tags, outermost first.
+    pub vlans: ArrayVec<&'a VlanHdr, MAX_VLANS>,
+    pub l3: Option<L3Hdr<'a>>,
+    pub l4: Option<L4Hdr<'a>>,
+    /// Length of the L2 headers, including VLAN tags.
+    pub l2_len: usize,
+    /// Length of the L3 header, including IPv4 options.
+    pub l3_len: usize,
+    /// Length of the L4 header, including TCP options.
+    pub l4_len: usize,
+}
+
+impl<'a> Headers<'a> {
+    /// Returns the ether type of the L3 header, i.e. the innermost VLAN tag's ether type, if any.
+    #[inline]
+    pub fn ether_type(&self) -> u16 {
+        self.vlans.last().map_or(self.ether.ether_type, |vlan| vlan.eth_proto).get()
+    }
+
+    #[inline]
+    pub fn l3_offset(&self) -> usize {
+        self.l2_len
+    }
+
+    #[inline]
+    pub fn l4_offset(&self) -> usize {
+        self.l2_len + self.l3_len
+    }
+
+    #[inline]
+    pub fn payload_offset(&self) -> usize {
+        self.l2_len + self.l3_len + self.l4_len
+    }
+}
+
+/// Parses the Ethernet, VLAN, L3 and L4 headers at the beginning of `packet`.
+///
+/// Returns `None` if the packet is too short to contain even an Ethernet header.
+///
+/// # Implementation notes
+/// - IPv6 extension headers are not traversed, so the L4 header is only found if it immediately follows the IPv6
+///   header.
+/// - The L4 header of non-first IPv4 fragments is not parsed, since they do not contain one.
+/// - Checksums and length fields are not validated, other than making sure the headers fit in the packet.
+pub fn parse_headers(packet: &[u8]) -> Option<Headers<'_>> {
+    let (ether, mut rest) = EtherHdr::ref_from_prefix(packet)?;
+    let mut headers =
+        Headers { ether, vlans: ArrayVec::new(), l3: None, l4: None, l2_len: EtherHdr::LEN, l3_len: 0, l4_len: 0 };
+
+    while matches!(headers.ether_type(), ETHER_TYPE_VLAN | ETHER_TYPE_QINQ) && !headers.vlans.is_full() {
+        match VlanHdr::ref_from_prefix(rest) {
+            Some((vlan, next)) => {
+                headers.vlans.push(vlan);
+                headers.l2_len += VlanHdr::LEN;
+                rest = next;
+            }
+            None => return Some(headers),
+        }
+    }
+
+    let (l3, l3_len) = match parse_l3(headers.ether_type(), rest) {
+        Some(l3) => l3,
+        None => return Some(headers),
+    };
+    headers.l3 = Some(l3);
+    headers.l3_len = l3_len;
+
+    if let Some((l4, l4_len)) = parse_l4(l3, &rest[l3_len..]) {
+        headers.l4 = Some(l4);
+        headers.l4_len = l4_len;
+    }
+
+    Some(headers)
+}
+
+fn parse_l3(ether_type: u16, bytes: &[u8]) -> Option<(L3Hdr<'_>, usize)> {
+    match ether_type {
+        ETHER_TYPE_IPV4 => {
+            let (ipv4, _) = Ipv4Hdr::ref_from_prefix(bytes)?;
+            let len = ipv4.header_len();
+            if ipv4.version() != 4 || len < Ipv4Hdr::LEN || len > bytes.len() {
+                return None;
+            }
+
+            Some((L3Hdr::Ipv4(ipv4), len))
+        }
+        ETHER_TYPE_IPV6 => {
+            let (ipv6, _) = Ipv6Hdr::ref_from_prefix(bytes)?;
+            if ipv6.version() != 6 {
+                return None;
+            }
+
+            Some((L3Hdr::Ipv6(ipv6), Ipv6Hdr::LEN))
+        }
+        ETHER_TYPE_ARP => {
+            let (arp, _) = ArpHdr::ref_from_prefix(bytes)?;
+            Some((L3Hdr::Arp(arp), ArpHdr::LEN))
+        }
+        _ => None,
+    }
+}
+
+fn parse_l4<'a>(l3: L3Hdr<'_>, bytes: &'a [u8]) -> Option<(L4Hdr<'a>, usize)> {
+    let (proto, icmp_proto) = match l3 {
+        L3Hdr::Ipv4(ipv4) if ipv4.fragment_data_offset() == 0 => (ipv4.next_proto_id, IPPROTO_ICMP),
+        L3Hdr::Ipv6(ipv6) => (ipv6.proto, IPPROTO_ICMPV6),
+        _ => return None,
+    };
+
+    match proto {
+        IPPROTO_TCP => {
+            let (tcp, _) = TcpHdr::ref_from_prefix(bytes)?;
+            let len = tcp.header_len();
+            (len >= TcpHdr::LEN && len <= bytes.len()).then_some((L4Hdr::Tcp(tcp), len))
+        }
+        IPPROTO_UDP => UdpHdr::ref_from_prefix(bytes).map(|(udp, _)| (L4Hdr::Udp(udp), UdpHdr::LEN)),
+        proto if proto == icmp_proto => {
+            IcmpHdr::ref_from_prefix(bytes).map(|(icmp, _)| (L4Hdr::Icmp(icmp), IcmpHdr::LEN))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+
+    use mac_addr::MacAddr;
+    use zerocopy::byteorder::network_endian::U16;
+
+    use super::*;
+    use crate::mbuf::{GlobalAllocator, MBuf};
+
+    fn ether(ether_type: u16) -> EtherHdr {
+        EtherHdr {
+            dst_addr: MacAddr::BROADCAST,
+            src_addr: MacAddr::new(2, 0, 0, 0, 0, 1),
+            ether_type: U16::new(ether_type),
+        }
+    }
+
+    fn ipv4(proto: u8) -> Ipv4Hdr {
+        Ipv4Hdr {
+            version_ihl: Ipv4Hdr::VERSION_IHL,
+            time_to_live: 64,
+            next_proto_id: proto,
+            src_addr: [10, 0, 0, 1],
+            dst_addr: [10, 0, 0, 2],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_vlan_udp() {
+        let vlan = VlanHdr { vlan_tci: U16::new(0x2064), eth_proto: U16::new(ETHER_TYPE_IPV4) };
+        let udp = UdpHdr { src_port: U16::new(1234), dst_port: U16::new(4789), ..Default::default() };
+
+        let mut packet = MBuf::<GlobalAllocator>::new();
+        packet.extend_from_slice(ether(ETHER_TYPE_VLAN).as_bytes());
+        packet.extend_from_slice(vlan.as_bytes());
+        packet.extend_from_slice(ipv4(IPPROTO_UDP).as_bytes());
+        packet.extend_from_slice(udp.as_bytes());
+        packet.extend_from_slice(b"payload");
+
+        let headers = packet.parse_headers().unwrap();
+        assert_eq!(headers.vlans[0].vid(), 100);
+        assert_eq!(headers.vlans[0].pcp(), 1);
+        assert_eq!(headers.ether_type(), ETHER_TYPE_IPV4);
+        assert!(matches!(headers.l3, Some(L3Hdr::Ipv4(ip)) if ip.dst() == Ipv4Addr::new(10, 0, 0, 2)));
+        assert_eq!(headers.l4, Some(L4Hdr::Udp(&udp)));
+        assert_eq!((headers.l2_len, headers.l3_len, headers.l4_len), (18, 20, 8));
+        assert_eq!(&packet[headers.payload_offset()..], b"payload");
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        let mut packet = ether(ETHER_TYPE_IPV4).as_bytes().to_vec();
+        packet.extend_from_slice(ipv4(IPPROTO_TCP).as_bytes());
+
+        // TCP header is missing
+        let headers = parse_headers(&packet).unwrap();
+        assert!(matches!(headers.l3, Some(L3Hdr::Ipv4(_))));
+        assert_eq!(headers.l4, None);
+
+        let headers = parse_headers(&packet[..EtherHdr::LEN + 10]).unwrap();
+        assert_eq!(headers.l3, None);
+        assert_eq!(headers.l3_offset(), EtherHdr::LEN);
+
+        assert_eq!(parse_headers(&packet[..EtherHdr::LEN - 1]), None);
+    }
+
+    #[test]
+    fn test_parse_arp() {
+        let arp = ArpHdr { arp_opcode: U16::new(1), arp_tip: [10, 0, 0, 1], ..Default::default() };
+
+        let mut packet = ether(ETHER_TYPE_ARP).as_bytes().to_vec();
+        packet.extend_from_slice(arp.as_bytes());
+
+        let headers = parse_headers(&packet).unwrap();
+        assert_eq!(headers.l3, Some(L3Hdr::Arp(&arp)));
+        assert_eq!(headers.l4, None);
+        assert_eq!(headers.payload_offset(), packet.len());
+    }
+}
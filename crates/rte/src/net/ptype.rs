@@ -0,0 +1,269 @@
+//! Typed representation of DPDK's packet types, see `rte_mbuf_ptype.h`:
+//! <https://doc.dpdk.org/api-22.11/rte__mbuf__ptype_8h.html>
+
+use std::fmt;
+
+/// Type of an L2 header, either the outer or the inner one (in which case only `Ether*` types are used).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum L2Type {
+    Unknown,
+    Ether,
+    EtherTimesync,
+    EtherArp,
+    EtherLldp,
+    EtherNsh,
+    EtherVlan,
+    EtherQinq,
+    EtherPppoe,
+    EtherFcoe,
+    EtherMpls,
+}
+
+/// Type of an L3 header, either the outer or the inner one.
+///
+/// `*Ext` types have IPv4 options or IPv6 extension headers, `*ExtUnknown` types may or may not have them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum L3Type {
+    Unknown,
+    Ipv4,
+    Ipv4Ext,
+    Ipv4ExtUnknown,
+    Ipv6,
+    Ipv6Ext,
+    Ipv6ExtUnknown,
+}
+
+impl L3Type {
+    #[inline]
+    pub fn is_ipv4(self) -> bool {
+        matches!(self, Self::Ipv4 | Self::Ipv4Ext | Self::Ipv4ExtUnknown)
+    }
+
+    #[inline]
+    pub fn is_ipv6(self) -> bool {
+        matches!(self, Self::Ipv6 | Self::Ipv6Ext | Self::Ipv6ExtUnknown)
+    }
+}
+
+/// Type of an L4 header, either the outer or the inner one (in which case [`L4Type::Igmp`] is not used).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum L4Type {
+    Unknown,
+    Tcp,
+    Udp,
+    /// An IP fragment, whose L4 type can only be determined after reassembly.
+    Frag,
+    Sctp,
+    Icmp,
+    /// Any other non-fragmented IP packet.
+    NonFrag,
+    Igmp,
+}
+
+/// Type of the tunnel encapsulating the inner packet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TunnelType {
+    None,
+    Ip,
+    Gre,
+    Vxlan,
+    Nvgre,
+    Geneve,
+    /// Transparent Ethernet bridging over GRE.
+    Grenat,
+    Gtpc,
+    Gtpu,
+    Esp,
+    L2tp,
+    VxlanGpe,
+    MplsInGre,
+    MplsInUdp,
+}
+
+const L2_TYPES: &[(u32, L2Type)] = &[
+    (ffi::RTE_PTYPE_L2_ETHER, L2Type::Ether),
+    (ffi::RTE_PTYPE_L2_ETHER_TIMESYNC, L2Type::EtherTimesync),
+    (ffi::RTE_PTYPE_L2_ETHER_ARP, L2Type::EtherArp),
+    (ffi::RTE_PTYPE_L2_ETHER_LLDP, L2Type::EtherLldp),
+    (ffi::RTE_PTYPE_L2_ETHER_NSH, L2Type::EtherNsh),
+    (ffi::RTE_PTYPE_L2_ETHER_VLAN, L2Type::EtherVlan),
+    (ffi::RTE_PTYPE_L2_ETHER_QINQ, L2Type::EtherQinq),
+    (ffi::RTE_PTYPE_L2_ETHER_PPPOE, L2Type::EtherPppoe),
+    (ffi::RTE_PTYPE_L2_ETHER_FCOE, L2Type::EtherFcoe),
+    (ffi::RTE_PTYPE_L2_ETHER_MPLS, L2Type::EtherMpls),
+];
+
+const L3_TYPES: &[(u32, L3Type)] = &[
+    (ffi::RTE_PTYPE_L3_IPV4, L3Type::Ipv4),
+    (ffi::RTE_PTYPE_L3_IPV4_EXT, L3Type::Ipv4Ext),
+    (ffi::RTE_PTYPE_L3_IPV4_EXT_UNKNOWN, L3Type::Ipv4ExtUnknown),
+    (ffi::RTE_PTYPE_L3_IPV6, L3Type::Ipv6),
+    (ffi::RTE_PTYPE_L3_IPV6_EXT, L3Type::Ipv6Ext),
+    (ffi::RTE_PTYPE_L3_IPV6_EXT_UNKNOWN, L3Type::Ipv6ExtUnknown),
+];
+
+const L4_TYPES: &[(u32, L4Type)] = &[
+    (ffi::RTE_PTYPE_L4_TCP, L4Type::Tcp),
+    (ffi::RTE_PTYPE_L4_UDP, L4Type::Udp),
+    (ffi::RTE_PTYPE_L4_FRAG, L4Type::Frag),
+    (ffi::RTE_PTYPE_L4_SCTP, L4Type::Sctp),
+    (ffi::RTE_PTYPE_L4_ICMP, L4Type::Icmp),
+    (ffi::RTE_PTYPE_L4_NONFRAG, L4Type::NonFrag),
+    (ffi::RTE_PTYPE_L4_IGMP, L4Type::Igmp),
+];
+
+const TUNNEL_TYPES: &[(u32, TunnelType)] = &[
+    (ffi::RTE_PTYPE_TUNNEL_IP, TunnelType::Ip),
+    (ffi::RTE_PTYPE_TUNNEL_GRE, TunnelType::Gre),
+    (ffi::RTE_PTYPE_TUNNEL_VXLAN, TunnelType::Vxlan),
+    (ffi::RTE_PTYPE_TUNNEL_NVGRE, TunnelType::Nvgre),
+    (ffi::RTE_PTYPE_TUNNEL_GENEVE, TunnelType::Geneve),
+    (ffi::RTE_PTYPE_TUNNEL_GRENAT, TunnelType::Grenat),
+    (ffi::RTE_PTYPE_TUNNEL_GTPC, TunnelType::Gtpc),
+    (ffi::RTE_PTYPE_TUNNEL_GTPU, TunnelType::Gtpu),
+    (ffi::RTE_PTYPE_TUNNEL_ESP, TunnelType::Esp),
+    (ffi::RTE_PTYPE_TUNNEL_L2TP, TunnelType::L2tp),
+    (ffi::RTE_PTYPE_TUNNEL_VXLAN_GPE, TunnelType::VxlanGpe),
+    (ffi::RTE_PTYPE_TUNNEL_MPLS_IN_GRE, TunnelType::MplsInGre),
+    (ffi::RTE_PTYPE_TUNNEL_MPLS_IN_UDP, TunnelType::MplsInUdp),
+];
+
+const INNER_L2_TYPES: &[(u32, L2Type)] = &[
+    (ffi::RTE_PTYPE_INNER_L2_ETHER, L2Type::Ether),
+    (ffi::RTE_PTYPE_INNER_L2_ETHER_VLAN, L2Type::EtherVlan),
+    (ffi::RTE_PTYPE_INNER_L2_ETHER_QINQ, L2Type::EtherQinq),
+];
+
+const INNER_L3_TYPES: &[(u32, L3Type)] = &[
+    (ffi::RTE_PTYPE_INNER_L3_IPV4, L3Type::Ipv4),
+    (ffi::RTE_PTYPE_INNER_L3_IPV4_EXT, L3Type::Ipv4Ext),
+    (ffi::RTE_PTYPE_INNER_L3_IPV4_EXT_UNKNOWN, L3Type::Ipv4ExtUnknown),
+    (ffi::RTE_PTYPE_INNER_L3_IPV6, L3Type::Ipv6),
+    (ffi::RTE_PTYPE_INNER_L3_IPV6_EXT, L3Type::Ipv6Ext),
+    (ffi::RTE_PTYPE_INNER_L3_IPV6_EXT_UNKNOWN, L3Type::Ipv6ExtUnknown),
+];
+
+const INNER_L4_TYPES: &[(u32, L4Type)] = &[
+    (ffi::RTE_PTYPE_INNER_L4_TCP, L4Type::Tcp),
+    (ffi::RTE_PTYPE_INNER_L4_UDP, L4Type::Udp),
+    (ffi::RTE_PTYPE_INNER_L4_FRAG, L4Type::Frag),
+    (ffi::RTE_PTYPE_INNER_L4_SCTP, L4Type::Sctp),
+    (ffi::RTE_PTYPE_INNER_L4_ICMP, L4Type::Icmp),
+    (ffi::RTE_PTYPE_INNER_L4_NONFRAG, L4Type::NonFrag),
+];
+
+/// A packet's type, as either reported by the NIC ([`MBuf::packet_type`](crate::mbuf::MBuf::packet_type)) or parsed
+/// in software ([`MBuf::classify`](crate::mbuf::MBuf::classify)).
+///
+/// This is a typed view of the mbuf's raw `packet_type` field, made up of the outer headers' types, the tunnel type, and
+/// the inner (encapsulated) headers' types.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PacketType(u32);
+
+impl PacketType {
+    #[inline]
+    pub const fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    #[inline]
+    pub const fn as_raw(self) -> u32 {
+        self.0
+    }
+
+    #[inline]
+    pub fn l2(self) -> L2Type {
+        self.decode(ffi::RTE_PTYPE_L2_MASK, L2_TYPES, L2Type::Unknown)
+    }
+
+    #[inline]
+    pub fn l3(self) -> L3Type {
+        self.decode(ffi::RTE_PTYPE_L3_MASK, L3_TYPES, L3Type::Unknown)
+    }
+
+    #[inline]
+    pub fn l4(self) -> L4Type {
+        self.decode(ffi::RTE_PTYPE_L4_MASK, L4_TYPES, L4Type::Unknown)
+    }
+
+    #[inline]
+    pub fn tunnel(self) -> TunnelType {
+        self.decode(ffi::RTE_PTYPE_TUNNEL_MASK, TUNNEL_TYPES, TunnelType::None)
+    }
+
+    #[inline]
+    pub fn inner_l2(self) -> L2Type {
+        self.decode(ffi::RTE_PTYPE_INNER_L2_MASK, INNER_L2_TYPES, L2Type::Unknown)
+    }
+
+    #[inline]
+    pub fn inner_l3(self) -> L3Type {
+        self.decode(ffi::RTE_PTYPE_INNER_L3_MASK, INNER_L3_TYPES, L3Type::Unknown)
+    }
+
+    #[inline]
+    pub fn inner_l4(self) -> L4Type {
+        self.decode(ffi::RTE_PTYPE_INNER_L4_MASK, INNER_L4_TYPES, L4Type::Unknown)
+    }
+
+    #[inline]
+    pub fn is_tunnel(self) -> bool {
+        self.tunnel() != TunnelType::None
+    }
+
+    fn decode<T: Copy>(self, mask: u32, types: &[(u32, T)], unknown: T) -> T {
+        types.iter().find(|(value, _)| self.0 & mask == *value).map_or(unknown, |&(_, ty)| ty)
+    }
+}
+
+impl From<PacketType> for u32 {
+    fn from(ptype: PacketType) -> Self {
+        ptype.0
+    }
+}
+
+impl fmt::Debug for PacketType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut f = f.debug_struct("PacketType");
+        f.field("l2", &self.l2()).field("l3", &self.l3()).field("l4", &self.l4());
+        if self.is_tunnel() {
+            f.field("tunnel", &self.tunnel())
+                .field("inner_l2", &self.inner_l2())
+                .field("inner_l3", &self.inner_l3())
+                .field("inner_l4", &self.inner_l4());
+        }
+        f.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let ptype = PacketType::from_raw(
+            ffi::RTE_PTYPE_L2_ETHER
+                | ffi::RTE_PTYPE_L3_IPV4_EXT_UNKNOWN
+                | ffi::RTE_PTYPE_L4_UDP
+                | ffi::RTE_PTYPE_TUNNEL_VXLAN
+                | ffi::RTE_PTYPE_INNER_L2_ETHER_VLAN
+                | ffi::RTE_PTYPE_INNER_L3_IPV6
+                | ffi::RTE_PTYPE_INNER_L4_TCP,
+        );
+
+        assert_eq!((ptype.l2(), ptype.l3(), ptype.l4()), (L2Type::Ether, L3Type::Ipv4ExtUnknown, L4Type::Udp));
+        assert_eq!(ptype.tunnel(), TunnelType::Vxlan);
+        assert_eq!(
+            (ptype.inner_l2(), ptype.inner_l3(), ptype.inner_l4()),
+            (L2Type::EtherVlan, L3Type::Ipv6, L4Type::Tcp)
+        );
+        assert!(ptype.l3().is_ipv4() && ptype.inner_l3().is_ipv6());
+
+        let unknown = PacketType::default();
+        assert_eq!(
+            (unknown.l2(), unknown.l3(), unknown.tunnel()),
+            (L2Type::Unknown, L3Type::Unknown, TunnelType::None)
+        );
+    }
+}
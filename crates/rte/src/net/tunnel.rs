@@ -0,0 +1,288 @@
+//! Helpers for encapsulating packets in (and decapsulating them from) VXLAN and GENEVE tunnels.
+//!
+//! See also: <https://datatracker.ietf.org/doc/html/rfc7348> and <https://datatracker.ietf.org/doc/html/rfc8926>
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use mac_addr::MacAddr;
+use zerocopy::byteorder::network_endian::{U16, U32};
+
+use super::{
+    ipv4_cksum, ipv6_udptcp_cksum, EtherHdr, GeneveHdr, Header, Ipv4Hdr, Ipv6Hdr, L3Hdr, L4Hdr, UdpHdr, VxlanHdr,
+    ETHER_TYPE_IPV4, ETHER_TYPE_IPV6, IPPROTO_UDP,
+};
+use crate::{
+    flags::PktTxOffload,
+    mbuf::{Allocator, MBuf, MetadataExt},
+};
+
+/// IANA assigned UDP port for VXLAN.
+pub const VXLAN_PORT: u16 = ffi::RTE_VXLAN_DEFAULT_PORT as u16;
+
+/// IANA assigned UDP port for GENEVE.
+pub const GENEVE_PORT: u16 = ffi::RTE_GENEVE_DEFAULT_PORT as u16;
+
+/// Ether type of transparent Ethernet bridging, i.e. an encapsulated Ethernet frame.
+pub const ETHER_TYPE_TEB: u16 = ffi::RTE_ETHER_TYPE_TEB as u16;
+
+/// A tunnel type along with its virtual network identifier (24 bits).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Tunnel {
+    Vxlan { vni: u32 },
+    Geneve { vni: u32 },
+}
+
+impl Tunnel {
+    fn header_len(self) -> usize {
+        match self {
+            Self::Vxlan { .. } => VxlanHdr::LEN,
+            Self::Geneve { .. } => GeneveHdr::LEN,
+        }
+    }
+
+    fn vni(self) -> u32 {
+        match self {
+            Self::Vxlan { vni } | Self::Geneve { vni } => vni,
+        }
+    }
+}
+
+/// The outer IP addresses of a tunnel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OuterIp {
+    V4 { src: Ipv4Addr, dst: Ipv4Addr },
+    V6 { src: Ipv6Addr, dst: Ipv6Addr },
+}
+
+/// The outer headers' parameters, used by [`encap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Underlay {
+    pub src_mac: MacAddr,
+    pub dst_mac: MacAddr,
+    pub ip: OuterIp,
+    /// IPv4 TTL or IPv6 hop limit.
+    pub ttl: u8,
+    /// The outer UDP source port, which should be derived from the inner packet's flow (e.g. its RSS hash, see
+    /// [`thash`](crate::thash)), so the underlay network can load balance between flows.
+    pub src_port: u16,
+}
+
+/// Encapsulates `mbuf` (which should contain an Ethernet frame) in `tunnel`, prepending the outer Ethernet, IP, UDP
+/// and tunnel headers using the mbuf's headroom.
+///
+/// The outer IPv4 header checksum is computed in software, and the outer UDP checksum is either left empty (for IPv4)
+/// or computed in software (for IPv6). The mbuf's tunnel offload flags and outer header lengths are set, and its
+/// `l2_len` (if set) is extended by the UDP and tunnel headers, as required for inner offloads.
+///
+/// Returns `false` (leaving the mbuf unmodified) if the VNI is larger than 24 bits, there is not enough headroom, or
+/// the resulting packet is too long.
+pub fn encap<A: Allocator>(mbuf: &mut MBuf<A>, underlay: &Underlay, tunnel: Tunnel) -> bool {
+    let (l3_len, ether_type, outer_flag) = match underlay.ip {
+        OuterIp::V4 { .. } => (Ipv4Hdr::LEN, ETHER_TYPE_IPV4, PktTxOffload::OUTER_IPV4),
+        OuterIp::V6 { .. } => (Ipv6Hdr::LEN, ETHER_TYPE_IPV6, PktTxOffload::OUTER_IPV6),
+    };
+    let l4_len = UdpHdr::LEN + tunnel.header_len() + mbuf.len();
+    if tunnel.vni() >= 1 << 24 || l3_len + l4_len > usize::from(u16::MAX) {
+        return false;
+    }
+    let l4_len = l4_len as u16;
+    if mbuf.prepend((EtherHdr::LEN + l3_len + UdpHdr::LEN + tunnel.header_len()) as u16).is_none() {
+        return false;
+    }
+
+    let (ether, l3) = EtherHdr::mut_from_prefix(mbuf).unwrap();
+    *ether = EtherHdr { dst_addr: underlay.dst_mac, src_addr: underlay.src_mac, ether_type: U16::new(ether_type) };
+
+    let (l3, l4) = l3.split_at_mut(l3_len);
+    let (udp, tunnel_hdr) = UdpHdr::mut_from_prefix(l4).unwrap();
+    *udp = UdpHdr {
+        src_port: U16::new(underlay.src_port),
+        dst_port: U16::new(match tunnel {
+            Tunnel::Vxlan { .. } => VXLAN_PORT,
+            Tunnel::Geneve { .. } => GENEVE_PORT,
+        }),
+        dgram_len: U16::new(l4_len),
+        dgram_cksum: U16::ZERO,
+    };
+
+    match tunnel {
+        Tunnel::Vxlan { vni } => {
+            *VxlanHdr::mut_from_prefix(tunnel_hdr).unwrap().0 = VxlanHdr::new(vni);
+        }
+        Tunnel::Geneve { vni } => {
+            let [_, a, b, c] = vni.to_be_bytes();
+            *GeneveHdr::mut_from_prefix(tunnel_hdr).unwrap().0 =
+                GeneveHdr { proto: U16::new(ETHER_TYPE_TEB), vni: [a, b, c], ..Default::default() };
+        }
+    }
+
+    match underlay.ip {
+        OuterIp::V4 { src, dst } => {
+            let (ipv4, _) = Ipv4Hdr::mut_from_prefix(l3).unwrap();
+            *ipv4 = Ipv4Hdr {
+                version_ihl: Ipv4Hdr::VERSION_IHL,
+                total_length: U16::new(l3_len as u16 + l4_len),
+                time_to_live: underlay.ttl,
+                next_proto_id: IPPROTO_UDP,
+                src_addr: src.octets(),
+                dst_addr: dst.octets(),
+                ..Default::default()
+            };
+            let cksum = ipv4_cksum(l3);
+            Ipv4Hdr::mut_from_prefix(l3).unwrap().0.hdr_checksum.set(cksum);
+        }
+        OuterIp::V6 { src, dst } => {
+            let (ipv6, _) = Ipv6Hdr::mut_from_prefix(l3).unwrap();
+            *ipv6 = Ipv6Hdr {
+                vtc_flow: U32::new(6 << 28),
+                payload_len: U16::new(l4_len),
+                proto: IPPROTO_UDP,
+                hop_limits: underlay.ttl,
+                src_addr: src.octets(),
+                dst_addr: dst.octets(),
+            };
+            let cksum = ipv6_udptcp_cksum(ipv6, l4);
+            UdpHdr::mut_from_prefix(l4).unwrap().0.dgram_cksum.set(cksum);
+        }
+    }
+
+    let l2_len = mbuf.l2_len();
+    if l2_len != 0 {
+        mbuf.set_l2_len(l2_len + (UdpHdr::LEN + tunnel.header_len()) as u64);
+    }
+    mbuf.set_outer_l2_len(EtherHdr::LEN as u64);
+    mbuf.set_outer_l3_len(l3_len as u64);
+    mbuf.enable_ol_flags(
+        outer_flag
+            | match tunnel {
+                Tunnel::Vxlan { .. } => PktTxOffload::TUNNEL_VXLAN,
+                Tunnel::Geneve { .. } => PktTxOffload::TUNNEL_GENEVE,
+            },
+    );
+    true
+}
+
+/// Strips the outer headers of a VXLAN or GENEVE encapsulated packet, leaving only the inner Ethernet frame, and
+/// returns the tunnel it was received on.
+///
+/// Tunnels are identified by the outer UDP destination port, and the outer IP and UDP lengths are validated against
+/// the packet's length (checksums are not validated).
+///
+/// Returns `None` (leaving the mbuf unmodified) if the packet is not a valid VXLAN or GENEVE packet.
+pub fn decap<A: Allocator>(mbuf: &mut MBuf<A>) -> Option<Tunnel> {
+    let (tunnel, outer_len) = {
+        let headers = mbuf.parse_headers()?;
+        let l3_end = match headers.l3? {
+            L3Hdr::Ipv4(ipv4) => headers.l3_offset() + usize::from(ipv4.total_length.get()),
+            L3Hdr::Ipv6(ipv6) => headers.l4_offset() + usize::from(ipv6.payload_len.get()),
+            L3Hdr::Arp(_) => return None,
+        };
+        let udp = match headers.l4? {
+            L4Hdr::Udp(udp) if headers.l4_offset() + usize::from(udp.dgram_len.get()) == l3_end => udp,
+            _ => return None,
+        };
+        if l3_end > mbuf.len() {
+            return None;
+        }
+
+        let tunnel_hdr = &mbuf[headers.payload_offset()..l3_end];
+        match udp.dst_port.get() {
+            VXLAN_PORT => {
+                let (vxlan, _) = VxlanHdr::ref_from_prefix(tunnel_hdr)?;
+                if vxlan.vx_flags.get() & VxlanHdr::FLAG_VNI == 0 {
+                    return None;
+                }
+                (Tunnel::Vxlan { vni: vxlan.vni() }, headers.payload_offset() + VxlanHdr::LEN)
+            }
+            GENEVE_PORT => {
+                let (geneve, _) = GeneveHdr::ref_from_prefix(tunnel_hdr)?;
+                if geneve.version() != 0
+                    || geneve.proto.get() != ETHER_TYPE_TEB
+                    || geneve.header_len() > tunnel_hdr.len()
+                {
+                    return None;
+                }
+                (Tunnel::Geneve { vni: geneve.vni() }, headers.payload_offset() + geneve.header_len())
+            }
+            _ => return None,
+        }
+    };
+
+    // the inner packet must at least contain an Ethernet header
+    if outer_len + EtherHdr::LEN > mbuf.len() {
+        return None;
+    }
+    mbuf.adj(outer_len as u16).then_some(tunnel)
+}
+
+#[cfg(test)]
+mod tests {
+    use zerocopy::AsBytes;
+
+    use super::*;
+    use crate::{mbuf::GlobalAllocator, net::raw_cksum};
+
+    const INNER: &[u8] = b"\x02\x00\x00\x00\x00\x02\x02\x00\x00\x00\x00\x01\x08\x00inner payload";
+
+    fn inner_mbuf() -> MBuf<GlobalAllocator> {
+        MBuf::new_with_data(INNER)
+    }
+
+    fn underlay(ip: OuterIp) -> Underlay {
+        Underlay {
+            src_mac: MacAddr::new(2, 0, 0, 0, 1, 1),
+            dst_mac: MacAddr::new(2, 0, 0, 0, 1, 2),
+            ip,
+            ttl: 64,
+            src_port: 49152,
+        }
+    }
+
+    #[test]
+    fn test_vxlan_ipv4() {
+        let mut mbuf = inner_mbuf();
+        let ip = OuterIp::V4 { src: Ipv4Addr::new(10, 0, 0, 1), dst: Ipv4Addr::new(10, 0, 0, 2) };
+        assert!(encap(&mut mbuf, &underlay(ip), Tunnel::Vxlan { vni: 0x123456 }));
+        assert_eq!(mbuf.len(), INNER.len() + 50);
+        assert!(mbuf.tx_offload_flags().contains(PktTxOffload::TUNNEL_VXLAN | PktTxOffload::OUTER_IPV4));
+
+        {
+            let headers = mbuf.parse_headers().unwrap();
+            assert!(matches!(headers.l3, Some(L3Hdr::Ipv4(ipv4)) if ipv4.dst() == Ipv4Addr::new(10, 0, 0, 2)));
+            assert!(matches!(headers.l4, Some(L4Hdr::Udp(udp)) if udp.dst_port.get() == VXLAN_PORT));
+            assert_eq!(raw_cksum(&mbuf[headers.l3_offset()..headers.l4_offset()]), 0xffff);
+        }
+
+        assert_eq!(decap(&mut mbuf), Some(Tunnel::Vxlan { vni: 0x123456 }));
+        assert_eq!(&mbuf[..], INNER);
+        assert_eq!(decap(&mut mbuf), None);
+    }
+
+    #[test]
+    fn test_geneve_ipv6() {
+        let mut mbuf = inner_mbuf();
+        let ip = OuterIp::V6 { src: Ipv6Addr::LOCALHOST, dst: Ipv6Addr::LOCALHOST };
+        assert!(encap(&mut mbuf, &underlay(ip), Tunnel::Geneve { vni: 42 }));
+        assert_eq!(mbuf.len(), INNER.len() + 70);
+
+        assert_eq!(decap(&mut mbuf), Some(Tunnel::Geneve { vni: 42 }));
+        assert_eq!(&mbuf[..], INNER);
+
+        // larger than 24 bits, leaving the mbuf unmodified
+        assert!(!encap(&mut mbuf, &underlay(ip), Tunnel::Geneve { vni: 1 << 24 }));
+        assert_eq!(&mbuf[..], INNER);
+    }
+
+    #[test]
+    fn test_decap_invalid() {
+        let mut mbuf = inner_mbuf();
+        let ip = OuterIp::V4 { src: Ipv4Addr::new(10, 0, 0, 1), dst: Ipv4Addr::new(10, 0, 0, 2) };
+        assert!(encap(&mut mbuf, &underlay(ip), Tunnel::Vxlan { vni: 1 }));
+
+        // clear the VXLAN "I" flag
+        let offset = EtherHdr::LEN + Ipv4Hdr::LEN + UdpHdr::LEN;
+        mbuf[offset..offset + VxlanHdr::LEN].copy_from_slice(VxlanHdr::default().as_bytes());
+        assert_eq!(decap(&mut mbuf), None);
+        assert_eq!(mbuf.len(), INNER.len() + 50);
+    }
+}
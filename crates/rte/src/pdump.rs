@@ -0,0 +1,165 @@
+//! Based on DPDK's `rte_pdump.h` and `rte_pcapng.h` APIs: <https://doc.dpdk.org/api-22.11/rte__pdump_8h.html>,
+//! <https://doc.dpdk.org/api-22.11/rte__pcapng_8h.html>
+//!
+//! Packet capture of live traffic, without stopping the datapath. Once [`Pdump`] is initialized, the RX/TX traffic of
+//! a port can be captured either by a secondary process (e.g. `dpdk-dumpcap`), or from within the application by
+//! [enabling](Pdump::enable) a [`Capture`], which copies the selected packets into a ring. Packets captured in the
+//! [pcapng format](PdumpFlags::PCAPNG) can then be written to a file using a [`PcapngWriter`].
+
+use std::{
+    fs::File,
+    os::unix::io::{AsRawFd, IntoRawFd},
+    ptr::{self, NonNull},
+};
+
+use rte_error::{check, ReturnValue as _};
+
+use crate::{
+    flags::PdumpFlags,
+    mbuf::{Allocator, MBuf},
+    mempool::MemoryPool,
+    ring::{MbufSender, Multi},
+    Result,
+};
+
+/// Capture statistics of a port, summed over all of its queues.
+pub type Stats = ffi::rte_pdump_stats;
+
+/// The queues of a port to capture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Queue {
+    All,
+    Id(u16),
+}
+
+impl Queue {
+    fn id(self) -> u16 {
+        match self {
+            // RTE_PDUMP_ALL_QUEUES
+            Self::All => u16::MAX,
+            Self::Id(id) => id,
+        }
+    }
+}
+
+/// The packet capture framework, which must be initialized (in the primary process) before any capture is enabled.
+///
+/// The framework is uninitialized when this struct is dropped, which requires all [`Capture`]s to be dropped first.
+#[derive(Debug)]
+pub struct Pdump(());
+
+impl Pdump {
+    /// Initializes the packet capture framework, allowing secondary processes to request captures.
+    #[inline]
+    pub fn init() -> Result<Self> {
+        unsafe { ffi::rte_pdump_init() }.rte_ok()?;
+        Ok(Self(()))
+    }
+
+    /// Starts capturing the traffic of `port_id`'s `queue` in the directions specified by `flags`, copying captured
+    /// packets into mbufs allocated from `pool` and enqueuing them to `sender`'s ring.
+    ///
+    /// Packets are dropped from the capture (but not from the datapath) if either the pool or the ring is exhausted.
+    /// The capture is stopped when the returned [`Capture`] is dropped.
+    ///
+    /// Note that DPDK requires both the ring and the pool to be multi-producer and multi-consumer.
+    #[inline]
+    pub fn enable<'a>(
+        &'a self,
+        port_id: u16,
+        queue: Queue,
+        flags: PdumpFlags,
+        sender: MbufSender<&'a MemoryPool, Multi, Multi>,
+        pool: &'a MemoryPool,
+    ) -> Result<Capture<'a>> {
+        unsafe {
+            ffi::rte_pdump_enable(port_id, queue.id(), flags.bits(), sender.as_raw(), pool.0.as_ptr(), ptr::null_mut())
+        }
+        .rte_ok()?;
+
+        Ok(Capture { port_id, queue, flags, _sender: sender })
+    }
+
+    /// Returns the capture statistics of `port_id`.
+    #[inline]
+    pub fn stats(&self, port_id: u16) -> Result<Stats> {
+        let mut stats = Stats::default();
+        unsafe { ffi::rte_pdump_stats(port_id, &mut stats) }.rte_ok()?;
+        Ok(stats)
+    }
+}
+
+impl Drop for Pdump {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_pdump_uninit() };
+    }
+}
+
+/// An enabled capture, see [`Pdump::enable`].
+pub struct Capture<'a> {
+    port_id: u16,
+    queue: Queue,
+    flags: PdumpFlags,
+    _sender: MbufSender<&'a MemoryPool, Multi, Multi>,
+}
+
+impl Capture<'_> {
+    #[inline]
+    pub fn port_id(&self) -> u16 {
+        self.port_id
+    }
+
+    #[inline]
+    pub fn queue(&self) -> Queue {
+        self.queue
+    }
+
+    #[inline]
+    pub fn flags(&self) -> PdumpFlags {
+        self.flags
+    }
+}
+
+impl Drop for Capture<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_pdump_disable(self.port_id, self.queue.id(), self.flags.bits()) };
+    }
+}
+
+/// A writer of pcapng capture files.
+///
+/// The file's header describes all the ports of the application, and only packets captured with the
+/// [`PdumpFlags::PCAPNG`] flag may be written to it.
+pub struct PcapngWriter(NonNull<ffi::rte_pcapng_t>);
+
+impl PcapngWriter {
+    /// Writes the pcapng file header to `file`, taking ownership of it.
+    #[inline]
+    pub fn new(file: File) -> Result<Self> {
+        let fd = file.as_raw_fd();
+        let writer =
+            unsafe { ffi::rte_pcapng_fdopen(fd, ptr::null(), ptr::null(), ptr::null(), ptr::null()) }.rte_ok()?;
+
+        // the file is closed by `rte_pcapng_close`
+        let _ = file.into_raw_fd();
+        Ok(Self(writer))
+    }
+
+    /// Writes the given (pcapng formatted) packets to the file, returning the number of bytes written.
+    #[inline]
+    pub fn write<A: Allocator>(&mut self, mbufs: &mut [MBuf<A>]) -> Result<usize> {
+        let len = mbufs.len().try_into().expect("too many mbufs");
+        // `MBuf` is a transparent wrapper of an mbuf pointer
+        let written = check!(
+            unsafe { ffi::rte_pcapng_write_packets(self.0.as_ptr(), mbufs.as_mut_ptr().cast(), len) },
+            |&written| written < 0
+        )?;
+        Ok(written as usize)
+    }
+}
+
+impl Drop for PcapngWriter {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_pcapng_close(self.0.as_ptr()) };
+    }
+}
@@ -0,0 +1,424 @@
+//! A framework for packet-processing applications made of stages, each run by one or more worker lcores: receiving
+//! packets from RX queues, processing them, and sending them on TX queues, with rings between the stages:
+//! ```rust,ignore
+//! let (rx_queues, tx_queues) = port.into_queues();
+//! let pipeline = PipelineBuilder::new("fwd")
+//!     .rx(rx_queues)
+//!     .worker(2, |pkts: &mut Burst| pkts.retain(|pkt| !is_blocked(pkt)))
+//!     .tx(tx_queues)
+//!     .launch()?;
+//!
+//! // ...
+//! for stats in pipeline.stop() {
+//!     println!("{stats}");
+//! }
+//! ```
+//!
+//! Lcores are assigned from the worker lcores which aren't running anything, preferring the ones on the socket of the
+//! ports (so that packets don't cross sockets), and [stopping](Pipeline::stop) the pipeline drains it: the RX stage
+//! stops receiving, and each following stage stops once the previous one stopped and its ring is empty.
+
+use std::{
+    fmt,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+use rte_error::Error;
+
+use crate::{
+    ethdev::{RxQueue, TxQueue},
+    launch::State,
+    lcore,
+    mbuf::PacketBatch,
+    memory::SocketId,
+    mempool::MemoryPool,
+    ring::{MbufReceiver, MbufRing, MbufSender},
+    Result,
+};
+
+/// The maximum number of packets handled at once by a stage.
+pub const BURST: usize = 32;
+
+/// The default number of packets held by the rings between the stages.
+pub const DEFAULT_RING_SIZE: u32 = 1024;
+
+/// The packets handled at once by a stage.
+pub type Burst = PacketBatch<&'static MemoryPool, BURST>;
+
+type Worker = Box<dyn FnMut(&mut Burst) + Send>;
+
+enum StageKind {
+    Rx(Vec<RxQueue<'static>>),
+    Worker(Vec<Worker>),
+    Tx(Vec<TxQueue<'static>>),
+}
+
+impl StageKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Rx(_) => "rx",
+            Self::Worker(_) => "worker",
+            Self::Tx(_) => "tx",
+        }
+    }
+}
+
+/// A builder of a [`Pipeline`], whose stages are an RX stage, any number of worker stages, and a TX stage.
+pub struct PipelineBuilder {
+    name: String,
+    ring_size: u32,
+    stages: Vec<StageKind>,
+}
+
+impl PipelineBuilder {
+    /// Creates a pipeline whose rings are named after `name`.
+    pub fn new(name: &str) -> Self {
+        Self { name: name.to_owned(), ring_size: DEFAULT_RING_SIZE, stages: vec![] }
+    }
+
+    /// Sets the number of packets held by the rings between the stages.
+    pub fn ring_size(mut self, ring_size: u32) -> Self {
+        self.ring_size = ring_size;
+        self
+    }
+
+    /// Adds the RX stage, with an lcore per queue.
+    pub fn rx(mut self, queues: Vec<RxQueue<'static>>) -> Self {
+        self.stages.push(StageKind::Rx(queues));
+        self
+    }
+
+    /// Adds a worker stage, run by `lcores` lcores (each with its own clone of `worker`), which processes the bursts
+    /// received from the previous stage: the packets left in the burst are passed on to the next stage.
+    pub fn worker<F>(mut self, lcores: usize, worker: F) -> Self
+    where
+        F: FnMut(&mut Burst) + Clone + Send + 'static,
+    {
+        self.stages.push(StageKind::Worker((0..lcores).map(|_| Box::new(worker.clone()) as Worker).collect()));
+        self
+    }
+
+    /// Adds the TX stage, with an lcore per queue.
+    pub fn tx(mut self, queues: Vec<TxQueue<'static>>) -> Self {
+        self.stages.push(StageKind::Tx(queues));
+        self
+    }
+
+    /// Creates the rings between the stages, and launches the stages on worker lcores.
+    ///
+    /// Fails with `EINVAL` if the stages aren't an RX stage, worker stages and a TX stage (each with at least one
+    /// lcore), or with `ENOSPC` if there aren't enough worker lcores waiting for a job.
+    ///
+    /// **NOTE:** should be executed on main lcore only. Will `panic` otherwise, if debug assertions are enabled.
+    pub fn launch(self) -> Result<Pipeline> {
+        let valid = match self.stages.as_slice() {
+            [StageKind::Rx(rx), workers @ .., StageKind::Tx(tx)] => {
+                !rx.is_empty()
+                    && !tx.is_empty()
+                    && workers.iter().all(|stage| matches!(stage, StageKind::Worker(workers) if !workers.is_empty()))
+            }
+            _ => false,
+        };
+        if !valid {
+            return Err(Error(libc::EINVAL));
+        }
+
+        // the socket of the first port, for the worker stages
+        let socket_id = match &self.stages[0] {
+            StageKind::Rx(queues) => queues[0].dev().socket_id().ok(),
+            _ => unreachable!(),
+        };
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let mut stages = vec![];
+        let mut instances = vec![];
+        // the index of the stage of each instance
+        let mut stage_indices = vec![];
+        let mut input: Option<(MbufReceiver<&'static MemoryPool>, Arc<AtomicUsize>)> = None;
+        let nb_stages = self.stages.len();
+
+        for (i, kind) in self.stages.into_iter().enumerate() {
+            let name = kind.name();
+            let output = if i + 1 < nb_stages {
+                let ring = MbufRing::new(format!("{}_{i}", self.name), self.ring_size, socket_id)?;
+                Some(ring.split())
+            } else {
+                None
+            };
+
+            let nb_instances = match &kind {
+                StageKind::Rx(queues) => queues.len(),
+                StageKind::Worker(workers) => workers.len(),
+                StageKind::Tx(queues) => queues.len(),
+            };
+            let running = Arc::new(AtomicUsize::new(nb_instances));
+            let counters = (0..nb_instances).map(|_| Arc::new(Counters::default())).collect::<Vec<_>>();
+
+            let upstream = input.take();
+            let instance = |input, worker, output, socket_id, counters: &Arc<Counters>| Instance {
+                input,
+                worker,
+                output,
+                socket_id,
+                counters: counters.clone(),
+                upstream: upstream.as_ref().map(|(_, running)| running.clone()),
+                stop: stop.clone(),
+                running: running.clone(),
+            };
+            let ring_input = || Input::Ring(upstream.as_ref().unwrap().0.clone());
+            let ring_output = || Output::Ring(output.as_ref().unwrap().0.clone());
+
+            match kind {
+                StageKind::Rx(queues) => {
+                    for (queue, counters) in queues.into_iter().zip(&counters) {
+                        let socket_id = queue.dev().socket_id().ok();
+                        instances.push(instance(Input::Queue(queue), None, ring_output(), socket_id, counters));
+                    }
+                }
+                StageKind::Worker(workers) => {
+                    for (worker, counters) in workers.into_iter().zip(&counters) {
+                        instances.push(instance(ring_input(), Some(worker), ring_output(), socket_id, counters));
+                    }
+                }
+                StageKind::Tx(queues) => {
+                    for (queue, counters) in queues.into_iter().zip(&counters) {
+                        let socket_id = queue.dev().socket_id().ok();
+                        instances.push(instance(ring_input(), None, Output::Queue(queue), socket_id, counters));
+                    }
+                }
+            }
+
+            stage_indices.resize(instances.len(), i);
+            stages.push(Stage { name, lcores: vec![], counters, running: running.clone() });
+            input = output.map(|(_, receiver)| (receiver, running));
+        }
+
+        let lcores = assign_lcores(instances.iter().map(|instance| instance.socket_id))?;
+        let mut pipeline = Pipeline { stop, stages, stopped: false };
+
+        let mut instances = instances.into_iter().zip(stage_indices);
+        for lcore in lcores {
+            let (instance, stage) = instances.next().unwrap();
+            if let Err(err) = lcore.launch(run, instance) {
+                // the instances which weren't launched won't ever stop
+                pipeline.stop.store(true, Ordering::Release);
+                for (instance, _) in instances {
+                    instance.running.fetch_sub(1, Ordering::Release);
+                }
+                pipeline.join();
+                return Err(err);
+            }
+            pipeline.stages[stage].lcores.push(lcore);
+        }
+
+        Ok(pipeline)
+    }
+}
+
+/// Assigns a waiting worker lcore to each instance, preferably on its socket.
+fn assign_lcores(sockets: impl Iterator<Item = Option<SocketId>>) -> Result<Vec<lcore::Id>> {
+    let mut free = lcore::Id::iter_enabled(true).filter(|id| id.state() == State::Wait).collect::<Vec<_>>();
+
+    sockets
+        .map(|socket_id| {
+            let same_socket = free.iter().position(|id| socket_id.is_some() && id.socket_id() == socket_id);
+            let index = same_socket.or_else(|| (!free.is_empty()).then_some(0)).ok_or(Error(libc::ENOSPC))?;
+            Ok(free.remove(index))
+        })
+        .collect()
+}
+
+enum Input {
+    Queue(RxQueue<'static>),
+    Ring(MbufReceiver<&'static MemoryPool>),
+}
+
+enum Output {
+    Ring(MbufSender<&'static MemoryPool>),
+    Queue(TxQueue<'static>),
+}
+
+#[derive(Default)]
+struct Counters {
+    packets_in: AtomicU64,
+    packets_out: AtomicU64,
+    dropped: AtomicU64,
+}
+
+/// An instance of a stage, run by an lcore.
+struct Instance {
+    input: Input,
+    worker: Option<Worker>,
+    output: Output,
+    socket_id: Option<SocketId>,
+    counters: Arc<Counters>,
+    /// The number of instances of the previous stage still running, `None` for the RX stage.
+    upstream: Option<Arc<AtomicUsize>>,
+    stop: Arc<AtomicBool>,
+    /// The number of instances of this stage still running.
+    running: Arc<AtomicUsize>,
+}
+
+fn run(instance: Instance) -> i32 {
+    let Instance { mut input, mut worker, mut output, counters, upstream, stop, running, .. } = instance;
+    let mut pkts = Burst::new();
+
+    loop {
+        // checked before dequeuing, so that the last packets of the previous stage are drained
+        let done = match &upstream {
+            Some(upstream) => upstream.load(Ordering::Acquire) == 0,
+            None => stop.load(Ordering::Acquire),
+        };
+
+        match &mut input {
+            Input::Queue(_) if done => break,
+            Input::Queue(queue) => queue.recv(&mut pkts),
+            Input::Ring(receiver) => {
+                receiver.dequeue_burst(&mut pkts);
+            }
+        }
+        if pkts.is_empty() {
+            if done {
+                break;
+            }
+            continue;
+        }
+        counters.packets_in.fetch_add(pkts.len() as u64, Ordering::Relaxed);
+
+        if let Some(worker) = &mut worker {
+            worker(&mut pkts);
+        }
+
+        let len = pkts.len();
+        match &mut output {
+            Output::Ring(sender) => {
+                sender.enqueue_burst(&mut pkts);
+            }
+            Output::Queue(queue) => queue.send(&mut pkts),
+        }
+        counters.packets_out.fetch_add((len - pkts.len()) as u64, Ordering::Relaxed);
+        counters.dropped.fetch_add(pkts.len() as u64, Ordering::Relaxed);
+        pkts.clear();
+    }
+
+    running.fetch_sub(1, Ordering::Release);
+    0
+}
+
+struct Stage {
+    name: &'static str,
+    lcores: Vec<lcore::Id>,
+    counters: Vec<Arc<Counters>>,
+    running: Arc<AtomicUsize>,
+}
+
+/// The counters of a stage, summed over its lcores.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageStats {
+    /// `rx`, `worker` or `tx`.
+    pub name: &'static str,
+    pub lcores: Vec<lcore::Id>,
+    /// The packets received from the RX queues, or the previous stage.
+    pub packets_in: u64,
+    /// The packets passed on to the next stage, or sent on the TX queues.
+    pub packets_out: u64,
+    /// The packets dropped because the next stage's ring, or the TX queue, was full (the ones dropped by a worker
+    /// being the difference between the packets in and out, and these).
+    pub dropped: u64,
+}
+
+impl fmt::Display for StageStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lcores = self.lcores.iter().map(ToString::to_string).collect::<Vec<_>>();
+        write!(
+            f,
+            "{} (lcores {}): {} packets in, {} out, {} dropped",
+            self.name,
+            lcores.join(","),
+            self.packets_in,
+            self.packets_out,
+            self.dropped
+        )
+    }
+}
+
+/// A running pipeline, see [`PipelineBuilder`]. It's stopped when dropped.
+pub struct Pipeline {
+    stop: Arc<AtomicBool>,
+    stages: Vec<Stage>,
+    stopped: bool,
+}
+
+impl Pipeline {
+    /// Returns the current counters of each stage.
+    pub fn stats(&self) -> Vec<StageStats> {
+        self.stages
+            .iter()
+            .map(|stage| {
+                let sum = |counter: fn(&Counters) -> &AtomicU64| {
+                    stage.counters.iter().map(|counters| counter(counters).load(Ordering::Relaxed)).sum()
+                };
+                StageStats {
+                    name: stage.name,
+                    lcores: stage.lcores.clone(),
+                    packets_in: sum(|counters| &counters.packets_in),
+                    packets_out: sum(|counters| &counters.packets_out),
+                    dropped: sum(|counters| &counters.dropped),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns whether all the stages stopped (e.g. after a [stop](Self::stop) was signaled).
+    pub fn is_stopped(&self) -> bool {
+        self.stages.iter().all(|stage| stage.running.load(Ordering::Acquire) == 0)
+    }
+
+    /// Signals the pipeline to stop, and waits for it to be drained, returning the final counters of each stage.
+    ///
+    /// **NOTE:** should be executed on main lcore only. Will `panic` otherwise, if debug assertions are enabled.
+    pub fn stop(mut self) -> Vec<StageStats> {
+        self.stop.store(true, Ordering::Release);
+        self.join();
+        self.stats()
+    }
+
+    fn join(&mut self) {
+        for lcore in self.stages.iter().flat_map(|stage| &stage.lcores) {
+            lcore.wait();
+        }
+        self.stopped = true;
+    }
+}
+
+impl Drop for Pipeline {
+    fn drop(&mut self) {
+        if !self.stopped {
+            self.stop.store(true, Ordering::Release);
+            self.join();
+        }
+    }
+}
+
+impl fmt::Debug for Pipeline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pipeline").field("stats", &self.stats()).finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_stages() {
+        let no_tx = PipelineBuilder::new("test_no_tx").rx(vec![]).worker(1, |_: &mut Burst| {});
+        assert_eq!(no_tx.launch().unwrap_err(), Error(libc::EINVAL));
+
+        let no_rx = PipelineBuilder::new("test_no_rx").worker(1, |_: &mut Burst| {}).tx(vec![]);
+        assert_eq!(no_rx.launch().unwrap_err(), Error(libc::EINVAL));
+    }
+}
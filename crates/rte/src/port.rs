@@ -0,0 +1,264 @@
+//! Bringing up a port in a single call: configuring the device and its queues, then starting it, e.g.:
+//! ```rust,ignore
+//! let port = PortSetup::new(EthDev::new(0), &mempool)
+//!     .queues(4, 4)
+//!     .rss(EthRssConf { rss_key: None, rss_hf: EthRss::IPV4 | EthRss::IPV6 })
+//!     .start()?;
+//! let (rx_queues, tx_queues) = port.into_queues();
+//! ```
+
+use crate::{
+    ethdev::{EthConf, EthDev, EthRssConf, RxQueue, RxQueueConf, TxQueue, TxQueueConf},
+    flags::{DevRxOffload, DevTxOffload, EthMqRxMode},
+    mempool::MemoryPool,
+    Result,
+};
+
+/// The default number of RX descriptors of each queue (before being adjusted to the device's limits).
+pub const DEFAULT_NB_RX_DESC: u16 = 1024;
+/// The default number of TX descriptors of each queue (before being adjusted to the device's limits).
+pub const DEFAULT_NB_TX_DESC: u16 = 1024;
+
+/// A builder of a started [`Port`], see the [module docs](self).
+#[derive(Clone)]
+pub struct PortSetup<'mp> {
+    dev: EthDev,
+    conf: EthConf,
+    nb_rx_queue: u16,
+    nb_tx_queue: u16,
+    nb_rx_desc: u16,
+    nb_tx_desc: u16,
+    rx_conf: Option<RxQueueConf>,
+    tx_conf: Option<TxQueueConf>,
+    mempools: Vec<&'mp MemoryPool>,
+    promiscuous: bool,
+}
+
+impl<'mp> PortSetup<'mp> {
+    /// Sets up `dev` (which must be stopped) with a single RX and TX queue, receiving packets into `mempool`.
+    pub fn new(dev: EthDev, mempool: &'mp MemoryPool) -> Self {
+        Self {
+            dev,
+            conf: EthConf::default(),
+            nb_rx_queue: 1,
+            nb_tx_queue: 1,
+            nb_rx_desc: DEFAULT_NB_RX_DESC,
+            nb_tx_desc: DEFAULT_NB_TX_DESC,
+            rx_conf: None,
+            tx_conf: None,
+            mempools: vec![mempool],
+            promiscuous: false,
+        }
+    }
+
+    pub fn queues(mut self, nb_rx_queue: u16, nb_tx_queue: u16) -> Self {
+        self.nb_rx_queue = nb_rx_queue;
+        self.nb_tx_queue = nb_tx_queue;
+        self
+    }
+
+    pub fn descriptors(mut self, nb_rx_desc: u16, nb_tx_desc: u16) -> Self {
+        self.nb_rx_desc = nb_rx_desc;
+        self.nb_tx_desc = nb_tx_desc;
+        self
+    }
+
+    /// Uses a memory pool per RX queue (e.g. one per socket of the lcores polling them), RX queue `i` (and TX queue
+    /// `i`, for the type of its packets) using the `i % mempools.len()`-th one.
+    pub fn mempools(mut self, mempools: impl IntoIterator<Item = &'mp MemoryPool>) -> Self {
+        self.mempools = mempools.into_iter().collect();
+        assert!(!self.mempools.is_empty(), "at least one memory pool is required");
+        self
+    }
+
+    /// Replaces the whole configuration of the device, including the offloads and RSS configuration.
+    pub fn conf(mut self, conf: EthConf) -> Self {
+        self.conf = conf;
+        self
+    }
+
+    /// Sets the RX offloads (`RTE_ETH_RX_OFFLOAD_*`) of all the queues.
+    pub fn rx_offloads(mut self, offloads: DevRxOffload) -> Self {
+        self.conf.rx_offloads = offloads;
+        self
+    }
+
+    pub fn tx_offloads(mut self, offloads: DevTxOffload) -> Self {
+        self.conf.tx_offloads = offloads;
+        self
+    }
+
+    /// Distributes the received packets between the RX queues with RSS.
+    pub fn rss(mut self, rss: EthRssConf) -> Self {
+        self.conf.rx_mq_mode |= EthMqRxMode::RSS_FLAG;
+        self.conf.rss = rss;
+        self
+    }
+
+    pub fn rx_queue_conf(mut self, conf: RxQueueConf) -> Self {
+        self.rx_conf = Some(conf);
+        self
+    }
+
+    pub fn tx_queue_conf(mut self, conf: TxQueueConf) -> Self {
+        self.tx_conf = Some(conf);
+        self
+    }
+
+    pub fn promiscuous(mut self, promiscuous: bool) -> Self {
+        self.promiscuous = promiscuous;
+        self
+    }
+
+    fn mempool(&self, queue_id: u16) -> &'mp MemoryPool {
+        self.mempools[usize::from(queue_id) % self.mempools.len()]
+    }
+
+    /// Configures the device, adjusting the numbers of descriptors to its limits, sets up its queues and starts it.
+    ///
+    /// If any step fails, the device is stopped and reset (unless its driver doesn't support it, in which case it's
+    /// left stopped, to be configured again).
+    pub fn start(self) -> Result<Port<'mp>> {
+        self.bring_up().map_err(|err| {
+            let _ = self.dev.stop();
+            let _ = self.dev.reset();
+            err
+        })
+    }
+
+    fn bring_up(&self) -> Result<Port<'mp>> {
+        let dev = &self.dev;
+
+        dev.configure_with(self.nb_rx_queue, self.nb_tx_queue, &self.conf)?;
+        let (mut nb_rx_desc, mut nb_tx_desc) = (self.nb_rx_desc, self.nb_tx_desc);
+        dev.adjust_nb_rx_tx_desc(&mut nb_rx_desc, &mut nb_tx_desc)?;
+
+        let rx_conf = self.rx_conf.as_ref().map(Into::into);
+        for queue_id in 0..self.nb_rx_queue {
+            dev.rx_queue_setup(queue_id, nb_rx_desc, rx_conf, self.mempool(queue_id))?;
+        }
+        let tx_conf = self.tx_conf.as_ref().map(Into::into);
+        for queue_id in 0..self.nb_tx_queue {
+            dev.tx_queue_setup(queue_id, nb_tx_desc, tx_conf)?;
+        }
+
+        dev.start()?;
+        if self.promiscuous {
+            dev.promiscuous_enable()?;
+        }
+
+        // Safety: the queues were set up with these memory pools, and the port hands out each of them once
+        let rx_queues = (0..self.nb_rx_queue)
+            .map(|queue_id| unsafe { RxQueue::new(dev.clone(), queue_id, self.mempool(queue_id)) });
+        let tx_queues = (0..self.nb_tx_queue)
+            .map(|queue_id| unsafe { TxQueue::new(dev.clone(), queue_id, self.mempool(queue_id)) });
+        Ok(Port {
+            dev: dev.clone(),
+            nb_rx_desc,
+            nb_tx_desc,
+            rx_queues: rx_queues.map(Some).collect(),
+            tx_queues: tx_queues.map(Some).collect(),
+        })
+    }
+}
+
+/// A started port, along with its queues, each of which is handed out once (so that it's only ever polled by a single
+/// lcore).
+pub struct Port<'mp> {
+    dev: EthDev,
+    nb_rx_desc: u16,
+    nb_tx_desc: u16,
+    /// `None` once taken.
+    rx_queues: Vec<Option<RxQueue<'mp>>>,
+    tx_queues: Vec<Option<TxQueue<'mp>>>,
+}
+
+impl<'mp> Port<'mp> {
+    #[inline]
+    pub fn dev(&self) -> &EthDev {
+        &self.dev
+    }
+
+    /// Returns the number of descriptors of each RX queue, as adjusted to the device's limits.
+    #[inline]
+    pub fn nb_rx_desc(&self) -> u16 {
+        self.nb_rx_desc
+    }
+
+    /// Returns the number of descriptors of each TX queue, as adjusted to the device's limits.
+    #[inline]
+    pub fn nb_tx_desc(&self) -> u16 {
+        self.nb_tx_desc
+    }
+
+    #[inline]
+    pub fn nb_rx_queues(&self) -> u16 {
+        self.rx_queues.len() as u16
+    }
+
+    #[inline]
+    pub fn nb_tx_queues(&self) -> u16 {
+        self.tx_queues.len() as u16
+    }
+
+    /// Takes RX queue `queue_id`, e.g. to hand it over to the lcore polling it, returning `None` if the port doesn't
+    /// have it or it was already taken.
+    #[inline]
+    pub fn take_rx_queue(&mut self, queue_id: u16) -> Option<RxQueue<'mp>> {
+        self.rx_queues.get_mut(usize::from(queue_id))?.take()
+    }
+
+    /// Takes TX queue `queue_id`, returning `None` if the port doesn't have it or it was already taken.
+    #[inline]
+    pub fn take_tx_queue(&mut self, queue_id: u16) -> Option<TxQueue<'mp>> {
+        self.tx_queues.get_mut(usize::from(queue_id))?.take()
+    }
+
+    /// Returns the queues which weren't taken yet (in order), e.g. to hand them over to the lcores polling them.
+    #[inline]
+    pub fn into_queues(self) -> (Vec<RxQueue<'mp>>, Vec<TxQueue<'mp>>) {
+        (self.rx_queues.into_iter().flatten().collect(), self.tx_queues.into_iter().flatten().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::{
+        mbuf::PacketBatch,
+        test_utils::{FakePort, TestPool},
+    };
+
+    #[rte_test]
+    fn test_port_setup() {
+        let mempool = TestPool::new(4095);
+        // removes the device when dropped
+        let fake = FakePort::null("port_setup", 256, &mempool).unwrap();
+        let dev = fake.dev().clone();
+        dev.stop().unwrap();
+
+        // rolled back, so that the device can be set up again
+        assert!(PortSetup::new(dev.clone(), &mempool).queues(2, u16::MAX).start().is_err());
+
+        let mut port = PortSetup::new(dev.clone(), &mempool).queues(2, 2).descriptors(256, 256).start().unwrap();
+        assert_eq!((port.nb_rx_queues(), port.nb_tx_queues()), (2, 2));
+
+        let mut rx_queue = port.take_rx_queue(1).unwrap();
+        let mut tx_queue = port.take_tx_queue(0).unwrap();
+        // each queue is handed out once
+        assert!(port.take_rx_queue(1).is_none());
+        assert!(port.take_rx_queue(2).is_none());
+
+        let mut pkts = PacketBatch::<_, 8>::new();
+        rx_queue.recv(&mut pkts);
+        assert!(!pkts.is_empty());
+        tx_queue.send(&mut pkts);
+        assert!(pkts.is_empty());
+
+        let (rx_queues, tx_queues) = port.into_queues();
+        assert_eq!(rx_queues.iter().map(RxQueue::queue_id).collect::<Vec<_>>(), [0]);
+        assert_eq!(tx_queues.iter().map(TxQueue::queue_id).collect::<Vec<_>>(), [1]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Based on DPDK's `rte_rcu_qsbr.h` API: <https://doc.dpdk.org/api-22.11/rte__rcu__qsbr_8h.html>
+//!
+//! Quiescent state based reclamation (QSBR), allowing data shared with reader lcores (e.g. routing tables) to be
+//! replaced without any locking on the readers' fast path. Each reader lcore registers a [`Reader`], and reports a
+//! quiescent state (i.e. that it holds no references to shared data) once per iteration of its poll loop, e.g.:
+//!
+//! ```ignore
+//! let mut reader = qsbr.register(lcore_id)?;
+//! loop {
+//!     let table = rcu.read(&reader);
+//!     // ... process a burst of packets using `table` ...
+//!     reader.quiescent();
+//! }
+//! ```
+//!
+//! Replaced values are dropped once every reader has gone through a quiescent state, either by blocking the writer
+//! ([`Rcu::replace`]) or by deferring their reclamation ([`Rcu::replace_deferred`]).
+
+use std::{
+    ffi::CString,
+    fmt,
+    marker::PhantomData,
+    mem,
+    os::raw::{c_uint, c_void},
+    ptr::{self, NonNull},
+    sync::{
+        atomic::{AtomicPtr, Ordering},
+        Mutex,
+    },
+};
+
+use rte_error::{check, rte_error, Error, ReturnValue as _};
+
+use crate::{memory::SocketId, Result};
+
+/// A QSBR variable, tracking the quiescent states of up to `max_threads` readers.
+pub struct Qsbr {
+    ptr: NonNull<ffi::rte_rcu_qsbr>,
+    max_threads: u32,
+    /// The ids of the registered readers, which DPDK doesn't prevent from being registered twice.
+    registered: Mutex<Vec<bool>>,
+}
+
+// # Safety
+// The QSBR variable is designed to be shared between the writer and reader lcores.
+unsafe impl Send for Qsbr {}
+unsafe impl Sync for Qsbr {}
+
+impl Qsbr {
+    /// Creates a QSBR variable for readers with ids in `0..max_threads` (e.g. lcore ids), whose memory is allocated
+    /// on the given socket.
+    #[inline]
+    pub fn new(max_threads: u32, socket_id: Option<SocketId>) -> Result<Self> {
+        // i.e. an invalid number of threads
+        let size = check!(unsafe { ffi::rte_rcu_qsbr_get_memsize(max_threads) }, |&size| size == 1)?;
+
+        // rte_zmalloc doesn't set rte_errno
+        let ptr = NonNull::new(unsafe {
+            ffi::rte_zmalloc_socket(
+                ptr::null(),
+                size,
+                ffi::RTE_CACHE_LINE_SIZE,
+                socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+            )
+        })
+        .ok_or(Error(libc::ENOMEM))?
+        .cast();
+
+        if unsafe { ffi::rte_rcu_qsbr_init(ptr.as_ptr(), max_threads) } != 0 {
+            let err = rte_error();
+            unsafe { ffi::rte_free(ptr.as_ptr().cast()) };
+            return Err(err);
+        }
+
+        Ok(Self { ptr, max_threads, registered: Mutex::new(vec![false; max_threads as usize]) })
+    }
+
+    #[inline]
+    pub fn max_threads(&self) -> u32 {
+        self.max_threads
+    }
+
+    /// Registers the reader with the given id, which is initially online (i.e. may access shared data) and must then
+    /// periodically report its quiescent state. The reader is unregistered when dropped.
+    ///
+    /// Fails with `EEXIST` if a reader with the same id is already registered.
+    #[inline]
+    pub fn register(&self, thread_id: u32) -> Result<Reader<'_>> {
+        let mut registered = self.registered.lock().unwrap();
+        match registered.get_mut(thread_id as usize) {
+            Some(true) => return Err(Error(libc::EEXIST)),
+            Some(registered) => *registered = true,
+            // i.e. an invalid id, which DPDK rejects
+            None => (),
+        }
+
+        if unsafe { ffi::rte_rcu_qsbr_thread_register(self.ptr.as_ptr(), thread_id) } != 0 {
+            if let Some(registered) = registered.get_mut(thread_id as usize) {
+                *registered = false;
+            }
+            return Err(rte_error());
+        }
+
+        unsafe { ffi::_rte_rcu_qsbr_thread_online(self.ptr.as_ptr(), thread_id) };
+        Ok(Reader { qsbr: self, thread_id, online: true, _marker: PhantomData })
+    }
+
+    /// Starts a grace period, returning a token to [check](Self::check) its end with.
+    #[inline]
+    pub fn start(&self) -> u64 {
+        unsafe { ffi::_rte_rcu_qsbr_start(self.ptr.as_ptr()) }
+    }
+
+    /// Returns whether all of the online readers have gone through a quiescent state since `token` was
+    /// [started](Self::start), without blocking.
+    #[inline]
+    pub fn check(&self, token: u64) -> bool {
+        unsafe { ffi::_rte_rcu_qsbr_check(self.ptr.as_ptr(), token, false) == 1 }
+    }
+
+    /// Blocks until all of the online readers have gone through a quiescent state, after which they no longer hold
+    /// references to any data replaced before this call.
+    ///
+    /// This must not be called by a registered reader (which would wait for itself).
+    #[inline]
+    pub fn synchronize(&self) {
+        unsafe { ffi::rte_rcu_qsbr_synchronize(self.ptr.as_ptr(), ffi::RTE_QSBR_THRID_INVALID) }
+    }
+}
+
+impl fmt::Debug for Qsbr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Qsbr").field("max_threads", &self.max_threads).finish_non_exhaustive()
+    }
+}
+
+impl Drop for Qsbr {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_free(self.ptr.as_ptr().cast()) }
+    }
+}
+
+/// A registered reader of a [`Qsbr`] variable, which is used by a single lcore.
+pub struct Reader<'q> {
+    qsbr: &'q Qsbr,
+    thread_id: u32,
+    online: bool,
+    // a reader's state is only updated by its own lcore
+    _marker: PhantomData<*mut ()>,
+}
+
+impl Reader<'_> {
+    #[inline]
+    pub fn thread_id(&self) -> u32 {
+        self.thread_id
+    }
+
+    #[inline]
+    pub fn is_online(&self) -> bool {
+        self.online
+    }
+
+    /// Reports that the reader holds no references to shared data, which is enforced by borrowing it mutably.
+    #[inline]
+    pub fn quiescent(&mut self) {
+        unsafe { ffi::_rte_rcu_qsbr_quiescent(self.qsbr.ptr.as_ptr(), self.thread_id) }
+    }
+
+    /// Marks the reader as offline, e.g. before blocking, so that writers don't wait for its quiescent state. An
+    /// offline reader may not [read](Rcu::read) shared data.
+    #[inline]
+    pub fn offline(&mut self) {
+        unsafe { ffi::_rte_rcu_qsbr_thread_offline(self.qsbr.ptr.as_ptr(), self.thread_id) };
+        self.online = false;
+    }
+
+    /// Marks the reader as online again, after which it may read shared data.
+    #[inline]
+    pub fn online(&mut self) {
+        unsafe { ffi::_rte_rcu_qsbr_thread_online(self.qsbr.ptr.as_ptr(), self.thread_id) };
+        self.online = true;
+    }
+}
+
+impl fmt::Debug for Reader<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Reader").field("thread_id", &self.thread_id).field("online", &self.online).finish()
+    }
+}
+
+impl Drop for Reader<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            if self.online {
+                ffi::_rte_rcu_qsbr_thread_offline(self.qsbr.ptr.as_ptr(), self.thread_id);
+            }
+            ffi::rte_rcu_qsbr_thread_unregister(self.qsbr.ptr.as_ptr(), self.thread_id);
+        }
+        self.qsbr.registered.lock().unwrap()[self.thread_id as usize] = false;
+    }
+}
+
+/// A value shared with the readers of a [`Qsbr`] variable, which may be replaced concurrently with their reads.
+pub struct Rcu<'q, T> {
+    ptr: AtomicPtr<T>,
+    qsbr: &'q Qsbr,
+}
+
+// # Safety
+// The value is shared between lcores, and dropped by whichever lcore replaces (or drops) it.
+unsafe impl<T: Send + Sync> Send for Rcu<'_, T> {}
+unsafe impl<T: Send + Sync> Sync for Rcu<'_, T> {}
+
+impl<'q, T> Rcu<'q, T> {
+    #[inline]
+    pub fn new(value: T, qsbr: &'q Qsbr) -> Self {
+        Self { ptr: AtomicPtr::new(Box::into_raw(Box::new(value))), qsbr }
+    }
+
+    /// Returns the current value, which remains valid until the reader's next quiescent state.
+    ///
+    /// # Panics
+    /// Panics if the reader is offline, or belongs to another QSBR variable.
+    #[inline]
+    pub fn read<'r>(&'r self, reader: &'r Reader<'_>) -> &'r T {
+        assert!(reader.online, "reader is offline");
+        assert!(ptr::eq(reader.qsbr, self.qsbr), "reader of another QSBR variable");
+        unsafe { &*self.ptr.load(Ordering::Acquire) }
+    }
+
+    /// Replaces the value, blocking until the readers no longer reference the previous value, which is then dropped.
+    ///
+    /// This must not be called by a registered reader.
+    #[inline]
+    pub fn replace(&self, value: T) {
+        let old = self.ptr.swap(Box::into_raw(Box::new(value)), Ordering::AcqRel);
+        self.qsbr.synchronize();
+        drop(unsafe { Box::from_raw(old) });
+    }
+
+    /// Replaces the value without blocking, handing the previous value to `queue`, which drops it once the readers no
+    /// longer reference it.
+    ///
+    /// # Panics
+    /// Panics if the queue belongs to another QSBR variable.
+    #[inline]
+    pub fn replace_deferred(&self, value: T, queue: &DeferQueue<'q, T>) {
+        assert!(ptr::eq(queue.qsbr, self.qsbr), "queue of another QSBR variable");
+        let old = self.ptr.swap(Box::into_raw(Box::new(value)), Ordering::AcqRel);
+        unsafe { queue.enqueue(old) };
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Rcu<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = unsafe { &*self.ptr.load(Ordering::Acquire) };
+        f.debug_tuple("Rcu").field(value).finish()
+    }
+}
+
+impl<T> Drop for Rcu<'_, T> {
+    fn drop(&mut self) {
+        // no reader may still borrow the value
+        drop(unsafe { Box::from_raw(*self.ptr.get_mut()) });
+    }
+}
+
+/// A queue of replaced values of a [`Qsbr`] variable's readers, which are dropped once the readers no longer
+/// reference them.
+///
+/// Values are reclaimed as the queue fills up (or when [reclaimed](DeferQueue::reclaim) explicitly), and the remaining
+/// ones are dropped (after waiting for the readers) when the queue is dropped.
+pub struct DeferQueue<'q, T> {
+    ptr: NonNull<ffi::rte_rcu_qsbr_dq>,
+    qsbr: &'q Qsbr,
+    _marker: PhantomData<Box<T>>,
+}
+
+// # Safety
+// The queue is thread-safe, and its values are dropped by whichever lcore reclaims them.
+unsafe impl<T: Send> Send for DeferQueue<'_, T> {}
+unsafe impl<T: Send> Sync for DeferQueue<'_, T> {}
+
+unsafe extern "C" fn free<T>(_p: *mut c_void, e: *mut c_void, n: c_uint) {
+    let values = e.cast::<*mut T>();
+    for i in 0..n as usize {
+        drop(Box::from_raw(values.add(i).read_unaligned()));
+    }
+}
+
+impl<'q, T> DeferQueue<'q, T> {
+    /// Creates a queue of up to `size` values of the readers of `qsbr`, which reclaims values once more than
+    /// `trigger_reclaim_limit` are queued.
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, qsbr: &'q Qsbr, size: u32, trigger_reclaim_limit: u32) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        let params = ffi::rte_rcu_qsbr_dq_parameters {
+            name: name.as_ptr(),
+            size,
+            esize: mem::size_of::<*mut T>() as u32,
+            trigger_reclaim_limit,
+            max_reclaim_size: size,
+            free_fn: Some(free::<T>),
+            v: qsbr.ptr.as_ptr(),
+            ..Default::default()
+        };
+
+        unsafe { ffi::rte_rcu_qsbr_dq_create(&params) }.rte_ok().map(|ptr| Self { ptr, qsbr, _marker: PhantomData })
+    }
+
+    /// Queues a value to be dropped once the readers no longer reference it. If the queue is full (even after
+    /// reclaiming values), this blocks until the readers no longer reference the value and drops it.
+    unsafe fn enqueue(&self, value: *mut T) {
+        let mut value = value;
+        if ffi::rte_rcu_qsbr_dq_enqueue(self.ptr.as_ptr(), (&mut value as *mut *mut T).cast()) != 0 {
+            self.qsbr.synchronize();
+            drop(Box::from_raw(value));
+        }
+    }
+
+    /// Drops the queued values which are no longer referenced by the readers, returning the number of dropped values.
+    #[inline]
+    pub fn reclaim(&self) -> u32 {
+        let mut freed = 0;
+        unsafe {
+            ffi::rte_rcu_qsbr_dq_reclaim(self.ptr.as_ptr(), u32::MAX, &mut freed, ptr::null_mut(), ptr::null_mut())
+        };
+        freed
+    }
+}
+
+impl<T> fmt::Debug for DeferQueue<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("DeferQueue").finish_non_exhaustive()
+    }
+}
+
+impl<T> Drop for DeferQueue<'_, T> {
+    fn drop(&mut self) {
+        // the queue can only be deleted once all of its values have been reclaimed
+        self.qsbr.synchronize();
+        unsafe { ffi::rte_rcu_qsbr_dq_delete(self.ptr.as_ptr()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[rte_test]
+    fn test_qsbr_check() {
+        let qsbr = Qsbr::new(4, None).unwrap();
+        let mut reader = qsbr.register(1).unwrap();
+        assert_eq!(qsbr.register(1).unwrap_err(), Error(libc::EEXIST));
+        assert!(qsbr.register(4).is_err());
+
+        // the grace period lasts until the online reader reports a quiescent state
+        let token = qsbr.start();
+        assert!(!qsbr.check(token));
+        reader.quiescent();
+        assert!(qsbr.check(token));
+
+        // offline readers aren't waited for
+        reader.offline();
+        assert!(qsbr.check(qsbr.start()));
+        reader.online();
+        assert!(!qsbr.check(qsbr.start()));
+
+        // an unregistered reader isn't waited for either, and its id can be registered again
+        drop(reader);
+        assert!(qsbr.check(qsbr.start()));
+        let reader = qsbr.register(1).unwrap();
+        assert!(reader.is_online());
+    }
+}
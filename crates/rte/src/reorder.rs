@@ -0,0 +1,185 @@
+//! Based on DPDK's `rte_reorder.h` API: <https://doc.dpdk.org/api-22.11/rte__reorder_8h.html>
+//!
+//! A [`ReorderBuffer`] restores the order of packets that were processed out of order (e.g. when spraying packets
+//! across worker lcores), based on a sequence number assigned to each mbuf before it was processed.
+
+use std::{ffi::CString, marker::PhantomData, ptr::NonNull};
+
+use arrayvec::ArrayVec;
+use rte_error::{rte_error, ReturnValue as _};
+
+use crate::{
+    mbuf::{Allocator, MBuf},
+    memory::SocketId,
+    mempool::MemoryPool,
+    Result,
+};
+
+/// The reason an mbuf could not be inserted into a [`ReorderBuffer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InsertError {
+    /// The mbuf's sequence number precedes the buffer's window, i.e. mbufs following it have already been drained.
+    Late,
+    /// The mbuf's sequence number is too far ahead of the buffer's window to fit in the buffer.
+    Early,
+    /// The mbuf's sequence number is ahead of the buffer's window, which couldn't slide as mbufs from the beginning of
+    /// the window must be drained first.
+    Full,
+}
+
+/// A wrapper around an [`rte_reorder_buffer`](ffi::rte_reorder_buffer).
+///
+/// The buffer holds a window of `size` sequence numbers, starting at the sequence number of the first inserted mbuf
+/// (or the one following the last drained mbuf). Any mbufs still in the buffer are freed when it is dropped.
+pub struct ReorderBuffer<'pool> {
+    ptr: NonNull<ffi::rte_reorder_buffer>,
+    size: u32,
+    /// The (lower bound of the) first sequence number of the window, which DPDK doesn't expose, or `None` until the
+    /// first mbuf is inserted.
+    window_start: Option<u32>,
+    _marker: PhantomData<MBuf<&'pool MemoryPool>>,
+}
+
+// # Safety
+// The mbufs held by the buffer are owned exclusively by it, and can only be inserted or drained through `&mut self`.
+unsafe impl Send for ReorderBuffer<'_> {}
+
+impl<'pool> ReorderBuffer<'pool> {
+    /// Creates a buffer able to hold `size` mbufs, which must be a power of 2.
+    ///
+    /// This also registers the mbuf dynamic field holding the sequence number.
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, size: u32, socket_id: Option<SocketId>) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        unsafe { ffi::rte_reorder_create(name.as_ptr(), socket_id.map(|id| id.get()).unwrap_or(u32::MAX), size) }
+            .rte_ok()
+            .map(|ptr| Self { ptr, size, window_start: None, _marker: PhantomData })
+    }
+
+    /// Returns the sequence number of `mbuf`.
+    #[inline]
+    pub fn seqn<A: Allocator>(&self, mbuf: &MBuf<A>) -> u32 {
+        unsafe { *ffi::_rte_reorder_seqn(mbuf.as_raw()) }
+    }
+
+    /// Sets the sequence number of `mbuf`, which should be done before the mbuf's order is changed.
+    #[inline]
+    pub fn set_seqn<A: Allocator>(&self, mbuf: &mut MBuf<A>, seqn: u32) {
+        unsafe { *ffi::_rte_reorder_seqn(mbuf.as_raw()) = seqn };
+    }
+
+    /// Inserts an mbuf (whose [sequence number](Self::set_seqn) is set) into the buffer.
+    ///
+    /// Returns the mbuf back if its sequence number precedes the buffer's window, is too far ahead of it, or if the
+    /// buffer is full. Late mbufs should usually be sent as-is (or dropped), as the mbufs following them have already
+    /// been drained, while the buffer should be drained before inserting mbufs again once it is full.
+    #[inline]
+    pub fn insert(&mut self, mbuf: MBuf<&'pool MemoryPool>) -> Result<(), (MBuf<&'pool MemoryPool>, InsertError)> {
+        let seqn = self.seqn(&mbuf);
+        let ptr = mbuf.into_raw();
+        if unsafe { ffi::rte_reorder_insert(self.ptr.as_ptr(), ptr.as_ptr()) } == 0 {
+            // the window starts at the first inserted mbuf, and slides to fit the mbufs inserted past its end
+            self.window_start.get_or_insert(seqn);
+            self.slide_window(seqn.wrapping_sub(self.size - 1));
+            return Ok(());
+        }
+
+        let mbuf = unsafe { MBuf::from_raw(ptr) };
+        let err = match rte_error().0 {
+            libc::ENOSPC => InsertError::Full,
+            // i.e. ERANGE, for sequence numbers on either side of the window
+            _ => match self.window_start {
+                Some(start) if (seqn.wrapping_sub(start) as i32) < 0 => InsertError::Late,
+                _ => InsertError::Early,
+            },
+        };
+        Err((mbuf, err))
+    }
+
+    /// Moves the start of the window forward to `seqn`, unless it's already past it (in sequence number order, which
+    /// wraps around).
+    fn slide_window(&mut self, seqn: u32) {
+        if let Some(start) = &mut self.window_start {
+            if (seqn.wrapping_sub(*start) as i32) > 0 {
+                *start = seqn;
+            }
+        }
+    }
+
+    /// Drains the in-order mbufs at the beginning of the buffer's window into `mbufs`, returning the number of drained
+    /// mbufs.
+    ///
+    /// Mbufs that are missing from the window (e.g. dropped by a worker) are only skipped once room is needed for
+    /// inserting later mbufs.
+    #[inline]
+    pub fn drain<const CAP: usize>(&mut self, mbufs: &mut ArrayVec<MBuf<&'pool MemoryPool>, CAP>) -> usize {
+        let old_len = mbufs.len();
+
+        let drained = unsafe {
+            let spare_cap = mbufs.as_mut_ptr().add(old_len);
+            let drained =
+                ffi::rte_reorder_drain(self.ptr.as_ptr(), spare_cap.cast(), mbufs.remaining_capacity() as _) as usize;
+            mbufs.set_len(old_len + drained);
+            drained
+        };
+
+        if let Some(last) = mbufs.last().filter(|_| drained > 0) {
+            let next = self.seqn(last).wrapping_add(1);
+            self.slide_window(next);
+        }
+        drained
+    }
+
+    /// Frees all mbufs in the buffer and resets its window.
+    #[inline]
+    pub fn reset(&mut self) {
+        unsafe { ffi::rte_reorder_reset(self.ptr.as_ptr()) };
+        self.window_start = None;
+    }
+}
+
+impl Drop for ReorderBuffer<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_reorder_free(self.ptr.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::TestPool;
+
+    fn insert<'pool>(
+        buffer: &mut ReorderBuffer<'pool>,
+        mempool: &'pool MemoryPool,
+        seqn: u32,
+    ) -> Result<(), InsertError> {
+        let mut mbuf = MBuf::new_with_provider_and_data(&mempool, [0; 60]);
+        buffer.set_seqn(&mut mbuf, seqn);
+        buffer.insert(mbuf).map_err(|(_, err)| err)
+    }
+
+    #[rte_test]
+    fn test_insert() {
+        let mempool = TestPool::new(63);
+        let mut buffer = ReorderBuffer::new("test_reorder", 8, None).unwrap();
+
+        // the window starts at the first inserted mbuf
+        assert_eq!(insert(&mut buffer, &mempool, 10), Ok(()));
+        assert_eq!(insert(&mut buffer, &mempool, 9), Err(InsertError::Late));
+        assert_eq!(insert(&mut buffer, &mempool, 26), Err(InsertError::Early));
+        assert_eq!(insert(&mut buffer, &mempool, 11), Ok(()));
+
+        let mut mbufs = ArrayVec::<_, 8>::new();
+        assert_eq!(buffer.drain(&mut mbufs), 2);
+        assert_eq!(mbufs.iter().map(|mbuf| buffer.seqn(mbuf)).collect::<Vec<_>>(), [10, 11]);
+        assert_eq!(insert(&mut buffer, &mempool, 11), Err(InsertError::Late));
+        assert_eq!(insert(&mut buffer, &mempool, 12), Ok(()));
+
+        buffer.reset();
+        assert_eq!(insert(&mut buffer, &mempool, 1), Ok(()));
+    }
+}
@@ -0,0 +1,187 @@
+use std::{marker::PhantomData, mem, ptr::NonNull};
+
+use super::{Multi, Receiver, Ring, Sender, SyncMode};
+use crate::{
+    mbuf::{Allocator, MBuf, PacketBatch},
+    memory::SocketId,
+    Result,
+};
+
+/// An owned mbuf pointer, as stored inside an [`MbufRing`].
+///
+/// Has the same layout as [`MBuf`], which allows moving mbufs in and out of the ring without any conversions.
+#[repr(transparent)]
+struct RawMBuf<A: Allocator> {
+    ptr: NonNull<ffi::rte_mbuf>,
+    _marker: PhantomData<fn() -> A>,
+}
+
+// # Safety
+// A `RawMBuf` is only ever accessed by whoever dequeued it from the ring, at which point it is turned back into an
+// `MBuf`, so ownership of the mbuf is transferred between threads rather than shared.
+unsafe impl<A: Allocator> Send for RawMBuf<A> {}
+
+impl<A: Allocator> From<MBuf<A>> for RawMBuf<A> {
+    #[inline]
+    fn from(mbuf: MBuf<A>) -> Self {
+        Self { ptr: mbuf.into_raw(), _marker: PhantomData }
+    }
+}
+
+impl<A: Allocator> RawMBuf<A> {
+    #[inline]
+    fn into_mbuf(self) -> MBuf<A> {
+        let ptr = self.ptr;
+        mem::forget(self);
+        unsafe { MBuf::from_raw(ptr) }
+    }
+}
+
+/// Frees mbufs that are still enqueued when the ring is dropped.
+impl<A: Allocator> Drop for RawMBuf<A> {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe { A::free(self.ptr) }
+    }
+}
+
+/// A [`Ring`] specialized for handing off [`MBuf`]s between lcores.
+///
+/// While `MBuf`s themselves are not `Send`, enqueuing an mbuf transfers its ownership to the ring, and dequeuing it
+/// transfers the ownership to the receiving side, so an mbuf is never accessible from two lcores at the same time.
+///
+/// Any mbufs still in the ring when it is dropped are freed.
+pub struct MbufRing<A: Allocator, P: SyncMode = Multi, C: SyncMode = Multi>(Ring<RawMBuf<A>, P, C>);
+
+impl<A: Allocator, P: SyncMode, C: SyncMode> MbufRing<A, P, C> {
+    /// See [`Ring::new`].
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, count: u32, socket_id: Option<SocketId>) -> Result<Self> {
+        Ring::new(name, count, socket_id).map(Self)
+    }
+
+    /// Splits this ring into its producer and consumer halves.
+    #[inline]
+    pub fn split(self) -> (MbufSender<A, P, C>, MbufReceiver<A, P, C>) {
+        let (tx, rx) = self.0.split();
+        (MbufSender(tx), MbufReceiver(rx))
+    }
+
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.0.capacity()
+    }
+
+    #[inline]
+    pub fn len(&self) -> u32 {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// The producer half of an [`MbufRing`].
+pub struct MbufSender<A: Allocator, P: SyncMode = Multi, C: SyncMode = Multi>(Sender<RawMBuf<A>, P, C>);
+
+impl<A: Allocator, P: SyncMode, C: SyncMode> MbufSender<A, P, C> {
+    /// See [`Ring::as_raw`].
+    #[inline]
+    pub(crate) unsafe fn as_raw(&self) -> *mut ffi::rte_ring {
+        self.0.ring.as_raw()
+    }
+
+    /// Enqueues a single mbuf, returning it back if the ring is full.
+    #[inline]
+    pub fn enqueue(&mut self, mbuf: MBuf<A>) -> Result<(), MBuf<A>> {
+        self.0.enqueue(mbuf.into()).map_err(RawMBuf::into_mbuf)
+    }
+
+    /// Enqueues as many mbufs from the beginning of `mbufs` as possible.
+    ///
+    /// Mbufs that have been enqueued are removed from `mbufs`, any mbufs remaining in the batch after this method has
+    /// completed were NOT enqueued (and are still owned by the caller). Returns the number of enqueued mbufs.
+    #[inline]
+    pub fn enqueue_burst<const CAP: usize>(&mut self, mbufs: &mut PacketBatch<A, CAP>) -> usize {
+        // the ring assumes ownership of the enqueued mbufs
+        unsafe { mbufs.consume_raw(|ptr, len| self.0.ring.enqueue_raw(ptr.cast(), len, false)) }
+    }
+
+    /// Enqueues either all mbufs or none of them, returning `true` on success (in which case `mbufs` is left empty).
+    #[inline]
+    pub fn enqueue_bulk<const CAP: usize>(&mut self, mbufs: &mut PacketBatch<A, CAP>) -> bool {
+        unsafe { mbufs.consume_raw(|ptr, len| self.0.ring.enqueue_raw(ptr.cast(), len, true)) };
+        mbufs.is_empty()
+    }
+}
+
+impl<A: Allocator, C: SyncMode> Clone for MbufSender<A, Multi, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// The consumer half of an [`MbufRing`].
+pub struct MbufReceiver<A: Allocator, P: SyncMode = Multi, C: SyncMode = Multi>(Receiver<RawMBuf<A>, P, C>);
+
+impl<A: Allocator, P: SyncMode, C: SyncMode> MbufReceiver<A, P, C> {
+    /// See [`Ring::as_raw`].
+    #[inline]
+    pub(crate) unsafe fn as_raw(&self) -> *mut ffi::rte_ring {
+        self.0.ring.as_raw()
+    }
+
+    /// Dequeues a single mbuf, returning `None` if the ring is empty.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<MBuf<A>> {
+        self.0.dequeue().map(RawMBuf::into_mbuf)
+    }
+
+    /// Dequeues up to `CAP - mbufs.len()` mbufs, appending them to `mbufs`. Returns the number of dequeued mbufs.
+    #[inline]
+    pub fn dequeue_burst<const CAP: usize>(&mut self, mbufs: &mut PacketBatch<A, CAP>) -> usize {
+        unsafe { mbufs.fill_raw(|ptr, spare_cap| self.0.ring.dequeue_raw(ptr.cast(), spare_cap, false)) }
+    }
+
+    /// Dequeues exactly `CAP - mbufs.len()` mbufs, or none at all if not enough mbufs are available.
+    /// Returns `true` on success.
+    #[inline]
+    pub fn dequeue_bulk<const CAP: usize>(&mut self, mbufs: &mut PacketBatch<A, CAP>) -> bool {
+        unsafe { mbufs.fill_raw(|ptr, spare_cap| self.0.ring.dequeue_raw(ptr.cast(), spare_cap, true)) != 0 }
+    }
+}
+
+impl<A: Allocator, P: SyncMode> Clone for MbufReceiver<A, P, Multi> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::{
+        mbuf::{alloc_mbufs, GlobalAllocator},
+        ring::Single,
+    };
+
+    #[rte_test]
+    fn test_mbuf_ring_handoff() {
+        let ring = MbufRing::<GlobalAllocator, Single, Single>::new("test_mbuf_ring_handoff", 2, None).unwrap();
+        let (mut tx, mut rx) = ring.split();
+
+        let mut mbufs: PacketBatch<_, 3> = alloc_mbufs([b"\x00", b"\x01", b"\x02"]);
+        assert_eq!(tx.enqueue_burst(&mut mbufs), 2);
+        assert_eq!(&mbufs[0][..], b"\x02");
+
+        assert_eq!(&rx.dequeue().unwrap()[..], b"\x00");
+
+        // the remaining mbuf is freed along with the ring
+    }
+}
@@ -0,0 +1,371 @@
+//! Based on DPDK's `rte_ring.h` API: <https://doc.dpdk.org/api-22.11/rte__ring_8h.html>
+//!
+//! A [`Ring`] is a fixed-size, lockless FIFO queue, commonly used for passing objects (e.g. mbufs) between lcores.
+//! The synchronization mode of each side of the ring (single or multi producer/consumer) is selected at compile time
+//! using the [`Single`] and [`Multi`] marker types, and the ring is used by [splitting](Ring::split) it into a
+//! [`Sender`] and a [`Receiver`].
+
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    os::raw::c_uint,
+    ptr::{self, NonNull},
+    sync::Arc,
+};
+
+use arrayvec::ArrayVec;
+use rte_error::ReturnValue as _;
+
+use crate::{memory::SocketId, Result};
+
+mod mbuf;
+
+pub use self::mbuf::{MbufReceiver, MbufRing, MbufSender};
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for super::Single {}
+    impl Sealed for super::Multi {}
+}
+
+/// The synchronization mode of one side (producer or consumer) of a [`Ring`].
+///
+/// Implemented by [`Single`] and [`Multi`].
+pub trait SyncMode: sealed::Sealed + 'static {
+    #[doc(hidden)]
+    const PRODUCER_FLAG: c_uint;
+    #[doc(hidden)]
+    const CONSUMER_FLAG: c_uint;
+}
+
+/// Only a single thread at a time may use this side of the ring, which allows DPDK to skip the atomic CAS operations.
+///
+/// A [`Sender`]/[`Receiver`] in this mode cannot be cloned.
+#[derive(Debug)]
+pub enum Single {}
+
+/// Any number of threads may concurrently use this side of the ring.
+///
+/// A [`Sender`]/[`Receiver`] in this mode can be cloned and handed out to multiple lcores.
+#[derive(Debug)]
+pub enum Multi {}
+
+impl SyncMode for Single {
+    const PRODUCER_FLAG: c_uint = ffi::RING_F_SP_ENQ;
+    const CONSUMER_FLAG: c_uint = ffi::RING_F_SC_DEQ;
+}
+
+impl SyncMode for Multi {
+    const PRODUCER_FLAG: c_uint = 0;
+    const CONSUMER_FLAG: c_uint = 0;
+}
+
+/// A ring with a single producer and a single consumer.
+pub type SpscRing<T> = Ring<T, Single, Single>;
+
+/// A ring with multiple producers and multiple consumers.
+pub type MpmcRing<T> = Ring<T, Multi, Multi>;
+
+/// A typed wrapper around an [`rte_ring`](ffi::rte_ring), created using [`rte_ring_create_elem`](ffi::rte_ring_create_elem).
+///
+/// Elements are stored by value inside the ring's memory, so `T` must have a size that is a non-zero multiple of 4 bytes
+/// (a DPDK requirement), which is verified at compile time. Pointer-sized types such as [`MBuf`](crate::mbuf::MBuf) or
+/// [`Box`] satisfy this requirement.
+///
+/// Any elements still in the ring when it is dropped are dequeued and dropped as well.
+pub struct Ring<T, P: SyncMode = Multi, C: SyncMode = Multi> {
+    ptr: NonNull<ffi::rte_ring>,
+    _marker: PhantomData<(T, fn() -> (P, C))>,
+}
+
+// # Safety
+// Only the producer and consumer halves (`Sender`/`Receiver`) can move elements in or out of the ring, and they enforce
+// the ring's synchronization mode. The methods available on a shared `Ring` only query its state.
+unsafe impl<T: Send, P: SyncMode, C: SyncMode> Send for Ring<T, P, C> {}
+unsafe impl<T: Send, P: SyncMode, C: SyncMode> Sync for Ring<T, P, C> {}
+
+impl<T, P: SyncMode, C: SyncMode> Ring<T, P, C> {
+    const ELEM_SIZE: c_uint = {
+        assert!(
+            mem::size_of::<T>() != 0 && mem::size_of::<T>() % 4 == 0,
+            "ring element size must be a non-zero multiple of 4 bytes"
+        );
+        mem::size_of::<T>() as c_uint
+    };
+
+    /// Creates a new ring that can hold exactly `count` elements.
+    ///
+    /// See also: <https://doc.dpdk.org/api-22.11/rte__ring__elem_8h.html>
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, count: u32, socket_id: Option<SocketId>) -> Result<Self> {
+        let name = CString::new(name).unwrap();
+
+        unsafe {
+            ffi::rte_ring_create_elem(
+                name.as_ptr(),
+                Self::ELEM_SIZE,
+                count,
+                socket_id.map(|id| id.get() as i32).unwrap_or(-1),
+                P::PRODUCER_FLAG | C::CONSUMER_FLAG | ffi::RING_F_EXACT_SZ,
+            )
+        }
+        .rte_ok()
+        .map(|ptr| Self { ptr, _marker: PhantomData })
+    }
+
+    /// Splits this ring into its producer and consumer halves.
+    #[inline]
+    pub fn split(self) -> (Sender<T, P, C>, Receiver<T, P, C>) {
+        let ring = Arc::new(self);
+        (Sender { ring: ring.clone() }, Receiver { ring })
+    }
+
+    #[inline]
+    pub fn name(&self) -> &CStr {
+        unsafe { CStr::from_ptr((*self.ptr.as_ptr()).name.as_ptr()) }
+    }
+
+    /// Returns the number of elements this ring can hold.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        unsafe { ffi::_rte_ring_get_capacity(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the number of elements currently in the ring.
+    ///
+    /// Note that when the ring is used concurrently, this value may already be stale once returned.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        unsafe { ffi::_rte_ring_count(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the number of free slots in the ring.
+    #[inline]
+    pub fn free_count(&self) -> u32 {
+        unsafe { ffi::_rte_ring_free_count(self.ptr.as_ptr()) }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.free_count() == 0
+    }
+
+    /// Returns the raw pointer to the `rte_ring` struct.
+    ///
+    /// # Safety
+    /// It is up to the caller to make sure the raw pointer isn't used to enqueue or dequeue elements in a way that
+    /// violates the ring's synchronization mode or element type.
+    #[inline]
+    pub unsafe fn as_raw(&self) -> *mut ffi::rte_ring {
+        self.ptr.as_ptr()
+    }
+
+    /// Enqueues up to `n` elements starting at `objs`, returning the number of enqueued elements.
+    ///
+    /// # Safety
+    /// `objs` must point to `n` initialized elements. Ownership of the returned number of leading elements is
+    /// transferred to the ring, so the caller must not drop them.
+    #[inline]
+    unsafe fn enqueue_raw(&self, objs: *const T, n: usize, bulk: bool) -> usize {
+        let enqueue = if bulk { ffi::_rte_ring_enqueue_bulk_elem } else { ffi::_rte_ring_enqueue_burst_elem };
+        enqueue(self.ptr.as_ptr(), objs.cast(), Self::ELEM_SIZE, n as c_uint, ptr::null_mut()) as usize
+    }
+
+    /// Dequeues up to `n` elements into the memory starting at `objs`, returning the number of dequeued elements.
+    ///
+    /// # Safety
+    /// `objs` must be valid for writing `n` elements. The caller takes ownership of the returned number of elements.
+    #[inline]
+    unsafe fn dequeue_raw(&self, objs: *mut T, n: usize, bulk: bool) -> usize {
+        let dequeue = if bulk { ffi::_rte_ring_dequeue_bulk_elem } else { ffi::_rte_ring_dequeue_burst_elem };
+        dequeue(self.ptr.as_ptr(), objs.cast(), Self::ELEM_SIZE, n as c_uint, ptr::null_mut()) as usize
+    }
+}
+
+impl<T, P: SyncMode, C: SyncMode> fmt::Debug for Ring<T, P, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ring")
+            .field("name", &self.name())
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T, P: SyncMode, C: SyncMode> Drop for Ring<T, P, C> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            let mut obj = MaybeUninit::<T>::uninit();
+            while unsafe { ffi::_rte_ring_dequeue_elem(self.ptr.as_ptr(), obj.as_mut_ptr().cast(), Self::ELEM_SIZE) }
+                == 0
+            {
+                unsafe { obj.assume_init_drop() };
+            }
+        }
+
+        unsafe { ffi::rte_ring_free(self.ptr.as_ptr()) }
+    }
+}
+
+/// The producer half of a [`Ring`], created by [`Ring::split`].
+pub struct Sender<T, P: SyncMode = Multi, C: SyncMode = Multi> {
+    ring: Arc<Ring<T, P, C>>,
+}
+
+impl<T, P: SyncMode, C: SyncMode> Sender<T, P, C> {
+    #[inline]
+    pub fn ring(&self) -> &Ring<T, P, C> {
+        &self.ring
+    }
+
+    /// Enqueues a single element, returning it back if the ring is full.
+    #[inline]
+    pub fn enqueue(&mut self, obj: T) -> Result<(), T> {
+        let obj = ManuallyDrop::new(obj);
+        let ret = unsafe {
+            ffi::_rte_ring_enqueue_elem(self.ring.as_raw(), &*obj as *const T as *mut _, Ring::<T, P, C>::ELEM_SIZE)
+        };
+
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(ManuallyDrop::into_inner(obj))
+        }
+    }
+
+    /// Enqueues as many elements from the beginning of `objs` as possible.
+    ///
+    /// Elements that have been enqueued are removed from `objs`, any elements remaining in the array after this method
+    /// has completed were NOT enqueued. Returns the number of enqueued elements.
+    #[inline]
+    pub fn enqueue_burst<const CAP: usize>(&mut self, objs: &mut ArrayVec<T, CAP>) -> usize {
+        let enqueued = unsafe { self.ring.enqueue_raw(objs.as_ptr(), objs.len(), false) };
+
+        // the ring now holds a bitwise copy of the enqueued elements, so they must not be dropped here
+        objs.drain(..enqueued).for_each(mem::forget);
+        enqueued
+    }
+
+    /// Enqueues either all elements of `objs` or none of them, returning `true` on success (in which case `objs` is
+    /// left empty).
+    #[inline]
+    pub fn enqueue_bulk<const CAP: usize>(&mut self, objs: &mut ArrayVec<T, CAP>) -> bool {
+        let enqueued = unsafe { self.ring.enqueue_raw(objs.as_ptr(), objs.len(), true) };
+
+        objs.drain(..enqueued).for_each(mem::forget);
+        objs.is_empty()
+    }
+}
+
+impl<T, C: SyncMode> Clone for Sender<T, Multi, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { ring: self.ring.clone() }
+    }
+}
+
+impl<T, P: SyncMode, C: SyncMode> fmt::Debug for Sender<T, P, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Sender").field(&self.ring).finish()
+    }
+}
+
+/// The consumer half of a [`Ring`], created by [`Ring::split`].
+pub struct Receiver<T, P: SyncMode = Multi, C: SyncMode = Multi> {
+    ring: Arc<Ring<T, P, C>>,
+}
+
+impl<T, P: SyncMode, C: SyncMode> Receiver<T, P, C> {
+    #[inline]
+    pub fn ring(&self) -> &Ring<T, P, C> {
+        &self.ring
+    }
+
+    /// Dequeues a single element, returning `None` if the ring is empty.
+    #[inline]
+    pub fn dequeue(&mut self) -> Option<T> {
+        let mut obj = MaybeUninit::<T>::uninit();
+        let ret = unsafe {
+            ffi::_rte_ring_dequeue_elem(self.ring.as_raw(), obj.as_mut_ptr().cast(), Ring::<T, P, C>::ELEM_SIZE)
+        };
+
+        (ret == 0).then(|| unsafe { obj.assume_init() })
+    }
+
+    /// Dequeues up to `CAP - objs.len()` elements, appending them to `objs`. Returns the number of dequeued elements.
+    #[inline]
+    pub fn dequeue_burst<const CAP: usize>(&mut self, objs: &mut ArrayVec<T, CAP>) -> usize {
+        let old_len = objs.len();
+
+        unsafe {
+            let dequeued = self.ring.dequeue_raw(objs.as_mut_ptr().add(old_len), objs.remaining_capacity(), false);
+            objs.set_len(old_len + dequeued);
+            dequeued
+        }
+    }
+
+    /// Dequeues exactly `CAP - objs.len()` elements, or none at all if not enough elements are available.
+    /// Returns `true` on success.
+    #[inline]
+    pub fn dequeue_bulk<const CAP: usize>(&mut self, objs: &mut ArrayVec<T, CAP>) -> bool {
+        let old_len = objs.len();
+
+        unsafe {
+            let dequeued = self.ring.dequeue_raw(objs.as_mut_ptr().add(old_len), objs.remaining_capacity(), true);
+            objs.set_len(old_len + dequeued);
+            dequeued != 0
+        }
+    }
+}
+
+impl<T, P: SyncMode> Clone for Receiver<T, P, Multi> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self { ring: self.ring.clone() }
+    }
+}
+
+impl<T, P: SyncMode, C: SyncMode> fmt::Debug for Receiver<T, P, C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Receiver").field(&self.ring).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[rte_test]
+    fn test_ring_roundtrip() {
+        let ring = SpscRing::<Box<u64>>::new("test_ring_roundtrip", 4, None).unwrap();
+        assert_eq!(ring.capacity(), 4);
+
+        let (mut tx, mut rx) = ring.split();
+
+        let mut objs = (0..6).map(Box::new).collect::<ArrayVec<_, 6>>();
+        assert_eq!(tx.enqueue_burst(&mut objs), 4);
+        assert_eq!(objs.len(), 2);
+        assert!(tx.ring().is_full());
+        assert_eq!(*tx.enqueue(Box::new(6)).unwrap_err(), 6);
+
+        assert_eq!(rx.dequeue().as_deref(), Some(&0));
+
+        let mut out = ArrayVec::<_, 8>::new();
+        assert_eq!(rx.dequeue_burst(&mut out), 3);
+        assert_eq!(out.iter().map(|obj| **obj).collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(rx.dequeue().is_none());
+
+        // remaining elements are released when the ring is dropped
+        assert_eq!(tx.enqueue_burst(&mut objs), 2);
+    }
+}
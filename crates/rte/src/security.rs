@@ -0,0 +1,201 @@
+//! Based on DPDK's `rte_security.h` API: <https://doc.dpdk.org/api-22.11/rte__security_8h.html>
+//!
+//! Offloads IPsec processing to a NIC, either inline (as packets are received or transmitted), or as a lookaside
+//! protocol offload. The [`SecurityCtx`] of a port is obtained using [`EthDev::security_ctx`], and its
+//! [capabilities](SecurityCtx::capabilities) list the supported offloads.
+//!
+//! Note that DPDK 22.11 does not support configuring MACsec offloads through `rte_security`, so only IPsec sessions
+//! are supported.
+//!
+//! [`EthDev::security_ctx`]: crate::ethdev::EthDev::security_ctx
+
+use std::{fmt, marker::PhantomData, os::raw::c_void, ptr::NonNull, slice};
+
+use rte_error::ReturnValue as _;
+
+use crate::{
+    cryptodev::SessionPool,
+    flags::PktTxOffload,
+    ipsec::SaParams,
+    mbuf::{Allocator, MBuf, MetadataExt},
+    Result,
+};
+
+pub type Capability = ffi::rte_security_capability;
+
+/// How a security session is offloaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// The NIC only performs the crypto, while the protocol (e.g. ESP headers and sequence numbers) is handled in
+    /// software (e.g. by an [`ipsec::Session`](crate::ipsec::Session)).
+    InlineCrypto,
+    /// The NIC performs the crypto and handles the protocol.
+    InlineProtocol,
+    /// A lookaside (crypto) device performs the crypto and handles the protocol.
+    LookasideProtocol,
+}
+
+impl Action {
+    pub(crate) fn as_raw(self) -> u32 {
+        match self {
+            Self::InlineCrypto => ffi::rte_security_session_action_type::RTE_SECURITY_ACTION_TYPE_INLINE_CRYPTO,
+            Self::InlineProtocol => ffi::rte_security_session_action_type::RTE_SECURITY_ACTION_TYPE_INLINE_PROTOCOL,
+            Self::LookasideProtocol => {
+                ffi::rte_security_session_action_type::RTE_SECURITY_ACTION_TYPE_LOOKASIDE_PROTOCOL
+            }
+        }
+    }
+}
+
+/// The security offload status of a received mbuf.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RxStatus {
+    /// The mbuf was not processed by an inline session.
+    NotOffloaded,
+    Success,
+    /// The processing failed (e.g. authentication or the replay check), so the mbuf should be dropped.
+    Failed,
+}
+
+impl RxStatus {
+    /// Returns the security offload status of `mbuf`, as indicated by its offload flags.
+    #[inline]
+    pub fn of<A: Allocator>(mbuf: &MBuf<A>) -> Self {
+        let ol_flags = unsafe { (*mbuf.as_raw()).ol_flags };
+
+        if ol_flags & ffi::RTE_MBUF_F_RX_SEC_OFFLOAD == 0 {
+            Self::NotOffloaded
+        } else if ol_flags & ffi::RTE_MBUF_F_RX_SEC_OFFLOAD_FAILED != 0 {
+            Self::Failed
+        } else {
+            Self::Success
+        }
+    }
+}
+
+/// The security context of a device, which is owned by the device.
+#[derive(Clone)]
+pub struct SecurityCtx(pub(crate) NonNull<ffi::rte_security_ctx>);
+
+// # Safety
+// The context is owned by its device, and session creation (as well as setting packet metadata) is thread-safe.
+unsafe impl Send for SecurityCtx {}
+unsafe impl Sync for SecurityCtx {}
+
+impl SecurityCtx {
+    /// Returns the offloads supported by the device.
+    #[inline]
+    pub fn capabilities(&self) -> &[Capability] {
+        unsafe {
+            let caps = ffi::rte_security_capabilities_get(self.0.as_ptr());
+            if caps.is_null() {
+                return &[];
+            }
+
+            // the array is terminated by an entry with no action
+            let mut len = 0;
+            while (*caps.add(len)).action != ffi::rte_security_session_action_type::RTE_SECURITY_ACTION_TYPE_NONE {
+                len += 1;
+            }
+            slice::from_raw_parts(caps, len)
+        }
+    }
+
+    /// Returns the size of the private data of this device's sessions, which must fit in the elements of the
+    /// [`SessionPool`] its sessions are created from.
+    #[inline]
+    pub fn session_size(&self) -> u32 {
+        unsafe { ffi::rte_security_session_get_size(self.0.as_ptr()) }
+    }
+
+    pub(crate) fn flags(&self) -> u32 {
+        unsafe { (*self.0.as_ptr()).flags }
+    }
+}
+
+impl fmt::Debug for SecurityCtx {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SecurityCtx").field("flags", &self.flags()).finish_non_exhaustive()
+    }
+}
+
+/// A security session of a [`SecurityCtx`], which is destroyed when dropped.
+pub struct Session<'a> {
+    ptr: NonNull<c_void>,
+    ctx: &'a SecurityCtx,
+    action: Action,
+    _marker: PhantomData<&'a SessionPool>,
+}
+
+// # Safety
+// Sessions are immutable once created, and may be used by any queue of the device.
+unsafe impl Send for Session<'_> {}
+unsafe impl Sync for Session<'_> {}
+
+impl<'a> Session<'a> {
+    /// Creates an IPsec session for the SA described by `params`, which are interpreted as for an
+    /// [`Sa`](crate::ipsec::Sa). For tunnel mode, the tunnel endpoints are taken from the header template.
+    #[inline]
+    pub fn ipsec(ctx: &'a SecurityCtx, action: Action, params: &SaParams, pool: &'a SessionPool) -> Result<Self> {
+        let mut xform = params.transform.as_raw();
+        let prm = params.as_raw(&mut xform)?;
+
+        let mut conf = ffi::rte_security_session_conf {
+            action_type: action.as_raw(),
+            protocol: ffi::rte_security_session_protocol::RTE_SECURITY_PROTOCOL_IPSEC,
+            crypto_xform: &mut xform,
+            ..Default::default()
+        };
+        conf.__bindgen_anon_1.ipsec = prm.ipsec_xform;
+
+        unsafe { ffi::rte_security_session_create(ctx.0.as_ptr(), &mut conf, pool.0.as_ptr()) }
+            .rte_ok()
+            .map(|ptr| Self { ptr: ptr.cast(), ctx, action, _marker: PhantomData })
+    }
+
+    #[inline]
+    pub fn ctx(&self) -> &'a SecurityCtx {
+        self.ctx
+    }
+
+    #[inline]
+    pub fn action(&self) -> Action {
+        self.action
+    }
+
+    /// Marks `mbuf` for outbound processing by this (inline) session, by setting its security session metadata and
+    /// enabling [`PktTxOffload::SEC_OFFLOAD`].
+    ///
+    /// This is not needed for mbufs processed by an [`ipsec::Session`](crate::ipsec::Session), which does so itself.
+    #[inline]
+    pub fn set_pkt_metadata<A: Allocator>(&self, mbuf: &mut MBuf<A>) -> Result<()> {
+        unsafe {
+            ffi::_rte_security_set_pkt_metadata(
+                self.ctx.0.as_ptr(),
+                self.ptr.as_ptr(),
+                mbuf.as_raw(),
+                std::ptr::null_mut(),
+            )
+        }
+        .rte_ok()?;
+
+        mbuf.enable_ol_flags(PktTxOffload::SEC_OFFLOAD);
+        Ok(())
+    }
+
+    pub(crate) fn as_raw(&self) -> *mut c_void {
+        self.ptr.as_ptr()
+    }
+}
+
+impl fmt::Debug for Session<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Session").field("action", &self.action).finish_non_exhaustive()
+    }
+}
+
+impl Drop for Session<'_> {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_security_session_destroy(self.ctx.0.as_ptr(), self.ptr.as_ptr().cast()) };
+    }
+}
@@ -0,0 +1,207 @@
+//! Based on DPDK's `rte_stack.h` API: <https://doc.dpdk.org/api-22.11/rte__stack_8h.html>
+//!
+//! A [`Stack`] is a fixed-size, multi-producer/multi-consumer LIFO of pointer-sized elements (e.g. [`Box`]es, mbufs,
+//! or indices of application objects), e.g. for managing free lists. Unlike a [`Ring`](crate::ring::Ring), recently
+//! pushed elements are popped first, which keeps them hot in the cache.
+//!
+//! This is also what the `stack` and `lf_stack` mempool drivers are built on.
+
+use std::{
+    ffi::{CStr, CString},
+    fmt,
+    marker::PhantomData,
+    mem::{self, ManuallyDrop, MaybeUninit},
+    os::raw::{c_uint, c_void},
+    ptr::NonNull,
+};
+
+use arrayvec::ArrayVec;
+use rte_error::ReturnValue as _;
+
+use crate::{memory::SocketId, Result};
+
+/// The implementation of a [`Stack`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    /// Synchronized using a spinlock.
+    Standard,
+    /// Synchronized using 128 bit compare-and-swap operations, so that an lcore being preempted does not block the
+    /// others. Only supported on x86_64 and arm64.
+    LockFree,
+}
+
+/// A typed wrapper around an [`rte_stack`](ffi::rte_stack), which may be pushed to and popped from by any number of
+/// lcores concurrently.
+///
+/// Elements are stored by value as `void *`, so `T` must be pointer-sized, which is verified at compile time. Any
+/// elements still in the stack when it is dropped are popped and dropped as well.
+pub struct Stack<T> {
+    ptr: NonNull<ffi::rte_stack>,
+    kind: Kind,
+    _marker: PhantomData<T>,
+}
+
+// # Safety
+// Both stack implementations are multi-producer/multi-consumer.
+unsafe impl<T: Send> Send for Stack<T> {}
+unsafe impl<T: Send> Sync for Stack<T> {}
+
+impl<T> Stack<T> {
+    const ELEM_SIZE_CHECK: () =
+        assert!(mem::size_of::<T>() == mem::size_of::<*mut c_void>(), "stack elements must be pointer-sized");
+
+    /// Creates a new stack that can hold `count` elements.
+    #[inline]
+    pub fn new<S: Into<Vec<u8>>>(name: S, count: u32, kind: Kind, socket_id: Option<SocketId>) -> Result<Self> {
+        let () = Self::ELEM_SIZE_CHECK;
+
+        let name = CString::new(name).unwrap();
+        let flags = match kind {
+            Kind::Standard => 0,
+            Kind::LockFree => ffi::RTE_STACK_F_LF,
+        };
+
+        unsafe { ffi::rte_stack_create(name.as_ptr(), count, socket_id.map(|id| id.get() as i32).unwrap_or(-1), flags) }
+            .rte_ok()
+            .map(|ptr| Self { ptr, kind, _marker: PhantomData })
+    }
+
+    #[inline]
+    pub fn name(&self) -> &CStr {
+        unsafe { CStr::from_ptr((*self.ptr.as_ptr()).name.as_ptr()) }
+    }
+
+    #[inline]
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Returns the number of elements this stack can hold.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        unsafe { (*self.ptr.as_ptr()).capacity }
+    }
+
+    /// Returns the number of elements currently in the stack.
+    ///
+    /// Note that when the stack is used concurrently, this value may already be stale once returned.
+    #[inline]
+    pub fn len(&self) -> u32 {
+        unsafe { ffi::_rte_stack_count(self.ptr.as_ptr()) }
+    }
+
+    /// Returns the number of free slots in the stack.
+    #[inline]
+    pub fn free_count(&self) -> u32 {
+        unsafe { ffi::_rte_stack_free_count(self.ptr.as_ptr()) }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Pushes a single element, returning it back if the stack is full.
+    #[inline]
+    pub fn push(&self, obj: T) -> Result<(), T> {
+        let obj = ManuallyDrop::new(obj);
+        let pushed = unsafe { ffi::_rte_stack_push(self.ptr.as_ptr(), (&*obj as *const T).cast(), 1) };
+
+        if pushed == 1 {
+            Ok(())
+        } else {
+            Err(ManuallyDrop::into_inner(obj))
+        }
+    }
+
+    /// Pushes either all elements of `objs` or none of them, returning `true` on success (in which case `objs` is left
+    /// empty).
+    #[inline]
+    pub fn push_bulk<const CAP: usize>(&self, objs: &mut ArrayVec<T, CAP>) -> bool {
+        let pushed =
+            unsafe { ffi::_rte_stack_push(self.ptr.as_ptr(), objs.as_ptr().cast(), objs.len() as c_uint) } as usize;
+
+        // the stack now holds a bitwise copy of the pushed elements, so they must not be dropped here
+        objs.drain(..pushed).for_each(mem::forget);
+        objs.is_empty()
+    }
+
+    /// Pops a single element, returning `None` if the stack is empty.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        let mut obj = MaybeUninit::<T>::uninit();
+        let popped = unsafe { ffi::_rte_stack_pop(self.ptr.as_ptr(), obj.as_mut_ptr().cast(), 1) };
+
+        (popped == 1).then(|| unsafe { obj.assume_init() })
+    }
+
+    /// Pops exactly `CAP - objs.len()` elements, or none at all if not enough elements are available, appending them
+    /// to `objs`. Returns `true` on success.
+    #[inline]
+    pub fn pop_bulk<const CAP: usize>(&self, objs: &mut ArrayVec<T, CAP>) -> bool {
+        let old_len = objs.len();
+
+        unsafe {
+            let popped = ffi::_rte_stack_pop(
+                self.ptr.as_ptr(),
+                objs.as_mut_ptr().add(old_len).cast(),
+                objs.remaining_capacity() as c_uint,
+            );
+            objs.set_len(old_len + popped as usize);
+            popped != 0
+        }
+    }
+}
+
+impl<T> fmt::Debug for Stack<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Stack")
+            .field("name", &self.name())
+            .field("kind", &self.kind)
+            .field("capacity", &self.capacity())
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T> Drop for Stack<T> {
+    fn drop(&mut self) {
+        if mem::needs_drop::<T>() {
+            while self.pop().is_some() {}
+        }
+
+        unsafe { ffi::rte_stack_free(self.ptr.as_ptr()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[rte_test]
+    fn test_stack_lifo() {
+        let stack = Stack::<Box<u64>>::new("test_stack_lifo", 4, Kind::Standard, None).unwrap();
+        assert_eq!(stack.capacity(), 4);
+
+        let mut objs = (0..3).map(Box::new).collect::<ArrayVec<_, 3>>();
+        assert!(stack.push_bulk(&mut objs));
+        assert!(objs.is_empty());
+        assert_eq!(stack.len(), 3);
+
+        let mut objs = (3..5).map(Box::new).collect::<ArrayVec<_, 2>>();
+        assert!(!stack.push_bulk(&mut objs));
+        assert_eq!(objs.len(), 2);
+
+        assert_eq!(stack.pop().as_deref(), Some(&2));
+
+        let mut out = ArrayVec::<_, 2>::new();
+        assert!(stack.pop_bulk(&mut out));
+        assert_eq!(out.iter().map(|obj| **obj).collect::<Vec<_>>(), [1, 0]);
+        assert!(stack.pop().is_none());
+
+        // remaining elements are released when the stack is dropped
+        assert!(stack.push(Box::new(5)).is_ok());
+    }
+}
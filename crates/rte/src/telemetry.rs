@@ -0,0 +1,222 @@
+//! Based on DPDK's `rte_telemetry.h` API: <https://doc.dpdk.org/api-22.11/rte__telemetry_8h.html>
+//!
+//! Publishes application commands on the telemetry socket (`dpdk-telemetry.sock`, e.g. used by
+//! `dpdk-telemetry.py`), alongside the commands of DPDK's libraries (e.g. `/ethdev/stats`). A command's callback builds
+//! its reply as [`Data`], which DPDK then formats as JSON.
+
+use std::{
+    collections::BTreeMap,
+    ffi::{CStr, CString},
+    fmt, mem,
+    os::raw::{c_char, c_int},
+    panic::{self, AssertUnwindSafe},
+    ptr::NonNull,
+    sync::{Arc, RwLock},
+};
+
+use rte_error::{Error, ReturnValue as _};
+
+use crate::Result;
+
+type Callback = dyn Fn(&str, &mut Data) -> Result<()> + Send + Sync;
+
+static COMMANDS: RwLock<BTreeMap<CString, Arc<Callback>>> = RwLock::new(BTreeMap::new());
+
+unsafe extern "C" fn dispatch(cmd: *const c_char, params: *const c_char, info: *mut ffi::rte_tel_data) -> c_int {
+    // the lock is released before calling the callback, which may register commands
+    let Some(callback) = COMMANDS.read().unwrap().get(CStr::from_ptr(cmd)).cloned() else { return -libc::ENOENT };
+
+    let params = if params.is_null() { "" } else { CStr::from_ptr(params).to_str().unwrap_or_default() };
+    let mut data = Data(NonNull::new_unchecked(info));
+
+    // a panic mustn't unwind into DPDK, it fails the command instead
+    match panic::catch_unwind(AssertUnwindSafe(|| callback(params, &mut data))) {
+        Ok(Ok(())) => 0,
+        Ok(Err(Error(errno))) => -errno,
+        Err(_) => -libc::EIO,
+    }
+}
+
+/// Registers a telemetry command (e.g. `/myapp/stats`), whose callback is called with the command's parameters (if
+/// any) to build its reply. Registering a command again replaces its callback.
+///
+/// A callback which panics fails the command with `EIO`.
+#[inline]
+pub fn register<F>(cmd: &str, help: &str, callback: F) -> Result<()>
+where
+    F: Fn(&str, &mut Data) -> Result<()> + Send + Sync + 'static,
+{
+    let cmd = CString::new(cmd).unwrap();
+    let help = CString::new(help).unwrap();
+
+    let mut commands = COMMANDS.write().unwrap();
+    if !commands.contains_key(&cmd) {
+        unsafe { ffi::rte_telemetry_register_cmd(cmd.as_ptr(), Some(dispatch), help.as_ptr()) }.rte_ok()?;
+    }
+    commands.insert(cmd, Arc::new(callback));
+    Ok(())
+}
+
+/// The type of the values of an array.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArrayType {
+    Int,
+    U64,
+    String,
+    Container,
+}
+
+impl ArrayType {
+    fn as_raw(self) -> ffi::rte_tel_value_type::Type {
+        match self {
+            Self::Int => ffi::rte_tel_value_type::RTE_TEL_INT_VAL,
+            Self::U64 => ffi::rte_tel_value_type::RTE_TEL_U64_VAL,
+            Self::String => ffi::rte_tel_value_type::RTE_TEL_STRING_VAL,
+            Self::Container => ffi::rte_tel_value_type::RTE_TEL_CONTAINER,
+        }
+    }
+}
+
+/// The reply of a telemetry command, which is either a string, an array or a dictionary (whose keys may only contain
+/// alphanumeric characters, `_` and `-`).
+///
+/// Arrays and dictionaries may be nested by adding a [`Container`].
+pub struct Data(NonNull<ffi::rte_tel_data>);
+
+impl Data {
+    /// Makes this data a string.
+    #[inline]
+    pub fn string(&mut self, value: &str) -> Result<()> {
+        let value = CString::new(value).unwrap();
+        unsafe { ffi::rte_tel_data_string(self.0.as_ptr(), value.as_ptr()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Makes this data an (empty) array of `ty` values.
+    #[inline]
+    pub fn start_array(&mut self, ty: ArrayType) -> Result<()> {
+        unsafe { ffi::rte_tel_data_start_array(self.0.as_ptr(), ty.as_raw()) }.rte_ok()?;
+        Ok(())
+    }
+
+    /// Makes this data an (empty) dictionary.
+    #[inline]
+    pub fn start_dict(&mut self) -> Result<()> {
+        unsafe { ffi::rte_tel_data_start_dict(self.0.as_ptr()) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_array_int(&mut self, value: i32) -> Result<()> {
+        unsafe { ffi::rte_tel_data_add_array_int(self.0.as_ptr(), value) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_array_u64(&mut self, value: u64) -> Result<()> {
+        unsafe { ffi::rte_tel_data_add_array_u64(self.0.as_ptr(), value) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_array_string(&mut self, value: &str) -> Result<()> {
+        let value = CString::new(value).unwrap();
+        unsafe { ffi::rte_tel_data_add_array_string(self.0.as_ptr(), value.as_ptr()) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_array_container(&mut self, value: Container) -> Result<()> {
+        unsafe { ffi::rte_tel_data_add_array_container(self.0.as_ptr(), value.as_raw(), 0) }.rte_ok()?;
+        value.added();
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_dict_int(&mut self, name: &str, value: i32) -> Result<()> {
+        let name = CString::new(name).unwrap();
+        unsafe { ffi::rte_tel_data_add_dict_int(self.0.as_ptr(), name.as_ptr(), value) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_dict_u64(&mut self, name: &str, value: u64) -> Result<()> {
+        let name = CString::new(name).unwrap();
+        unsafe { ffi::rte_tel_data_add_dict_u64(self.0.as_ptr(), name.as_ptr(), value) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_dict_string(&mut self, name: &str, value: &str) -> Result<()> {
+        let name = CString::new(name).unwrap();
+        let value = CString::new(value).unwrap();
+        unsafe { ffi::rte_tel_data_add_dict_string(self.0.as_ptr(), name.as_ptr(), value.as_ptr()) }.rte_ok()?;
+        Ok(())
+    }
+
+    #[inline]
+    pub fn add_dict_container(&mut self, name: &str, value: Container) -> Result<()> {
+        let name = CString::new(name).unwrap();
+        unsafe { ffi::rte_tel_data_add_dict_container(self.0.as_ptr(), name.as_ptr(), value.as_raw(), 0) }.rte_ok()?;
+        value.added();
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Data {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Data").finish_non_exhaustive()
+    }
+}
+
+/// An array or dictionary nested in a [`Data`], which is freed when dropped (unless added to its parent).
+pub struct Container(Data);
+
+// # Safety
+// The container is exclusively owned until it's added to its parent.
+unsafe impl Send for Container {}
+
+impl Container {
+    /// Allocates an (empty) container, which must then be made an array or a dictionary.
+    #[inline]
+    pub fn new() -> Result<Self> {
+        unsafe { ffi::rte_tel_data_alloc() }.rte_ok().map(|ptr| Self(Data(ptr)))
+    }
+
+    fn as_raw(&self) -> *mut ffi::rte_tel_data {
+        (self.0).0.as_ptr()
+    }
+
+    /// Hands the container over to the parent it was added to, which frees it.
+    fn added(self) {
+        mem::forget(self);
+    }
+}
+
+impl std::ops::Deref for Container {
+    type Target = Data;
+
+    #[inline]
+    fn deref(&self) -> &Data {
+        &self.0
+    }
+}
+
+impl std::ops::DerefMut for Container {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Data {
+        &mut self.0
+    }
+}
+
+impl fmt::Debug for Container {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Container").finish_non_exhaustive()
+    }
+}
+
+impl Drop for Container {
+    fn drop(&mut self) {
+        unsafe { ffi::rte_tel_data_free((self.0).0.as_ptr()) }
+    }
+}
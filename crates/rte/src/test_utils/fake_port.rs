@@ -0,0 +1,173 @@
+use std::{ffi::CString, iter};
+
+use rte_error::{check, ReturnValue as _};
+
+use crate::{
+    ethdev::{Conf, EthDev},
+    lcore,
+    mbuf::{MBuf, PacketBatch},
+    mempool::MemoryPool,
+    ring::{MbufReceiver, MbufRing, MbufSender},
+    Result,
+};
+
+/// The rings backing a `net_ring` port.
+struct Rings<'mp> {
+    /// Packets enqueued here are received by the port.
+    rx: MbufSender<&'mp MemoryPool>,
+    /// Packets transmitted by the port are enqueued here.
+    tx: MbufReceiver<&'mp MemoryPool>,
+}
+
+/// A fake [`EthDev`] with a single RX and TX queue, for testing rx/tx-path code without hardware. It's either backed
+/// by a `net_ring` vdev, which receives the packets of [`inject_rx`](Self::inject_rx) and whose transmitted packets
+/// are returned by [`drain_tx`](Self::drain_tx), or by a `net_null` vdev, which endlessly receives packets (of 64 bytes)
+/// and drops the transmitted ones.
+///
+/// The device is removed when dropped.
+pub struct FakePort<'mp> {
+    dev: EthDev,
+    name: CString,
+    mempool: &'mp MemoryPool,
+    rings: Option<Rings<'mp>>,
+}
+
+impl<'mp> FakePort<'mp> {
+    const BUS: &'static [u8] = b"vdev\0";
+
+    /// Creates and starts a `net_ring_<name>` port, whose rings hold up to `nb_desc` packets, allocating the injected
+    /// packets from `mempool`.
+    pub fn ring(name: &str, nb_desc: u16, mempool: &'mp MemoryPool) -> Result<Self> {
+        let (rx, rx_ring) = MbufRing::new(format!("{name}_rx"), nb_desc.into(), None)?.split();
+        let (tx_ring, tx) = MbufRing::new(format!("{name}_tx"), nb_desc.into(), None)?.split();
+
+        let name = CString::new(format!("net_ring_{name}")).unwrap();
+        let socket_id = lcore::socket_id().map_or(0, |id| id.get());
+        let port_id = unsafe {
+            let (rx_queues, tx_queues) = ([rx_ring.as_raw()], [tx_ring.as_raw()]);
+            check!(
+                ffi::rte_eth_from_rings(name.as_ptr(), rx_queues.as_ptr(), 1, tx_queues.as_ptr(), 1, socket_id),
+                |&port_id| port_id < 0
+            )?
+        };
+
+        Self::setup(name, port_id as u16, Some(Rings { rx, tx }), nb_desc, mempool)
+    }
+
+    /// Creates and starts a `net_null_<name>` port, allocating the received packets from `mempool`.
+    pub fn null(name: &str, nb_desc: u16, mempool: &'mp MemoryPool) -> Result<Self> {
+        let name = CString::new(format!("net_null_{name}")).unwrap();
+        unsafe { ffi::rte_eal_hotplug_add(Self::BUS.as_ptr().cast(), name.as_ptr(), b"\0".as_ptr().cast()) }
+            .rte_ok()?;
+
+        let mut port_id = 0;
+        if let Err(err) = unsafe { ffi::rte_eth_dev_get_port_by_name(name.as_ptr(), &mut port_id) }.rte_ok() {
+            unsafe { ffi::rte_eal_hotplug_remove(Self::BUS.as_ptr().cast(), name.as_ptr()) };
+            return Err(err);
+        }
+
+        Self::setup(name, port_id, None, nb_desc, mempool)
+    }
+
+    fn setup(
+        name: CString,
+        port_id: u16,
+        rings: Option<Rings<'mp>>,
+        nb_desc: u16,
+        mempool: &'mp MemoryPool,
+    ) -> Result<Self> {
+        let dev = EthDev::new(port_id);
+        let setup = dev
+            .configure(1, 1, &Conf::default())
+            .and_then(|()| dev.rx_queue_setup(0, nb_desc, None, mempool))
+            .and_then(|()| dev.tx_queue_setup(0, nb_desc, None))
+            .and_then(|()| dev.start());
+
+        // the device is removed (when dropped) even if it couldn't be set up
+        let port = Self { dev, name, mempool, rings };
+        setup.map(|()| port)
+    }
+
+    #[inline]
+    pub fn dev(&self) -> &EthDev {
+        &self.dev
+    }
+
+    /// Receives packets from the port, see [`EthDev::rx_burst`].
+    #[inline]
+    pub fn recv<const CAP: usize>(&mut self, pkts: &mut PacketBatch<&'mp MemoryPool, CAP>) {
+        // Safety: the queue was set up with this memory pool
+        unsafe { self.dev.rx_burst(0, self.mempool, pkts) }
+    }
+
+    /// Sends packets on the port, see [`EthDev::tx_burst`].
+    #[inline]
+    pub fn send<const CAP: usize>(&mut self, pkts: &mut PacketBatch<&'mp MemoryPool, CAP>) {
+        // Safety: the queue was set up with this memory pool
+        unsafe { self.dev.tx_burst(0, self.mempool, pkts) }
+    }
+
+    fn rings(&mut self) -> &mut Rings<'mp> {
+        self.rings.as_mut().expect("only net_ring ports can inject and drain packets")
+    }
+
+    /// Copies each of `packets` into an mbuf to be received by the port, returning how many of them were injected
+    /// (i.e. until its RX ring was full).
+    ///
+    /// Panics for `net_null` ports.
+    pub fn inject_rx<I>(&mut self, packets: I) -> usize
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let mempool = self.mempool;
+        let rx = &mut self.rings().rx;
+
+        packets
+            .into_iter()
+            .map_while(|packet| rx.enqueue(MBuf::new_with_provider_and_data(&mempool, packet)).ok())
+            .count()
+    }
+
+    /// Returns the packets transmitted by the port so far.
+    ///
+    /// Panics for `net_null` ports.
+    pub fn drain_tx(&mut self) -> Vec<MBuf<&'mp MemoryPool>> {
+        let tx = &mut self.rings().tx;
+        iter::from_fn(|| tx.dequeue()).collect()
+    }
+}
+
+impl Drop for FakePort<'_> {
+    fn drop(&mut self) {
+        // the device may have never been started
+        let _ = self.dev.stop();
+        let _ = self.dev.close();
+        unsafe { ffi::rte_eal_hotplug_remove(Self::BUS.as_ptr().cast(), self.name.as_ptr()) };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::TestPool;
+
+    #[rte_test]
+    fn test_fake_port_loopback() {
+        let mempool = TestPool::new(63);
+        let mut port = FakePort::ring("test_fake_port", 4, &mempool).unwrap();
+
+        assert_eq!(port.inject_rx([[1u8; 60], [2; 60]]), 2);
+
+        let mut pkts = PacketBatch::<_, 8>::new();
+        port.recv(&mut pkts);
+        assert_eq!(pkts.iter().map(|pkt| pkt.as_slice()[0]).collect::<Vec<_>>(), [1, 2]);
+
+        port.send(&mut pkts);
+        assert!(pkts.is_empty());
+        assert_eq!(port.drain_tx().iter().map(|pkt| pkt.as_slice().to_vec()).collect::<Vec<_>>(), [[1; 60], [2; 60]]);
+        assert!(port.drain_tx().is_empty());
+    }
+}
@@ -0,0 +1,84 @@
+use std::{marker::PhantomData, time::Duration};
+
+use crate::cycles::{self, MOCK_TSC};
+
+/// Mocks [`cycles::tsc`] on the current thread until dropped, so that time-dependent code (e.g. metering, or
+/// periodic work) can be tested deterministically, by advancing the time explicitly rather than sleeping:
+/// ```rust
+/// # use rte::{cycles, test_utils::MockClock};
+/// let clock = MockClock::new(1000);
+/// assert_eq!(cycles::tsc(), 1000);
+/// clock.advance_cycles(500);
+/// assert_eq!(cycles::tsc(), 1500);
+/// ```
+///
+/// The clock is thread-local, as is the TSC, so it doesn't affect other tests. It only affects the Rust code reading
+/// the TSC though, not DPDK's own.
+pub struct MockClock {
+    /// The mocked TSC value this clock replaced (when nested), restored when dropped.
+    prev: Option<u64>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl MockClock {
+    /// Starts mocking the TSC at the value `tsc`.
+    pub fn new(tsc: u64) -> Self {
+        let prev = MOCK_TSC.with(|mock| mock.replace(Some(tsc)));
+        Self { prev, _not_send: PhantomData }
+    }
+
+    /// Returns the mocked TSC value.
+    #[inline]
+    pub fn now(&self) -> u64 {
+        MOCK_TSC.with(|mock| mock.get()).unwrap()
+    }
+
+    /// Advances the TSC by a number of cycles, wrapping around (like a real TSC would) rather than overflowing.
+    #[inline]
+    pub fn advance_cycles(&self, cycles: u64) {
+        MOCK_TSC.with(|mock| mock.set(Some(self.now().wrapping_add(cycles))))
+    }
+
+    /// Advances the TSC by the equivalent number of cycles of `duration`.
+    ///
+    /// **NOTE:** requires the EAL to be initialized, see [`cycles::tsc_hz`].
+    #[inline]
+    pub fn advance(&self, duration: Duration) {
+        self.advance_cycles(cycles::duration_to_cycles(duration))
+    }
+}
+
+impl Drop for MockClock {
+    fn drop(&mut self) {
+        MOCK_TSC.with(|mock| mock.set(self.prev))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rte_test_macros::rte_test;
+
+    use super::*;
+
+    #[test]
+    fn test_nested_mock_clocks() {
+        let clock = MockClock::new(10);
+        {
+            let nested = MockClock::new(100);
+            nested.advance_cycles(1);
+            assert_eq!(cycles::tsc(), 101);
+        }
+        clock.advance_cycles(1);
+        assert_eq!(cycles::tsc(), 11);
+
+        clock.advance_cycles(u64::MAX);
+        assert_eq!(cycles::tsc(), 10);
+    }
+
+    #[rte_test]
+    fn test_advance() {
+        let clock = MockClock::new(0);
+        clock.advance(Duration::from_secs(2));
+        assert_eq!(clock.now(), 2 * cycles::tsc_hz());
+    }
+}
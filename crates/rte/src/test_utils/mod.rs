@@ -0,0 +1,185 @@
+pub mod packet_gen;
+
+mod fake_port;
+mod mock_clock;
+mod packet_diff;
+mod test_pool;
+
+use std::{
+    any::Any,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+use once_cell::sync::OnceCell;
+pub use rte_test_macros::rte_test;
+
+pub use self::{
+    fake_port::FakePort,
+    mock_clock::MockClock,
+    packet_diff::{parse_hex, PacketDiff},
+    test_pool::TestPool,
+};
+
+use crate::{launch, lcore, mempool::MemoryPool};
+
+/// The EAL arguments of tests, unless overridden with `#[rte_test(eal_args = "...")]`.
+pub const DEFAULT_EAL_ARGS: &str = "--no-huge -m 1024 --no-shconf";
+
+/// The arguments EAL was initialized with, by the first test to run.
+static EAL_ARGS: OnceCell<String> = OnceCell::new();
+
+pub fn init_test_eal() {
+    init_test_eal_with(DEFAULT_EAL_ARGS)
+}
+
+/// Initializes EAL with the whitespace-separated arguments.
+pub fn init_test_eal_with(eal_args: &str) {
+    let args = std::iter::once("").chain(eal_args.split_whitespace());
+    let _ = rte_eal::init(args).expect("Could not initialize EAL for tests");
+}
+
+/// The next lcore id to try handing out as a mock lcore, see [`mock_lcore`].
+static NEXT_MOCK_LCORE: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    /// The mock lcore id of the current thread, allocated on first use.
+    static MOCK_LCORE: u32 = alloc_mock_lcore();
+}
+
+/// Allocates an lcore id that isn't enabled in EAL (so that it doesn't collide with a real lcore), wrapping around once
+/// all of them were handed out (which only collides with another thread if that many tests run concurrently).
+fn alloc_mock_lcore() -> u32 {
+    (0..ffi::RTE_MAX_LCORE)
+        .map(|_| NEXT_MOCK_LCORE.fetch_add(1, Ordering::Relaxed) % ffi::RTE_MAX_LCORE)
+        .find(|&id| !lcore::Id::new(id).is_enabled())
+        .expect("All lcores are enabled, none is left to mock")
+}
+
+/// Mocks the current lcore with an id unique to the current thread (call after init).
+pub fn mock_lcore() {
+    set_mock_lcore(MOCK_LCORE.with(|&id| id))
+}
+
+/// Runs `f` with the current lcore mocked as `id`, then restores the current lcore (even if `f` panics).
+pub fn with_mock_lcore<R>(id: lcore::Id, f: impl FnOnce() -> R) -> R {
+    struct Restore(u32);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            set_mock_lcore(self.0)
+        }
+    }
+
+    let _restore = Restore(lcore::current().get());
+    set_mock_lcore(id.get());
+    f()
+}
+
+fn set_mock_lcore(lcore_id: u32) {
+    unsafe { ffi::_rte_set_mock_lcore(lcore_id) };
+}
+
+/// Mocks the current lcore as the main one (call after init), so that the test can launch workers with
+/// [`run_on_workers`].
+pub fn mock_main_lcore() {
+    set_mock_lcore(lcore::main().get())
+}
+
+/// Returns the EAL arguments with `workers` worker lcores besides the main one, all floating over the available CPUs
+/// (so that they don't need as many).
+pub fn eal_args_with_workers(eal_args: &str, workers: u32) -> String {
+    let cpus = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    format!("{eal_args} --lcores=(0-{workers})@(0-{})", cpus - 1)
+}
+
+type Worker = Box<dyn FnOnce() + Send>;
+type Panic = Arc<Mutex<Option<Box<dyn Any + Send>>>>;
+
+/// Serializes the tests launching workers, as each worker lcore runs a single function at a time.
+static WORKERS: Mutex<()> = Mutex::new(());
+
+fn run_worker((worker, panic): (Worker, Panic)) -> i32 {
+    match panic::catch_unwind(AssertUnwindSafe(worker)) {
+        Ok(()) => 0,
+        Err(payload) => {
+            panic.lock().unwrap().get_or_insert(payload);
+            -1
+        }
+    }
+}
+
+/// Runs each closure on its own worker lcore (from the main one, see [`mock_main_lcore`]) and waits for all of them,
+/// then resumes the first of their panics, if any, so that it fails the test.
+pub fn run_on_workers<I>(workers: I)
+where
+    I: IntoIterator,
+    I::Item: FnOnce() + Send + 'static,
+{
+    let _guard = WORKERS.lock().unwrap_or_else(|err| err.into_inner());
+
+    let workers = workers.into_iter().map(|worker| Box::new(worker) as Worker).collect::<Vec<_>>();
+    let lcores = lcore::Id::iter_enabled(true).take(workers.len()).collect::<Vec<_>>();
+    assert_eq!(
+        lcores.len(),
+        workers.len(),
+        "not enough worker lcores, initialize EAL with `#[rte_test(workers = {})]`",
+        workers.len()
+    );
+
+    let panic = Panic::default();
+    for (lcore, worker) in lcores.into_iter().zip(workers) {
+        lcore.launch(run_worker, (worker, panic.clone())).expect("Could not launch a worker lcore");
+    }
+    launch::join_lcores();
+
+    if let Some(payload) = panic.lock().unwrap().take() {
+        panic::resume_unwind(payload);
+    }
+}
+
+/// Runs `f` and asserts that it leaves as many mbufs of `pool` in use as before, catching the ones it leaked (e.g. with
+/// `mem::forget`, or by missing a free in burst-path code). The mbufs `f` returns count as leaked.
+#[track_caller]
+pub fn assert_no_mbuf_leaks<R>(pool: &MemoryPool, f: impl FnOnce() -> R) -> R {
+    let before = pool.get_in_use_count();
+    let ret = f();
+    let after = pool.get_in_use_count();
+
+    assert!(
+        before == after,
+        "mbufs of {} leaked: {before} in use before, {after} after ({:+})",
+        String::from_utf8_lossy(pool.name()),
+        i64::from(after) - i64::from(before)
+    );
+    ret
+}
+
+pub fn init_test_env() {
+    init_test_env_with(DEFAULT_EAL_ARGS)
+}
+
+/// Initializes EAL (once per process) with the whitespace-separated arguments. As EAL can only be initialized once, the
+/// first test to run wins, and the tests requiring other arguments fail, meaning tests with non-default arguments should
+/// rather run in their own test binary (e.g. under `tests/`).
+///
+/// # Panics
+/// Panics if EAL was already initialized with other arguments.
+#[track_caller]
+pub fn init_test_env_with(eal_args: &str) {
+    let initialized = EAL_ARGS.get_or_init(|| {
+        init_test_eal_with(eal_args);
+        eal_args.to_owned()
+    });
+
+    assert!(
+        initialized.split_whitespace().eq(eal_args.split_whitespace()),
+        "this test requires the EAL arguments {eal_args:?}, but EAL was already initialized with {initialized:?} by \
+         another test, run it in its own test binary"
+    );
+}
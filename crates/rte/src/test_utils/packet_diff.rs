@@ -0,0 +1,249 @@
+use std::{fmt, ops::Range};
+
+use crate::net::{parse_headers, EtherHdr, Header, L3Hdr, L4Hdr, VlanHdr};
+
+/// Asserts that a packet (e.g. an [`MBuf`](crate::mbuf::MBuf)) equals the expected bytes, given as a hex string
+/// (in which whitespace, `:` separators and `#` comments are ignored, so that golden packets can be laid out by
+/// header):
+/// ```rust
+/// # use rte::{assert_packet_eq, mbuf::{GlobalAllocator, MBuf}};
+/// let mbuf = MBuf::<GlobalAllocator>::new_with_data(b"\x02\x00\x00\x00\x00\x02\x02\x00\x00\x00\x00\x01\x08\x06");
+/// assert_packet_eq!(
+///     mbuf,
+///     "02:00:00:00:00:02 02:00:00:00:00:01  # MACs
+///      0806                                 # ether type"
+/// );
+/// ```
+///
+/// On failure, the differing bytes are shown along with the header fields they fall in, see [`PacketDiff`].
+#[macro_export]
+macro_rules! assert_packet_eq {
+    ($packet:expr, $expected_hex:expr $(,)?) => {
+        match (&$packet[..], $crate::test_utils::parse_hex($expected_hex)) {
+            (packet, expected) => {
+                if packet != &expected[..] {
+                    panic!("{}", $crate::test_utils::PacketDiff::new(packet, &expected));
+                }
+            }
+        }
+    };
+    ($packet:expr, $expected_hex:expr, $($arg:tt)+) => {
+        match (&$packet[..], $crate::test_utils::parse_hex($expected_hex)) {
+            (packet, expected) => {
+                if packet != &expected[..] {
+                    panic!("{}: {}", format_args!($($arg)+), $crate::test_utils::PacketDiff::new(packet, &expected));
+                }
+            }
+        }
+    };
+}
+
+/// Parses a hex string, ignoring whitespace, `:` separators and `#` comments (up to the end of their line).
+///
+/// # Panics
+/// Panics if the string contains anything else than pairs of hex digits.
+#[track_caller]
+pub fn parse_hex(hex: &str) -> Vec<u8> {
+    let digits = hex
+        .lines()
+        .flat_map(|line| line.split('#').next().unwrap().chars())
+        .filter(|c| !c.is_whitespace() && *c != ':')
+        .map(|c| c.to_digit(16).unwrap_or_else(|| panic!("invalid hex digit {c:?} in {hex:?}")) as u8)
+        .collect::<Vec<_>>();
+    assert!(digits.len() % 2 == 0, "odd number of hex digits in {hex:?}");
+
+    digits.chunks(2).map(|pair| (pair[0] << 4) | pair[1]).collect()
+}
+
+/// The fields of each header, in order, with their lengths.
+const ETHER_FIELDS: &[(&str, usize)] = &[("dst_addr", 6), ("src_addr", 6), ("ether_type", 2)];
+const VLAN_FIELDS: &[(&str, usize)] = &[("vlan_tci", 2), ("eth_proto", 2)];
+const IPV4_FIELDS: &[(&str, usize)] = &[
+    ("version_ihl", 1),
+    ("type_of_service", 1),
+    ("total_length", 2),
+    ("packet_id", 2),
+    ("fragment_offset", 2),
+    ("time_to_live", 1),
+    ("next_proto_id", 1),
+    ("hdr_checksum", 2),
+    ("src_addr", 4),
+    ("dst_addr", 4),
+];
+const IPV6_FIELDS: &[(&str, usize)] =
+    &[("vtc_flow", 4), ("payload_len", 2), ("proto", 1), ("hop_limits", 1), ("src_addr", 16), ("dst_addr", 16)];
+const ARP_FIELDS: &[(&str, usize)] = &[
+    ("arp_hardware", 2),
+    ("arp_protocol", 2),
+    ("arp_hlen", 1),
+    ("arp_plen", 1),
+    ("arp_opcode", 2),
+    ("arp_sha", 6),
+    ("arp_sip", 4),
+    ("arp_tha", 6),
+    ("arp_tip", 4),
+];
+const TCP_FIELDS: &[(&str, usize)] = &[
+    ("src_port", 2),
+    ("dst_port", 2),
+    ("sent_seq", 4),
+    ("recv_ack", 4),
+    ("data_off", 1),
+    ("tcp_flags", 1),
+    ("rx_win", 2),
+    ("cksum", 2),
+    ("tcp_urp", 2),
+];
+const UDP_FIELDS: &[(&str, usize)] = &[("src_port", 2), ("dst_port", 2), ("dgram_len", 2), ("dgram_cksum", 2)];
+const ICMP_FIELDS: &[(&str, usize)] =
+    &[("icmp_type", 1), ("icmp_code", 1), ("icmp_cksum", 2), ("icmp_ident", 2), ("icmp_seq_nb", 2)];
+
+/// Maximal number of differences shown by [`PacketDiff`].
+const MAX_DIFFS: usize = 32;
+
+/// The byte-level differences between a packet and the expected one, [`Display`](fmt::Display)ed as ranges of
+/// differing bytes, each annotated with the header field it falls in (as parsed from the expected packet), e.g.:
+/// ```text
+/// packets differ (actual 60 bytes, expected 60 bytes):
+///   [22] ipv4.time_to_live: actual 3f, expected 40
+///   [24..26] ipv4.hdr_checksum: actual 12 34, expected 56 78
+/// ```
+pub struct PacketDiff<'a> {
+    actual: &'a [u8],
+    expected: &'a [u8],
+}
+
+impl<'a> PacketDiff<'a> {
+    pub fn new(actual: &'a [u8], expected: &'a [u8]) -> Self {
+        Self { actual, expected }
+    }
+
+    /// Returns the ranges of the header fields of the expected packet (including options), with their names.
+    fn fields(&self) -> Vec<(Range<usize>, String)> {
+        let mut fields = vec![];
+        let mut push = |layer: &str, layer_range: Range<usize>, layer_fields: &[(&str, usize)]| {
+            let mut start = layer_range.start;
+            for (name, len) in layer_fields {
+                fields.push((start..start + len, format!("{layer}.{name}")));
+                start += len;
+            }
+            if start < layer_range.end {
+                fields.push((start..layer_range.end, format!("{layer}.options")));
+            }
+        };
+
+        let Some(headers) = parse_headers(self.expected) else { return fields };
+        push("ether", 0..EtherHdr::LEN, ETHER_FIELDS);
+        for i in 0..headers.vlans.len() {
+            let offset = EtherHdr::LEN + i * VlanHdr::LEN;
+            push(&format!("vlan{i}"), offset..offset + VlanHdr::LEN, VLAN_FIELDS);
+        }
+
+        let l3 = headers.l3_offset()..headers.l4_offset();
+        match headers.l3 {
+            Some(L3Hdr::Ipv4(_)) => push("ipv4", l3, IPV4_FIELDS),
+            Some(L3Hdr::Ipv6(_)) => push("ipv6", l3, IPV6_FIELDS),
+            Some(L3Hdr::Arp(_)) => push("arp", l3, ARP_FIELDS),
+            None => {}
+        }
+
+        let l4 = headers.l4_offset()..headers.payload_offset();
+        match headers.l4 {
+            Some(L4Hdr::Tcp(_)) => push("tcp", l4, TCP_FIELDS),
+            Some(L4Hdr::Udp(_)) => push("udp", l4, UDP_FIELDS),
+            Some(L4Hdr::Icmp(_)) => push("icmp", l4, ICMP_FIELDS),
+            None => {}
+        }
+
+        fields
+    }
+}
+
+impl fmt::Display for PacketDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn hex(bytes: &[u8]) -> String {
+            if bytes.is_empty() {
+                return "none".to_owned();
+            }
+            bytes.iter().map(|byte| format!("{byte:02x}")).collect::<Vec<_>>().join(" ")
+        }
+
+        let fields = self.fields();
+        let field = |i: usize| match fields.iter().find(|(range, _)| range.contains(&i)) {
+            Some((_, name)) => name.as_str(),
+            None if i < self.expected.len() => "payload",
+            None => "past the end",
+        };
+
+        // groups the differing bytes by field
+        let mut diffs: Vec<(Range<usize>, &str)> = vec![];
+        for i in 0..self.actual.len().max(self.expected.len()) {
+            if self.actual.get(i) == self.expected.get(i) {
+                continue;
+            }
+            match diffs.last_mut() {
+                Some((range, name)) if range.end == i && *name == field(i) => range.end += 1,
+                _ => diffs.push((i..i + 1, field(i))),
+            }
+        }
+
+        write!(f, "packets differ (actual {} bytes, expected {} bytes):", self.actual.len(), self.expected.len())?;
+        for (range, name) in diffs.iter().take(MAX_DIFFS) {
+            let bytes = |packet: &[u8]| hex(&packet[range.start.min(packet.len())..range.end.min(packet.len())]);
+            match range.len() {
+                1 => write!(f, "\n  [{}]", range.start)?,
+                _ => write!(f, "\n  [{}..{}]", range.start, range.end)?,
+            }
+            write!(f, " {name}: actual {}, expected {}", bytes(self.actual), bytes(self.expected))?;
+        }
+        if diffs.len() > MAX_DIFFS {
+            write!(f, "\n  ... and {} more", diffs.len() - MAX_DIFFS)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PACKET: &str = "
+        02:00:00:00:00:02 02:00:00:00:00:01 0800    # ether
+        45 00 0020 0000 0000 40 11 0000 0a000001 0a000002    # ipv4
+        0400 0035 000c 0000    # udp
+        01 02 03 04    # payload
+    ";
+
+    #[test]
+    fn test_parse_hex() {
+        assert_eq!(parse_hex("01:ab CD # comment 12\n ef"), [0x01, 0xab, 0xcd, 0xef]);
+    }
+
+    #[test]
+    #[should_panic(expected = "odd number of hex digits")]
+    fn test_parse_odd_hex() {
+        parse_hex("012");
+    }
+
+    #[test]
+    fn test_packet_diff() {
+        let expected = parse_hex(PACKET);
+        let mut actual = expected.clone();
+        actual[22] = 0x3f;
+        actual[37] = 0x36;
+        actual.truncate(expected.len() - 1);
+
+        assert_eq!(
+            PacketDiff::new(&actual, &expected).to_string(),
+            "packets differ (actual 45 bytes, expected 46 bytes):\n  \
+             [22] ipv4.time_to_live: actual 3f, expected 40\n  \
+             [37] udp.dst_port: actual 36, expected 35\n  \
+             [45] payload: actual none, expected 04"
+        );
+    }
+
+    #[test]
+    fn test_assert_packet_eq() {
+        assert_packet_eq!(parse_hex(PACKET), PACKET);
+    }
+}
@@ -0,0 +1,223 @@
+//! [`proptest`] strategies generating packets, for fuzz-style coverage of parsers and classifiers in unit tests:
+//! ```rust
+//! # use proptest::prelude::*;
+//! # use rte::{net::parse_headers, test_utils::packet_gen};
+//! proptest!(|(packet in packet_gen::packet())| {
+//!     prop_assert!(parse_headers(&packet).unwrap().l4.is_some());
+//! });
+//! ```
+//!
+//! The packets don't need an EAL, as they are either byte vectors or mbufs of the
+//! [`GlobalAllocator`](crate::mbuf::GlobalAllocator).
+
+use mac_addr::MacAddr;
+use proptest::{collection::vec, option, prelude::*, sample::Index};
+use zerocopy::{
+    byteorder::network_endian::{U16, U32},
+    AsBytes,
+};
+
+use crate::{
+    mbuf::{GlobalAllocator, MBuf},
+    net::{
+        ipv4_cksum, ipv4_udptcp_cksum, ipv6_udptcp_cksum, EtherHdr, Header, Ipv4Hdr, Ipv6Hdr, TcpHdr, UdpHdr, VlanHdr,
+        ETHER_TYPE_IPV4, ETHER_TYPE_IPV6, ETHER_TYPE_VLAN, IPPROTO_TCP, IPPROTO_UDP,
+    },
+};
+
+/// Maximal length of the generated payloads, so that packets fit in a single mbuf.
+const MAX_PAYLOAD_LEN: usize = 512;
+
+#[derive(Debug, Clone, Copy)]
+enum L3 {
+    Ipv4 { src: [u8; 4], dst: [u8; 4], ttl: u8 },
+    Ipv6 { src: [u8; 16], dst: [u8; 16], hop_limits: u8 },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum L4 {
+    Tcp { src_port: u16, dst_port: u16, seq: u32, flags: u8 },
+    Udp { src_port: u16, dst_port: u16 },
+}
+
+fn mac_addr() -> impl Strategy<Value = MacAddr> {
+    any::<[u8; 6]>().prop_map(|[a, b, c, d, e, f]| MacAddr::new(a, b, c, d, e, f))
+}
+
+fn ipv4() -> impl Strategy<Value = L3> {
+    any::<([u8; 4], [u8; 4], u8)>().prop_map(|(src, dst, ttl)| L3::Ipv4 { src, dst, ttl })
+}
+
+fn ipv6() -> impl Strategy<Value = L3> {
+    any::<([u8; 16], [u8; 16], u8)>().prop_map(|(src, dst, hop_limits)| L3::Ipv6 { src, dst, hop_limits })
+}
+
+fn l4() -> impl Strategy<Value = L4> {
+    prop_oneof![
+        any::<(u16, u16, u32, u8)>().prop_map(|(src_port, dst_port, seq, flags)| L4::Tcp {
+            src_port,
+            dst_port,
+            seq,
+            flags
+        }),
+        any::<(u16, u16)>().prop_map(|(src_port, dst_port)| L4::Udp { src_port, dst_port }),
+    ]
+}
+
+/// Builds the L4 header followed by the payload (with the checksum left as 0), returning it along with the L4 protocol
+/// and the offset of the checksum.
+fn build_l4(l4: L4, payload: &[u8]) -> (u8, Vec<u8>, usize) {
+    let (proto, header, cksum_offset) = match l4 {
+        L4::Tcp { src_port, dst_port, seq, flags } => {
+            let tcp = TcpHdr {
+                src_port: U16::new(src_port),
+                dst_port: U16::new(dst_port),
+                sent_seq: U32::new(seq),
+                data_off: ((TcpHdr::LEN / 4) as u8) << 4,
+                tcp_flags: flags,
+                rx_win: U16::new(u16::MAX),
+                ..Default::default()
+            };
+            (IPPROTO_TCP, tcp.as_bytes().to_vec(), 16)
+        }
+        L4::Udp { src_port, dst_port } => {
+            let udp = UdpHdr {
+                src_port: U16::new(src_port),
+                dst_port: U16::new(dst_port),
+                dgram_len: U16::new((UdpHdr::LEN + payload.len()) as u16),
+                ..Default::default()
+            };
+            (IPPROTO_UDP, udp.as_bytes().to_vec(), 6)
+        }
+    };
+
+    (proto, [header, payload.to_vec()].concat(), cksum_offset)
+}
+
+/// Builds a packet with valid lengths and checksums.
+fn build(src_mac: MacAddr, dst_mac: MacAddr, vlan: Option<u16>, l3: L3, l4: L4, payload: &[u8]) -> Vec<u8> {
+    let (proto, mut l4, cksum_offset) = build_l4(l4, payload);
+
+    let (ether_type, l3) = match l3 {
+        L3::Ipv4 { src, dst, ttl } => {
+            let mut ipv4 = Ipv4Hdr {
+                version_ihl: Ipv4Hdr::VERSION_IHL,
+                total_length: U16::new((Ipv4Hdr::LEN + l4.len()) as u16),
+                time_to_live: ttl,
+                next_proto_id: proto,
+                src_addr: src,
+                dst_addr: dst,
+                ..Default::default()
+            };
+            ipv4.hdr_checksum.set(ipv4_cksum(ipv4.as_bytes()));
+            let cksum = ipv4_udptcp_cksum(&ipv4, &l4);
+            l4[cksum_offset..][..2].copy_from_slice(&cksum.to_be_bytes());
+            (ETHER_TYPE_IPV4, ipv4.as_bytes().to_vec())
+        }
+        L3::Ipv6 { src, dst, hop_limits } => {
+            let ipv6 = Ipv6Hdr {
+                vtc_flow: U32::new(6 << 28),
+                payload_len: U16::new(l4.len() as u16),
+                proto,
+                hop_limits,
+                src_addr: src,
+                dst_addr: dst,
+            };
+            let cksum = ipv6_udptcp_cksum(&ipv6, &l4);
+            l4[cksum_offset..][..2].copy_from_slice(&cksum.to_be_bytes());
+            (ETHER_TYPE_IPV6, ipv6.as_bytes().to_vec())
+        }
+    };
+
+    let mut packet = vec![];
+    match vlan {
+        Some(vid) => {
+            let ether = EtherHdr { dst_addr: dst_mac, src_addr: src_mac, ether_type: U16::new(ETHER_TYPE_VLAN) };
+            let vlan = VlanHdr { vlan_tci: U16::new(vid), eth_proto: U16::new(ether_type) };
+            packet.extend_from_slice(ether.as_bytes());
+            packet.extend_from_slice(vlan.as_bytes());
+        }
+        None => {
+            let ether = EtherHdr { dst_addr: dst_mac, src_addr: src_mac, ether_type: U16::new(ether_type) };
+            packet.extend_from_slice(ether.as_bytes());
+        }
+    }
+    packet.extend_from_slice(&l3);
+    packet.extend_from_slice(&l4);
+    packet
+}
+
+fn packet_with(l3: impl Strategy<Value = L3>) -> impl Strategy<Value = Vec<u8>> {
+    (mac_addr(), mac_addr(), option::of(0..0x1000u16), l3, l4(), vec(any::<u8>(), 0..MAX_PAYLOAD_LEN))
+        .prop_map(|(src_mac, dst_mac, vlan, l3, l4, payload)| build(src_mac, dst_mac, vlan, l3, l4, &payload))
+}
+
+/// Generates valid Ethernet frames (possibly VLAN-tagged) carrying TCP or UDP over IPv4, with correct lengths and
+/// checksums.
+pub fn ipv4_packet() -> impl Strategy<Value = Vec<u8>> {
+    packet_with(ipv4())
+}
+
+/// IPv6 version of [`ipv4_packet`].
+pub fn ipv6_packet() -> impl Strategy<Value = Vec<u8>> {
+    packet_with(ipv6())
+}
+
+/// Generates packets of either [`ipv4_packet`] or [`ipv6_packet`].
+pub fn packet() -> impl Strategy<Value = Vec<u8>> {
+    packet_with(prop_oneof![ipv4(), ipv6()])
+}
+
+/// Generates malformed packets, which parsers should handle gracefully: valid packets truncated at any point, valid
+/// packets with a few corrupted bytes (which may happen to still be valid), or random bytes altogether.
+pub fn malformed_packet() -> impl Strategy<Value = Vec<u8>> {
+    prop_oneof![
+        (packet(), any::<Index>()).prop_map(|(mut packet, len)| {
+            packet.truncate(len.index(packet.len()));
+            packet
+        }),
+        (packet(), vec(any::<(Index, u8)>(), 1..4)).prop_map(|(mut packet, corruptions)| {
+            for (index, mask) in corruptions {
+                let i = index.index(packet.len());
+                // i.e. a non-zero mask, so that the byte actually changes
+                packet[i] ^= mask.max(1);
+            }
+            packet
+        }),
+        vec(any::<u8>(), 0..MAX_PAYLOAD_LEN),
+    ]
+}
+
+/// Turns the generated packets into mbufs, e.g. `mbuf(packet())`.
+pub fn mbuf(packets: impl Strategy<Value = Vec<u8>>) -> impl Strategy<Value = MBuf<GlobalAllocator>> {
+    packets.prop_map(MBuf::new_with_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::{parse_headers, L3Hdr, L4Hdr};
+
+    proptest! {
+        #[test]
+        fn test_valid_packets(packet in packet()) {
+            let headers = parse_headers(&packet).unwrap();
+            let l4_len = packet.len() - headers.l4_offset();
+            match (headers.l3, headers.l4) {
+                (Some(L3Hdr::Ipv4(ipv4)), Some(L4Hdr::Tcp(_) | L4Hdr::Udp(_))) => {
+                    prop_assert_eq!(usize::from(ipv4.total_length.get()), headers.l3_len + l4_len);
+                }
+                (Some(L3Hdr::Ipv6(ipv6)), Some(L4Hdr::Tcp(_) | L4Hdr::Udp(_))) => {
+                    prop_assert_eq!(usize::from(ipv6.payload_len.get()), l4_len);
+                }
+                (l3, l4) => prop_assert!(false, "unexpected headers {:?} / {:?}", l3, l4),
+            }
+        }
+
+        #[test]
+        fn test_malformed_packets(packet in malformed_packet()) {
+            // only checks that parsing doesn't panic
+            let _ = parse_headers(&packet);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    ops::{Deref, DerefMut},
+    process,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{mbuf::MBuf, mempool::MemoryPool};
+
+/// Numbers the pools of the process, so that concurrent tests never reuse a name.
+static NEXT_POOL: AtomicU32 = AtomicU32::new(0);
+
+/// A memory pool for tests, with a unique name (as memory pools are global to EAL, and tests run concurrently) and
+/// the default data room. Being a plain [`MemoryPool`] underneath, it's freed when dropped, including when the test
+/// panics.
+pub struct TestPool(MemoryPool);
+
+impl TestPool {
+    /// Creates a pool of `size` mbufs, which should be at least the number of packets the test holds at once (including
+    /// the ones set aside by the devices it sets up).
+    pub fn new(size: u32) -> Self {
+        // fits in RTE_MEMPOOL_NAMESIZE, whatever the pid
+        let name = format!("test_{}_{}", process::id(), NEXT_POOL.fetch_add(1, Ordering::Relaxed));
+        let mempool = MemoryPool::new(name, size, 0, 0, ffi::RTE_MBUF_DEFAULT_BUF_SIZE as u16, None)
+            .expect("Could not create a memory pool for tests");
+        Self(mempool)
+    }
+
+    /// Copies each of `packets` into an mbuf of the pool.
+    ///
+    /// # Panics
+    /// Panics if the pool is exhausted.
+    pub fn alloc_packets(&self, packets: &[&[u8]]) -> Vec<MBuf<&MemoryPool>> {
+        packets.iter().map(|packet| MBuf::new_with_provider_and_data(&&self.0, packet)).collect()
+    }
+}
+
+impl Deref for TestPool {
+    type Target = MemoryPool;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for TestPool {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::mem;
+
+    use rte_test_macros::rte_test;
+
+    use super::*;
+    use crate::test_utils::assert_no_mbuf_leaks;
+
+    #[rte_test]
+    fn test_pool_alloc_packets() {
+        let (pool, other) = (TestPool::new(7), TestPool::new(7));
+        assert_ne!(pool.name(), other.name());
+
+        let packets = pool.alloc_packets(&[&[1; 60], &[2; 100]]);
+        assert_eq!(packets.iter().map(|pkt| pkt.as_slice().to_vec()).collect::<Vec<_>>(), [vec![1; 60], vec![2; 100]]);
+        assert_eq!(pool.get_in_use_count(), 2);
+
+        drop(packets);
+        assert_eq!(pool.get_in_use_count(), 0);
+    }
+
+    #[rte_test]
+    #[should_panic(expected = "leaked: 0 in use before, 1 after (+1)")]
+    fn test_assert_no_mbuf_leaks() {
+        let pool = TestPool::new(7);
+        assert_no_mbuf_leaks(&pool, || drop(pool.alloc_packets(&[&[0; 60]])));
+        assert_no_mbuf_leaks(&pool, || pool.alloc_packets(&[&[0; 60]]).into_iter().for_each(mem::forget));
+    }
+}
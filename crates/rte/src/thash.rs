@@ -0,0 +1,268 @@
+//! Software implementation of the Toeplitz hash used by NICs for RSS, based on DPDK's `rte_thash.h` API:
+//! <https://doc.dpdk.org/api-22.11/rte__thash_8h.html>
+//!
+//! Allows computing (in software) the same hash, and therefore the same RX queue, a NIC would have chosen for a
+//! packet, e.g. for making sure response packets are generated on the lcore that will receive the flow's traffic.
+
+use std::{
+    fmt,
+    net::{Ipv4Addr, Ipv6Addr},
+};
+
+use arrayvec::ArrayVec;
+
+/// Length (in bytes) of the RSS key used by most NICs.
+pub const RSS_KEY_LEN: usize = 40;
+
+/// Maximal supported RSS key length (in bytes).
+pub const MAX_RSS_KEY_LEN: usize = 52;
+
+/// Number of 32-bit words in the longest input tuple (IPv6 addresses + L4 ports), see `RTE_THASH_V6_L4_LEN`.
+const MAX_TUPLE_LEN: usize = 9;
+
+/// A Toeplitz hash key.
+///
+/// Stored 4-byte aligned, as required by DPDK's hash implementations.
+#[repr(C, align(4))]
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RssKey {
+    bytes: [u8; MAX_RSS_KEY_LEN],
+    len: usize,
+}
+
+impl RssKey {
+    /// The default RSS key used by most NIC drivers (originally from Microsoft's RSS specification).
+    pub const DEFAULT: Self = Self::from_array([
+        0x6d, 0x5a, 0x56, 0xda, 0x25, 0x5b, 0x0e, 0xc2, 0x41, 0x67, 0x25, 0x3d, 0x43, 0xa3, 0x8f, 0xb0, 0xd0, 0xca,
+        0x2b, 0xcb, 0xae, 0x7b, 0x30, 0xb4, 0x77, 0xcb, 0x2d, 0xa3, 0x80, 0x30, 0xf2, 0x0c, 0x6a, 0x42, 0xb7, 0x3b,
+        0xbe, 0xac, 0x01, 0xfa,
+    ]);
+
+    /// A symmetric RSS key: packets of both directions of a flow (i.e. with swapped source/destination addresses and
+    /// ports) are hashed to the same value, and therefore RSS'd to the same queue.
+    ///
+    /// See also: <https://www.ndsl.kaist.edu/~kyoungsoo/papers/TR-symRSS.pdf>
+    pub const SYMMETRIC: Self = {
+        let mut key = [0; RSS_KEY_LEN];
+        let mut i = 0;
+        while i < RSS_KEY_LEN {
+            key[i] = if i % 2 == 0 { 0x6d } else { 0x5a };
+            i += 1;
+        }
+
+        Self::from_array(key)
+    };
+
+    const fn from_array(key: [u8; RSS_KEY_LEN]) -> Self {
+        let mut bytes = [0; MAX_RSS_KEY_LEN];
+        let mut i = 0;
+        while i < RSS_KEY_LEN {
+            bytes[i] = key[i];
+            i += 1;
+        }
+
+        Self { bytes, len: RSS_KEY_LEN }
+    }
+
+    /// Creates a key from the given bytes, e.g. the key configured in a port's [`rte_eth_rss_conf`](ffi::rte_eth_rss_conf).
+    ///
+    /// Returns `None` if the key's length is not a multiple of 4, or is longer than [`MAX_RSS_KEY_LEN`].
+    #[inline]
+    pub fn new(key: &[u8]) -> Option<Self> {
+        if key.len() % 4 != 0 || key.len() > MAX_RSS_KEY_LEN {
+            return None;
+        }
+
+        let mut bytes = [0; MAX_RSS_KEY_LEN];
+        bytes[..key.len()].copy_from_slice(key);
+        Some(Self { bytes, len: key.len() })
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// Computes the Toeplitz hash of `tuple` using this key.
+    ///
+    /// See also: [`softrss`].
+    #[inline]
+    pub fn hash(&self, tuple: &Tuple) -> u32 {
+        softrss(tuple.as_words(), self)
+    }
+
+    /// Converts this key into the form required by the optimized [`softrss_be`] implementation.
+    #[inline]
+    pub fn to_be(&self) -> BeRssKey {
+        let mut converted = Self { bytes: [0; MAX_RSS_KEY_LEN], len: self.len };
+        unsafe {
+            ffi::_rte_convert_rss_key(
+                self.bytes.as_ptr().cast(),
+                converted.bytes.as_mut_ptr().cast(),
+                self.len as i32,
+            )
+        };
+        BeRssKey(converted)
+    }
+
+    fn assert_fits(&self, input: &[u32]) {
+        // the hash reads one word past the input's length from the key
+        assert!(
+            (input.len() + 1) * 4 <= self.len,
+            "RSS key of {} bytes is too short for an input of {} words",
+            self.len,
+            input.len()
+        );
+    }
+}
+
+impl Default for RssKey {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl fmt::Debug for RssKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("RssKey(")?;
+        self.as_bytes().iter().try_for_each(|b| write!(f, "{b:02x}"))?;
+        f.write_str(")")
+    }
+}
+
+/// An [`RssKey`] converted for use with [`softrss_be`], created by [`RssKey::to_be`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BeRssKey(RssKey);
+
+impl BeRssKey {
+    /// Computes the Toeplitz hash of `tuple` using this key, equivalent to [`RssKey::hash`] with the original key.
+    #[inline]
+    pub fn hash(&self, tuple: &Tuple) -> u32 {
+        softrss_be(tuple.as_words(), self)
+    }
+}
+
+/// The input to the Toeplitz hash function, made up of a packet's addresses and optionally its L4 ports.
+///
+/// Which fields should be part of the tuple depends on the RSS hash functions configured for the port
+/// (see [`EthRss`](crate::flags::EthRss)).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tuple(ArrayVec<u32, MAX_TUPLE_LEN>);
+
+impl Tuple {
+    /// See `RTE_THASH_V4_L3_LEN`.
+    #[inline]
+    pub fn ipv4(src: Ipv4Addr, dst: Ipv4Addr) -> Self {
+        Self([u32::from(src), u32::from(dst)].into_iter().collect())
+    }
+
+    /// See `RTE_THASH_V4_L4_LEN`.
+    #[inline]
+    pub fn ipv4_l4(src: Ipv4Addr, dst: Ipv4Addr, src_port: u16, dst_port: u16) -> Self {
+        let mut tuple = Self::ipv4(src, dst);
+        tuple.0.push(Self::ports_word(src_port, dst_port));
+        tuple
+    }
+
+    /// See `RTE_THASH_V6_L3_LEN`.
+    #[inline]
+    pub fn ipv6(src: Ipv6Addr, dst: Ipv6Addr) -> Self {
+        // equivalent to rte_thash_load_v6_addrs
+        Self(
+            src.octets()
+                .chunks_exact(4)
+                .chain(dst.octets().chunks_exact(4))
+                .map(|word| u32::from_be_bytes(word.try_into().unwrap()))
+                .collect(),
+        )
+    }
+
+    /// See `RTE_THASH_V6_L4_LEN`.
+    #[inline]
+    pub fn ipv6_l4(src: Ipv6Addr, dst: Ipv6Addr, src_port: u16, dst_port: u16) -> Self {
+        let mut tuple = Self::ipv6(src, dst);
+        tuple.0.push(Self::ports_word(src_port, dst_port));
+        tuple
+    }
+
+    /// Returns the tuple as (host byte order) 32-bit words, as expected by [`softrss`].
+    #[inline]
+    pub fn as_words(&self) -> &[u32] {
+        &self.0
+    }
+
+    fn ports_word(src_port: u16, dst_port: u16) -> u32 {
+        (u32::from(src_port) << 16) | u32::from(dst_port)
+    }
+}
+
+/// Computes the Toeplitz hash of `input` (32-bit words in host byte order) using `key`.
+///
+/// See also: <https://doc.dpdk.org/api-22.11/rte__thash_8h.html>
+///
+/// # Panics
+/// Panics if the key is too short for the given input, i.e. shorter than `input.len() + 1` words.
+#[inline]
+pub fn softrss(input: &[u32], key: &RssKey) -> u32 {
+    key.assert_fits(input);
+    unsafe { ffi::_rte_softrss(input.as_ptr() as *mut _, input.len() as u32, key.bytes.as_ptr()) }
+}
+
+/// Optimized version of [`softrss`], which requires the key to be converted using [`RssKey::to_be`].
+///
+/// # Panics
+/// Panics if the key is too short for the given input, i.e. shorter than `input.len() + 1` words.
+#[inline]
+pub fn softrss_be(input: &[u32], key: &BeRssKey) -> u32 {
+    key.0.assert_fits(input);
+    unsafe { ffi::_rte_softrss_be(input.as_ptr() as *mut _, input.len() as u32, key.0.bytes.as_ptr()) }
+}
+
+/// Returns the queue a NIC would RSS a packet with the given hash to, using the port's redirection table.
+///
+/// # Panics
+/// Panics if `reta` is empty.
+#[inline]
+pub fn reta_queue(hash: u32, reta: &[u16]) -> u16 {
+    reta[hash as usize % reta.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // test vectors taken from Microsoft's RSS verification suite, also used by DPDK's own tests
+    const SRC: Ipv4Addr = Ipv4Addr::new(66, 9, 149, 187);
+    const DST: Ipv4Addr = Ipv4Addr::new(161, 142, 100, 80);
+    const SRC_PORT: u16 = 2794;
+    const DST_PORT: u16 = 1766;
+
+    #[test]
+    fn test_softrss() {
+        let l3 = Tuple::ipv4(SRC, DST);
+        let l4 = Tuple::ipv4_l4(SRC, DST, SRC_PORT, DST_PORT);
+
+        assert_eq!(RssKey::DEFAULT.hash(&l3), 0x323e8fc2);
+        assert_eq!(RssKey::DEFAULT.hash(&l4), 0x51ccc178);
+
+        let be_key = RssKey::DEFAULT.to_be();
+        assert_eq!(be_key.hash(&l3), 0x323e8fc2);
+        assert_eq!(be_key.hash(&l4), 0x51ccc178);
+    }
+
+    #[test]
+    fn test_symmetric_key() {
+        let key = RssKey::SYMMETRIC;
+        assert_eq!(
+            key.hash(&Tuple::ipv4_l4(SRC, DST, SRC_PORT, DST_PORT)),
+            key.hash(&Tuple::ipv4_l4(DST, SRC, DST_PORT, SRC_PORT))
+        );
+    }
+
+    #[test]
+    fn test_key_len() {
+        assert!(RssKey::new(&[0; 41]).is_none());
+        assert!(RssKey::new(&[0; 56]).is_none());
+        assert_eq!(RssKey::new(RssKey::DEFAULT.as_bytes()), Some(RssKey::DEFAULT));
+    }
+}
@@ -0,0 +1,50 @@
+//! Runs a pipeline on worker lcores, which EAL must be initialized with, hence this test binary of its own.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use rte::{
+    pipeline::{Burst, PipelineBuilder, StageStats},
+    port::PortSetup,
+    test_utils::{rte_test, FakePort, TestPool},
+};
+
+const NB_PACKETS: u8 = 100;
+
+#[rte_test(workers = 3)]
+fn test_pipeline() {
+    // the queues of the pipeline use the pool for as long as they're running
+    let mempool: &'static TestPool = Box::leak(Box::new(TestPool::new(1023)));
+    let mut fake = FakePort::ring("pipeline", 256, mempool).unwrap();
+    fake.dev().stop().unwrap();
+    let port = PortSetup::new(fake.dev().clone(), mempool).descriptors(256, 256).start().unwrap();
+    let (rx_queues, tx_queues) = port.into_queues();
+
+    assert_eq!(fake.inject_rx((0..NB_PACKETS).map(|i| [i; 60])), NB_PACKETS.into());
+    let pipeline = PipelineBuilder::new("test_pipeline")
+        .rx(rx_queues)
+        .worker(1, |pkts: &mut Burst| pkts.retain(|pkt| pkt.as_slice()[0] % 2 == 0))
+        .tx(tx_queues)
+        .launch()
+        .unwrap();
+
+    // stopped as soon as all the packets were received, so that the following stages are drained
+    let deadline = Instant::now() + Duration::from_secs(10);
+    while pipeline.stats()[0].packets_in < NB_PACKETS.into() {
+        assert!(Instant::now() < deadline, "the packets weren't received: {pipeline:?}");
+        thread::yield_now();
+    }
+    let stats = pipeline.stop();
+
+    let counters = |stats: &StageStats| (stats.name, stats.packets_in, stats.packets_out, stats.dropped);
+    assert_eq!(
+        stats.iter().map(counters).collect::<Vec<_>>(),
+        [("rx", 100, 100, 0), ("worker", 100, 50, 0), ("tx", 50, 50, 0)]
+    );
+    assert!(stats.iter().all(|stats| stats.lcores.len() == 1));
+
+    let sent = fake.drain_tx().iter().map(|pkt| pkt.as_slice()[0]).collect::<Vec<_>>();
+    assert_eq!(sent, (0..NB_PACKETS).step_by(2).collect::<Vec<_>>());
+}